@@ -1,3 +1,10 @@
 fn main() {
+    // Build time (unix seconds) reported by `get_app_metadata`
+    let build_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={build_timestamp}");
+
     tauri_build::build()
 }
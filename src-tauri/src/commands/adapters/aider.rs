@@ -63,11 +63,12 @@ const MAX_HISTORY_FILES: usize = 100;
 /// Collect candidate search directories under the user's home directory.
 ///
 /// Mirrors the upstream Aider locator: common project-holding subdirectories
-/// plus the home directory itself. Uses `dirs::home_dir()` so it resolves
+/// plus the home directory itself. Uses [`crate::utils::resolve_home`], so
+/// `$HISTORY_VIEWER_HOME` is honored and the real home directory resolves
 /// correctly on Windows, macOS, and Linux.
 pub fn get_aider_search_dirs() -> Vec<PathBuf> {
     let mut dirs: Vec<PathBuf> = Vec::new();
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = crate::utils::resolve_home() {
         for subdir in ["client", "projects", "code", "src", "dev", "work", "repos"] {
             let d = home.join(subdir);
             if d.is_dir() {
@@ -477,6 +478,7 @@ fn flush_turn(
         project_id: project_id.to_string(),
         source_id: source_id.to_string(),
         provider_id: "aider".to_string(),
+        provider_root: None,
         timestamp: timestamp.to_string(),
         sequence_number: *seq,
         role: msg_role,
@@ -64,7 +64,7 @@ const TOOL_PATTERNS: &[(&str, &str)] = &[
 
 /// Default Antigravity root: `~/.gemini/antigravity`.
 fn default_antigravity_root() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".gemini").join("antigravity"))
+    crate::utils::resolve_home().map(|h| h.join(".gemini").join("antigravity"))
 }
 
 /// True if `dir` looks like an Antigravity root (carries a known marker).
@@ -713,6 +713,7 @@ fn build_message(
         project_id: project_id.to_string(),
         source_id: source_id.to_string(),
         provider_id: "antigravity".to_string(),
+        provider_root: None,
         timestamp: timestamp.to_string(),
         sequence_number,
         role,
@@ -117,6 +117,7 @@ pub fn claude_message_to_universal(
         project_id,
         source_id,
         provider_id: "claude-code".to_string(),
+        provider_root: None,
 
         // TEMPORAL
         timestamp: msg.timestamp.clone(),
@@ -201,14 +202,15 @@ fn convert_content(msg: &ClaudeMessage) -> Vec<UniversalContent> {
         match content_value {
             // String content (simple text)
             Value::String(text) => {
-                content_items.push(UniversalContent {
+                let data = json!({"text": text});
+                content_items.push(detect_binary(&data).unwrap_or(UniversalContent {
                     content_type: ContentType::Text,
-                    data: json!({"text": text}),
+                    data,
                     encoding: None,
                     mime_type: Some("text/plain".to_string()),
                     size: Some(text.len()),
                     hash: None,
-                });
+                }));
             }
 
             // Array content (structured content items)
@@ -222,14 +224,16 @@ fn convert_content(msg: &ClaudeMessage) -> Vec<UniversalContent> {
 
             // Object content (treat as single data blob)
             Value::Object(_) => {
-                content_items.push(UniversalContent {
-                    content_type: ContentType::Text,
-                    data: content_value.clone(),
-                    encoding: None,
-                    mime_type: Some("application/json".to_string()),
-                    size: None,
-                    hash: None,
-                });
+                content_items.push(detect_binary(content_value).unwrap_or_else(|| {
+                    UniversalContent {
+                        content_type: ContentType::Text,
+                        data: content_value.clone(),
+                        encoding: None,
+                        mime_type: Some("application/json".to_string()),
+                        size: None,
+                        hash: None,
+                    }
+                }));
             }
 
             _ => {
@@ -268,14 +272,15 @@ fn convert_content_item(item: &Value) -> Option<UniversalContent> {
     match content_type {
         "text" => {
             let text = item.get("text")?.as_str()?.to_string();
-            Some(UniversalContent {
+            let data = json!({"text": text});
+            Some(detect_binary(&data).unwrap_or(UniversalContent {
                 content_type: ContentType::Text,
-                data: json!({"text": text}),
+                data,
                 encoding: None,
                 mime_type: Some("text/plain".to_string()),
                 size: Some(text.len()),
                 hash: None,
-            })
+            }))
         }
 
         "tool_use" => Some(UniversalContent {
@@ -317,19 +322,118 @@ fn convert_content_item(item: &Value) -> Option<UniversalContent> {
         }
 
         _ => {
-            // Unknown content type - preserve as-is
-            Some(UniversalContent {
+            // Unknown content type (e.g. a base64 "document") - preserve as-is
+            Some(detect_binary(item).unwrap_or_else(|| UniversalContent {
                 content_type: ContentType::Text,
                 data: item.clone(),
                 encoding: None,
                 mime_type: Some("application/json".to_string()),
                 size: None,
                 hash: None,
-            })
+            }))
         }
     }
 }
 
+/// Minimum length of a string before it is considered a base64 blob; shorter
+/// strings are left alone so ordinary identifiers and tokens stay text.
+const MIN_BASE64_LEN: usize = 256;
+
+/// True when `s` is clearly base64-encoded data: long, only base64 alphabet
+/// (standard or URL-safe, optionally line-wrapped), padded to a multiple of
+/// four, and mixing upper/lowercase letters and digits. An optional
+/// `data:<mime>;base64,` prefix is accepted.
+fn is_base64_blob(s: &str) -> bool {
+    let payload = strip_data_uri(s).1;
+    let compact: Vec<u8> = payload
+        .bytes()
+        .filter(|b| !matches!(b, b'\n' | b'\r'))
+        .collect();
+    if compact.len() < MIN_BASE64_LEN || compact.len() % 4 != 0 {
+        return false;
+    }
+
+    let body_len = compact.len() - compact.iter().rev().take_while(|&&b| b == b'=').count();
+    if compact.len() - body_len > 2 {
+        return false;
+    }
+    let body = &compact[..body_len];
+    body.iter()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_'))
+        && body.iter().any(u8::is_ascii_uppercase)
+        && body.iter().any(u8::is_ascii_lowercase)
+        && body.iter().any(u8::is_ascii_digit)
+}
+
+/// Split an optional `data:<mime>;base64,` prefix off `s`, returning the
+/// mime type (if any) and the remaining payload.
+fn strip_data_uri(s: &str) -> (Option<&str>, &str) {
+    if let Some(rest) = s.strip_prefix("data:") {
+        if let Some((header, payload)) = rest.split_once(',') {
+            if let Some(mime) = header.strip_suffix(";base64") {
+                return ((!mime.is_empty()).then_some(mime), payload);
+            }
+        }
+    }
+    (None, s)
+}
+
+/// Find a base64 payload in a content value: the value itself, one of its
+/// `text`/`data`/`base64` fields, or those fields inside a nested
+/// `source`/`file` object (the shape of API documents and Read tool results).
+/// Returns the payload and any mime type declared next to it.
+fn find_base64_payload(value: &Value) -> Option<(&str, Option<&str>)> {
+    match value {
+        Value::String(s) if is_base64_blob(s) => Some((s, strip_data_uri(s).0)),
+        Value::Object(obj) => {
+            let mime = ["media_type", "mime_type", "mimeType"]
+                .iter()
+                .find_map(|key| obj.get(*key).and_then(|v| v.as_str()));
+            ["text", "data", "base64"]
+                .iter()
+                .filter_map(|key| obj.get(*key))
+                .find_map(|field| match field {
+                    Value::String(s) if is_base64_blob(s) => {
+                        Some((s.as_str(), strip_data_uri(s).0.or(mime)))
+                    }
+                    _ => None,
+                })
+                .or_else(|| {
+                    ["source", "file"]
+                        .iter()
+                        .filter_map(|key| obj.get(*key))
+                        .find_map(|nested| match nested {
+                            Value::Object(_) => find_base64_payload(nested),
+                            _ => None,
+                        })
+                })
+        }
+        _ => None,
+    }
+}
+
+/// Type `data` as `ContentType::Binary` when it carries a base64 blob, so the
+/// viewer shows a placeholder instead of rendering the encoded text. `size`
+/// is the decoded byte count.
+fn detect_binary(data: &Value) -> Option<UniversalContent> {
+    let (payload, mime) = find_base64_payload(data)?;
+    let payload = strip_data_uri(payload).1;
+    let encoded_len = payload
+        .bytes()
+        .filter(|b| !matches!(b, b'\n' | b'\r'))
+        .count();
+    let padding = payload.bytes().rev().take_while(|&b| b == b'=').count();
+
+    Some(UniversalContent {
+        content_type: ContentType::Binary,
+        data: data.clone(),
+        encoding: Some("base64".to_string()),
+        mime_type: mime.map(str::to_string),
+        size: Some(encoded_len / 4 * 3 - padding),
+        hash: None,
+    })
+}
+
 /// Convert tool_use field to ToolCall structures
 /// Extracts tool calls from both:
 /// 1. The legacy tool_use field (if present)
@@ -435,7 +539,32 @@ fn extract_thinking(msg: &ClaudeMessage) -> Option<ThinkingBlock> {
     None
 }
 
-/// Extract error information from tool results
+/// HTTP status signatures of transient Anthropic API failures, as they appear
+/// in error text (e.g. `API Error: 529 {...}`).
+const RATE_LIMIT_STATUS_PATTERNS: &[&str] =
+    &["api error: 429", "http 429", "status 429", "\"status\":429"];
+const OVERLOADED_STATUS_PATTERNS: &[&str] =
+    &["api error: 529", "http 529", "status 529", "\"status\":529"];
+
+/// Classify rate-limit (`rate_limited`) and overloaded (`overloaded`) API
+/// responses so transient API issues can be told apart from tool failures.
+fn transient_api_error_code(text: &str) -> Option<&'static str> {
+    let lower = text.to_ascii_lowercase();
+    if lower.contains("overloaded_error")
+        || OVERLOADED_STATUS_PATTERNS.iter().any(|p| lower.contains(p))
+    {
+        Some("overloaded")
+    } else if lower.contains("rate_limit")
+        || lower.contains("rate limit")
+        || RATE_LIMIT_STATUS_PATTERNS.iter().any(|p| lower.contains(p))
+    {
+        Some("rate_limited")
+    } else {
+        None
+    }
+}
+
+/// Extract error information from tool results and API error messages
 fn extract_errors(msg: &ClaudeMessage) -> Option<Vec<ErrorInfo>> {
     let mut errors = Vec::new();
 
@@ -445,7 +574,9 @@ fn extract_errors(msg: &ClaudeMessage) -> Option<Vec<ErrorInfo>> {
         if let Value::String(error_str) = tool_result {
             if error_str.starts_with("Error:") || error_str.contains("error") {
                 errors.push(ErrorInfo {
-                    code: "tool_error".to_string(),
+                    code: transient_api_error_code(error_str)
+                        .unwrap_or("tool_error")
+                        .to_string(),
                     message: error_str.clone(),
                     details: None,
                     timestamp: msg.timestamp.clone(),
@@ -463,7 +594,9 @@ fn extract_errors(msg: &ClaudeMessage) -> Option<Vec<ErrorInfo>> {
                     .to_string();
 
                 errors.push(ErrorInfo {
-                    code: "tool_result_error".to_string(),
+                    code: transient_api_error_code(&error_msg)
+                        .unwrap_or("tool_result_error")
+                        .to_string(),
                     message: error_msg,
                     details: Some(tool_result.clone()),
                     timestamp: msg.timestamp.clone(),
@@ -485,13 +618,33 @@ fn extract_errors(msg: &ClaudeMessage) -> Option<Vec<ErrorInfo>> {
                             .to_string();
 
                         errors.push(ErrorInfo {
-                            code: "tool_result_error".to_string(),
+                            code: transient_api_error_code(&error_msg)
+                                .unwrap_or("tool_result_error")
+                                .to_string(),
                             message: error_msg,
                             details: Some(item.clone()),
                             timestamp: msg.timestamp.clone(),
                         });
                     }
                 }
+
+                // Claude Code records failed API calls as assistant text
+                // such as `API Error: 429 {...}`
+                if msg.message_type == "assistant"
+                    && item.get("type").and_then(|t| t.as_str()) == Some("text")
+                {
+                    let text = item.get("text").and_then(|t| t.as_str()).unwrap_or("");
+                    if text.starts_with("API Error") {
+                        errors.push(ErrorInfo {
+                            code: transient_api_error_code(text)
+                                .unwrap_or("api_error")
+                                .to_string(),
+                            message: text.to_string(),
+                            details: None,
+                            timestamp: msg.timestamp.clone(),
+                        });
+                    }
+                }
             }
         }
     }
@@ -550,4 +703,109 @@ mod tests {
         let project_id = extract_project_id(&project_path, "");
         assert_eq!(project_id, "test-project");
     }
+
+    fn base64_blob() -> String {
+        "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJ".repeat(8)
+    }
+
+    #[test]
+    fn test_base64_blob_detection() {
+        assert!(is_base64_blob(&base64_blob()));
+        assert!(is_base64_blob(&format!(
+            "data:image/png;base64,{}",
+            base64_blob()
+        )));
+        // Long prose, short tokens, and single-class strings stay text
+        assert!(!is_base64_blob(&"plain words here ".repeat(30)));
+        assert!(!is_base64_blob("aGVsbG8gd29ybGQ="));
+        assert!(!is_base64_blob(&"a".repeat(400)));
+    }
+
+    #[test]
+    fn test_detect_binary_types_nested_source_payload() {
+        let item = json!({
+            "type": "document",
+            "source": {"type": "base64", "media_type": "application/pdf", "data": base64_blob()},
+        });
+        let content = convert_content_item(&item).unwrap();
+        assert_eq!(content.content_type, ContentType::Binary);
+        assert_eq!(content.encoding.as_deref(), Some("base64"));
+        assert_eq!(content.mime_type.as_deref(), Some("application/pdf"));
+        assert_eq!(content.size, Some(base64_blob().len() / 4 * 3));
+        assert_eq!(content.data, item);
+    }
+
+    fn api_error_message(message_type: &str, content: Value) -> ClaudeMessage {
+        ClaudeMessage {
+            uuid: "u1".to_string(),
+            parent_uuid: None,
+            session_id: "s1".to_string(),
+            timestamp: "2025-06-01T10:00:00Z".to_string(),
+            message_type: message_type.to_string(),
+            content: Some(content),
+            tool_use: None,
+            tool_use_result: None,
+            is_sidechain: None,
+            usage: None,
+            role: Some(message_type.to_string()),
+            message_id: None,
+            model: Some("<synthetic>".to_string()),
+            project_path: None,
+            stop_reason: None,
+            subtype: None,
+            system_metadata: None,
+        }
+    }
+
+    fn error_codes(msg: &ClaudeMessage) -> Vec<String> {
+        extract_errors(msg)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| e.code)
+            .collect()
+    }
+
+    #[test]
+    fn test_api_errors_are_classified() {
+        let rate_limited = api_error_message(
+            "assistant",
+            json!([{"type": "text", "text": r#"API Error: 429 {"type":"error","error":{"type":"rate_limit_error","message":"Number of request tokens has exceeded your per-minute rate limit"}}"#}]),
+        );
+        assert_eq!(error_codes(&rate_limited), vec!["rate_limited"]);
+
+        let overloaded = api_error_message(
+            "assistant",
+            json!([{"type": "text", "text": r#"API Error: 529 {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#}]),
+        );
+        assert_eq!(error_codes(&overloaded), vec!["overloaded"]);
+
+        let other = api_error_message(
+            "assistant",
+            json!([{"type": "text", "text": "API Error: 400 invalid_request_error"}]),
+        );
+        assert_eq!(error_codes(&other), vec!["api_error"]);
+    }
+
+    #[test]
+    fn test_tool_failures_keep_tool_codes() {
+        let tool_failure = api_error_message(
+            "user",
+            json!([{"type": "tool_result", "tool_use_id": "t1", "is_error": true, "content": "Exit code 1: file not found at line 429"}]),
+        );
+        assert_eq!(error_codes(&tool_failure), vec!["tool_result_error"]);
+
+        let fetch_rate_limited = api_error_message(
+            "user",
+            json!([{"type": "tool_result", "tool_use_id": "t1", "is_error": true, "content": "Request failed: HTTP 429 Too Many Requests"}]),
+        );
+        assert_eq!(error_codes(&fetch_rate_limited), vec!["rate_limited"]);
+    }
+
+    #[test]
+    fn test_plain_text_item_stays_text() {
+        let item = json!({"type": "text", "text": "hello"});
+        let content = convert_content_item(&item).unwrap();
+        assert_eq!(content.content_type, ContentType::Text);
+        assert!(content.encoding.is_none());
+    }
 }
@@ -62,7 +62,7 @@ pub fn get_all_cline_base_paths() -> Vec<(PathBuf, String)> {
 
     // macOS: ~/Library/Application Support
     #[cfg(target_os = "macos")]
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = crate::utils::resolve_home() {
         roots.push(home.join("Library").join("Application Support"));
     }
 
@@ -642,6 +642,7 @@ fn build_message(
         project_id: project_id.to_string(),
         source_id: source_id.to_string(),
         provider_id: "cline".to_string(),
+        provider_root: None,
         timestamp: timestamp.to_string(),
         sequence_number,
         role,
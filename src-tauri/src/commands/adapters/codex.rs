@@ -127,8 +127,21 @@ pub fn codex_event_to_universal(
     // Determine message type
     let message_type: MessageType = MessageType::Message; // Codex doesn't have summary/sidechain
 
-    // Convert content
-    let content: Vec<UniversalContent> = convert_content(event);
+    // Extract extended reasoning (Codex `reasoning` events)
+    let thinking: Option<ThinkingBlock> = extract_thinking(event);
+
+    // Convert content (reasoning renders as a Thinking block, not plain text)
+    let content: Vec<UniversalContent> = match thinking {
+        Some(ref block) => vec![UniversalContent {
+            content_type: ContentType::Thinking,
+            data: json!({"thinking": block.content}),
+            encoding: None,
+            mime_type: Some("text/plain".to_string()),
+            size: Some(block.content.len()),
+            hash: None,
+        }],
+        None => convert_content(event),
+    };
 
     // Extract CWD with priority logic
     let cwd: Option<String> = extract_cwd(event);
@@ -141,6 +154,11 @@ pub fn codex_event_to_universal(
         .and_then(|m| m.as_str())
         .map(String::from);
 
+    let thinking: Option<ThinkingBlock> = thinking.map(|block| ThinkingBlock {
+        model: model.clone(),
+        ..block
+    });
+
     // ⭐ CRITICAL: Use camelCase for metadata (Gemini lesson learned!)
     // CLEAN CODE: Standardized metadata keys across all providers
     let mut metadata: HashMap<String, Value> = HashMap::new();
@@ -166,6 +184,7 @@ pub fn codex_event_to_universal(
         project_id,
         source_id,
         provider_id: "codex".to_string(),
+        provider_root: None,
 
         // TEMPORAL
         timestamp: event
@@ -188,9 +207,9 @@ pub fn codex_event_to_universal(
 
         // METADATA
         model,
-        tokens: None,       // Codex doesn't expose token counts
+        tokens: None,       // Attached per turn by coalesce_codex_events
         tool_calls: None,   // TODO: Extract from payload
-        thinking: None,     // TODO: Check if Codex has thinking blocks
+        thinking,
         attachments: None,
         errors: None,       // TODO: Extract from execution_context
 
@@ -200,10 +219,572 @@ pub fn codex_event_to_universal(
     }
 }
 
+// ============================================================================
+// EVENT COALESCING
+// ============================================================================
+
+/// Codex `payload.type` of a response_item event, if any.
+fn payload_type(event: &CodexEvent) -> Option<&str> {
+    event
+        .payload
+        .as_ref()
+        .and_then(|p| p.get("type"))
+        .and_then(|t| t.as_str())
+}
+
+/// Group raw Codex events into logical conversation messages.
+///
+/// Codex writes one turn as several events (assistant `message`, `reasoning`,
+/// `function_call`, `function_call_output`). This folds every event between
+/// two user messages into a single assistant message: text and reasoning are
+/// appended to its content, function calls become `tool_calls`, and each
+/// function output is attached to its call (matched by `call_id`) and added
+/// as a ToolResult content item.
+///
+/// User and system messages are emitted as-is. Usage from `token_count`
+/// events is added to the open assistant turn (or the last assistant message)
+/// via [`CodexTokenTracker`]. Other events that carry no displayable content
+/// (e.g. `event_msg` telemetry, encrypted-only reasoning) are dropped.
+/// `session_id` is left empty for the caller to set, as with
+/// [`codex_event_to_universal`].
+pub fn coalesce_codex_events(
+    events: &[CodexEvent],
+    project_id: &str,
+    source_id: &str,
+    file_path: &str,
+) -> Vec<UniversalMessage> {
+    let mut messages: Vec<UniversalMessage> = Vec::new();
+    let mut turn: Option<UniversalMessage> = None;
+    let mut token_tracker = CodexTokenTracker::default();
+
+    for (idx, event) in events.iter().enumerate() {
+        if let Some(usage) = token_tracker.usage(event) {
+            let owner = turn.as_mut().or_else(|| {
+                messages
+                    .iter_mut()
+                    .rev()
+                    .find(|m| m.role == MessageRole::Assistant)
+            });
+            if let Some(msg) = owner {
+                msg.tokens = Some(match msg.tokens.take() {
+                    Some(existing) => add_token_usage(existing, usage),
+                    None => usage,
+                });
+            }
+            continue;
+        }
+
+        let sequence_number: i32 = idx as i32;
+        let to_universal = || {
+            codex_event_to_universal(
+                event,
+                project_id.to_string(),
+                source_id.to_string(),
+                sequence_number,
+                file_path,
+            )
+        };
+
+        match payload_type(event) {
+            Some("function_call") => {
+                let payload = event.payload.as_ref();
+                let call_id: String = payload
+                    .and_then(|p| p.get("call_id"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("codex-call-{}", sequence_number));
+                let name: String = payload
+                    .and_then(|p| p.get("name"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                // `arguments` is a JSON-encoded string in Codex logs
+                let input: HashMap<String, Value> = match payload.and_then(|p| p.get("arguments")) {
+                    Some(Value::String(raw)) => match serde_json::from_str::<Value>(raw) {
+                        Ok(Value::Object(map)) => map.into_iter().collect(),
+                        _ => HashMap::from([("arguments".to_string(), json!(raw))]),
+                    },
+                    Some(Value::Object(map)) => map.clone().into_iter().collect(),
+                    _ => HashMap::new(),
+                };
+
+                let msg = turn.get_or_insert_with(|| {
+                    let mut msg = to_universal();
+                    msg.role = MessageRole::Assistant;
+                    msg.content.clear();
+                    msg
+                });
+                msg.tool_calls.get_or_insert_with(Vec::new).push(ToolCall {
+                    id: call_id,
+                    name,
+                    input,
+                    output: None,
+                    error: None,
+                    status: ToolCallStatus::Pending,
+                });
+            }
+
+            Some("function_call_output") => {
+                let payload = event.payload.as_ref();
+                let call_id: String = payload
+                    .and_then(|p| p.get("call_id"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let (output_text, exit_code) =
+                    parse_function_output(payload.and_then(|p| p.get("output")));
+                let failed: bool = exit_code.is_some_and(|code| code != 0);
+
+                let msg = turn.get_or_insert_with(|| {
+                    let mut msg = to_universal();
+                    msg.role = MessageRole::Assistant;
+                    msg.content.clear();
+                    msg
+                });
+                let mut tool_name: Option<String> = None;
+                if let Some(call) = msg
+                    .tool_calls
+                    .as_mut()
+                    .and_then(|calls| calls.iter_mut().find(|c| c.id == call_id))
+                {
+                    call.output =
+                        Some(HashMap::from([("content".to_string(), json!(output_text))]));
+                    call.status = if failed {
+                        ToolCallStatus::Error
+                    } else {
+                        ToolCallStatus::Success
+                    };
+                    if failed {
+                        call.error = Some(output_text.clone());
+                    }
+                    tool_name = Some(call.name.clone());
+                }
+                msg.content.push(UniversalContent {
+                    content_type: ContentType::ToolResult,
+                    data: json!({
+                        "tool_use_id": call_id,
+                        "name": tool_name,
+                        "content": output_text,
+                        "is_error": failed,
+                    }),
+                    encoding: None,
+                    mime_type: Some("text/plain".to_string()),
+                    size: Some(output_text.len()),
+                    hash: None,
+                });
+            }
+
+            _ => {
+                let msg: UniversalMessage = to_universal();
+                if msg.content.is_empty() {
+                    continue;
+                }
+
+                if msg.role != MessageRole::Assistant {
+                    // User/system message closes the current assistant turn
+                    messages.extend(turn.take());
+                    messages.push(msg);
+                    continue;
+                }
+
+                match turn.as_mut() {
+                    Some(current) => {
+                        current.content.extend(msg.content);
+                        if current.model.is_none() {
+                            current.model = msg.model;
+                        }
+                        match (current.thinking.as_mut(), msg.thinking) {
+                            (Some(existing), Some(more)) => {
+                                existing.content.push_str("\n\n");
+                                existing.content.push_str(&more.content);
+                            }
+                            (None, more) => current.thinking = more,
+                            (Some(_), None) => {}
+                        }
+                    }
+                    None => turn = Some(msg),
+                }
+            }
+        }
+    }
+
+    messages.extend(turn);
+    messages
+}
+
+/// Split a Codex `function_call_output.output` into display text and exit
+/// code. The output is usually a JSON string `{"output": "...", "metadata":
+/// {"exit_code": N}}`; anything else is shown verbatim.
+fn parse_function_output(output: Option<&Value>) -> (String, Option<i64>) {
+    let raw: String = match output {
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => return (String::new(), None),
+    };
+
+    match serde_json::from_str::<Value>(&raw) {
+        Ok(parsed) if parsed.get("output").is_some() => {
+            let text: String = match parsed.get("output") {
+                Some(Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            };
+            let exit_code: Option<i64> = parsed
+                .get("metadata")
+                .and_then(|m| m.get("exit_code"))
+                .and_then(|c| c.as_i64());
+            (text, exit_code)
+        }
+        _ => (raw, None),
+    }
+}
+
+// ============================================================================
+// TOKEN USAGE
+// ============================================================================
+
+/// Counters from a Codex usage object. `input` includes `cached_input`, as
+/// in the OpenAI API.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct CodexUsage {
+    input: u64,
+    cached_input: u64,
+    output: u64,
+}
+
+impl CodexUsage {
+    /// Read `input_tokens` / `cached_input_tokens` / `output_tokens`;
+    /// None when none of them is present.
+    fn parse(value: &Value) -> Option<CodexUsage> {
+        let field = |key: &str| value.get(key).and_then(Value::as_u64);
+        let (input, cached_input, output) = (
+            field("input_tokens"),
+            field("cached_input_tokens"),
+            field("output_tokens"),
+        );
+        if input.is_none() && cached_input.is_none() && output.is_none() {
+            return None;
+        }
+        Some(CodexUsage {
+            input: input.unwrap_or(0),
+            cached_input: cached_input.unwrap_or(0),
+            output: output.unwrap_or(0),
+        })
+    }
+
+    fn saturating_sub(self, earlier: CodexUsage) -> CodexUsage {
+        CodexUsage {
+            input: self.input.saturating_sub(earlier.input),
+            cached_input: self.cached_input.saturating_sub(earlier.cached_input),
+            output: self.output.saturating_sub(earlier.output),
+        }
+    }
+
+    /// Cached input is reported as cache reads and excluded from
+    /// `input_tokens`, matching the Claude usage fields.
+    fn to_token_usage(self) -> Option<TokenUsage> {
+        if self == CodexUsage::default() {
+            return None;
+        }
+        let to_i32 = |v: u64| -> i32 { v.try_into().unwrap_or(i32::MAX) };
+        let input_tokens: i32 = to_i32(self.input.saturating_sub(self.cached_input));
+        let output_tokens: i32 = to_i32(self.output);
+        Some(TokenUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens.saturating_add(output_tokens),
+            cache_creation_tokens: None,
+            cache_read_tokens: (self.cached_input > 0).then(|| to_i32(self.cached_input)),
+            service_tier: None,
+        })
+    }
+}
+
+/// Turns Codex `token_count` events into per-message usage.
+///
+/// Newer rollouts report `info.total_token_usage`, a running total for the
+/// session, and often repeat the same total; the tracker returns only the
+/// growth since the previous total so summing messages does not double-count.
+/// Without a running total, `info.last_token_usage` or usage fields on the
+/// payload itself are taken as absolute per-request counts.
+#[derive(Debug, Default)]
+pub struct CodexTokenTracker {
+    previous_total: Option<CodexUsage>,
+}
+
+impl CodexTokenTracker {
+    /// Usage contributed by `event`; None for non-`token_count` events and
+    /// for reports that add no tokens.
+    pub fn usage(&mut self, event: &CodexEvent) -> Option<TokenUsage> {
+        let payload = event.payload.as_ref()?;
+        if event.event_type != "token_count" && payload_type(event) != Some("token_count") {
+            return None;
+        }
+        let info: &Value = payload.get("info").unwrap_or(payload);
+
+        if let Some(total) = info.get("total_token_usage").and_then(CodexUsage::parse) {
+            let delta = total.saturating_sub(self.previous_total.unwrap_or_default());
+            self.previous_total = Some(total);
+            return delta.to_token_usage();
+        }
+
+        info.get("last_token_usage")
+            .and_then(CodexUsage::parse)
+            .or_else(|| CodexUsage::parse(info))?
+            .to_token_usage()
+    }
+}
+
+/// Sum two usage records (e.g. several model calls within one turn).
+fn add_token_usage(a: TokenUsage, b: TokenUsage) -> TokenUsage {
+    let add_opt = |x: Option<i32>, y: Option<i32>| match (x, y) {
+        (None, None) => None,
+        (x, y) => Some(x.unwrap_or(0).saturating_add(y.unwrap_or(0))),
+    };
+    TokenUsage {
+        input_tokens: a.input_tokens.saturating_add(b.input_tokens),
+        output_tokens: a.output_tokens.saturating_add(b.output_tokens),
+        total_tokens: a.total_tokens.saturating_add(b.total_tokens),
+        cache_creation_tokens: add_opt(a.cache_creation_tokens, b.cache_creation_tokens),
+        cache_read_tokens: add_opt(a.cache_read_tokens, b.cache_read_tokens),
+        service_tier: a.service_tier.or(b.service_tier),
+    }
+}
+
+// ============================================================================
+// TOOL CALL NORMALIZATION
+// ============================================================================
+
+/// Expand Codex `apply_patch` invocations into canonical file tool calls.
+///
+/// Codex edits files through `apply_patch`, either as its own function call
+/// or via `shell` (`["apply_patch", "*** Begin Patch..."]`). Each file section
+/// of the patch becomes a `Write` (added file), `Edit` (single hunk) or
+/// `MultiEdit` (several hunks) call with Claude-style inputs. Failed calls and
+/// calls without a patch are returned unchanged.
+pub fn convert_codex_tool_calls(calls: &[ToolCall]) -> Vec<ToolCall> {
+    let mut converted: Vec<ToolCall> = Vec::new();
+
+    for call in calls {
+        let file_calls: Vec<ToolCall> = match call.status {
+            ToolCallStatus::Error => Vec::new(),
+            _ => codex_patch_text(call)
+                .map(|patch| patch_to_tool_calls(call, &patch))
+                .unwrap_or_default(),
+        };
+
+        if file_calls.is_empty() {
+            converted.push(call.clone());
+        } else {
+            converted.extend(file_calls);
+        }
+    }
+
+    converted
+}
+
+/// Locate the `*** Begin Patch` .. `*** End Patch` block of a patch call.
+fn codex_patch_text(call: &ToolCall) -> Option<String> {
+    const BEGIN: &str = "*** Begin Patch";
+    const END: &str = "*** End Patch";
+
+    let candidates: Vec<&str> = match call.name.as_str() {
+        "apply_patch" => ["input", "patch"]
+            .iter()
+            .filter_map(|key| call.input.get(*key).and_then(Value::as_str))
+            .collect(),
+        "shell" | "exec_command" => match call.input.get("command") {
+            Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+            Some(Value::String(command)) => vec![command.as_str()],
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+
+    candidates.into_iter().find_map(|text| {
+        let start: usize = text.find(BEGIN)?;
+        let end: usize = text[start..]
+            .find(END)
+            .map(|i| start + i + END.len())
+            .unwrap_or(text.len());
+        Some(text[start..end].to_string())
+    })
+}
+
+/// One file section of a Codex patch.
+enum PatchSection {
+    Add {
+        path: String,
+        lines: Vec<String>,
+    },
+    Update {
+        path: String,
+        hunks: Vec<(Vec<String>, Vec<String>)>,
+    },
+    Delete,
+}
+
+/// Split a Codex patch into per-file `ToolCall`s (see [`convert_codex_tool_calls`]).
+fn patch_to_tool_calls(call: &ToolCall, patch: &str) -> Vec<ToolCall> {
+    let mut sections: Vec<PatchSection> = Vec::new();
+
+    for line in patch.lines() {
+        if let Some(path) = line.strip_prefix("*** Add File: ") {
+            sections.push(PatchSection::Add {
+                path: path.trim().to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(path) = line.strip_prefix("*** Update File: ") {
+            sections.push(PatchSection::Update {
+                path: path.trim().to_string(),
+                hunks: Vec::new(),
+            });
+        } else if line.starts_with("*** Delete File: ") {
+            sections.push(PatchSection::Delete);
+        } else if line.starts_with("***") {
+            // Begin/End Patch, Move to, End of File
+            continue;
+        } else {
+            match sections.last_mut() {
+                Some(PatchSection::Add { lines, .. }) => {
+                    if let Some(text) = line.strip_prefix('+') {
+                        lines.push(text.to_string());
+                    }
+                }
+                Some(PatchSection::Update { hunks, .. }) => {
+                    if line.starts_with("@@") || hunks.is_empty() {
+                        hunks.push((Vec::new(), Vec::new()));
+                    }
+                    if line.starts_with("@@") {
+                        continue;
+                    }
+                    let (old, new) = hunks.last_mut().expect("hunk pushed above");
+                    if let Some(text) = line.strip_prefix('-') {
+                        old.push(text.to_string());
+                    } else if let Some(text) = line.strip_prefix('+') {
+                        new.push(text.to_string());
+                    } else {
+                        let text: &str = line.strip_prefix(' ').unwrap_or(line);
+                        old.push(text.to_string());
+                        new.push(text.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    sections
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, section)| {
+            let (name, input): (&str, HashMap<String, Value>) = match section {
+                PatchSection::Add { path, lines } => (
+                    "Write",
+                    HashMap::from([
+                        ("file_path".to_string(), json!(path)),
+                        ("content".to_string(), json!(lines.join("\n"))),
+                    ]),
+                ),
+                PatchSection::Update { path, hunks } if hunks.len() == 1 => {
+                    let (old, new) = &hunks[0];
+                    (
+                        "Edit",
+                        HashMap::from([
+                            ("file_path".to_string(), json!(path)),
+                            ("old_string".to_string(), json!(old.join("\n"))),
+                            ("new_string".to_string(), json!(new.join("\n"))),
+                        ]),
+                    )
+                }
+                PatchSection::Update { path, hunks } if !hunks.is_empty() => {
+                    let edits: Vec<Value> = hunks
+                        .iter()
+                        .map(|(old, new)| {
+                            json!({
+                                "old_string": old.join("\n"),
+                                "new_string": new.join("\n"),
+                            })
+                        })
+                        .collect();
+                    (
+                        "MultiEdit",
+                        HashMap::from([
+                            ("file_path".to_string(), json!(path)),
+                            ("edits".to_string(), json!(edits)),
+                        ]),
+                    )
+                }
+                PatchSection::Update { .. } | PatchSection::Delete => return None,
+            };
+
+            Some(ToolCall {
+                id: format!("{}-{}", call.id, idx),
+                name: name.to_string(),
+                input,
+                output: None,
+                error: None,
+                status: call.status.clone(),
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // HELPER FUNCTIONS
 // ============================================================================
 
+/// True for Codex reasoning events: a top-level `reasoning` event or a
+/// `response_item` whose `payload.type` is `reasoning`.
+pub fn is_reasoning_event(event: &CodexEvent) -> bool {
+    event.event_type == "reasoning"
+        || event
+            .payload
+            .as_ref()
+            .and_then(|p| p.get("type"))
+            .and_then(|t| t.as_str())
+            == Some("reasoning")
+}
+
+/// Extract a ThinkingBlock from a reasoning event.
+/// Prefers the full `payload.content[].text` (reasoning_text items), then the
+/// `payload.summary[].text` (summary_text items), then a plain `payload.text`.
+/// Returns None for non-reasoning events or when no reasoning text is present
+/// (e.g. only `encrypted_content` was recorded).
+pub fn extract_thinking(event: &CodexEvent) -> Option<ThinkingBlock> {
+    if !is_reasoning_event(event) {
+        return None;
+    }
+    let payload = event.payload.as_ref()?;
+
+    let join_texts = |key: &str| -> Option<String> {
+        let texts: Vec<&str> = payload
+            .get(key)?
+            .as_array()?
+            .iter()
+            .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+            .filter(|text| !text.trim().is_empty())
+            .collect();
+        (!texts.is_empty()).then(|| texts.join("\n\n"))
+    };
+
+    let content = join_texts("content")
+        .or_else(|| join_texts("summary"))
+        .or_else(|| {
+            payload
+                .get("text")
+                .and_then(|t| t.as_str())
+                .filter(|text| !text.trim().is_empty())
+                .map(String::from)
+        })?;
+
+    Some(ThinkingBlock {
+        content,
+        signature: None,
+        model: None, // Filled in by codex_event_to_universal
+    })
+}
+
 /// Determine MessageRole from event type and payload.role
 /// CLEAN CODE: Explicit return type annotation
 fn determine_role(event: &CodexEvent) -> MessageRole {
@@ -431,4 +1012,326 @@ mod tests {
 
         assert_eq!(extract_cwd(&event3), None);
     }
+
+    fn reasoning_event(event_type: &str, payload: Value) -> CodexEvent {
+        CodexEvent {
+            id: Some("r1".to_string()),
+            timestamp: Some("2025-01-27T14:30:45Z".to_string()),
+            event_type: event_type.to_string(),
+            payload: Some(payload),
+            internal: None,
+            environment_context: None,
+            execution_context: None,
+        }
+    }
+
+    #[test]
+    fn test_reasoning_summary_becomes_thinking() {
+        let event = reasoning_event(
+            "response_item",
+            json!({
+                "type": "reasoning",
+                "summary": [
+                    {"type": "summary_text", "text": "**Planning**"},
+                    {"type": "summary_text", "text": "Read the config first."}
+                ],
+                "content": null,
+                "encrypted_content": "gAAAA..."
+            }),
+        );
+
+        assert!(is_reasoning_event(&event));
+        let msg = codex_event_to_universal(&event, "p".into(), "s".into(), 0, "/nonexistent");
+        let thinking = msg.thinking.expect("reasoning should populate thinking");
+        assert_eq!(thinking.content, "**Planning**\n\nRead the config first.");
+        assert_eq!(msg.role, MessageRole::Assistant);
+        assert_eq!(msg.content.len(), 1);
+        assert_eq!(msg.content[0].content_type, ContentType::Thinking);
+    }
+
+    #[test]
+    fn test_reasoning_prefers_full_content_and_keeps_model() {
+        let event = reasoning_event(
+            "reasoning",
+            json!({
+                "model": "gpt-5-codex",
+                "summary": [{"type": "summary_text", "text": "short"}],
+                "content": [{"type": "reasoning_text", "text": "the full chain"}]
+            }),
+        );
+
+        let msg = codex_event_to_universal(&event, "p".into(), "s".into(), 0, "/nonexistent");
+        let thinking = msg.thinking.unwrap();
+        assert_eq!(thinking.content, "the full chain");
+        assert_eq!(thinking.model.as_deref(), Some("gpt-5-codex"));
+    }
+
+    #[test]
+    fn test_encrypted_only_reasoning_has_no_thinking() {
+        let event = reasoning_event(
+            "response_item",
+            json!({"type": "reasoning", "summary": [], "encrypted_content": "gAAAA..."}),
+        );
+        assert!(extract_thinking(&event).is_none());
+    }
+
+    #[test]
+    fn test_regular_message_has_no_thinking() {
+        let event = reasoning_event(
+            "response_item",
+            json!({
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "output_text", "text": "done"}]
+            }),
+        );
+        assert!(!is_reasoning_event(&event));
+        let msg = codex_event_to_universal(&event, "p".into(), "s".into(), 0, "/nonexistent");
+        assert!(msg.thinking.is_none());
+        assert_eq!(msg.content[0].content_type, ContentType::Text);
+    }
+
+    fn response_item(payload: Value) -> CodexEvent {
+        reasoning_event("response_item", payload)
+    }
+
+    #[test]
+    fn test_coalesce_groups_turn_into_one_assistant_message() {
+        let events = vec![
+            response_item(json!({
+                "type": "message", "role": "user",
+                "content": [{"type": "input_text", "text": "list files"}]
+            })),
+            response_item(json!({
+                "type": "reasoning",
+                "summary": [{"type": "summary_text", "text": "Use ls"}]
+            })),
+            response_item(json!({
+                "type": "function_call", "name": "shell", "call_id": "call_1",
+                "arguments": "{\"command\":[\"ls\"]}"
+            })),
+            reasoning_event("event_msg", json!({"type": "token_count"})),
+            response_item(json!({
+                "type": "function_call_output", "call_id": "call_1",
+                "output": "{\"output\":\"a.rs\\nb.rs\",\"metadata\":{\"exit_code\":0}}"
+            })),
+            response_item(json!({
+                "type": "message", "role": "assistant",
+                "content": [{"type": "output_text", "text": "Two files."}]
+            })),
+            response_item(json!({
+                "type": "message", "role": "user",
+                "content": [{"type": "input_text", "text": "thanks"}]
+            })),
+        ];
+
+        let messages = coalesce_codex_events(&events, "p", "s", "/nonexistent");
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].role, MessageRole::User);
+        assert_eq!(messages[2].role, MessageRole::User);
+
+        let turn = &messages[1];
+        assert_eq!(turn.role, MessageRole::Assistant);
+        assert_eq!(turn.sequence_number, 1);
+        assert_eq!(turn.thinking.as_ref().unwrap().content, "Use ls");
+
+        let calls = turn.tool_calls.as_ref().expect("tool call attached");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "call_1");
+        assert_eq!(calls[0].name, "shell");
+        assert_eq!(calls[0].input["command"], json!(["ls"]));
+        assert!(matches!(calls[0].status, ToolCallStatus::Success));
+        assert_eq!(
+            calls[0].output.as_ref().unwrap()["content"],
+            json!("a.rs\nb.rs")
+        );
+
+        let types: Vec<&ContentType> = turn.content.iter().map(|c| &c.content_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                &ContentType::Thinking,
+                &ContentType::ToolResult,
+                &ContentType::Text
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_marks_failed_call_as_error() {
+        let events = vec![
+            response_item(json!({
+                "type": "function_call", "name": "shell", "call_id": "c",
+                "arguments": "{\"command\":[\"false\"]}"
+            })),
+            response_item(json!({
+                "type": "function_call_output", "call_id": "c",
+                "output": "{\"output\":\"boom\",\"metadata\":{\"exit_code\":1}}"
+            })),
+        ];
+
+        let messages = coalesce_codex_events(&events, "p", "s", "/nonexistent");
+        assert_eq!(messages.len(), 1);
+        let call = &messages[0].tool_calls.as_ref().unwrap()[0];
+        assert!(matches!(call.status, ToolCallStatus::Error));
+        assert_eq!(call.error.as_deref(), Some("boom"));
+    }
+
+    fn token_count(info: Value) -> CodexEvent {
+        reasoning_event("event_msg", json!({"type": "token_count", "info": info}))
+    }
+
+    fn assistant_text(text: &str) -> CodexEvent {
+        response_item(json!({
+            "type": "message", "role": "assistant",
+            "content": [{"type": "output_text", "text": text}]
+        }))
+    }
+
+    fn user_text(text: &str) -> CodexEvent {
+        response_item(json!({
+            "type": "message", "role": "user",
+            "content": [{"type": "input_text", "text": text}]
+        }))
+    }
+
+    #[test]
+    fn test_coalesce_attaches_delta_of_cumulative_usage() {
+        let total = |input: u64, cached: u64, output: u64| {
+            json!({
+                "total_token_usage": {
+                    "input_tokens": input,
+                    "cached_input_tokens": cached,
+                    "output_tokens": output
+                },
+                "last_token_usage": {"input_tokens": 1, "output_tokens": 1}
+            })
+        };
+        let events = vec![
+            user_text("one"),
+            assistant_text("first"),
+            token_count(total(1000, 200, 50)),
+            // Repeated report of the same running total adds nothing
+            token_count(total(1000, 200, 50)),
+            user_text("two"),
+            assistant_text("second"),
+            token_count(total(2500, 900, 80)),
+        ];
+
+        let messages = coalesce_codex_events(&events, "p", "s", "/nonexistent");
+        assert_eq!(messages.len(), 4);
+        assert!(messages[0].tokens.is_none());
+
+        let first = messages[1].tokens.as_ref().expect("first turn usage");
+        assert_eq!(first.input_tokens, 800);
+        assert_eq!(first.cache_read_tokens, Some(200));
+        assert_eq!(first.output_tokens, 50);
+        assert_eq!(first.total_tokens, 850);
+
+        let second = messages[3].tokens.as_ref().expect("second turn usage");
+        assert_eq!(second.input_tokens, 800);
+        assert_eq!(second.cache_read_tokens, Some(700));
+        assert_eq!(second.output_tokens, 30);
+    }
+
+    #[test]
+    fn test_coalesce_sums_absolute_usage_within_turn() {
+        let events = vec![
+            user_text("go"),
+            assistant_text("step"),
+            token_count(json!({"last_token_usage": {"input_tokens": 100, "output_tokens": 10}})),
+            assistant_text("done"),
+            // Older rollouts put the counters directly on the payload
+            response_item(json!({"type": "token_count", "input_tokens": 40, "output_tokens": 5})),
+            user_text("next"),
+            // No assistant turn open: goes to the last assistant message
+            token_count(json!({"last_token_usage": {"input_tokens": 7, "output_tokens": 1}})),
+            token_count(Value::Null),
+        ];
+
+        let messages = coalesce_codex_events(&events, "p", "s", "/nonexistent");
+        assert_eq!(messages.len(), 3);
+        let usage = messages[1].tokens.as_ref().expect("turn usage");
+        assert_eq!(usage.input_tokens, 147);
+        assert_eq!(usage.output_tokens, 16);
+        assert_eq!(usage.total_tokens, 163);
+        assert_eq!(usage.cache_read_tokens, None);
+        assert!(messages[2].tokens.is_none());
+    }
+
+    #[test]
+    fn test_convert_codex_tool_calls_expands_apply_patch() {
+        let patch = "*** Begin Patch\n*** Add File: /repo/new.rs\n+fn a() {}\n+fn b() {}\n*** Update File: /repo/lib.rs\n@@ fn main\n fn main() {\n-    old();\n+    new();\n }\n*** Delete File: /repo/gone.rs\n*** End Patch";
+        let calls = vec![
+            ToolCall {
+                id: "c1".to_string(),
+                name: "shell".to_string(),
+                input: HashMap::from([("command".to_string(), json!(["apply_patch", patch]))]),
+                output: None,
+                error: None,
+                status: ToolCallStatus::Success,
+            },
+            ToolCall {
+                id: "c2".to_string(),
+                name: "shell".to_string(),
+                input: HashMap::from([("command".to_string(), json!(["ls"]))]),
+                output: None,
+                error: None,
+                status: ToolCallStatus::Success,
+            },
+        ];
+
+        let converted = convert_codex_tool_calls(&calls);
+        assert_eq!(converted.len(), 3);
+
+        assert_eq!(converted[0].name, "Write");
+        assert_eq!(converted[0].input["file_path"], "/repo/new.rs");
+        assert_eq!(converted[0].input["content"], "fn a() {}\nfn b() {}");
+
+        assert_eq!(converted[1].name, "Edit");
+        assert_eq!(converted[1].input["file_path"], "/repo/lib.rs");
+        assert_eq!(
+            converted[1].input["old_string"],
+            "fn main() {\n    old();\n}"
+        );
+        assert_eq!(
+            converted[1].input["new_string"],
+            "fn main() {\n    new();\n}"
+        );
+
+        // Non-patch calls pass through untouched
+        assert_eq!(converted[2].id, "c2");
+        assert_eq!(converted[2].name, "shell");
+    }
+
+    #[test]
+    fn test_convert_codex_tool_calls_multiple_hunks_and_failures() {
+        let patch = "*** Begin Patch\n*** Update File: a.rs\n@@\n-x\n+y\n@@\n-z\n+w\n*** End Patch";
+        let mut call = ToolCall {
+            id: "p".to_string(),
+            name: "apply_patch".to_string(),
+            input: HashMap::from([("input".to_string(), json!(patch))]),
+            output: None,
+            error: None,
+            status: ToolCallStatus::Success,
+        };
+
+        let converted = convert_codex_tool_calls(std::slice::from_ref(&call));
+        assert_eq!(converted.len(), 1);
+        assert_eq!(converted[0].name, "MultiEdit");
+        assert_eq!(converted[0].input["edits"].as_array().unwrap().len(), 2);
+
+        call.status = ToolCallStatus::Error;
+        let converted = convert_codex_tool_calls(std::slice::from_ref(&call));
+        assert_eq!(converted[0].name, "apply_patch");
+    }
+
+    #[test]
+    fn test_parse_function_output_plain_text() {
+        assert_eq!(
+            parse_function_output(Some(&json!("plain output"))),
+            ("plain output".to_string(), None)
+        );
+        assert_eq!(parse_function_output(None), (String::new(), None));
+    }
 }
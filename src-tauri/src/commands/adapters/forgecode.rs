@@ -94,7 +94,7 @@ pub fn get_forgecode_base_path() -> Option<PathBuf> {
     }
 
     // 2. ~/.forge
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = crate::utils::resolve_home() {
         let default_path = home.join(".forge");
         if default_path.exists() {
             if let Ok(canon) = std::fs::canonicalize(&default_path) {
@@ -964,6 +964,7 @@ fn build_message(
         project_id: project_id.to_string(),
         source_id: source_id.to_string(),
         provider_id: PROVIDER_ID.to_string(),
+        provider_root: None,
         timestamp: timestamp.to_string(),
         sequence_number: sequence_number.try_into().unwrap_or(i32::MAX),
         role,
@@ -1157,7 +1158,7 @@ fn collect_cwd_votes(value: &Value, votes: &mut BTreeMap<String, usize>) {
 
 /// Pick the most-voted cwd, excluding bare home directories.
 fn choose_best_cwd(cwd_votes: &BTreeMap<String, usize>) -> Option<String> {
-    let home_dir = dirs::home_dir();
+    let home_dir = crate::utils::resolve_home();
     cwd_votes
         .iter()
         .filter(|(path, _)| {
@@ -326,6 +326,7 @@ pub fn gemini_message_to_universal(
         project_id,
         source_id,
         provider_id: "gemini".to_string(),
+        provider_root: None,
         timestamp: msg
             .timestamp
             .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
@@ -347,6 +348,43 @@ pub fn gemini_message_to_universal(
     })
 }
 
+/// Load every message of a Gemini session file as `UniversalMessage`s.
+///
+/// `session_id` defaults to the one produced by `gemini_file_to_session`, so
+/// messages can be joined back to their session. Unparseable messages are
+/// skipped.
+pub fn gemini_file_to_messages(
+    file_path: &Path,
+    session_id: Option<String>,
+    project_id: String,
+    source_id: String,
+) -> Result<Vec<UniversalMessage>, String> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| format!("Failed to read Gemini session: {}", e))?;
+
+    let session: GeminiSession = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse Gemini session: {}", e))?;
+
+    let session_id = session_id.unwrap_or_else(|| compute_sha256(&file_path.to_string_lossy()));
+    let messages = session.messages.or(session.history).unwrap_or_default();
+
+    Ok(messages
+        .iter()
+        .enumerate()
+        .filter_map(|(i, msg_value)| {
+            gemini_message_to_universal(
+                msg_value,
+                session_id.clone(),
+                project_id.clone(),
+                source_id.clone(),
+                i as i32,
+            )
+            .map_err(|e| eprintln!("Failed to parse Gemini message {}: {}", i, e))
+            .ok()
+        })
+        .collect())
+}
+
 // ============================================================================
 // HELPER FUNCTIONS
 // ============================================================================
@@ -381,7 +419,7 @@ fn read_gemini_project_root(project_dir: &Path) -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
-fn find_gemini_sessions(root: &Path) -> Result<Vec<PathBuf>, String> {
+pub(crate) fn find_gemini_sessions(root: &Path) -> Result<Vec<PathBuf>, String> {
     let mut sessions = Vec::new();
 
     fn visit_dirs(dir: &Path, sessions: &mut Vec<PathBuf>) -> std::io::Result<()> {
@@ -728,10 +766,11 @@ fn convert_gemini_tool_calls(msg: &GeminiMessage, raw: &Value) -> Option<Vec<Too
             } else {
                 None
             };
+            let name = map_gemini_tool_name(name);
             calls.push(ToolCall {
                 id,
-                name: map_gemini_tool_name(name).to_string(),
-                input: value_to_map(tc.get("args")),
+                name: name.to_string(),
+                input: normalize_gemini_tool_input(name, value_to_map(tc.get("args"))),
                 output,
                 error,
                 status,
@@ -749,10 +788,11 @@ fn convert_gemini_tool_calls(msg: &GeminiMessage, raw: &Value) -> Option<Vec<Too
                     .and_then(Value::as_str)
                     .map(String::from)
                     .unwrap_or_else(|| format!("fc-{}", uuid::Uuid::new_v4()));
+                let name = map_gemini_tool_name(name);
                 calls.push(ToolCall {
                     id,
-                    name: map_gemini_tool_name(name).to_string(),
-                    input: value_to_map(fc.get("args")),
+                    name: name.to_string(),
+                    input: normalize_gemini_tool_input(name, value_to_map(fc.get("args"))),
                     output: None,
                     error: None,
                     status: ToolCallStatus::Success,
@@ -769,10 +809,11 @@ fn convert_gemini_tool_calls(msg: &GeminiMessage, raw: &Value) -> Option<Vec<Too
         )
     {
         if let Some(name) = msg.name.clone().or_else(|| msg.tool.clone()) {
+            let name = map_gemini_tool_name(&name);
             calls.push(ToolCall {
                 id: format!("tool-{}", uuid::Uuid::new_v4()),
-                name: map_gemini_tool_name(&name).to_string(),
-                input: value_to_map(msg.input.as_ref()),
+                name: name.to_string(),
+                input: normalize_gemini_tool_input(name, value_to_map(msg.input.as_ref())),
                 output: None,
                 error: None,
                 status: ToolCallStatus::Success,
@@ -816,9 +857,9 @@ fn map_gemini_tool_name(name: &str) -> &str {
     match name {
         "read_file" | "ReadFile" => "Read",
         "write_file" | "WriteFile" | "create_file" => "Write",
-        "edit_file" | "EditFile" => "Edit",
+        "edit_file" | "EditFile" | "replace" => "Edit",
         "shell" | "run_command" | "execute_command" => "Bash",
-        "list_directory" | "list_dir" => "Glob",
+        "list_directory" | "list_dir" | "glob" => "Glob",
         "search_files" | "grep" => "Grep",
         "web_search" => "WebSearch",
         "web_fetch" => "WebFetch",
@@ -826,6 +867,29 @@ fn map_gemini_tool_name(name: &str) -> &str {
     }
 }
 
+/// Rename Gemini tool arguments to the keys used by the canonical tools, so
+/// downstream consumers (e.g. file activity extraction) can read them
+/// without provider-specific cases. Existing canonical keys win.
+fn normalize_gemini_tool_input(
+    canonical_name: &str,
+    mut input: HashMap<String, Value>,
+) -> HashMap<String, Value> {
+    let aliases: &[(&str, &str)] = match canonical_name {
+        "Read" | "Write" | "Edit" => &[("absolute_path", "file_path"), ("path", "file_path")],
+        "Glob" => &[("path", "pattern"), ("dir_path", "pattern")],
+        _ => &[],
+    };
+    for (from, to) in aliases {
+        if input.contains_key(*to) {
+            break;
+        }
+        if let Some(value) = input.remove(*from) {
+            input.insert(to.to_string(), value);
+        }
+    }
+    input
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -897,6 +961,29 @@ mod tests {
         assert_eq!(output["content"], "file content");
     }
 
+    #[test]
+    fn test_tool_call_inputs_normalized_to_canonical_keys() {
+        let msg = to_msg(json!({
+            "type": "gemini",
+            "content": "",
+            "toolCalls": [
+                {"id": "t1", "name": "read_file", "args": {"absolute_path": "/a.rs"}},
+                {"id": "t2", "name": "replace", "args": {
+                    "file_path": "/b.rs", "old_string": "x", "new_string": "y"
+                }},
+                {"id": "t3", "name": "list_directory", "args": {"path": "/src"}}
+            ]
+        }));
+        let calls = msg.tool_calls.unwrap();
+        assert_eq!(calls[0].name, "Read");
+        assert_eq!(calls[0].input["file_path"], "/a.rs");
+        assert!(!calls[0].input.contains_key("absolute_path"));
+        assert_eq!(calls[1].name, "Edit");
+        assert_eq!(calls[1].input["old_string"], "x");
+        assert_eq!(calls[2].name, "Glob");
+        assert_eq!(calls[2].input["pattern"], "/src");
+    }
+
     #[test]
     fn test_tool_call_error_status() {
         let msg = to_msg(json!({
@@ -137,7 +137,7 @@ pub fn get_opencode_base_path() -> Option<PathBuf> {
     }
 
     // Priority 3: ~/.local/share/opencode (Linux/macOS default)
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = crate::utils::resolve_home() {
         let path = home.join(".local").join("share").join("opencode");
         if path.exists() {
             return Some(path);
@@ -990,6 +990,7 @@ fn opencode_message_to_universal(
         project_id: project_id.to_string(),
         source_id: source_id.to_string(),
         provider_id: "opencode".to_string(),
+        provider_root: None,
         timestamp: epoch_ms_to_rfc3339(msg.time.created),
         sequence_number,
         role,
@@ -8,7 +8,6 @@
 pub struct ProviderCapabilities {
     pub supports_resume: bool,
     pub resume_command_template: Option<String>, // e.g., "claude --resume {session_id}"
-    #[allow(dead_code)]
     pub cli_name: Option<String>,                // e.g., "claude", "codex"
     pub resume_type: ResumeType,                 // How this provider handles resume
 }
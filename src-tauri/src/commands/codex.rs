@@ -6,7 +6,10 @@
 // CLEAN CODE: Explicit types, standardized error prefixes, provider abstraction
 
 use crate::commands::adapters::codex::*;
+use crate::commands::search_match::{message_matches, MessageFilter, QueryMatcher};
 use crate::models::universal::*;
+use crate::models::{SearchFilters, SearchResult};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -16,11 +19,11 @@ use std::path::{Path, PathBuf};
 // ============================================================================
 
 /// Get default Codex CLI installation path
-/// Returns: ~/.codex/agent-sessions
+/// Returns: ~/.codex/sessions
 /// CLEAN CODE: Explicit return type, standardized error messages
 #[tauri::command]
 pub async fn get_codex_path() -> Result<String, String> {
-    let home_dir: PathBuf = dirs::home_dir()
+    let home_dir: PathBuf = crate::utils::resolve_home()
         .ok_or("HOME_DIRECTORY_NOT_FOUND: Could not determine home directory")?;
 
     // Codex CLI stores sessions at ~/.codex/sessions (with YYYY/MM/DD subdirectories)
@@ -104,74 +107,21 @@ pub async fn scan_codex_projects(
         ));
     }
 
-    // Group rollout files by session ID
-    let mut session_groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
-
-    // Recursively find all rollout files (handles YYYY/MM/DD subdirectories)
-    fn find_rollout_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
-        if dir.is_dir() {
-            for entry in fs::read_dir(dir)? {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_dir() {
-                    // Recurse into subdirectories
-                    find_rollout_files(&path, files)?;
-                } else if path.is_file() {
-                    let filename = path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("");
-
-                    // Check if it's a rollout file
-                    if filename.starts_with("rollout-") && filename.ends_with(".jsonl") {
-                        files.push(path);
-                    }
-                }
-            }
-        }
-        Ok(())
-    }
-
-    let mut rollout_files: Vec<PathBuf> = Vec::new();
-    find_rollout_files(path, &mut rollout_files)
-        .map_err(|e| format!("CODEX_READ_ERROR: Failed to scan directory: {}", e))?;
-
-    println!("📁 Found {} rollout file(s)", rollout_files.len());
-
-    for file_path in rollout_files {
-        let filename: String = file_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        // Parse filename to extract UUID
-        if let Some((_, uuid)) = parse_rollout_filename(&filename) {
-            // Try to get actual session ID from file
-            let session_id: String = match parse_codex_jsonl(&file_path) {
-                Ok(events) => {
-                    if let Some(first_event) = events.first() {
-                        extract_session_id(first_event, &uuid)
-                    } else {
-                        uuid.clone()
-                    }
-                }
-                Err(_) => uuid.clone(), // Fallback to UUID if parse fails
-            };
-
-            session_groups
-                .entry(session_id)
-                .or_insert_with(Vec::new)
-                .push(file_path);
-        }
-    }
+    let session_groups: Vec<CodexSessionGroup> = collect_codex_session_groups(path)?;
 
     // Convert session groups to UniversalProjects
     let mut projects: Vec<UniversalProject> = Vec::new();
 
-    for (session_id, files) in session_groups {
-        // Use first file to extract metadata
-        let first_file: &PathBuf = files.first().ok_or("CODEX_ERROR: Empty file group")?;
+    for group in session_groups {
+        let CodexSessionGroup {
+            session_id,
+            files,
+            message_count,
+            first_message_at,
+            last_message_at,
+        } = group;
+        // Use first (oldest) file to extract metadata
+        let first_file: &String = files.first().ok_or("CODEX_ERROR: Empty file group")?;
 
         let display_name: String = format!("Codex Session {}", session_id.chars().take(8).collect::<String>());
 
@@ -184,11 +134,11 @@ pub async fn scan_codex_projects(
             source_id: source_id.clone(),
             provider_id: "codex".to_string(),
             name: display_name,
-            path: first_file.to_string_lossy().to_string(),
+            path: first_file.clone(),
             session_count: files.len(),
-            total_messages: 0, // TODO: Count messages across all sessions
-            first_activity_at: None,
-            last_activity_at: None,
+            total_messages: message_count,
+            first_activity_at: first_message_at,
+            last_activity_at: last_message_at,
             metadata,
         });
     }
@@ -200,6 +150,142 @@ pub async fn scan_codex_projects(
     Ok(projects)
 }
 
+/// Rollout files forming one logical Codex session: a rollout plus the
+/// rollouts that resumed it, which share its `internal.session.id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSessionGroup {
+    pub session_id: String,
+    /// Rollout file paths, oldest first
+    pub files: Vec<String>,
+    /// Events across all files of the group
+    pub message_count: usize,
+    /// Earliest event timestamp across the files
+    pub first_message_at: Option<String>,
+    /// Latest event timestamp across the files
+    pub last_message_at: Option<String>,
+}
+
+/// Group Codex rollout files into logical sessions, so a resumed session
+/// shows up once with its combined message count and time span.
+/// Groups are ordered by most recent activity first.
+#[tauri::command]
+pub async fn group_codex_sessions(codex_path: String) -> Result<Vec<CodexSessionGroup>, String> {
+    let path: &Path = Path::new(&codex_path);
+    if !path.exists() {
+        return Err(format!(
+            "CODEX_PATH_ERROR: Path does not exist: {}",
+            codex_path
+        ));
+    }
+
+    let mut groups = collect_codex_session_groups(path)?;
+    groups.sort_by(|a, b| match (&a.last_message_at, &b.last_message_at) {
+        (Some(a_ts), Some(b_ts)) => b_ts.cmp(a_ts),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.session_id.cmp(&b.session_id),
+    });
+    Ok(groups)
+}
+
+/// Recursively find all rollout files (handles YYYY/MM/DD subdirectories)
+pub(crate) fn find_rollout_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if dir.is_dir() {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                // Recurse into subdirectories
+                find_rollout_files(&path, files)?;
+            } else if path.is_file() {
+                let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+                // Check if it's a rollout file
+                if filename.starts_with("rollout-") && filename.ends_with(".jsonl") {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Group the rollout files under `codex_dir` by session ID
+/// (`internal.session.id`, falling back to the filename UUID).
+/// Files within a group are ordered by their rollout timestamp.
+fn collect_codex_session_groups(codex_dir: &Path) -> Result<Vec<CodexSessionGroup>, String> {
+    let mut rollout_files: Vec<PathBuf> = Vec::new();
+    find_rollout_files(codex_dir, &mut rollout_files)
+        .map_err(|e| format!("CODEX_READ_ERROR: Failed to scan directory: {}", e))?;
+
+    println!("📁 Found {} rollout file(s)", rollout_files.len());
+
+    // Session ID -> (rollout timestamp, file) plus running totals
+    let mut groups: HashMap<String, (Vec<(String, PathBuf)>, CodexSessionGroup)> = HashMap::new();
+
+    for file_path in rollout_files {
+        let filename: String = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_string();
+
+        // Parse filename to extract UUID
+        let Some((rollout_timestamp, uuid)) = parse_rollout_filename(&filename) else {
+            continue;
+        };
+
+        // Try to get actual session ID from file
+        let events: Vec<CodexEvent> = parse_codex_jsonl(&file_path).unwrap_or_default();
+        let session_id: String = match events.first() {
+            Some(first_event) => extract_session_id(first_event, &uuid),
+            None => uuid.clone(), // Fallback to UUID if parse fails
+        };
+
+        let (files, group) = groups.entry(session_id.clone()).or_insert_with(|| {
+            (
+                Vec::new(),
+                CodexSessionGroup {
+                    session_id,
+                    files: Vec::new(),
+                    message_count: 0,
+                    first_message_at: None,
+                    last_message_at: None,
+                },
+            )
+        });
+        files.push((rollout_timestamp, file_path));
+        group.message_count += events.len();
+        let timestamps = || events.iter().filter_map(|e| e.timestamp.clone());
+        group.first_message_at = group
+            .first_message_at
+            .take()
+            .into_iter()
+            .chain(timestamps())
+            .min();
+        group.last_message_at = group
+            .last_message_at
+            .take()
+            .into_iter()
+            .chain(timestamps())
+            .max();
+    }
+
+    Ok(groups
+        .into_values()
+        .map(|(mut files, mut group)| {
+            files.sort();
+            group.files = files
+                .into_iter()
+                .map(|(_, path)| path.to_string_lossy().to_string())
+                .collect();
+            group
+        })
+        .collect())
+}
+
 /// Load sessions for a Codex project
 /// For Codex, each rollout file is a session
 /// CLEAN CODE: Explicit return type, proper pagination
@@ -223,8 +309,59 @@ pub async fn load_codex_sessions(
         ));
     }
 
+    let session: UniversalSession = rollout_to_session(&file_path, project_id, source_id)?;
+    Ok(vec![session])
+}
+
+/// List every Codex rollout file under `codex_path` as a session, newest
+/// first. Unlike [`scan_codex_projects`], resumed rollouts are not merged;
+/// each session's `project_id` is its resolved session ID, so rollouts of
+/// one resumed session share it.
+#[tauri::command]
+pub async fn scan_codex_sessions(
+    codex_path: String,
+    source_id: String,
+) -> Result<Vec<UniversalSession>, String> {
+    let path: &Path = Path::new(&codex_path);
+
+    if !path.exists() {
+        return Err(format!(
+            "CODEX_PATH_ERROR: Path does not exist: {}",
+            codex_path
+        ));
+    }
+
+    let mut rollout_files: Vec<PathBuf> = Vec::new();
+    find_rollout_files(path, &mut rollout_files)
+        .map_err(|e| format!("CODEX_READ_ERROR: Failed to scan directory: {}", e))?;
+
+    let mut sessions: Vec<UniversalSession> = Vec::new();
+    for file_path in rollout_files {
+        // project_id is replaced by the session ID below
+        match rollout_to_session(&file_path, String::new(), source_id.clone()) {
+            Ok(mut session) => {
+                session.project_id = session.id.clone();
+                sessions.push(session);
+            }
+            Err(e) => log::warn!("Skipping {}: {}", file_path.display(), e),
+        }
+    }
+
+    sessions.sort_by(|a, b| b.last_message_at.cmp(&a.last_message_at));
+    Ok(sessions)
+}
+
+/// Build the session for one rollout file. The session ID is resolved with
+/// [`extract_session_id`] (`internal.session.id`, then the filename UUID).
+fn rollout_to_session(
+    file_path: &Path,
+    project_id: String,
+    source_id: String,
+) -> Result<UniversalSession, String> {
+    let project_path: String = file_path.to_string_lossy().to_string();
+
     // Parse JSONL to get message count and metadata
-    let events: Vec<CodexEvent> = parse_codex_jsonl(&file_path)?;
+    let events: Vec<CodexEvent> = parse_codex_jsonl(file_path)?;
     let message_count: usize = events.len();
 
     // Extract session ID from first event or filename
@@ -243,13 +380,7 @@ pub async fn load_codex_sessions(
         uuid.clone()
     };
 
-    // Extract CWD from first event
-    let cwd: Option<String> = events
-        .first()
-        .and_then(|e| e.environment_context.as_ref())
-        .and_then(|ctx| ctx.get("cwd"))
-        .and_then(|v| v.as_str())
-        .map(String::from);
+    let cwd: Option<String> = rollout_cwd(&events);
 
     // Metadata with camelCase keys
     let mut metadata: HashMap<String, serde_json::Value> = HashMap::new();
@@ -308,7 +439,7 @@ pub async fn load_codex_sessions(
         checksum: format!("{:x}", session_id.len()), // Simple checksum placeholder
     };
 
-    Ok(vec![session])
+    Ok(session)
 }
 
 /// Load messages for a Codex session (paginated)
@@ -328,6 +459,119 @@ pub async fn load_codex_messages(
 
     // Parse JSONL file
     let events: Vec<CodexEvent> = parse_codex_jsonl(&file_path)?;
+    let mut messages: Vec<UniversalMessage> = rollout_messages(&file_path, &events)?;
+
+    // Apply pagination
+    let total: usize = messages.len();
+    let start: usize = offset.min(total);
+    let end: usize = (offset + limit).min(total);
+
+    messages = messages[start..end].to_vec();
+
+    println!("✓ Loaded {} messages ({}..{} of {})", messages.len(), start, end, total);
+    Ok(messages)
+}
+
+/// Default page size of [`search_codex_messages`].
+const CODEX_SEARCH_PAGE_SIZE: usize = 100;
+
+/// Search every Codex rollout under `codex_path` for `query`.
+///
+/// A message matches when its content contains every query term (ASCII
+/// case-insensitive), as in the federated search. Returns the matches in
+/// `[offset, offset + limit)`, newest first (`limit` defaults to
+/// [`CODEX_SEARCH_PAGE_SIZE`]), with the total match count. `projects` is
+/// matched against each rollout's working directory and the other filters
+/// are applied per message; `fuzzy` is not supported and is reported in
+/// `ignoredFilters`.
+#[tauri::command]
+pub async fn search_codex_messages(
+    codex_path: String,
+    query: String,
+    filters: SearchFilters,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<SearchResult, String> {
+    let limit = limit.unwrap_or(CODEX_SEARCH_PAGE_SIZE);
+    let offset = offset.unwrap_or(0);
+    let path: &Path = Path::new(&codex_path);
+
+    if !path.exists() {
+        return Err(format!(
+            "CODEX_PATH_ERROR: Path does not exist: {}",
+            codex_path
+        ));
+    }
+
+    let matcher = QueryMatcher::from_query(&query);
+    let message_filter = MessageFilter::new(&filters);
+    let mut matches: Vec<UniversalMessage> = Vec::new();
+
+    if !matcher.is_empty() {
+        let mut rollout_files: Vec<PathBuf> = Vec::new();
+        find_rollout_files(path, &mut rollout_files)
+            .map_err(|e| format!("CODEX_READ_ERROR: Failed to scan directory: {}", e))?;
+
+        for file_path in rollout_files {
+            let events: Vec<CodexEvent> = match parse_codex_jsonl(&file_path) {
+                Ok(events) => events,
+                Err(e) => {
+                    log::warn!("Skipping {}: {}", file_path.display(), e);
+                    continue;
+                }
+            };
+
+            if let Some(ref projects) = filters.projects {
+                let in_project = rollout_cwd(&events)
+                    .is_some_and(|cwd| projects.iter().any(|p| cwd.contains(p.as_str())));
+                if !in_project {
+                    continue;
+                }
+            }
+
+            match rollout_messages(&file_path, &events) {
+                Ok(messages) => {
+                    matches.extend(messages.into_iter().filter(|msg| {
+                        message_matches(msg, &matcher) && message_filter.matches(msg)
+                    }))
+                }
+                Err(e) => log::warn!("Skipping {}: {}", file_path.display(), e),
+            }
+        }
+    }
+
+    matches.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    let total = matches.len();
+    let ignored_filters = if filters.fuzzy == Some(true) {
+        vec!["fuzzy".to_string()]
+    } else {
+        Vec::new()
+    };
+    Ok(SearchResult {
+        messages: matches.into_iter().skip(offset).take(limit).collect(),
+        total,
+        has_more: total > offset.saturating_add(limit),
+        ignored_filters,
+    })
+}
+
+/// Working directory recorded in the rollout's first event.
+fn rollout_cwd(events: &[CodexEvent]) -> Option<String> {
+    events
+        .first()
+        .and_then(|e| e.environment_context.as_ref())
+        .and_then(|ctx| ctx.get("cwd"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+/// All messages of one parsed rollout file, tagged with its session ID.
+pub(crate) fn rollout_messages(
+    file_path: &Path,
+    events: &[CodexEvent],
+) -> Result<Vec<UniversalMessage>, String> {
+    let session_path: String = file_path.to_string_lossy().to_string();
 
     // Extract session ID from filename
     let filename: String = file_path
@@ -339,45 +583,19 @@ pub async fn load_codex_messages(
     let (_, uuid) = parse_rollout_filename(&filename)
         .ok_or("CODEX_PARSE_ERROR: Invalid rollout filename format")?;
 
-    // Filter and convert events to UniversalMessages
-    // Only process events that are actual messages (response_item with type: message)
-    let mut messages: Vec<UniversalMessage> = events
-        .iter()
-        .enumerate()
-        .filter(|(_, event)| {
-            // Include response_item events with payload.type == "message"
-            if event.event_type == "response_item" {
-                if let Some(ref payload) = event.payload {
-                    if let Some(msg_type) = payload.get("type").and_then(|t| t.as_str()) {
-                        return msg_type == "message";
-                    }
-                }
-            }
-            // Also include event_msg events (backup)
-            event.event_type == "event_msg"
-        })
-        .map(|(idx, event)| {
-            let session_id: String = extract_session_id(event, &uuid);
-            let mut msg: UniversalMessage = codex_event_to_universal(
-                event,
-                "codex".to_string(), // project_id
-                session_path.clone(), // source_id
-                idx as i32,
-                &session_path,
-            );
-            msg.session_id = session_id;
-            msg
-        })
-        .collect();
-
-    // Apply pagination
-    let total: usize = messages.len();
-    let start: usize = offset.min(total);
-    let end: usize = (offset + limit).min(total);
-
-    messages = messages[start..end].to_vec();
-
-    println!("✓ Loaded {} messages ({}..{} of {})", messages.len(), start, end, total);
+    // Same session ID resolution as load_codex_sessions (first event, then filename)
+    let session_id: String = events
+        .first()
+        .map(|event| extract_session_id(event, &uuid))
+        .unwrap_or_else(|| uuid.clone());
+
+    // Group raw events into logical messages: one assistant message per turn,
+    // with its reasoning, function calls and their outputs attached
+    let mut messages: Vec<UniversalMessage> =
+        coalesce_codex_events(events, "codex", &session_path, &session_path);
+    for msg in messages.iter_mut() {
+        msg.session_id = session_id.clone();
+    }
     Ok(messages)
 }
 
@@ -393,3 +611,208 @@ fn determine_role(event: &CodexEvent) -> MessageRole {
         _ => MessageRole::Assistant,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_rollout(dir: &Path, name: &str, lines: &[&str]) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(name), lines.join("\n")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn groups_resumed_rollouts_by_session_id() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let day1 = tmp.path().join("2025/06/01");
+        let day2 = tmp.path().join("2025/06/02");
+        let event = |ts: &str, session: &str| {
+            format!(
+                r#"{{"type":"message","timestamp":"{}","internal":{{"session":{{"id":"{}"}}}}}}"#,
+                ts, session
+            )
+        };
+        write_rollout(
+            &day1,
+            "rollout-2025-06-01T10-00-00-aaaaaaaa-0000-0000-0000-000000000001.jsonl",
+            &[
+                &event("2025-06-01T10:00:00Z", "chain"),
+                &event("2025-06-01T10:05:00Z", "chain"),
+            ],
+        );
+        write_rollout(
+            &day2,
+            "rollout-2025-06-02T09-00-00-aaaaaaaa-0000-0000-0000-000000000002.jsonl",
+            &[
+                &event("2025-06-02T09:00:00Z", "chain"),
+                &event("2025-06-02T09:30:00Z", "chain"),
+                &event("2025-06-02T09:45:00Z", "chain"),
+            ],
+        );
+        write_rollout(
+            &day1,
+            "rollout-2025-06-01T08-00-00-bbbbbbbb-0000-0000-0000-000000000003.jsonl",
+            &[r#"{"type":"message","timestamp":"2025-06-01T08:00:00Z"}"#],
+        );
+
+        let groups = group_codex_sessions(tmp.path().to_string_lossy().to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(groups.len(), 2);
+        let chain = &groups[0];
+        assert_eq!(chain.session_id, "chain");
+        assert_eq!(chain.files.len(), 2);
+        assert!(chain.files[0].contains("2025-06-01T10-00-00"));
+        assert_eq!(chain.message_count, 5);
+        assert_eq!(
+            chain.first_message_at.as_deref(),
+            Some("2025-06-01T10:00:00Z")
+        );
+        assert_eq!(
+            chain.last_message_at.as_deref(),
+            Some("2025-06-02T09:45:00Z")
+        );
+
+        // Without internal.session.id the filename UUID is the session id
+        assert_eq!(groups[1].session_id, "bbbbbbbb-0000-0000-0000-000000000003");
+        assert_eq!(groups[1].message_count, 1);
+    }
+
+    #[tokio::test]
+    async fn scans_each_rollout_as_a_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let day = tmp.path().join("2025/06/01");
+        write_rollout(
+            &day,
+            "rollout-2025-06-01T10-00-00-aaaaaaaa-0000-0000-0000-000000000001.jsonl",
+            &[
+                r#"{"type":"message","timestamp":"2025-06-01T10:00:00Z","internal":{"session":{"id":"chain"}}}"#,
+            ],
+        );
+        write_rollout(
+            &day,
+            "rollout-2025-06-01T08-00-00-bbbbbbbb-0000-0000-0000-000000000002.jsonl",
+            &[r#"{"type":"message","timestamp":"2025-06-01T08:00:00Z"}"#],
+        );
+        write_rollout(&day, "rollout-not-a-valid-name.jsonl", &["{}"]);
+
+        let sessions =
+            scan_codex_sessions(tmp.path().to_string_lossy().to_string(), "src".to_string())
+                .await
+                .unwrap();
+
+        let ids: Vec<(&str, &str)> = sessions
+            .iter()
+            .map(|s| (s.id.as_str(), s.project_id.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("chain", "chain"),
+                (
+                    "bbbbbbbb-0000-0000-0000-000000000002",
+                    "bbbbbbbb-0000-0000-0000-000000000002"
+                ),
+            ]
+        );
+        assert_eq!(sessions[0].source_id, "src");
+    }
+
+    #[tokio::test]
+    async fn searches_rollouts_with_filters_newest_first() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let day = tmp.path().join("2025/06/01");
+        let first = |cwd: &str| {
+            format!(
+                r#"{{"type":"session_meta","timestamp":"2025-06-01T00:00:00Z","environment_context":{{"cwd":"{}"}}}}"#,
+                cwd
+            )
+        };
+        let message = |ts: &str, role: &str, kind: &str, text: &str| {
+            format!(
+                r#"{{"type":"response_item","timestamp":"{}","payload":{{"type":"message","role":"{}","content":[{{"type":"{}","text":"{}"}}]}}}}"#,
+                ts, role, kind, text
+            )
+        };
+        write_rollout(
+            &day,
+            "rollout-2025-06-01T10-00-00-aaaaaaaa-0000-0000-0000-000000000001.jsonl",
+            &[
+                &first("/work/app"),
+                &message(
+                    "2025-06-01T10:00:00Z",
+                    "user",
+                    "input_text",
+                    "deploy the app",
+                ),
+                &message(
+                    "2025-06-01T10:01:00Z",
+                    "assistant",
+                    "output_text",
+                    "Deploying now",
+                ),
+                &message("2025-06-01T10:02:00Z", "user", "input_text", "thanks"),
+            ],
+        );
+        write_rollout(
+            &day,
+            "rollout-2025-06-01T09-00-00-bbbbbbbb-0000-0000-0000-000000000002.jsonl",
+            &[
+                &first("/work/other"),
+                &message("2025-06-01T09:00:00Z", "user", "input_text", "deploy other"),
+            ],
+        );
+
+        let codex_path = tmp.path().to_string_lossy().to_string();
+        let search = |filters: SearchFilters, limit: Option<usize>| {
+            search_codex_messages(
+                codex_path.clone(),
+                "DEPLOY".to_string(),
+                filters,
+                limit,
+                None,
+            )
+        };
+        let timestamps = |result: &SearchResult| -> Vec<String> {
+            result
+                .messages
+                .iter()
+                .map(|m| m.timestamp.clone())
+                .collect()
+        };
+
+        let all = search(SearchFilters::default(), None).await.unwrap();
+        assert_eq!(
+            timestamps(&all),
+            vec![
+                "2025-06-01T10:01:00Z",
+                "2025-06-01T10:00:00Z",
+                "2025-06-01T09:00:00Z"
+            ]
+        );
+        assert!(all.messages.iter().all(|m| m.provider_id == "codex"));
+        assert_eq!(
+            all.messages[2].session_id,
+            "bbbbbbbb-0000-0000-0000-000000000002"
+        );
+
+        let in_app = SearchFilters {
+            projects: Some(vec!["/work/app".to_string()]),
+            message_type: Some("user".to_string()),
+            ..Default::default()
+        };
+        let page = search(in_app, None).await.unwrap();
+        assert_eq!(timestamps(&page), vec!["2025-06-01T10:00:00Z"]);
+
+        let fuzzy = SearchFilters {
+            fuzzy: Some(true),
+            ..Default::default()
+        };
+        let first_page = search(fuzzy, Some(1)).await.unwrap();
+        assert_eq!(first_page.messages.len(), 1);
+        assert_eq!(first_page.total, 3);
+        assert!(first_page.has_more);
+        assert_eq!(first_page.ignored_filters, vec!["fuzzy".to_string()]);
+    }
+}
@@ -0,0 +1,263 @@
+//! Session context usage analysis
+//!
+//! `find_duplicate_tool_results` spots tool results that were fed back into
+//! the context more than once (e.g. the same file read ten times) and
+//! estimates the tokens those repeats cost. `get_context_pressure` tracks
+//! how full the context window was at each assistant turn.
+
+use crate::commands::stats::estimate_tokens;
+use crate::models::{ContextPoint, DuplicateGroup, RawLogEntry};
+use crate::utils::{content_hash, read_session_to_string};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Text of a `tool_result` block: its string content, or the `text` blocks of
+/// an array content joined by newlines.
+fn tool_result_text(block: &Value) -> String {
+    match block.get("content") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Content blocks of `content` with the given `type`.
+fn blocks_of_type<'a>(content: &'a Value, block_type: &'a str) -> impl Iterator<Item = &'a Value> {
+    content
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(move |block| block.get("type").and_then(Value::as_str) == Some(block_type))
+}
+
+/// Group the tool results of the session JSONL `content` by content hash,
+/// keeping groups with at least two results, most wasted tokens first.
+fn group_duplicate_tool_results(content: &str) -> Vec<DuplicateGroup> {
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    let mut group_index: HashMap<String, usize> = HashMap::new();
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<RawLogEntry>(line) else {
+            continue;
+        };
+        let Some(message) = entry.message else {
+            continue;
+        };
+
+        for tool_use in blocks_of_type(&message.content, "tool_use") {
+            if let (Some(id), Some(name)) = (
+                tool_use.get("id").and_then(Value::as_str),
+                tool_use.get("name").and_then(Value::as_str),
+            ) {
+                tool_names.insert(id.to_string(), name.to_string());
+            }
+        }
+
+        for result in blocks_of_type(&message.content, "tool_result") {
+            let text = tool_result_text(result);
+            if text.is_empty() {
+                continue;
+            }
+            let tool_use_id = result
+                .get("tool_use_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            // Same hash the loaders attach to string tool results
+            let hash = content_hash(&json!({ "content": text }));
+
+            match group_index.get(&hash) {
+                Some(&idx) => groups[idx].tool_use_ids.push(tool_use_id),
+                None => {
+                    group_index.insert(hash.clone(), groups.len());
+                    groups.push(DuplicateGroup {
+                        hash,
+                        tool_name: tool_names.get(&tool_use_id).cloned(),
+                        tool_use_ids: vec![tool_use_id],
+                        count: 0,
+                        tokens_per_result: estimate_tokens(&text),
+                        wasted_tokens: 0,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateGroup> = groups
+        .into_iter()
+        .filter(|group| group.tool_use_ids.len() > 1)
+        .map(|mut group| {
+            group.count = group.tool_use_ids.len();
+            group.wasted_tokens = group.tokens_per_result * (group.count - 1);
+            group
+        })
+        .collect();
+    duplicates.sort_by_key(|group| std::cmp::Reverse(group.wasted_tokens));
+    duplicates
+}
+
+/// Context window of current Claude models, in tokens.
+const CONTEXT_LIMIT_TOKENS: u64 = 200_000;
+
+/// Fraction of the context limit at which a turn is flagged as near it.
+const NEAR_LIMIT_RATIO: f64 = 0.8;
+
+/// Text of message `content` that ends up in the context: plain strings,
+/// `text`/`thinking` blocks, tool results and serialized tool inputs.
+fn context_text(content: &Value) -> String {
+    let Some(blocks) = content.as_array() else {
+        return content.as_str().unwrap_or_default().to_string();
+    };
+    let mut parts: Vec<String> = Vec::new();
+    for block in blocks {
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => {
+                parts.extend(block.get("text").and_then(Value::as_str).map(String::from))
+            }
+            Some("thinking") => parts.extend(
+                block
+                    .get("thinking")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            ),
+            Some("tool_result") => parts.push(tool_result_text(block)),
+            Some("tool_use") => parts.extend(block.get("input").map(Value::to_string)),
+            _ => {}
+        }
+    }
+    parts.join("\n")
+}
+
+/// Context size at each assistant turn of the session JSONL `content`.
+///
+/// Turns with reported usage use it as is. Turns without usage extend the
+/// previous size by the approximate tokens of everything logged since it.
+/// Lines of one streamed API response (same `message.id`) form one turn.
+fn compute_context_pressure(content: &str) -> Vec<ContextPoint> {
+    let mut points: Vec<ContextPoint> = Vec::new();
+    let mut last_context: u64 = 0;
+    // Approximate tokens logged since `last_context` was measured
+    let mut pending: u64 = 0;
+    let mut last_response_id: Option<String> = None;
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<RawLogEntry>(line) else {
+            continue;
+        };
+        let Some(message) = entry.message else {
+            continue;
+        };
+        let tokens = estimate_tokens(&context_text(&message.content)) as u64;
+
+        let is_continuation =
+            message.role == "assistant" && message.id.is_some() && message.id == last_response_id;
+        if message.role != "assistant" || is_continuation {
+            pending += tokens;
+            continue;
+        }
+        last_response_id = message.id.clone();
+
+        let reported = message.usage.as_ref().and_then(|usage| {
+            usage.input_tokens.map(|input| {
+                u64::from(input)
+                    + u64::from(usage.cache_creation_input_tokens.unwrap_or(0))
+                    + u64::from(usage.cache_read_input_tokens.unwrap_or(0))
+            })
+        });
+        let context_tokens = reported.unwrap_or(last_context + pending);
+        let limit_ratio = context_tokens as f64 / CONTEXT_LIMIT_TOKENS as f64;
+
+        points.push(ContextPoint {
+            message_id: entry.uuid.unwrap_or_default(),
+            timestamp: entry.timestamp,
+            context_tokens,
+            estimated: reported.is_none(),
+            limit_ratio,
+            near_limit: limit_ratio >= NEAR_LIMIT_RATIO,
+        });
+        last_context = context_tokens;
+        pending = tokens;
+    }
+
+    points
+}
+
+/// Find tool results of a session whose content is identical, with the
+/// estimated token cost of every repeat after the first.
+#[tauri::command]
+pub async fn find_duplicate_tool_results(
+    session_path: String,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let content = read_session_to_string(Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+    Ok(group_duplicate_tool_results(&content))
+}
+
+/// Estimate how full the context window was at each assistant turn of a
+/// session, flagging turns near the limit of current models.
+#[tauri::command]
+pub async fn get_context_pressure(session_path: String) -> Result<Vec<ContextPoint>, String> {
+    let content = read_session_to_string(Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+    Ok(compute_context_pressure(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_results_grouped_with_wasted_tokens() {
+        let file = "x".repeat(400);
+        let lines = [
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Read","input":{}},{"type":"tool_use","id":"t2","name":"Read","input":{}}]}}"#.to_string(),
+            format!(r#"{{"type":"user","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"t1","content":"{file}"}}]}}}}"#),
+            format!(r#"{{"type":"user","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"t2","content":[{{"type":"text","text":"{file}"}}]}}]}}}}"#),
+            format!(r#"{{"type":"user","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"t3","content":"{file}"}}]}}}}"#),
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t4","content":"unique"}]}}"#.to_string(),
+        ]
+        .join("\n");
+
+        let groups = group_duplicate_tool_results(&lines);
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.tool_name.as_deref(), Some("Read"));
+        assert_eq!(group.tool_use_ids, vec!["t1", "t2", "t3"]);
+        assert_eq!(group.count, 3);
+        assert_eq!(group.tokens_per_result, 100);
+        assert_eq!(group.wasted_tokens, 200);
+    }
+
+    #[test]
+    fn context_pressure_uses_usage_and_estimates_gaps() {
+        let lines = [
+            r#"{"uuid":"u1","type":"user","message":{"role":"user","content":"hello"}}"#.to_string(),
+            r#"{"uuid":"a1","type":"assistant","message":{"role":"assistant","id":"r1","content":[{"type":"text","text":"hi"}],"usage":{"input_tokens":10,"cache_creation_input_tokens":1000,"cache_read_input_tokens":150000,"output_tokens":5}}}"#.to_string(),
+            r#"{"uuid":"a1b","type":"assistant","message":{"role":"assistant","id":"r1","content":[{"type":"text","text":"abcdefgh"}],"usage":{"input_tokens":10,"cache_creation_input_tokens":1000,"cache_read_input_tokens":150000,"output_tokens":5}}}"#.to_string(),
+            format!(r#"{{"uuid":"u2","type":"user","message":{{"role":"user","content":"{}"}}}}"#, "x".repeat(40_000)),
+            r#"{"uuid":"a2","type":"assistant","message":{"role":"assistant","id":"r2","content":[{"type":"text","text":"ok"}]}}"#.to_string(),
+        ]
+        .join("\n");
+
+        let points = compute_context_pressure(&lines);
+        assert_eq!(points.len(), 2);
+
+        assert_eq!(points[0].message_id, "a1");
+        assert_eq!(points[0].context_tokens, 151_010);
+        assert!(!points[0].estimated);
+        assert!(!points[0].near_limit);
+
+        // 151_010 + "hi" (1) + "abcdefgh" (2) + 10_000 for the user message
+        assert_eq!(points[1].message_id, "a2");
+        assert_eq!(points[1].context_tokens, 161_013);
+        assert!(points[1].estimated);
+        assert!(points[1].near_limit);
+    }
+}
@@ -5,11 +5,12 @@
 
 use crate::models::universal::TokenUsage;
 use crate::models::universal::*;
+use crate::models::SearchResult;
 use chrono::Utc;
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 // ============================================================================
@@ -25,6 +26,18 @@ pub struct CursorWorkspace {
     pub state_db_path: String,
     pub session_count: usize,
     pub last_activity: Option<String>, // ISO 8601 timestamp of most recent composer
+    /// Why `session_count` is zero (no composer metadata, composers without
+    /// messages, unreadable DB); `None` when the workspace has sessions
+    pub diagnostics: Option<String>,
+}
+
+/// Sessions found in one workspace by `count_workspace_composers_with_cached_conn`
+#[derive(Debug, Default)]
+struct WorkspaceComposerScan {
+    session_count: usize,
+    last_activity: Option<String>,
+    /// Set when `session_count` is zero
+    diagnostics: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,10 +50,25 @@ pub struct CursorSession {
     pub last_modified: String,
 }
 
+/// Health of Cursor's global chat database (`cursor_global_db_status`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CursorDbStatus {
+    /// `<cursor_path>/User/globalStorage/state.vscdb`
+    pub db_path: String,
+    pub exists: bool,
+    /// Opened read-only and its schema could be read
+    pub openable: bool,
+    pub has_disk_kv_table: bool,
+    /// Number of `bubbleId:` rows (chat messages) in `cursorDiskKV`
+    pub bubble_count: usize,
+    /// First error hit while probing, if any
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct CursorBubble {
     #[serde(rename = "type")]
-    bubble_type: Option<i32>, // 1 = user, 2 = assistant (Some entries might not have this)
+    bubble_type: Option<i32>, // See CURSOR_BUBBLE_ROLES (Some entries might not have this)
     #[serde(default)]
     text: String,
 
@@ -78,6 +106,57 @@ struct CursorBubble {
     // New Cursor format: toolFormerData contains actual file operations
     #[serde(rename = "toolFormerData", default)]
     tool_former_data: Option<serde_json::Value>,
+
+    // Creation time: ISO-8601 string, or epoch milliseconds in some builds
+    #[serde(rename = "createdAt", default)]
+    created_at: Option<serde_json::Value>,
+
+    // Request timing, epoch milliseconds (clientStartTime, clientEndTime, ...)
+    #[serde(rename = "timingInfo", default)]
+    timing_info: Option<serde_json::Value>,
+}
+
+/// `timingInfo` fields tried, in order, when a bubble has no `createdAt`.
+const CURSOR_TIMING_FIELDS: &[&str] = &[
+    "clientStartTime",
+    "clientRpcSendTime",
+    "clientSettleTime",
+    "clientEndTime",
+];
+
+/// A Cursor time value: an RFC 3339 string or epoch milliseconds (values
+/// below 1e11 are taken as seconds).
+fn parse_cursor_time(value: &serde_json::Value) -> Option<chrono::DateTime<Utc>> {
+    let epoch = match value {
+        serde_json::Value::String(s) => {
+            if let Ok(time) = chrono::DateTime::parse_from_rfc3339(s) {
+                return Some(time.with_timezone(&Utc));
+            }
+            s.trim().parse::<f64>().ok()?
+        }
+        serde_json::Value::Number(n) => n.as_f64()?,
+        _ => return None,
+    };
+    if !epoch.is_finite() || epoch <= 0.0 {
+        return None;
+    }
+    let millis = if epoch < 1e11 { epoch * 1000.0 } else { epoch };
+    chrono::DateTime::from_timestamp_millis(millis as i64)
+}
+
+impl CursorBubble {
+    /// When the bubble was created, if Cursor recorded it.
+    fn recorded_timestamp(&self) -> Option<chrono::DateTime<Utc>> {
+        self.created_at
+            .as_ref()
+            .and_then(parse_cursor_time)
+            .or_else(|| {
+                let timing = self.timing_info.as_ref()?;
+                CURSOR_TIMING_FIELDS
+                    .iter()
+                    .find_map(|field| timing.get(*field).and_then(parse_cursor_time))
+            })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -114,14 +193,28 @@ struct ComposerMetadata {
     created_at: Option<i64>, // Unix timestamp in milliseconds
 }
 
+/// Roles of the known Cursor bubble `type` codes. Other codes (system/tool
+/// bubbles of newer Cursor versions) map to `MessageRole::System`; the raw
+/// code is kept in `provider_metadata.bubble_type` so the UI can label them.
+const CURSOR_BUBBLE_ROLES: &[(i32, MessageRole)] =
+    &[(1, MessageRole::User), (2, MessageRole::Assistant)];
+
+/// Role of a bubble `type` code (see [`CURSOR_BUBBLE_ROLES`]).
+fn cursor_bubble_role(bubble_type: i32) -> MessageRole {
+    CURSOR_BUBBLE_ROLES
+        .iter()
+        .find(|(code, _)| *code == bubble_type)
+        .map_or(MessageRole::System, |(_, role)| role.clone())
+}
+
 // ============================================================================
 // CURSOR PATH DETECTION
 // ============================================================================
 
 #[tauri::command]
 pub async fn get_cursor_path() -> Result<String, String> {
-    let home_dir =
-        dirs::home_dir().ok_or("HOME_DIRECTORY_NOT_FOUND:Could not determine home directory")?;
+    let home_dir = crate::utils::resolve_home()
+        .ok_or("HOME_DIRECTORY_NOT_FOUND:Could not determine home directory")?;
 
     // Try platform-specific paths
     #[cfg(target_os = "windows")]
@@ -169,6 +262,71 @@ pub async fn validate_cursor_folder(path: String) -> Result<bool, String> {
     Ok(false)
 }
 
+/// Diagnose the Cursor global chat database before scanning: whether
+/// `User/globalStorage/state.vscdb` exists, opens (read-only), has the
+/// `cursorDiskKV` table and how many `bubbleId:` rows it holds. Problems are
+/// reported in the status rather than as errors.
+#[tauri::command]
+pub async fn cursor_global_db_status(cursor_path: String) -> Result<CursorDbStatus, String> {
+    let db_path = PathBuf::from(&cursor_path)
+        .join("User")
+        .join("globalStorage")
+        .join("state.vscdb");
+    let mut status = CursorDbStatus {
+        db_path: db_path.to_string_lossy().to_string(),
+        exists: db_path.is_file(),
+        openable: false,
+        has_disk_kv_table: false,
+        bubble_count: 0,
+        error: None,
+    };
+    if !status.exists {
+        return Ok(status);
+    }
+
+    let conn = match Connection::open_with_flags(
+        &db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    ) {
+        Ok(conn) => conn,
+        Err(e) => {
+            status.error = Some(format!("Failed to open database: {}", e));
+            return Ok(status);
+        }
+    };
+    // Cursor may hold a lock while running; don't wait on it forever
+    let _ = conn.execute_batch("PRAGMA busy_timeout = 3000;");
+
+    // Opening is lazy: reading the schema is what proves the file is a database
+    match conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='cursorDiskKV')",
+        params![],
+        |row| row.get::<_, bool>(0),
+    ) {
+        Ok(has_table) => {
+            status.openable = true;
+            status.has_disk_kv_table = has_table;
+        }
+        Err(e) => {
+            status.error = Some(format!("Failed to read database schema: {}", e));
+            return Ok(status);
+        }
+    }
+    if !status.has_disk_kv_table {
+        return Ok(status);
+    }
+
+    match conn.query_row(
+        "SELECT COUNT(*) FROM cursorDiskKV WHERE key LIKE 'bubbleId:%'",
+        params![],
+        |row| row.get::<_, i64>(0),
+    ) {
+        Ok(count) => status.bubble_count = count as usize,
+        Err(e) => status.error = Some(format!("Failed to count messages: {}", e)),
+    }
+    Ok(status)
+}
+
 // ============================================================================
 // WORKSPACE SCANNING
 // ============================================================================
@@ -218,14 +376,29 @@ pub async fn scan_cursor_workspaces(cursor_path: String) -> Result<Vec<CursorWor
         });
 
         // Count actual composers (sessions) that have messages for this workspace
-        let (session_count, last_activity) = if let Some(ref gconn) = global_conn {
-            count_workspace_composers_with_cached_conn(gconn, &state_db).unwrap_or((0, None))
-        } else {
-            (0, None)
+        let scan = match global_conn {
+            Some(ref gconn) => count_workspace_composers_with_cached_conn(gconn, &state_db)
+                .unwrap_or_else(|e| WorkspaceComposerScan {
+                    diagnostics: Some(format!("Workspace database unreadable: {}", e)),
+                    ..Default::default()
+                }),
+            None => WorkspaceComposerScan {
+                diagnostics: Some(
+                    "Cursor global chat database (User/globalStorage/state.vscdb) is missing, \
+                     unreadable or has no cursorDiskKV table"
+                        .to_string(),
+                ),
+                ..Default::default()
+            },
         };
+        let WorkspaceComposerScan {
+            session_count,
+            last_activity,
+            diagnostics,
+        } = scan;
 
         #[cfg(debug_assertions)]
-        println!(
+        log::debug!(
             "  📊 Workspace {}: {} sessions with messages (last: {:?})",
             workspace_id,
             session_count,
@@ -240,11 +413,12 @@ pub async fn scan_cursor_workspaces(cursor_path: String) -> Result<Vec<CursorWor
             state_db_path: state_db.to_string_lossy().to_string(),
             session_count,
             last_activity,
+            diagnostics,
         });
     }
 
     let elapsed = start_time.elapsed();
-    println!(
+    log::debug!(
         "📊 scan_cursor_workspaces: {} workspaces scanned in {}ms",
         workspaces.len(),
         elapsed.as_millis()
@@ -258,7 +432,7 @@ pub async fn scan_cursor_workspaces(cursor_path: String) -> Result<Vec<CursorWor
 fn count_workspace_composers_with_cached_conn(
     global_conn: &Connection,
     state_db: &PathBuf,
-) -> Result<(usize, Option<String>), String> {
+) -> Result<WorkspaceComposerScan, String> {
     // Open workspace database to read composer metadata (with timeout for lock contention)
     let conn = Connection::open(state_db)
         .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open workspace DB: {}", e))?;
@@ -276,16 +450,34 @@ fn count_workspace_composers_with_cached_conn(
                 .map_err(|e| format!("CURSOR_PARSE_ERROR: Failed to parse composer data: {}", e))?;
             workspace_composer_data.all_composers
         }
-        Err(_) => return Ok((0, None)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Ok(WorkspaceComposerScan {
+                diagnostics: Some(
+                    "No composer metadata (composer.composerData) in workspace".to_string(),
+                ),
+                ..Default::default()
+            })
+        }
+        Err(e) => {
+            return Err(format!(
+                "CURSOR_DB_ERROR: Failed to read composer data: {}",
+                e
+            ))
+        }
     };
 
     if composers.is_empty() {
-        return Ok((0, None));
+        return Ok(WorkspaceComposerScan {
+            diagnostics: Some("Workspace has no composers (chat sessions)".to_string()),
+            ..Default::default()
+        });
     }
+    let composer_total = composers.len();
 
     // Check which composers have messages using the shared global connection
     let mut count = 0;
     let mut most_recent_timestamp: Option<i64> = None;
+    let mut most_recent_created: Option<i64> = None;
 
     for composer in composers {
         // Use EXISTS for faster check (stops at first match instead of counting all)
@@ -303,20 +495,49 @@ fn count_workspace_composers_with_cached_conn(
                     None => Some(timestamp),
                 };
             }
+            most_recent_created = most_recent_created.max(composer.created_at);
         }
     }
 
-    let last_activity = most_recent_timestamp.map(|ts| {
-        chrono::DateTime::<Utc>::from_timestamp(ts / 1000, ((ts % 1000) * 1_000_000) as u32)
-            .unwrap_or_else(|| chrono::DateTime::<Utc>::from_timestamp(0, 0).unwrap())
-            .to_rfc3339()
+    // Fallback precedence when composers with messages lack `lastUpdatedAt`:
+    // newest `createdAt`, then the workspace DB's mtime (Cursor rewrites it
+    // when composers change). Bubble rowids carry no time, so they can't date
+    // activity.
+    let last_activity = if count == 0 {
+        None
+    } else {
+        most_recent_timestamp
+            .or(most_recent_created)
+            .map(|ts| {
+                chrono::DateTime::<Utc>::from_timestamp(ts / 1000, ((ts % 1000) * 1_000_000) as u32)
+                    .unwrap_or_else(|| chrono::DateTime::<Utc>::from_timestamp(0, 0).unwrap())
+                    .to_rfc3339()
+            })
+            .or_else(|| {
+                let modified = std::fs::metadata(state_db)
+                    .and_then(|m| m.modified())
+                    .ok()?;
+                Some(chrono::DateTime::<Utc>::from(modified).to_rfc3339())
+            })
+    };
+
+    let diagnostics = (count == 0).then(|| {
+        format!(
+            "{} composer(s) found but none has messages (bubbleId rows) in the global database",
+            composer_total
+        )
     });
 
-    Ok((count, last_activity))
+    Ok(WorkspaceComposerScan {
+        session_count: count,
+        last_activity,
+        diagnostics,
+    })
 }
 
 /// Count the number of composers (sessions) in a workspace that actually have messages
-/// Returns (count, most_recent_timestamp)
+/// Returns (count, most_recent_timestamp); see
+/// `count_workspace_composers_with_cached_conn` for the timestamp fallbacks
 /// NOTE: Legacy function — prefer count_workspace_composers_with_cached_conn for batch use
 fn count_workspace_composers_with_messages(
     cursor_base: &PathBuf,
@@ -328,7 +549,8 @@ fn count_workspace_composers_with_messages(
     }
     let global_conn = Connection::open(&session_dbs[0])
         .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open global DB: {}", e))?;
-    count_workspace_composers_with_cached_conn(&global_conn, state_db)
+    let scan = count_workspace_composers_with_cached_conn(&global_conn, state_db)?;
+    Ok((scan.session_count, scan.last_activity))
 }
 
 // ============================================================================
@@ -340,9 +562,9 @@ pub async fn load_cursor_sessions(
     cursor_path: String,
     workspace_id: Option<String>,
 ) -> Result<Vec<CursorSession>, String> {
-    println!("🔍 [Rust] load_cursor_sessions called:");
-    println!("  cursor_path: {}", cursor_path);
-    println!("  workspace_id: {:?}", workspace_id);
+    log::debug!("🔍 [Rust] load_cursor_sessions called:");
+    log::debug!("  cursor_path: {}", cursor_path);
+    log::debug!("  workspace_id: {:?}", workspace_id);
 
     let cursor_base = PathBuf::from(&cursor_path);
 
@@ -357,13 +579,13 @@ pub async fn load_cursor_sessions(
         return Err("CURSOR_INVALID_ARGUMENT: workspace_id is required".to_string());
     };
 
-    println!(
+    log::debug!(
         "  📂 Reading workspace storage: {}",
         workspace_storage_path.display()
     );
 
     if !workspace_storage_path.exists() {
-        println!("  ✗ Workspace storage not found");
+        log::debug!("  ✗ Workspace storage not found");
         return Ok(vec![]);
     }
 
@@ -381,7 +603,7 @@ pub async fn load_cursor_sessions(
         .ok();
 
     let workspace_composers: Vec<ComposerMetadata> = if let Some(json_str) = composer_data_json {
-        println!("  📋 Parsing composer data ({} chars)...", json_str.len());
+        log::debug!("  📋 Parsing composer data ({} chars)...", json_str.len());
 
         let workspace_composer_data: WorkspaceComposerData = serde_json::from_str(&json_str)
             .map_err(|e| {
@@ -391,20 +613,20 @@ pub async fn load_cursor_sessions(
                 )
             })?;
 
-        println!(
+        log::debug!(
             "  ✓ Found {} composers from workspace metadata",
             workspace_composer_data.all_composers.len()
         );
         workspace_composer_data.all_composers
     } else {
-        println!("  ⚠️  No workspace metadata found - will discover sessions from global DB");
+        log::debug!("  ⚠️  No workspace metadata found - will discover sessions from global DB");
         vec![]
     };
 
     let session_dbs = find_cursor_session_dbs(&cursor_base);
 
     if session_dbs.is_empty() {
-        println!("  ✗ No session databases found");
+        log::debug!("  ✗ No session databases found");
         return Ok(vec![]);
     }
 
@@ -412,7 +634,7 @@ pub async fn load_cursor_sessions(
 
     // Open the global database
     for global_db in session_dbs {
-        println!("  📂 Opening global database: {}", global_db.display());
+        log::debug!("  📂 Opening global database: {}", global_db.display());
 
         let conn = Connection::open(&global_db)
             .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open global database: {}", e))?;
@@ -446,21 +668,21 @@ pub async fn load_cursor_sessions(
             session_message_counts.insert(session_id, message_count as usize);
         }
 
-        println!(
+        log::debug!(
             "  📊 Found {} sessions in global DB:",
             session_message_counts.len()
         );
         for (sid, count) in session_message_counts.iter().take(5) {
-            println!("      - {}: {} messages", sid, count);
+            log::debug!("      - {}: {} messages", sid, count);
         }
         if session_message_counts.len() > 5 {
-            println!("      ... and {} more", session_message_counts.len() - 5);
+            log::debug!("      ... and {} more", session_message_counts.len() - 5);
         }
 
         // If we have workspace metadata, process only those sessions
         // Otherwise, process ALL sessions found in global DB
         if !workspace_composers.is_empty() {
-            println!(
+            log::debug!(
                 "  📋 Processing {} workspace composers:",
                 workspace_composers.len()
             );
@@ -470,7 +692,7 @@ pub async fn load_cursor_sessions(
                 let message_count = session_message_counts.get(session_id).copied().unwrap_or(0);
 
                 if message_count == 0 {
-                    println!(
+                    log::debug!(
                         "    ✗ Session {} has no messages in global DB, skipping",
                         session_id
                     );
@@ -498,9 +720,11 @@ pub async fn load_cursor_sessions(
                         Utc::now().to_rfc3339()
                     };
 
-                println!(
+                log::debug!(
                     "    ✓ Session: {} ({} messages, timestamp={})",
-                    session_id, message_count, last_modified_timestamp
+                    session_id,
+                    message_count,
+                    last_modified_timestamp
                 );
 
                 // Encode session ID, workspace ID, and timestamp in db_path
@@ -527,17 +751,17 @@ pub async fn load_cursor_sessions(
         } else {
             // No workspace metadata - we can't determine which sessions belong to this workspace
             // Return empty list instead of returning ALL sessions (which belong to other workspaces)
-            println!(
+            log::debug!(
                 "  ⚠️  No workspace metadata found - cannot determine sessions for this workspace"
             );
-            println!(
+            log::debug!(
                 "  💡 This workspace might not have any Cursor sessions, or metadata is missing"
             );
 
             // Don't process any sessions - we can't know which ones belong here
             // The commented code below would return ALL global sessions incorrectly:
             /*
-            println!("  📋 Processing {} sessions from global DB (no workspace metadata):", session_message_counts.len());
+            log::debug!("  📋 Processing {} sessions from global DB (no workspace metadata):", session_message_counts.len());
 
             for (session_id, message_count) in &session_message_counts {
                 if *message_count == 0 {
@@ -547,7 +771,7 @@ pub async fn load_cursor_sessions(
                 // Use current time as fallback since we don't have metadata timestamps
                 let last_modified_timestamp = Utc::now().to_rfc3339();
 
-                println!("    ✓ Session: {} ({} messages, timestamp={})",
+                log::debug!("    ✓ Session: {} ({} messages, timestamp={})",
                          session_id,
                          message_count,
                          last_modified_timestamp);
@@ -573,7 +797,7 @@ pub async fn load_cursor_sessions(
         }
     }
 
-    println!("  ✅ Total sessions loaded: {}", sessions.len());
+    log::debug!("  ✅ Total sessions loaded: {}", sessions.len());
 
     // Sort by last_modified timestamp (newest first)
     // Sessions are already created with rowid-based timestamps, so this will sort correctly
@@ -586,17 +810,12 @@ pub async fn load_cursor_sessions(
 // MESSAGE LOADING
 // ============================================================================
 
-#[tauri::command]
-pub async fn load_cursor_messages(
-    cursor_path: String,
-    session_db_path: String,
-) -> Result<Vec<UniversalMessage>, String> {
-    println!("🔍 [Rust] load_cursor_messages called:");
-    println!("  cursor_path: {}", cursor_path);
-    println!("  session_db_path: {}", session_db_path);
-
-    // Parse session ID, workspace ID, and timestamp from db_path
-    // Format: <db-path>#session=<session-id>#workspace=<workspace-id>#timestamp=<iso-timestamp>
+/// Split a Cursor session ref into its database path, session ID and session
+/// timestamp.
+/// Format: <db-path>#session=<session-id>#workspace=<workspace-id>#timestamp=<iso-timestamp>
+fn parse_cursor_session_ref(
+    session_db_path: &str,
+) -> Result<(&str, String, chrono::DateTime<Utc>), String> {
     let (db_path_str, session_id, session_timestamp) = if let Some(session_pos) =
         session_db_path.find("#session=")
     {
@@ -643,17 +862,16 @@ pub async fn load_cursor_messages(
         return Err("CURSOR_INVALID_ARGUMENT: Session ID not found in db_path. Expected format: <path>#session=<id>#workspace=<ws-id>#timestamp=<timestamp>".to_string());
     };
 
-    println!("  📂 Database: {}", db_path_str);
-    println!("  🆔 Session ID: {}", session_id);
-    println!(
-        "  ⏰ Session timestamp: {}",
-        session_timestamp.format("%Y-%m-%d %H:%M:%S")
-    );
+    Ok((db_path_str, session_id, session_timestamp))
+}
 
+/// Resolve `db_path_str`, rejecting anything other than the Cursor global
+/// storage DB under `cursor_path`.
+fn validate_cursor_db_path(cursor_path: &str, db_path_str: &str) -> Result<PathBuf, String> {
     let db_path = PathBuf::from(db_path_str);
 
     // Validate DB path: must be the Cursor global storage DB
-    let allowed_db = PathBuf::from(&cursor_path)
+    let allowed_db = PathBuf::from(cursor_path)
         .join("User")
         .join("globalStorage")
         .join("state.vscdb");
@@ -682,12 +900,35 @@ pub async fn load_cursor_messages(
         ));
     }
 
+    Ok(db_path)
+}
+
+#[tauri::command]
+pub async fn load_cursor_messages(
+    cursor_path: String,
+    session_db_path: String,
+) -> Result<Vec<UniversalMessage>, String> {
+    log::debug!("🔍 [Rust] load_cursor_messages called:");
+    log::debug!("  cursor_path: {}", cursor_path);
+    log::debug!("  session_db_path: {}", session_db_path);
+
+    let (db_path_str, session_id, session_timestamp) = parse_cursor_session_ref(&session_db_path)?;
+
+    log::debug!("  📂 Database: {}", db_path_str);
+    log::debug!("  🆔 Session ID: {}", session_id);
+    log::debug!(
+        "  ⏰ Session timestamp: {}",
+        session_timestamp.format("%Y-%m-%d %H:%M:%S")
+    );
+
+    let db_path = validate_cursor_db_path(&cursor_path, db_path_str)?;
+
     let conn = Connection::open(&db_path)
         .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open database: {}", e))?;
 
     // Filter messages by session ID: bubbleId:<session-id>:<message-id>
     let query_pattern = format!("bubbleId:{}:%", session_id);
-    println!("  🔎 Query pattern: {}", query_pattern);
+    log::debug!("  🔎 Query pattern: {}", query_pattern);
 
     let mut stmt = conn
         .prepare("SELECT rowid, key, value FROM cursorDiskKV WHERE key LIKE ?1 ORDER BY rowid")
@@ -708,7 +949,7 @@ pub async fn load_cursor_messages(
     let row_vec: Vec<(i64, String, String)> = rows.filter_map(|r| r.ok()).collect();
 
     if row_vec.is_empty() {
-        println!("  ✗ No messages found for session {}", session_id);
+        log::debug!("  ✗ No messages found for session {}", session_id);
         return Ok(messages);
     }
 
@@ -716,16 +957,16 @@ pub async fn load_cursor_messages(
     let max_rowid = row_vec.last().map(|(rid, _, _)| *rid).unwrap_or(0);
     let rowid_range = max_rowid - min_rowid;
 
-    println!(
+    log::debug!(
         "  📨 Processing {} messages (rowid range: {} to {}):",
         row_vec.len(),
         min_rowid,
         max_rowid
     );
 
-    // Calculate message timestamps: spread them backwards from session timestamp
-    // Assume messages span a conversation (estimate based on length)
-    // For each 10 messages, assume ~5 minutes average
+    // Bubbles without a recorded time get an estimate: spread them backwards
+    // from the session timestamp, assuming the conversation took ~5 minutes
+    // per 10 messages
     let estimated_duration_minutes = (row_vec.len() as i64) * 5 / 10;
     let base_time = session_timestamp; // Use session's actual timestamp as end time
 
@@ -734,7 +975,7 @@ pub async fn load_cursor_messages(
         let bubble: CursorBubble = match serde_json::from_str::<CursorBubble>(&value_str) {
             Ok(b) => b,
             Err(e) => {
-                println!("    ⚠️  Skipping entry with key {}: {}", key, e);
+                log::debug!("    ⚠️  Skipping entry with key {}: {}", key, e);
                 continue;
             }
         };
@@ -743,7 +984,7 @@ pub async fn load_cursor_messages(
         let bubble_type = match bubble.bubble_type {
             Some(t) => t,
             None => {
-                println!("    ⚠️  Skipping entry without type field: {}", key);
+                log::debug!("    ⚠️  Skipping entry without type field: {}", key);
                 continue;
             }
         };
@@ -753,21 +994,19 @@ pub async fn load_cursor_messages(
             continue;
         }
 
-        // Determine role
-        let role = if bubble_type == 1 {
-            MessageRole::User
-        } else {
-            MessageRole::Assistant
-        };
+        let role = cursor_bubble_role(bubble_type);
 
-        // Calculate estimated timestamp for this message
-        let message_timestamp = if rowid_range > 0 {
-            let ratio = (*rowid - min_rowid) as f64 / rowid_range as f64;
-            let minutes_offset = (ratio * estimated_duration_minutes as f64) as i64;
-            base_time - chrono::Duration::minutes(estimated_duration_minutes - minutes_offset)
-        } else {
-            base_time
-        };
+        let recorded_timestamp = bubble.recorded_timestamp();
+        let timestamp_estimated = recorded_timestamp.is_none();
+        let message_timestamp = recorded_timestamp.unwrap_or_else(|| {
+            if rowid_range > 0 {
+                let ratio = (*rowid - min_rowid) as f64 / rowid_range as f64;
+                let minutes_offset = (ratio * estimated_duration_minutes as f64) as i64;
+                base_time - chrono::Duration::minutes(estimated_duration_minutes - minutes_offset)
+            } else {
+                base_time
+            }
+        });
 
         // Build content array with text + tool results + attachments
         let mut content_items = vec![UniversalContent {
@@ -815,7 +1054,7 @@ pub async fn load_cursor_messages(
         let extracted_tool_count = tool_calls.as_ref().map(|t| t.len()).unwrap_or(0);
         let has_tool_former = bubble.tool_former_data.is_some();
 
-        println!(
+        log::debug!(
             "    [{}] {:?}: {} chars @ {} (extracted_tools:{} has_toolFormerData:{})",
             sequence_number,
             role,
@@ -831,8 +1070,9 @@ pub async fn load_cursor_messages(
             id: key.clone(),
             session_id: session_id.clone(),
             project_id: "".to_string(),
-            source_id: "".to_string(),
+            source_id: db_path_str.to_string(),
             provider_id: "cursor".to_string(),
+            provider_root: Some(cursor_path.clone()),
 
             // TEMPORAL
             timestamp: message_timestamp.to_rfc3339(),
@@ -871,6 +1111,10 @@ pub async fn load_cursor_messages(
                 let mut map = HashMap::new();
                 map.insert("rowid".to_string(), serde_json::json!(rowid));
                 map.insert("bubble_type".to_string(), serde_json::json!(bubble_type));
+                map.insert(
+                    "timestamp_estimated".to_string(),
+                    serde_json::json!(timestamp_estimated),
+                );
                 map.insert(
                     "session_id".to_string(),
                     serde_json::json!(session_id.clone()),
@@ -944,7 +1188,7 @@ pub async fn load_cursor_messages(
         messages.push(message);
     }
 
-    println!(
+    log::debug!(
         "  ✅ Loaded {} messages for session {}",
         messages.len(),
         session_id
@@ -953,6 +1197,29 @@ pub async fn load_cursor_messages(
     Ok(messages)
 }
 
+/// Count the `bubbleId:<session>:%` rows of a Cursor session without loading
+/// them. Rows that `load_cursor_messages` later skips (metadata, empty
+/// bubbles) are included, so this is an upper bound on the message count.
+pub(crate) fn count_cursor_messages(
+    cursor_path: &str,
+    session_db_path: &str,
+) -> Result<usize, String> {
+    let (db_path_str, session_id, _) = parse_cursor_session_ref(session_db_path)?;
+    let db_path = validate_cursor_db_path(cursor_path, db_path_str)?;
+
+    let conn = Connection::open(&db_path)
+        .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open database: {}", e))?;
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM cursorDiskKV WHERE key LIKE ?1",
+            params![format!("bubbleId:{}:%", session_id)],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("CURSOR_DB_ERROR: Failed to count messages: {}", e))?;
+
+    Ok(count as usize)
+}
+
 // ============================================================================
 // SEARCH
 // ============================================================================
@@ -969,26 +1236,145 @@ pub struct SearchFilters {
     pub has_errors: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct SearchResult {
-    pub messages: Vec<UniversalMessage>,
-    pub total: usize,
+/// Default number of messages materialized per Cursor search.
+const CURSOR_SEARCH_RESULT_CAP: usize = 100;
+
+/// Split a global-DB bubble key `bubbleId:<composer_id>:<bubble_id>` into
+/// `(composer_id, bubble_id)`. Only the first separator after the prefix
+/// splits, so a bubble id containing `:` stays intact.
+fn parse_bubble_key(key: &str) -> Option<(&str, &str)> {
+    let rest = key.strip_prefix("bubbleId:")?;
+    let (composer_id, bubble_id) = rest.split_once(':')?;
+    if composer_id.is_empty() || bubble_id.is_empty() {
+        return None;
+    }
+    Some((composer_id, bubble_id))
 }
 
+/// Map each composer (session) id to the workspace storage directory that
+/// lists it in its `composer.composerData`. Composer ids are not workspace
+/// ids; this is the only link between a global-DB bubble and its workspace.
+fn build_composer_workspace_map(cursor_base: &Path) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    let workspace_storage = cursor_base.join("User").join("workspaceStorage");
+    let Ok(entries) = std::fs::read_dir(&workspace_storage) else {
+        return map;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let state_db = entry.path().join("state.vscdb");
+        if !state_db.exists() {
+            continue;
+        }
+        let Ok(conn) =
+            Connection::open_with_flags(&state_db, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        else {
+            continue;
+        };
+        let composer_data_json: Option<String> = conn
+            .query_row(
+                "SELECT value FROM ItemTable WHERE key = 'composer.composerData'",
+                params![],
+                |row| row.get(0),
+            )
+            .ok();
+        let Some(data) = composer_data_json
+            .and_then(|json| serde_json::from_str::<WorkspaceComposerData>(&json).ok())
+        else {
+            continue;
+        };
+
+        let workspace_path = entry.path().to_string_lossy().to_string();
+        for composer in data.all_composers {
+            map.insert(composer.composer_id, workspace_path.clone());
+        }
+    }
+    map
+}
+
+/// Escape `%`, `_` and `\` for a SQL `LIKE ... ESCAPE '\'` pattern.
+fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Whether any string inside `value` contains `query_lower`, ignoring case.
+fn json_contains(value: &serde_json::Value, query_lower: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s.to_lowercase().contains(query_lower),
+        serde_json::Value::Array(items) => items.iter().any(|v| json_contains(v, query_lower)),
+        serde_json::Value::Object(map) => map.values().any(|v| json_contains(v, query_lower)),
+        _ => false,
+    }
+}
+
+/// Whether a bubble's text, tool parameters or thinking blocks contain
+/// `query_lower`, ignoring case.
+fn bubble_matches_query(bubble: &CursorBubble, query_lower: &str) -> bool {
+    if bubble.text.to_lowercase().contains(query_lower) {
+        return true;
+    }
+    if bubble
+        .all_thinking_blocks
+        .iter()
+        .any(|block| json_contains(block, query_lower))
+    {
+        return true;
+    }
+    // `params` is a JSON-encoded string; match on its decoded values so
+    // escaping inside it does not hide paths or code
+    bubble
+        .tool_former_data
+        .as_ref()
+        .and_then(|tool_data| tool_data.get("params"))
+        .is_some_and(|params| match params.as_str() {
+            Some(raw) => match serde_json::from_str::<serde_json::Value>(raw) {
+                Ok(decoded) => json_contains(&decoded, query_lower),
+                Err(_) => raw.to_lowercase().contains(query_lower),
+            },
+            None => json_contains(params, query_lower),
+        })
+}
+
+/// Search Cursor bubbles for `query`.
+///
+/// Returns the matches in `[offset, offset + limit)` (newest first; `limit`
+/// defaults to [`CURSOR_SEARCH_RESULT_CAP`]) together with the total match
+/// count. Messages are only built until `offset + limit` matches have been
+/// seen; the remaining rows are just counted. Rows whose raw JSON cannot
+/// contain the query are rejected by a SQL `LIKE` before parsing, and the
+/// bubble text, tool parameters and thinking blocks are then checked exactly.
+///
+/// Message timestamps come from the bubble's recorded creation time. Bubbles
+/// without one get a rough rowid-based estimate (flagged with
+/// `timestamp_estimated` in provider metadata) that `dateRange` cannot be
+/// applied to: they are kept, and the filter is reported in `ignoredFilters`.
 #[tauri::command]
 pub async fn search_cursor_messages(
     cursor_path: String,
     query: String,
     filters: SearchFilters,
+    limit: Option<usize>,
+    offset: Option<usize>,
 ) -> Result<SearchResult, String> {
+    let limit = limit.unwrap_or(CURSOR_SEARCH_RESULT_CAP);
+    let offset = offset.unwrap_or(0);
+    let window_end = offset.saturating_add(limit);
     let cursor_base = PathBuf::from(&cursor_path);
     let global_db = cursor_base
         .join("User")
         .join("globalStorage")
         .join("state.vscdb");
 
-    // Store cursor_path for use in message construction
+    // Stored for use in message construction
     let cursor_path_str = cursor_path.clone();
+    let global_db_str = global_db.to_string_lossy().to_string();
 
     if !global_db.exists() {
         return Err(format!(
@@ -1000,16 +1386,32 @@ pub async fn search_cursor_messages(
     let conn = Connection::open(&global_db)
         .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open global database: {}", e))?;
 
-    // Search pattern: case-insensitive search in bubble text
-    let _search_pattern = format!("%{}%", query.to_lowercase());
+    // Case-insensitive search in bubble text, tool params and thinking
+    let query_lower = query.to_lowercase();
+    // JSON escaping leaves the query verbatim in the raw row unless it
+    // contains quotes, backslashes or control characters
+    let raw_prefilter = !query_lower
+        .chars()
+        .any(|c| c == '"' || c == '\\' || c.is_control());
+    // SQLite's LIKE only folds ASCII case, so non-ASCII queries are
+    // pre-filtered in Rust instead
+    let sql_prefilter = raw_prefilter && query_lower.is_ascii();
+    let value_pattern = if sql_prefilter {
+        format!("%{}%", escape_like(&query_lower))
+    } else {
+        "%".to_string()
+    };
 
-    // Query all bubbles and filter by text content
-    let mut stmt = conn.prepare(
-        "SELECT rowid, key, value FROM cursorDiskKV WHERE key LIKE 'bubbleId:%' ORDER BY rowid DESC"
-    ).map_err(|e| format!("CURSOR_DB_ERROR: Failed to prepare search query: {}", e))?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT rowid, key, value FROM cursorDiskKV
+             WHERE key LIKE 'bubbleId:%' AND value LIKE ?1 ESCAPE '\\'
+             ORDER BY rowid DESC",
+        )
+        .map_err(|e| format!("CURSOR_DB_ERROR: Failed to prepare search query: {}", e))?;
 
     let rows = stmt
-        .query_map(params![], |row| {
+        .query_map(params![value_pattern], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
                 row.get::<_, String>(1)?,
@@ -1018,13 +1420,28 @@ pub async fn search_cursor_messages(
         })
         .map_err(|e| format!("CURSOR_DB_ERROR: Failed to execute search: {}", e))?;
 
+    // A range needs two RFC 3339 bounds, as in the other providers' search
+    let date_range = match filters.date_range.as_deref() {
+        Some([start, end]) => chrono::DateTime::parse_from_rfc3339(start)
+            .ok()
+            .zip(chrono::DateTime::parse_from_rfc3339(end).ok()),
+        _ => None,
+    };
+
+    let composer_workspaces = build_composer_workspace_map(&cursor_base);
     let mut matching_messages = Vec::new();
-    let mut sequence = 0;
+    let mut total_matches = 0usize;
+    let mut date_range_ignored = false;
+    let mut sequence = offset as i32;
 
     for row_result in rows {
         let (rowid, key, value_str) =
             row_result.map_err(|e| format!("CURSOR_DB_ERROR: Row error: {}", e))?;
 
+        if raw_prefilter && !sql_prefilter && !value_str.to_lowercase().contains(&query_lower) {
+            continue;
+        }
+
         // Parse bubble
         let bubble: CursorBubble = match serde_json::from_str(&value_str) {
             Ok(b) => b,
@@ -1037,38 +1454,30 @@ pub async fn search_cursor_messages(
             None => continue,
         };
 
-        // Check if text matches search query (case-insensitive)
-        if !bubble.text.to_lowercase().contains(&query.to_lowercase()) {
+        if !bubble_matches_query(&bubble, &query_lower) {
             continue;
         }
 
         // Extract session ID from key (format: bubbleId:<session-id>:<message-id>)
-        let parts: Vec<&str> = key.split(':').collect();
-        if parts.len() < 2 {
+        let Some((session_id, _)) = parse_bubble_key(&key) else {
             continue;
-        }
-        let session_id = parts[1].to_string();
+        };
+        let session_id = session_id.to_string();
 
-        // Try to extract workspace ID from session ID (it's typically the workspace ID)
-        // Construct the workspace storage path
-        let workspace_path = cursor_base
-            .join("User")
-            .join("workspaceStorage")
-            .join(&session_id);
-        let project_path = workspace_path.to_string_lossy().to_string();
+        // Workspace that owns this composer; empty when no workspace lists it
+        let project_path = composer_workspaces
+            .get(&session_id)
+            .cloned()
+            .unwrap_or_default();
 
-        // Determine role
-        let role = if bubble_type == 1 {
-            MessageRole::User
-        } else {
-            MessageRole::Assistant
-        };
+        let role = cursor_bubble_role(bubble_type);
 
         // Apply message type filter
         if let Some(ref message_type) = filters.message_type {
             let role_str = match role {
                 MessageRole::User => "user",
                 MessageRole::Assistant => "assistant",
+                MessageRole::System => "system",
                 _ => "unknown",
             };
             // Filter: if messageType is "all", don't filter; otherwise match the specified type
@@ -1104,25 +1513,27 @@ pub async fn search_cursor_messages(
             }
         }
 
-        // Estimate timestamp (we don't have exact timestamps, use rowid as proxy)
-        let estimated_time = Utc::now() - chrono::Duration::days((rowid / 100) as i64);
-
-        // Apply date range filter
-        if let Some(ref date_range) = filters.date_range {
-            if date_range.len() >= 2 {
-                if let (Ok(start), Ok(end)) = (
-                    chrono::DateTime::parse_from_rfc3339(&date_range[0]),
-                    chrono::DateTime::parse_from_rfc3339(&date_range[1]),
-                ) {
-                    if estimated_time < start.with_timezone(&Utc)
-                        || estimated_time > end.with_timezone(&Utc)
-                    {
-                        continue;
-                    }
-                }
+        // Apply date range filter to bubbles with a recorded time
+        let recorded_timestamp = bubble.recorded_timestamp();
+        if let Some((start, end)) = date_range {
+            match recorded_timestamp {
+                Some(time) if time < start || time > end => continue,
+                Some(_) => {}
+                None => date_range_ignored = true,
             }
         }
 
+        // Without a recorded time, use rowid as a rough proxy
+        let timestamp_estimated = recorded_timestamp.is_none();
+        let message_timestamp =
+            recorded_timestamp.unwrap_or_else(|| Utc::now() - chrono::Duration::days(rowid / 100));
+
+        // Count every match, but only build messages inside the requested window
+        total_matches += 1;
+        if total_matches <= offset || total_matches > window_end {
+            continue;
+        }
+
         // Build content
         let mut content_items = vec![UniversalContent {
             content_type: ContentType::Text,
@@ -1155,9 +1566,10 @@ pub async fn search_cursor_messages(
             id: key.clone(),
             session_id: session_id.clone(),
             project_id: project_path.clone(), // Use workspace-specific path
-            source_id: cursor_path_str.clone(), // Use Cursor base path as source identifier
+            source_id: global_db_str.clone(),
             provider_id: "cursor".to_string(),
-            timestamp: estimated_time.to_rfc3339(),
+            provider_root: Some(cursor_path_str.clone()),
+            timestamp: message_timestamp.to_rfc3339(),
             sequence_number: sequence,
             role,
             message_type: MessageType::Message,
@@ -1179,21 +1591,30 @@ pub async fn search_cursor_messages(
             attachments: None,
             errors: None,
             original_format: "cursor-sqlite".to_string(),
-            provider_metadata: HashMap::new(),
+            provider_metadata: HashMap::from([
+                (
+                    "timestamp_estimated".to_string(),
+                    serde_json::json!(timestamp_estimated),
+                ),
+                ("bubble_type".to_string(), serde_json::json!(bubble_type)),
+            ]),
         };
 
         matching_messages.push(message);
         sequence += 1;
-
-        // Limit results to 100 for performance
-        if matching_messages.len() >= 100 {
-            break;
-        }
     }
 
+    let ignored_filters = if date_range_ignored {
+        vec!["dateRange".to_string()]
+    } else {
+        Vec::new()
+    };
+
     Ok(SearchResult {
-        total: matching_messages.len(),
+        total: total_matches,
+        has_more: total_matches > window_end,
         messages: matching_messages,
+        ignored_filters,
     })
 }
 
@@ -1356,7 +1777,7 @@ struct ProjectInfo {
 fn find_cursor_session_dbs(cursor_base: &PathBuf) -> Vec<PathBuf> {
     let mut session_dbs = Vec::new();
 
-    println!("🔍 [Rust] Searching for session databases:");
+    log::debug!("🔍 [Rust] Searching for session databases:");
 
     // CORRECT APPROACH: Cursor stores ALL chat messages in global storage
     // User/globalStorage/state.vscdb contains ALL chat data shared across workspaces
@@ -1366,17 +1787,17 @@ fn find_cursor_session_dbs(cursor_base: &PathBuf) -> Vec<PathBuf> {
         .join("globalStorage")
         .join("state.vscdb");
 
-    println!(
+    log::debug!(
         "  📂 Checking global storage: {}",
         global_storage_db.display()
     );
 
     if !global_storage_db.exists() {
-        println!("    ✗ Global storage database doesn't exist");
+        log::debug!("    ✗ Global storage database doesn't exist");
         return session_dbs;
     }
 
-    println!("    ✓ Global storage exists, checking for chat data...");
+    log::debug!("    ✓ Global storage exists, checking for chat data...");
 
     // Check if this database contains chat data.
     // On Windows, Cursor IDE can hold OS-level file locks that block even read-only
@@ -1399,26 +1820,28 @@ fn find_cursor_session_dbs(cursor_base: &PathBuf) -> Vec<PathBuf> {
                 .unwrap_or(false);
 
             if has_table {
-                println!("      ✓ cursorDiskKV table found — adding as session DB");
+                log::debug!("      ✓ cursorDiskKV table found — adding as session DB");
                 session_dbs.push(global_storage_db);
             } else {
-                println!("      ✗ cursorDiskKV table not found");
+                log::debug!("      ✗ cursorDiskKV table not found");
             }
         }
         Err(e) => {
-            println!(
+            log::warn!(
                 "      ✗ Failed to open global storage database (Cursor may be locking it): {}",
                 e
             );
             // Even if we can't verify, add it — the per-workspace scan will gracefully handle errors
             if global_storage_db.exists() {
-                println!("      ⚠ Adding anyway (file exists) — will retry during workspace scan");
+                log::debug!(
+                    "      ⚠ Adding anyway (file exists) — will retry during workspace scan"
+                );
                 session_dbs.push(global_storage_db);
             }
         }
     }
 
-    println!("  📊 Total session databases: {}", session_dbs.len());
+    log::debug!("  📊 Total session databases: {}", session_dbs.len());
     session_dbs
 }
 
@@ -1507,19 +1930,511 @@ fn find_common_prefix(paths: &[String]) -> String {
     }
 }
 
-// Helper function kept for potential future use
-#[allow(dead_code)]
-fn count_cursor_messages(session_db: &PathBuf) -> Result<usize, String> {
-    let conn = Connection::open(session_db)
-        .map_err(|e| format!("CURSOR_DB_ERROR: Failed to open database: {}", e))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scan_cursor_workspaces_explains_empty_workspaces() {
+        let temp = tempfile::tempdir().unwrap();
+        let storage = temp.path().join("User").join("globalStorage");
+        std::fs::create_dir_all(&storage).unwrap();
+        let global = Connection::open(storage.join("state.vscdb")).unwrap();
+        global
+            .execute_batch(
+                "CREATE TABLE cursorDiskKV (key TEXT, value BLOB);
+                 INSERT INTO cursorDiskKV VALUES ('bubbleId:c1:m1', '{}');",
+            )
+            .unwrap();
+        drop(global);
+
+        let workspace = |id: &str, composer_data: Option<&str>| {
+            let dir = temp.path().join("User").join("workspaceStorage").join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            let conn = Connection::open(dir.join("state.vscdb")).unwrap();
+            conn.execute_batch("CREATE TABLE ItemTable (key TEXT, value TEXT);")
+                .unwrap();
+            if let Some(data) = composer_data {
+                conn.execute(
+                    "INSERT INTO ItemTable VALUES ('composer.composerData', ?1)",
+                    params![data],
+                )
+                .unwrap();
+            }
+        };
+        workspace("active", Some(r#"{"allComposers":[{"composerId":"c1"}]}"#));
+        workspace("no-metadata", None);
+        workspace(
+            "no-bubbles",
+            Some(r#"{"allComposers":[{"composerId":"c2"}]}"#),
+        );
 
-    let mut stmt = conn
-        .prepare("SELECT COUNT(*) FROM cursorDiskKV WHERE key LIKE 'bubbleId:%'")
-        .map_err(|e| format!("CURSOR_DB_ERROR: Failed to prepare count query: {}", e))?;
+        let workspaces = scan_cursor_workspaces(temp.path().to_string_lossy().to_string())
+            .await
+            .unwrap();
+        let by_id = |id: &str| workspaces.iter().find(|w| w.id == id).unwrap();
+
+        assert_eq!(by_id("active").session_count, 1);
+        assert_eq!(by_id("active").diagnostics, None);
+        let no_metadata = by_id("no-metadata").diagnostics.as_deref().unwrap();
+        assert!(no_metadata.contains("No composer metadata"));
+        let no_bubbles = by_id("no-bubbles").diagnostics.as_deref().unwrap();
+        assert!(no_bubbles.contains("none has messages"));
+    }
 
-    let count: i64 = stmt
-        .query_row(params![], |row| row.get(0))
-        .map_err(|e| format!("CURSOR_DB_ERROR: Failed to count messages: {}", e))?;
+    #[test]
+    fn workspace_last_activity_falls_back_without_last_updated_at() {
+        let temp = tempfile::tempdir().unwrap();
+        let global = Connection::open_in_memory().unwrap();
+        global
+            .execute_batch(
+                "CREATE TABLE cursorDiskKV (key TEXT, value BLOB);
+                 INSERT INTO cursorDiskKV VALUES ('bubbleId:c1:m1', '{}');",
+            )
+            .unwrap();
+        let state_db = temp.path().join("state.vscdb");
+        let conn = Connection::open(&state_db).unwrap();
+        conn.execute_batch("CREATE TABLE ItemTable (key TEXT, value TEXT);")
+            .unwrap();
+        let set_composers = |data: &str| {
+            conn.execute(
+                "INSERT OR REPLACE INTO ItemTable (rowid, key, value) \
+                 VALUES (1, 'composer.composerData', ?1)",
+                params![data],
+            )
+            .unwrap();
+        };
 
-    Ok(count as usize)
+        // createdAt when lastUpdatedAt is missing
+        set_composers(r#"{"allComposers":[{"composerId":"c1","createdAt":1700000000000}]}"#);
+        let scan = count_workspace_composers_with_cached_conn(&global, &state_db).unwrap();
+        assert_eq!(scan.session_count, 1);
+        assert_eq!(
+            scan.last_activity.as_deref(),
+            Some("2023-11-14T22:13:20+00:00")
+        );
+
+        // Workspace DB mtime when the composer has no timestamps at all
+        set_composers(r#"{"allComposers":[{"composerId":"c1"}]}"#);
+        let scan = count_workspace_composers_with_cached_conn(&global, &state_db).unwrap();
+        let mtime: chrono::DateTime<Utc> = std::fs::metadata(&state_db)
+            .unwrap()
+            .modified()
+            .unwrap()
+            .into();
+        assert_eq!(scan.last_activity, Some(mtime.to_rfc3339()));
+    }
+
+    #[tokio::test]
+    async fn cursor_global_db_status_reports_each_stage() {
+        let temp = tempfile::tempdir().unwrap();
+        let cursor_path = temp.path().to_string_lossy().to_string();
+        let storage = temp.path().join("User").join("globalStorage");
+
+        let status = cursor_global_db_status(cursor_path.clone()).await.unwrap();
+        assert!(!status.exists && !status.openable);
+
+        std::fs::create_dir_all(&storage).unwrap();
+        std::fs::write(storage.join("state.vscdb"), "not a database").unwrap();
+        let status = cursor_global_db_status(cursor_path.clone()).await.unwrap();
+        assert!(status.exists && !status.openable);
+        assert!(status.error.is_some());
+
+        std::fs::remove_file(storage.join("state.vscdb")).unwrap();
+        let conn = Connection::open(storage.join("state.vscdb")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE cursorDiskKV (key TEXT, value BLOB);
+             INSERT INTO cursorDiskKV VALUES ('bubbleId:c:1', '{}'), ('bubbleId:c:2', '{}'),
+                 ('composerData:c', '{}');",
+        )
+        .unwrap();
+        drop(conn);
+        let status = cursor_global_db_status(cursor_path).await.unwrap();
+        assert!(status.openable && status.has_disk_kv_table);
+        assert_eq!(status.bubble_count, 2);
+        assert_eq!(status.error, None);
+    }
+
+    #[test]
+    fn cursor_bubble_role_maps_known_and_unknown_types() {
+        assert_eq!(cursor_bubble_role(1), MessageRole::User);
+        assert_eq!(cursor_bubble_role(2), MessageRole::Assistant);
+        assert_eq!(cursor_bubble_role(30), MessageRole::System);
+    }
+
+    #[test]
+    fn parse_bubble_key_splits_on_first_two_colons() {
+        assert_eq!(
+            parse_bubble_key("bubbleId:3f2a-composer:9b1c-bubble"),
+            Some(("3f2a-composer", "9b1c-bubble"))
+        );
+        // Bubble ids may themselves contain colons
+        assert_eq!(
+            parse_bubble_key("bubbleId:abc:def:ghi"),
+            Some(("abc", "def:ghi"))
+        );
+        // Unusual but colon-free characters in composer ids are kept verbatim
+        assert_eq!(
+            parse_bubble_key("bubbleId:täsk_#1.(x)%20:m"),
+            Some(("täsk_#1.(x)%20", "m"))
+        );
+    }
+
+    #[test]
+    fn parse_bubble_key_rejects_malformed_keys() {
+        assert_eq!(parse_bubble_key("composerData:abc"), None);
+        assert_eq!(parse_bubble_key("bubbleId:abc"), None);
+        assert_eq!(parse_bubble_key("bubbleId::msg"), None);
+        assert_eq!(parse_bubble_key("bubbleId:abc:"), None);
+        assert_eq!(parse_bubble_key("xbubbleId:abc:def"), None);
+    }
+
+    #[test]
+    fn composer_workspace_map_reads_workspace_composers() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let workspace_dir = temp_dir
+            .path()
+            .join("User")
+            .join("workspaceStorage")
+            .join("ws-hash");
+        std::fs::create_dir_all(&workspace_dir).unwrap();
+        let conn = Connection::open(workspace_dir.join("state.vscdb")).unwrap();
+        conn.execute_batch("CREATE TABLE ItemTable (key TEXT, value TEXT);")
+            .unwrap();
+        conn.execute(
+            "INSERT INTO ItemTable VALUES ('composer.composerData', ?)",
+            params![r#"{"allComposers":[{"composerId":"c-1"},{"composerId":"c-2"}]}"#],
+        )
+        .unwrap();
+        drop(conn);
+
+        let map = build_composer_workspace_map(temp_dir.path());
+        let expected = workspace_dir.to_string_lossy().to_string();
+        assert_eq!(map.get("c-1"), Some(&expected));
+        assert_eq!(map.get("c-2"), Some(&expected));
+        assert_eq!(map.get("ws-hash"), None);
+    }
+
+    #[tokio::test]
+    async fn load_messages_prefers_recorded_bubble_times() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let global_dir = temp_dir.path().join("User").join("globalStorage");
+        std::fs::create_dir_all(&global_dir).unwrap();
+        let db_path = global_dir.join("state.vscdb");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch("CREATE TABLE cursorDiskKV (key TEXT, value TEXT);")
+            .unwrap();
+        for (key, value) in [
+            (
+                "bubbleId:c-1:m-1",
+                r#"{"type":1,"text":"hi","createdAt":"2025-05-01T09:00:00Z"}"#,
+            ),
+            (
+                "bubbleId:c-1:m-2",
+                r#"{"type":2,"text":"hello","timingInfo":{"clientStartTime":1746090060000}}"#,
+            ),
+            ("bubbleId:c-1:m-3", r#"{"type":1,"text":"no time"}"#),
+        ] {
+            conn.execute(
+                "INSERT INTO cursorDiskKV VALUES (?, ?)",
+                params![key, value],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        let messages = load_cursor_messages(
+            temp_dir.path().to_string_lossy().to_string(),
+            format!(
+                "{}#session=c-1#timestamp=2025-06-01T12:00:00Z",
+                db_path.display()
+            ),
+        )
+        .await
+        .unwrap();
+
+        let times: Vec<(&str, bool)> = messages
+            .iter()
+            .map(|m| {
+                (
+                    m.timestamp.as_str(),
+                    m.provider_metadata["timestamp_estimated"] == true,
+                )
+            })
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                ("2025-05-01T09:00:00+00:00", false),
+                ("2025-05-01T09:01:00+00:00", false),
+                ("2025-06-01T12:00:00+00:00", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_cursor_time_accepts_iso_millis_and_seconds() {
+        let expected = "2025-05-01T09:01:00+00:00";
+        for value in [
+            serde_json::json!("2025-05-01T09:01:00Z"),
+            serde_json::json!(1746090060000u64),
+            serde_json::json!(1746090060),
+            serde_json::json!("1746090060000"),
+        ] {
+            assert_eq!(parse_cursor_time(&value).unwrap().to_rfc3339(), expected);
+        }
+        assert_eq!(parse_cursor_time(&serde_json::json!(0)), None);
+        assert_eq!(parse_cursor_time(&serde_json::json!("soon")), None);
+        assert_eq!(parse_cursor_time(&serde_json::json!(null)), None);
+    }
+
+    #[tokio::test]
+    async fn search_reports_total_beyond_result_cap() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let global_dir = temp_dir.path().join("User").join("globalStorage");
+        std::fs::create_dir_all(&global_dir).unwrap();
+        let conn = Connection::open(global_dir.join("state.vscdb")).unwrap();
+        conn.execute_batch("CREATE TABLE cursorDiskKV (key TEXT, value TEXT);")
+            .unwrap();
+        let match_count = CURSOR_SEARCH_RESULT_CAP + 5;
+        for i in 0..match_count {
+            conn.execute(
+                "INSERT INTO cursorDiskKV VALUES (?, ?)",
+                params![
+                    format!("bubbleId:c-1:m-{i}"),
+                    r#"{"type":1,"text":"deploy failed again"}"#
+                ],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO cursorDiskKV VALUES ('bubbleId:c-1:other', ?)",
+            params![r#"{"type":2,"text":"unrelated"}"#],
+        )
+        .unwrap();
+        drop(conn);
+
+        let filters = SearchFilters {
+            date_range: None,
+            message_type: None,
+            has_tool_calls: None,
+            has_errors: None,
+        };
+        let cursor_path = temp_dir.path().to_string_lossy().to_string();
+        let result = search_cursor_messages(
+            cursor_path.clone(),
+            "DEPLOY".to_string(),
+            filters,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.messages.len(), CURSOR_SEARCH_RESULT_CAP);
+        assert_eq!(result.total, match_count);
+        assert!(result.has_more);
+
+        // Paging past the default cap returns the remainder
+        let filters = SearchFilters {
+            date_range: None,
+            message_type: None,
+            has_tool_calls: None,
+            has_errors: None,
+        };
+        let page = search_cursor_messages(
+            cursor_path,
+            "deploy".to_string(),
+            filters,
+            Some(10),
+            Some(CURSOR_SEARCH_RESULT_CAP),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.messages.len(), 5);
+        assert_eq!(page.total, match_count);
+        assert!(!page.has_more);
+        assert_eq!(
+            page.messages[0].sequence_number,
+            CURSOR_SEARCH_RESULT_CAP as i32
+        );
+        assert!(page.ignored_filters.is_empty());
+
+        // Estimated timestamps are never filtered on; the filter is reported
+        let filters = SearchFilters {
+            date_range: Some(vec![
+                "2000-01-01T00:00:00Z".to_string(),
+                "2000-01-02T00:00:00Z".to_string(),
+            ]),
+            message_type: None,
+            has_tool_calls: None,
+            has_errors: None,
+        };
+        let dated = search_cursor_messages(
+            temp_dir.path().to_string_lossy().to_string(),
+            "deploy".to_string(),
+            filters,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(dated.total, match_count);
+        assert_eq!(dated.ignored_filters, vec!["dateRange".to_string()]);
+        assert_eq!(
+            dated.messages[0].provider_metadata["timestamp_estimated"],
+            true
+        );
+    }
+
+    #[tokio::test]
+    async fn search_applies_date_range_to_recorded_timestamps() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let global_dir = temp_dir.path().join("User").join("globalStorage");
+        std::fs::create_dir_all(&global_dir).unwrap();
+        let conn = Connection::open(global_dir.join("state.vscdb")).unwrap();
+        conn.execute_batch("CREATE TABLE cursorDiskKV (key TEXT, value TEXT);")
+            .unwrap();
+        for (key, value) in [
+            (
+                "bubbleId:c-1:inside",
+                r#"{"type":1,"text":"deploy now","createdAt":"2025-05-01T09:00:00Z"}"#,
+            ),
+            (
+                "bubbleId:c-1:outside",
+                r#"{"type":1,"text":"deploy later","createdAt":"2025-06-01T09:00:00Z"}"#,
+            ),
+        ] {
+            conn.execute(
+                "INSERT INTO cursorDiskKV VALUES (?, ?)",
+                params![key, value],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        let filters = || SearchFilters {
+            date_range: Some(vec![
+                "2025-05-01T00:00:00Z".to_string(),
+                "2025-05-02T00:00:00Z".to_string(),
+            ]),
+            message_type: None,
+            has_tool_calls: None,
+            has_errors: None,
+        };
+        let cursor_path = temp_dir.path().to_string_lossy().to_string();
+        let result = search_cursor_messages(
+            cursor_path.clone(),
+            "deploy".to_string(),
+            filters(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.total, 1);
+        assert_eq!(result.messages[0].id, "bubbleId:c-1:inside");
+        assert_eq!(result.messages[0].timestamp, "2025-05-01T09:00:00+00:00");
+        assert_eq!(
+            result.messages[0].provider_metadata["timestamp_estimated"],
+            false
+        );
+        assert!(result.ignored_filters.is_empty());
+
+        // A bubble without a recorded time is kept and the filter reported
+        let conn = Connection::open(global_dir.join("state.vscdb")).unwrap();
+        conn.execute(
+            "INSERT INTO cursorDiskKV VALUES ('bubbleId:c-1:undated', ?)",
+            params![r#"{"type":1,"text":"deploy whenever"}"#],
+        )
+        .unwrap();
+        drop(conn);
+        let result =
+            search_cursor_messages(cursor_path, "deploy".to_string(), filters(), None, None)
+                .await
+                .unwrap();
+        assert_eq!(result.total, 2);
+        assert_eq!(result.ignored_filters, vec!["dateRange".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn search_matches_tool_params_and_thinking() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let global_dir = temp_dir.path().join("User").join("globalStorage");
+        std::fs::create_dir_all(&global_dir).unwrap();
+        let conn = Connection::open(global_dir.join("state.vscdb")).unwrap();
+        conn.execute_batch("CREATE TABLE cursorDiskKV (key TEXT, value TEXT);")
+            .unwrap();
+        for (key, value) in [
+            (
+                "bubbleId:c-1:edit",
+                r#"{"type":2,"text":"","toolFormerData":{"name":"edit_file","params":"{\"targetFile\":\"src/Main_Window.rs\"}"}}"#,
+            ),
+            (
+                "bubbleId:c-1:think",
+                r#"{"type":2,"text":"done","allThinkingBlocks":[{"thinking":"Check main_window.rs first"}]}"#,
+            ),
+            // Mentions the file only in a field that is not searched
+            (
+                "bubbleId:c-1:other",
+                r#"{"type":2,"text":"ok","consoleLogs":["main_window.rs"]}"#,
+            ),
+            // `_` must not act as a LIKE wildcard
+            ("bubbleId:c-1:wild", r#"{"type":1,"text":"mainXwindow.rs"}"#),
+        ] {
+            conn.execute(
+                "INSERT INTO cursorDiskKV VALUES (?, ?)",
+                params![key, value],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        let filters = SearchFilters {
+            date_range: None,
+            message_type: None,
+            has_tool_calls: None,
+            has_errors: None,
+        };
+        let result = search_cursor_messages(
+            temp_dir.path().to_string_lossy().to_string(),
+            "MAIN_WINDOW.rs".to_string(),
+            filters,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let mut ids: Vec<&str> = result.messages.iter().map(|m| m.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["bubbleId:c-1:edit", "bubbleId:c-1:think"]);
+        assert_eq!(result.total, 2);
+    }
+    #[test]
+    fn count_counts_session_bubble_rows() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let global_dir = temp_dir.path().join("User").join("globalStorage");
+        std::fs::create_dir_all(&global_dir).unwrap();
+        let db_path = global_dir.join("state.vscdb");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE cursorDiskKV (key TEXT, value TEXT);
+             INSERT INTO cursorDiskKV VALUES ('bubbleId:s-1:a', '{}');
+             INSERT INTO cursorDiskKV VALUES ('bubbleId:s-1:b', '{}');
+             INSERT INTO cursorDiskKV VALUES ('bubbleId:s-2:a', '{}');
+             INSERT INTO cursorDiskKV VALUES ('composerData:s-1', '{}');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let cursor_path = temp_dir.path().to_string_lossy().to_string();
+        let session_ref = format!(
+            "{}#session=s-1#workspace=ws#timestamp=2025-01-01T00:00:00Z",
+            db_path.display()
+        );
+        let count = count_cursor_messages(&cursor_path, &session_ref).unwrap();
+        assert_eq!(count, 2);
+
+        let err = count_cursor_messages(&cursor_path, &db_path.to_string_lossy()).unwrap_err();
+        assert!(err.starts_with("CURSOR_INVALID_ARGUMENT"));
+    }
 }
@@ -4,8 +4,7 @@
 //! a paginated view of recent changes with restore capability.
 
 use crate::models::{ClaudeMessage, RawLogEntry, RecentFileEdit};
-use crate::utils::find_line_ranges;
-use memmap2::Mmap;
+use crate::utils::{find_line_ranges, is_session_file, read_session_bytes};
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -19,13 +18,11 @@ struct SessionEditsResult {
 }
 
 /// Process a single session file and extract edit information
-#[allow(unsafe_code)] // Required for mmap performance optimization
 fn process_session_file_for_edits(file_path: &PathBuf) -> Option<SessionEditsResult> {
     let file = fs::File::open(file_path).ok()?;
 
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let mmap = read_session_bytes(&file, file_path).ok()?;
 
     let mut edits: Vec<RecentFileEdit> = Vec::with_capacity(16);
     let mut cwd_counts: HashMap<String, usize> = HashMap::new();
@@ -492,7 +489,7 @@ pub async fn get_recent_edits(
     let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
         .into_iter()
         .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|e| is_session_file(e.path()))
         .map(|e| e.path().to_path_buf())
         .collect();
 
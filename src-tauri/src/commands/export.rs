@@ -0,0 +1,533 @@
+//! Session export
+//!
+//! `export_messages_api` turns a Claude Code session file into the Anthropic
+//! Messages API request shape,
+//! `{ model, max_tokens, system?, messages: [{ role, content }] }`, so the
+//! conversation can be replayed against the API as-is. Claude Code specific
+//! fields (`toolUseResult`, uuids, usage) are dropped, content blocks are
+//! reduced to the fields the API schema accepts, and tool_use / tool_result
+//! blocks that lost their counterpart (e.g. across a compaction) are stripped
+//! so the request stays valid.
+//!
+//! `export_session_markdown` renders any provider's session (via its
+//! `UniversalMessage`s) as a readable Markdown document.
+
+use crate::models::universal::{ContentType, MessageRole, UniversalContent, UniversalMessage};
+use crate::utils::read_session_to_string;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// `max_tokens` written into the exported request (required by the API).
+const EXPORT_MAX_TOKENS: u64 = 8192;
+
+/// Reduce a content block to the fields the Messages API accepts.
+/// Returns `None` for blocks that cannot be replayed (thinking, unknown types).
+fn sanitize_block(block: &Value) -> Option<Value> {
+    let obj = block.as_object()?;
+    let pick = |keys: &[&str]| -> Value {
+        let mut out = Map::new();
+        for key in keys {
+            if let Some(value) = obj.get(*key) {
+                out.insert((*key).to_string(), value.clone());
+            }
+        }
+        Value::Object(out)
+    };
+
+    match obj.get("type")?.as_str()? {
+        "text" => {
+            let text = obj.get("text")?.as_str()?;
+            (!text.is_empty()).then(|| json!({ "type": "text", "text": text }))
+        }
+        "image" => Some(pick(&["type", "source"])),
+        "document" => Some(pick(&["type", "source", "title", "context"])),
+        "tool_use" => {
+            let mut out = pick(&["type", "id", "name", "input"]);
+            if out.get("input").is_none() {
+                out["input"] = json!({});
+            }
+            Some(out)
+        }
+        "tool_result" => {
+            let mut out = pick(&["type", "tool_use_id", "is_error"]);
+            if let Some(content) = obj.get("content") {
+                out["content"] = match content {
+                    Value::Array(items) => {
+                        Value::Array(items.iter().filter_map(sanitize_block).collect())
+                    }
+                    other => other.clone(),
+                };
+            }
+            Some(out)
+        }
+        // Thinking blocks are tied to the original request's thinking config
+        // and signature; they are not replayable.
+        _ => None,
+    }
+}
+
+/// Content of a message as a list of sanitized API blocks.
+fn content_blocks(content: &Value) -> Vec<Value> {
+    match content {
+        Value::String(text) if !text.is_empty() => vec![json!({ "type": "text", "text": text })],
+        Value::Array(items) => items.iter().filter_map(sanitize_block).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn block_type(block: &Value) -> Option<&str> {
+    block.get("type").and_then(|t| t.as_str())
+}
+
+/// Append `blocks` as a `role` message, merging into the previous message when
+/// it has the same role (the API requires alternating turns, while Claude Code
+/// writes one line per assistant content block).
+fn push_turn(messages: &mut Vec<(String, Vec<Value>)>, role: &str, blocks: Vec<Value>) {
+    if blocks.is_empty() {
+        return;
+    }
+    match messages.last_mut() {
+        Some((last_role, last_blocks)) if last_role == role => last_blocks.extend(blocks),
+        _ => messages.push((role.to_string(), blocks)),
+    }
+}
+
+/// Convert session JSONL content into a Messages API request body.
+fn build_messages_api_request(content: &str) -> Value {
+    let mut system_parts: Vec<String> = Vec::new();
+    let mut turns: Vec<(String, Vec<Value>)> = Vec::new();
+    let mut model: Option<String> = None;
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if entry.get("isSidechain").and_then(|v| v.as_bool()) == Some(true) {
+            continue;
+        }
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        let role = message.get("role").and_then(|r| r.as_str()).unwrap_or("");
+        let blocks = message
+            .get("content")
+            .map(content_blocks)
+            .unwrap_or_default();
+
+        match role {
+            "system" => system_parts.extend(
+                blocks
+                    .iter()
+                    .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                    .map(str::to_string),
+            ),
+            "user" | "assistant" => {
+                if role == "assistant" {
+                    if let Some(m) = message.get("model").and_then(|m| m.as_str()) {
+                        // Skip placeholder models written for synthetic messages
+                        if !m.starts_with('<') {
+                            model = Some(m.to_string());
+                        }
+                    }
+                }
+                push_turn(&mut turns, role, blocks);
+            }
+            _ => {}
+        }
+    }
+
+    // Keep only tool_use/tool_result pairs that are both present
+    let use_ids: HashSet<String> = turns
+        .iter()
+        .flat_map(|(_, blocks)| blocks.iter())
+        .filter(|b| block_type(b) == Some("tool_use"))
+        .filter_map(|b| b.get("id").and_then(|v| v.as_str()).map(str::to_string))
+        .collect();
+    let result_ids: HashSet<String> = turns
+        .iter()
+        .flat_map(|(_, blocks)| blocks.iter())
+        .filter(|b| block_type(b) == Some("tool_result"))
+        .filter_map(|b| {
+            b.get("tool_use_id")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .collect();
+
+    let mut messages: Vec<(String, Vec<Value>)> = Vec::new();
+    for (role, blocks) in turns {
+        let kept: Vec<Value> = blocks
+            .into_iter()
+            .filter(|b| match block_type(b) {
+                Some("tool_use") => b
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|id| result_ids.contains(id)),
+                Some("tool_result") => b
+                    .get("tool_use_id")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|id| use_ids.contains(id)),
+                _ => true,
+            })
+            .collect();
+        // Stripping can leave neighbours with the same role; merge them again
+        push_turn(&mut messages, &role, kept);
+    }
+
+    let mut request = Map::new();
+    if let Some(model) = model {
+        request.insert("model".to_string(), json!(model));
+    }
+    request.insert("max_tokens".to_string(), json!(EXPORT_MAX_TOKENS));
+    if !system_parts.is_empty() {
+        request.insert("system".to_string(), json!(system_parts.join("\n\n")));
+    }
+    request.insert(
+        "messages".to_string(),
+        Value::Array(
+            messages
+                .into_iter()
+                .map(|(role, content)| json!({ "role": role, "content": content }))
+                .collect(),
+        ),
+    );
+    Value::Object(request)
+}
+
+/// Export a Claude Code session as an Anthropic Messages API request body
+/// (pretty-printed JSON), ready to re-run the conversation via the API.
+#[tauri::command]
+pub async fn export_messages_api(session_path: String) -> Result<String, String> {
+    let content = read_session_to_string(Path::new(&session_path))
+        .map_err(|e| format!("EXPORT_READ_ERROR: Failed to read session file: {}", e))?;
+
+    serde_json::to_string_pretty(&build_messages_api_request(&content))
+        .map_err(|e| format!("EXPORT_SERIALIZE_ERROR: Failed to serialize request: {}", e))
+}
+
+/// Fence that cannot be closed early by backtick runs inside `text`.
+fn code_fence(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest_run.max(2) + 1)
+}
+
+fn push_code_block(out: &mut String, language: &str, code: &str) {
+    let fence = code_fence(code);
+    out.push_str(&format!(
+        "{}{}\n{}\n{}\n\n",
+        fence,
+        language,
+        code.trim_end(),
+        fence
+    ));
+}
+
+fn push_block_quote(out: &mut String, text: &str) {
+    for line in text.trim_end().lines() {
+        out.push_str(if line.is_empty() { ">" } else { "> " });
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+fn push_tool_call(out: &mut String, name: &str, input: &Value) {
+    out.push_str(&format!("**Tool call: `{}`**\n\n", name));
+    let input = serde_json::to_string_pretty(input).unwrap_or_else(|_| input.to_string());
+    push_code_block(out, "json", &input);
+}
+
+fn push_tool_result(out: &mut String, result: &str, is_error: bool) {
+    let summary = if is_error {
+        "Tool error"
+    } else {
+        "Tool result"
+    };
+    out.push_str(&format!("<details>\n<summary>{}</summary>\n\n", summary));
+    push_code_block(out, "", result);
+    out.push_str("</details>\n\n");
+}
+
+/// Readable text of a tool result: the text of a `tool_result` block's
+/// content, otherwise the value as pretty JSON.
+fn tool_result_text(data: &Value) -> String {
+    let content = match data.get("type").and_then(Value::as_str) {
+        Some("tool_result") => data.get("content").unwrap_or(&Value::Null),
+        _ => data,
+    };
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(items) if items.iter().all(|i| i.get("text").is_some()) => items
+            .iter()
+            .filter_map(|i| i.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Null => String::new(),
+        other => serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string()),
+    }
+}
+
+fn is_tool_result_block(item: &UniversalContent) -> bool {
+    item.content_type == ContentType::ToolResult
+        && item.data.get("type").and_then(Value::as_str) == Some("tool_result")
+}
+
+/// Render one message's content items, falling back to its `tool_calls` and
+/// `thinking` fields for providers that do not put those in `content`.
+fn push_message_body(out: &mut String, message: &UniversalMessage) {
+    let has = |content_type: ContentType| {
+        message
+            .content
+            .iter()
+            .any(|c| c.content_type == content_type)
+    };
+    // Claude Code repeats a tool_result block's output as a raw `toolUseResult` item
+    let has_result_blocks = message.content.iter().any(is_tool_result_block);
+
+    if !has(ContentType::Thinking) {
+        if let Some(thinking) = &message.thinking {
+            push_block_quote(out, &thinking.content);
+        }
+    }
+
+    for item in &message.content {
+        let data = &item.data;
+        let text_field = |key: &str| data.get(key).and_then(Value::as_str);
+        match item.content_type {
+            ContentType::Text => match text_field("text").or_else(|| data.as_str()) {
+                Some(text) if !text.trim().is_empty() => {
+                    out.push_str(text.trim_end());
+                    out.push_str("\n\n");
+                }
+                Some(_) => {}
+                None => {
+                    let json = serde_json::to_string_pretty(data).unwrap_or_default();
+                    push_code_block(out, "json", &json);
+                }
+            },
+            ContentType::Code => {
+                let code = text_field("code")
+                    .or_else(|| text_field("text"))
+                    .unwrap_or_default();
+                push_code_block(out, text_field("language").unwrap_or_default(), code);
+            }
+            ContentType::Thinking => {
+                let thinking = text_field("thinking")
+                    .or_else(|| text_field("content"))
+                    .or_else(|| text_field("text"))
+                    .unwrap_or_default();
+                if !thinking.trim().is_empty() {
+                    push_block_quote(out, thinking);
+                }
+            }
+            ContentType::ToolUse => push_tool_call(
+                out,
+                text_field("name").unwrap_or("tool"),
+                data.get("input").unwrap_or(&Value::Null),
+            ),
+            ContentType::ToolResult => {
+                if has_result_blocks && !is_tool_result_block(item) {
+                    continue;
+                }
+                let is_error = data
+                    .get("is_error")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                push_tool_result(out, &tool_result_text(data), is_error);
+            }
+            _ => out.push_str(&format!(
+                "_[{}]_\n\n",
+                serde_json::to_value(&item.content_type)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default()
+            )),
+        }
+    }
+
+    if !has(ContentType::ToolUse) {
+        for call in message.tool_calls.iter().flatten() {
+            push_tool_call(out, &call.name, &json!(call.input));
+            if let Some(output) = &call.output {
+                push_tool_result(out, &tool_result_text(&json!(output)), false);
+            } else if let Some(error) = &call.error {
+                push_tool_result(out, error, true);
+            }
+        }
+    }
+}
+
+fn role_heading(role: &MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "User",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::System => "System",
+        MessageRole::Function => "Tool",
+    }
+}
+
+/// Render a session as Markdown: a heading with the session id and date
+/// range, then one `### Role` section per message.
+fn render_session_markdown(session_id: &str, messages: &[UniversalMessage]) -> String {
+    let mut out = format!("# Session {}\n\n", session_id);
+    let first = messages.iter().map(|m| m.timestamp.as_str()).min();
+    let last = messages.iter().map(|m| m.timestamp.as_str()).max();
+    if let (Some(first), Some(last)) = (first, last) {
+        out.push_str(&format!("_{} – {}_\n\n", first, last));
+    }
+
+    for message in messages {
+        let mut body = String::new();
+        push_message_body(&mut body, message);
+        if body.trim().is_empty() {
+            continue;
+        }
+        out.push_str(&format!("### {}\n\n", role_heading(&message.role)));
+        out.push_str(&body);
+    }
+    format!("{}\n", out.trim_end())
+}
+
+/// Export a session of any provider as a Markdown document. Tool calls are
+/// fenced JSON, tool results collapsible `<details>` blocks and thinking
+/// block quotes. Returns the Markdown for the frontend to save.
+#[tauri::command]
+pub async fn export_session_markdown(
+    provider_id: String,
+    source_path: String,
+    session_id: String,
+) -> Result<String, String> {
+    if !std::path::Path::new(&source_path).is_absolute() {
+        return Err("EXPORT_INVALID_ARGUMENT: source_path must be absolute".to_string());
+    }
+    let messages = crate::commands::stats::load_universal_session_messages(
+        &provider_id,
+        &source_path,
+        &session_id,
+    )
+    .await?;
+    Ok(render_session_markdown(&session_id, &messages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn session(lines: &[Value]) -> String {
+        lines.iter().map(|l| format!("{}\n", l)).collect()
+    }
+
+    #[test]
+    fn merges_assistant_blocks_and_pairs_tool_calls() {
+        let content = session(&[
+            json!({"type": "user", "message": {"role": "user", "content": "list files"}}),
+            json!({"type": "assistant", "message": {"role": "assistant", "model": "claude-sonnet-4", "content": [
+                {"type": "thinking", "thinking": "hmm", "signature": "sig"},
+            ]}}),
+            json!({"type": "assistant", "message": {"role": "assistant", "model": "claude-sonnet-4", "content": [
+                {"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "ls"}, "caller": {"type": "direct"}},
+            ]}}),
+            json!({"type": "user", "toolUseResult": {"stdout": "a.txt"}, "message": {"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": "t1", "content": "a.txt"},
+            ]}}),
+            json!({"type": "assistant", "message": {"role": "assistant", "model": "claude-sonnet-4", "content": [
+                {"type": "text", "text": "One file."},
+            ]}}),
+        ]);
+
+        let request = build_messages_api_request(&content);
+        assert_eq!(request["model"], "claude-sonnet-4");
+        assert_eq!(request["max_tokens"], EXPORT_MAX_TOKENS);
+        assert!(request.get("system").is_none());
+
+        let messages = request["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0]["content"][0]["text"], "list files");
+        assert_eq!(
+            messages[1]["content"],
+            json!([{"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "ls"}}])
+        );
+        assert_eq!(
+            messages[2]["content"],
+            json!([{"type": "tool_result", "tool_use_id": "t1", "content": "a.txt"}])
+        );
+        assert_eq!(messages[3]["role"], "assistant");
+    }
+
+    #[test]
+    fn strips_orphaned_tool_blocks_and_keeps_system_prompt() {
+        let content = session(&[
+            json!({"type": "system", "message": {"role": "system", "content": "Be terse."}}),
+            json!({"type": "user", "message": {"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": "gone", "content": "stale"},
+                {"type": "text", "text": "continue"},
+            ]}}),
+            json!({"type": "assistant", "message": {"role": "assistant", "content": [
+                {"type": "text", "text": "Sure."},
+                {"type": "tool_use", "id": "t9", "name": "Read", "input": {}},
+            ]}}),
+            json!({"type": "user", "isSidechain": true, "message": {"role": "user", "content": "side"}}),
+        ]);
+
+        let request = build_messages_api_request(&content);
+        assert_eq!(request["system"], "Be terse.");
+        assert_eq!(
+            request["messages"],
+            json!([
+                {"role": "user", "content": [{"type": "text", "text": "continue"}]},
+                {"role": "assistant", "content": [{"type": "text", "text": "Sure."}]},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_session_markdown() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("s1.jsonl");
+        let line = |uuid: &str, ts: &str, role: &str, content: Value| {
+            json!({"uuid": uuid, "sessionId": "s1", "timestamp": ts, "type": role,
+                   "message": {"role": role, "content": content}})
+        };
+        fs::write(
+            &path,
+            session(&[
+                line("u1", "2025-06-01T10:00:00Z", "user", json!("list files")),
+                line("a1", "2025-06-01T10:00:05Z", "assistant", json!([
+                    {"type": "thinking", "thinking": "Use ls.\nQuickly."},
+                    {"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "ls ```"}},
+                ])),
+                json!({"uuid": "u2", "sessionId": "s1", "timestamp": "2025-06-01T10:00:09Z",
+                       "type": "user", "toolUseResult": {"stdout": "a.txt"},
+                       "message": {"role": "user", "content": [
+                           {"type": "tool_result", "tool_use_id": "t1", "content": "a.txt"},
+                       ]}}),
+                line("a2", "2025-06-01T10:01:00Z", "assistant", json!([{"type": "text", "text": "One file."}])),
+            ]),
+        )
+        .unwrap();
+
+        let markdown = export_session_markdown(
+            "claude-code".to_string(),
+            path.to_string_lossy().to_string(),
+            "s1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            markdown.starts_with("# Session s1\n\n_2025-06-01T10:00:00Z – 2025-06-01T10:01:00Z_\n")
+        );
+        assert!(markdown.contains("### User\n\nlist files\n"));
+        assert!(markdown.contains("> Use ls.\n> Quickly.\n"));
+        // The input contains a triple backtick, so the fence is longer
+        assert!(markdown.contains("**Tool call: `Bash`**\n\n````json\n"));
+        // One result block despite the duplicate toolUseResult
+        assert_eq!(markdown.matches("<details>").count(), 1);
+        assert!(
+            markdown.contains("<summary>Tool result</summary>\n\n```\na.txt\n```\n\n</details>")
+        );
+        assert!(markdown.ends_with("### Assistant\n\nOne file.\n"));
+    }
+}
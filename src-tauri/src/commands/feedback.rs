@@ -8,6 +8,16 @@ pub struct SystemInfo {
     pub arch: String,
 }
 
+/// Version and build information for bug reports and self-tests.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppMetadata {
+    pub app_version: String,
+    pub tauri_version: String,
+    pub build_timestamp: String, // ISO 8601
+    pub target: String,          // "<arch>-<os>"
+    pub providers: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FeedbackData {
     pub subject: String,
@@ -31,6 +41,11 @@ pub async fn send_feedback(feedback: FeedbackData) -> Result<(), String> {
             system_info.os_type, system_info.os_version
         ));
         email_body.push_str(&format!("Architecture: {}\n", system_info.arch));
+        let metadata = get_app_metadata().await?;
+        email_body.push_str(&format!(
+            "Tauri: {} (built {})\n",
+            metadata.tauri_version, metadata.build_timestamp
+        ));
     }
 
     // Adjust email subject based on feedback type
@@ -71,6 +86,27 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
     })
 }
 
+#[tauri::command]
+pub async fn get_app_metadata() -> Result<AppMetadata, String> {
+    let build_timestamp = env!("BUILD_TIMESTAMP")
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(AppMetadata {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        build_timestamp,
+        target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+        providers: crate::commands::multi_provider::SUPPORTED_PROVIDERS
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
+    })
+}
+
 #[tauri::command]
 pub async fn open_github_issues() -> Result<(), String> {
     let github_url = "https://github.com/ndokutovich/claude-code-history-viewer/issues/new";
@@ -80,3 +116,17 @@ pub async fn open_github_issues() -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn app_metadata_reports_build_info() {
+        let metadata = get_app_metadata().await.unwrap();
+        assert_eq!(metadata.app_version, env!("CARGO_PKG_VERSION"));
+        assert!(chrono::DateTime::parse_from_rfc3339(&metadata.build_timestamp).is_ok());
+        assert!(metadata.target.ends_with(std::env::consts::OS));
+        assert!(metadata.providers.iter().any(|p| p == "claude-code"));
+    }
+}
@@ -2,25 +2,116 @@
 // FILE ACTIVITY EXTRACTION
 // ============================================================================
 // Extracts file operations from conversation history across all providers
-// Supports: Claude Code, Cursor IDE, and future providers via UniversalMessage
+// Supports: Claude Code, Cursor IDE, Gemini CLI, Codex CLI, and future providers via UniversalMessage
 
 use crate::commands::adapters::claude_code::{claude_message_to_universal, extract_project_id};
+use crate::commands::adapters::codex::{convert_codex_tool_calls, parse_codex_jsonl};
+use crate::commands::adapters::gemini::{find_gemini_sessions, gemini_file_to_messages};
+use crate::commands::codex::{find_rollout_files, rollout_messages};
 use crate::commands::cursor::{load_cursor_messages, load_cursor_sessions};
-use crate::commands::project::scan_projects;
-use crate::commands::session::load_project_sessions;
+use crate::commands::project::{scan_projects, scan_projects_since};
+use crate::commands::session::project_session_entries;
 use crate::models::universal::*;
 use crate::models::*;
+use crate::utils::{open_session_reader, project_folder_display_name};
 use chrono::{DateTime, Utc};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use uuid::Uuid;
 
+/// Provider that owns a single project path passed to `get_file_activities`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileActivitySource {
+    Claude,
+    Cursor,
+    Gemini,
+    Codex,
+}
+
+/// Detect the provider of a project path by checking its path components
+/// (more robust than string.contains()) and, for Codex, its rollout files
+fn detect_file_activity_source(path: &Path) -> FileActivitySource {
+    let components: Vec<_> = path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+
+    let has_cursor = components.iter().any(|c| c.eq_ignore_ascii_case("cursor"));
+    let has_workspace_storage = components
+        .iter()
+        .any(|c| c.eq_ignore_ascii_case("workspaceStorage"));
+    if has_cursor && has_workspace_storage {
+        return FileActivitySource::Cursor;
+    }
+
+    if components.contains(&".gemini") {
+        return FileActivitySource::Gemini;
+    }
+
+    if components.contains(&".codex") || is_rollout_file(path) {
+        return FileActivitySource::Codex;
+    }
+    let has_rollouts = path.is_dir()
+        && std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| is_rollout_file(&entry.path()))
+            })
+            .unwrap_or(false);
+    if has_rollouts {
+        return FileActivitySource::Codex;
+    }
+
+    FileActivitySource::Claude
+}
+
+/// True for Codex rollout files: `rollout-*.jsonl`
+fn is_rollout_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"))
+}
+
+/// Event reporting progress of an all-projects `get_file_activities` scan
+pub const FILE_ACTIVITY_PROGRESS_EVENT: &str = "file-activity-progress";
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct FileActivityProgress {
+    projects_done: usize,
+    projects_total: usize,
+    activities_found: usize,
+}
+
+pub(crate) type FileActivityProgressCallback<'a> = dyn Fn(FileActivityProgress) + Send + Sync + 'a;
+
 /// Extract file activities from a single project or all projects
-/// If project_path is empty or "*", loads from all available projects
+/// If project_path is empty or "*", loads from all available projects and
+/// emits a [`FILE_ACTIVITY_PROGRESS_EVENT`] after each project
 #[tauri::command]
 pub async fn get_file_activities(
+    app_handle: tauri::AppHandle,
     project_path: String,
     source_path: Option<String>,
     filters: FileActivityFilters,
+) -> Result<Vec<FileActivity>, String> {
+    use tauri::Emitter;
+
+    let emit_progress = |progress: FileActivityProgress| {
+        if let Err(e) = app_handle.emit(FILE_ACTIVITY_PROGRESS_EVENT, progress) {
+            eprintln!("Failed to emit file activity progress: {}", e);
+        }
+    };
+    collect_file_activities(project_path, source_path, filters, Some(&emit_progress)).await
+}
+
+/// `get_file_activities` without the Tauri event plumbing: `on_progress` is
+/// called after each project of an all-projects scan
+pub(crate) async fn collect_file_activities(
+    project_path: String,
+    source_path: Option<String>,
+    filters: FileActivityFilters,
+    on_progress: Option<&FileActivityProgressCallback<'_>>,
 ) -> Result<Vec<FileActivity>, String> {
     // Enforce absolute paths if provided
     if !project_path.is_empty()
@@ -35,6 +126,7 @@ pub async fn get_file_activities(
         }
     }
 
+    let since = parse_since(&filters)?;
     let mut activities = Vec::new();
 
     // Determine if we should load all projects or just one
@@ -46,22 +138,24 @@ pub async fn get_file_activities(
         let claude_path = source_path.ok_or_else(|| {
             "FILES_INVALID_ARGUMENT: Source path required for loading all projects".to_string()
         })?;
-        let projects = scan_projects(claude_path).await?;
-
-        for project in projects {
-            let sessions = load_project_sessions(project.path.clone(), Some(true), None).await?;
-
-            for session in sessions {
-                let messages = load_session_messages_for_files(&session.file_path).await?;
+        // Projects untouched since the cutoff cannot contain newer sessions
+        let projects = match filters.since.clone() {
+            Some(since) => scan_projects_since(claude_path, since).await?,
+            None => scan_projects(claude_path, None).await?,
+        };
+        let projects_total = projects.len();
+
+        for (projects_done, project) in (1..).zip(projects) {
+            let project_name = claude_project_name(Path::new(&project.path));
+            for session_file in recent_session_files(Path::new(&project.path), since) {
+                let messages = load_session_messages_for_files(&session_file).await?;
 
                 for msg in messages {
                     if let Some(tool_calls) = &msg.tool_calls {
                         for tool_call in tool_calls {
-                            if let Some(activity) = extract_file_activity_from_tool(
-                                &tool_call,
-                                &msg,
-                                &session.project_name,
-                            ) {
+                            if let Some(activity) =
+                                extract_file_activity_from_tool(&tool_call, &msg, &project_name)
+                            {
                                 if should_include_activity(&activity, &filters) {
                                     activities.push(activity);
                                 }
@@ -70,24 +164,21 @@ pub async fn get_file_activities(
                     }
                 }
             }
+
+            if let Some(on_progress) = on_progress {
+                on_progress(FileActivityProgress {
+                    projects_done,
+                    projects_total,
+                    activities_found: activities.len(),
+                });
+            }
         }
     } else {
         // Load from a specific project
-        // Detect if this is a Cursor workspace by checking path components
-        // More robust than string.contains() - checks actual path components
         let path = Path::new(&project_path);
-        let components: Vec<_> = path
-            .components()
-            .filter_map(|c| c.as_os_str().to_str())
-            .collect();
-
-        let has_cursor = components.iter().any(|c| c.eq_ignore_ascii_case("cursor"));
-        let has_workspace_storage = components
-            .iter()
-            .any(|c| c.eq_ignore_ascii_case("workspaceStorage"));
-        let is_cursor_workspace = has_cursor && has_workspace_storage;
+        let source = detect_file_activity_source(path);
 
-        if is_cursor_workspace {
+        if source == FileActivitySource::Cursor {
             // Extract Cursor base path from workspace path
             // Format: C:\Users\xxx\AppData\Roaming\Cursor\User\workspaceStorage\{workspace-id}
             let cursor_base = path
@@ -110,6 +201,15 @@ pub async fn get_file_activities(
             println!("  Found {} Cursor sessions", sessions.len());
 
             for session in sessions {
+                let last_modified = DateTime::parse_from_rfc3339(&session.last_modified)
+                    .map(SystemTime::from)
+                    .ok();
+                if let (Some(since), Some(last_modified)) = (since, last_modified) {
+                    if last_modified < since {
+                        continue;
+                    }
+                }
+
                 println!("  Loading messages for session: {}", session.id);
 
                 // Build session DB path: {db_path}#session={id}#timestamp={last_modified}
@@ -147,21 +247,67 @@ pub async fn get_file_activities(
 
                 println!("    Extracted {} file activities", session_files);
             }
+        } else if source == FileActivitySource::Gemini {
+            // Gemini CLI project: ~/.gemini/tmp/{projectHash}
+            let project_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Unknown")
+                .to_string();
+
+            println!("📂 Loading Gemini files from project: {}", project_path);
+
+            let messages = load_gemini_messages_for_files(&project_path, since).await?;
+
+            for msg in messages {
+                if let Some(tool_calls) = &msg.tool_calls {
+                    for tool_call in tool_calls {
+                        if let Some(activity) =
+                            extract_file_activity_from_tool(tool_call, &msg, &project_name)
+                        {
+                            if should_include_activity(&activity, &filters) {
+                                activities.push(activity);
+                            }
+                        }
+                    }
+                }
+            }
+        } else if source == FileActivitySource::Codex {
+            // Codex CLI: a rollout file or a directory of rollout files
+            println!("📂 Loading Codex files from: {}", project_path);
+
+            let messages = load_codex_messages_for_files(&project_path, since).await?;
+
+            for msg in messages {
+                // Codex projects are named after their session (see scan_codex_projects)
+                let project_name = format!(
+                    "Codex Session {}",
+                    msg.session_id.chars().take(8).collect::<String>()
+                );
+                if let Some(tool_calls) = &msg.tool_calls {
+                    for tool_call in convert_codex_tool_calls(tool_calls) {
+                        if let Some(activity) =
+                            extract_file_activity_from_tool(&tool_call, &msg, &project_name)
+                        {
+                            if should_include_activity(&activity, &filters) {
+                                activities.push(activity);
+                            }
+                        }
+                    }
+                }
+            }
         } else {
             // Claude Code project
-            let sessions = load_project_sessions(project_path.clone(), Some(true), None).await?;
-
-            for session in sessions {
-                let messages = load_session_messages_for_files(&session.file_path).await?;
+            let project_name = claude_project_name(Path::new(&project_path));
+            for session_file in recent_session_files(Path::new(&project_path), since) {
+                let messages = load_session_messages_for_files(&session_file).await?;
 
                 for msg in messages {
                     if let Some(tool_calls) = &msg.tool_calls {
                         for tool_call in tool_calls {
-                            if let Some(activity) = extract_file_activity_from_tool(
-                                &tool_call,
-                                &msg,
-                                &session.project_name,
-                            ) {
+                            if let Some(activity) =
+                                extract_file_activity_from_tool(&tool_call, &msg, &project_name)
+                            {
                                 if should_include_activity(&activity, &filters) {
                                     activities.push(activity);
                                 }
@@ -179,21 +325,64 @@ pub async fn get_file_activities(
     Ok(activities)
 }
 
+/// Parse the `since` filter into a cutoff for session file mtimes
+fn parse_since(filters: &FileActivityFilters) -> Result<Option<SystemTime>, String> {
+    filters
+        .since
+        .as_deref()
+        .map(|since| {
+            DateTime::parse_from_rfc3339(since)
+                .map(SystemTime::from)
+                .map_err(|e| format!("FILES_INVALID_ARGUMENT: Invalid since timestamp: {}", e))
+        })
+        .transpose()
+}
+
+/// True if `path` was modified at or after `since` (or there is no cutoff).
+/// Files whose mtime cannot be read are kept.
+fn modified_since(path: &Path, since: Option<SystemTime>) -> bool {
+    let Some(since) = since else {
+        return true;
+    };
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|modified| modified >= since)
+        .unwrap_or(true)
+}
+
+/// Session files of a Claude project modified at or after `since`, found by
+/// their mtime alone so stale sessions are never parsed.
+fn recent_session_files(project_path: &Path, since: Option<SystemTime>) -> Vec<String> {
+    project_session_entries(project_path)
+        .into_iter()
+        .filter(|entry| modified_since(entry.path(), since))
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .collect()
+}
+
+/// Display name of a Claude project, from its encoded folder name.
+fn claude_project_name(project_path: &Path) -> String {
+    project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(project_folder_display_name)
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
 /// Load messages from a session file (lightweight version for file extraction)
 async fn load_session_messages_for_files(
     session_path: &str,
 ) -> Result<Vec<UniversalMessage>, String> {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
+    use std::io::BufRead;
 
     // Clone path for move into spawn_blocking
     let session_path = session_path.to_string();
 
     // Wrap blocking I/O in spawn_blocking to avoid blocking the async runtime
     let messages = tokio::task::spawn_blocking(move || -> Result<Vec<UniversalMessage>, String> {
-        let file = File::open(&session_path)
+        // Decompresses `.jsonl.gz` sessions on the fly
+        let reader = open_session_reader(Path::new(&session_path))
             .map_err(|e| format!("FILE_READ_ERROR: Failed to open session file: {}", e))?;
-        let reader = BufReader::new(file);
 
         let mut messages = Vec::new();
 
@@ -253,8 +442,11 @@ async fn load_session_messages_for_files(
                     let project_id = extract_project_id(&None, &session_path);
                     let source_id = session_path.clone();
 
-                    let universal_msg =
+                    let mut universal_msg =
                         claude_message_to_universal(&claude_message, project_id, source_id, 0);
+                    universal_msg.provider_root = Some(
+                        crate::commands::session::session_project_id_and_root(&session_path).1,
+                    );
 
                     messages.push(universal_msg);
                 }
@@ -277,6 +469,79 @@ async fn load_session_messages_for_files(
     Ok(messages)
 }
 
+/// Load messages from every session file of a Gemini project directory
+async fn load_gemini_messages_for_files(
+    project_path: &str,
+    since: Option<SystemTime>,
+) -> Result<Vec<UniversalMessage>, String> {
+    let project_path = project_path.to_string();
+
+    tokio::task::spawn_blocking(move || -> Result<Vec<UniversalMessage>, String> {
+        let project_dir = Path::new(&project_path);
+        let project_id = project_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let session_files = find_gemini_sessions(project_dir)
+            .map_err(|e| format!("FILES_GEMINI_SCAN_ERROR: {}", e))?;
+
+        let mut messages = Vec::new();
+        for file in session_files {
+            if !modified_since(&file, since) {
+                continue;
+            }
+            match gemini_file_to_messages(&file, None, project_id.clone(), project_path.clone()) {
+                Ok(session_messages) => messages.extend(session_messages),
+                Err(e) => {
+                    eprintln!("Failed to parse Gemini session {}: {}", file.display(), e);
+                }
+            }
+        }
+
+        Ok(messages)
+    })
+    .await
+    .map_err(|e| format!("FILE_TASK_ERROR: Task join error: {}", e))?
+}
+
+/// Load messages from a Codex rollout file, or from every rollout file below a directory
+async fn load_codex_messages_for_files(
+    project_path: &str,
+    since: Option<SystemTime>,
+) -> Result<Vec<UniversalMessage>, String> {
+    let project_path = project_path.to_string();
+
+    tokio::task::spawn_blocking(move || -> Result<Vec<UniversalMessage>, String> {
+        let root = Path::new(&project_path);
+        let mut rollout_files: Vec<PathBuf> = Vec::new();
+        if root.is_file() {
+            rollout_files.push(root.to_path_buf());
+        } else {
+            find_rollout_files(root, &mut rollout_files)
+                .map_err(|e| format!("FILES_CODEX_SCAN_ERROR: {}", e))?;
+        }
+
+        let mut messages = Vec::new();
+        for file in rollout_files {
+            if !modified_since(&file, since) {
+                continue;
+            }
+            match parse_codex_jsonl(&file).and_then(|events| rollout_messages(&file, &events)) {
+                Ok(session_messages) => messages.extend(session_messages),
+                Err(e) => {
+                    log::warn!("Failed to parse Codex rollout {}: {}", file.display(), e);
+                }
+            }
+        }
+
+        Ok(messages)
+    })
+    .await
+    .map_err(|e| format!("FILE_TASK_ERROR: Task join error: {}", e))?
+}
+
 /// Extract file activity from a tool call
 fn extract_file_activity_from_tool(
     tool_call: &ToolCall,
@@ -316,6 +581,21 @@ fn extract_read_activity(
 
     let size = content.as_ref().map(|c| c.len());
 
+    // A ranged Read returns a slice; so does one cut short by the tool
+    let ranged = ["offset", "limit"]
+        .iter()
+        .any(|key| tool_call.input.get(*key).is_some_and(|v| !v.is_null()));
+    let output_file = tool_call.output.as_ref().and_then(|o| o.get("file"));
+    let line_count = |key: &str| {
+        output_file
+            .and_then(|f| f.get(key))
+            .and_then(|v| v.as_u64())
+    };
+    let truncated = matches!(
+        (line_count("numLines"), line_count("totalLines")),
+        (Some(num), Some(total)) if num < total
+    );
+
     Some(FileActivity {
         file_path,
         operation: FileOperation::Read,
@@ -328,6 +608,7 @@ fn extract_read_activity(
         content_after: content,
         size_before: None,
         size_after: size,
+        partial_read: (ranged || truncated).then_some(true),
         changes: None,
         lines_added: None,
         lines_removed: None,
@@ -357,6 +638,7 @@ fn extract_write_activity(
         content_after: content,
         size_before: None,
         size_after: size,
+        partial_read: None,
         changes: None,
         lines_added: None,
         lines_removed: None,
@@ -394,11 +676,17 @@ fn extract_edit_activity(
         (None, None)
     };
 
+    let replace_all = tool_call
+        .input
+        .get("replace_all")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
     let changes = vec![FileChange {
         old_string,
         new_string,
         line_start: None,
         line_end: None,
+        replace_all,
     }];
 
     Some(FileActivity {
@@ -413,6 +701,7 @@ fn extract_edit_activity(
         content_after: None,
         size_before: None,
         size_after: None,
+        partial_read: None,
         changes: Some(changes),
         lines_added,
         lines_removed,
@@ -451,6 +740,7 @@ fn extract_glob_activity(
         content_after: Some(format!("Found {} files", file_count)),
         size_before: None,
         size_after: Some(file_count),
+        partial_read: None,
         changes: None,
         lines_added: None,
         lines_removed: None,
@@ -489,6 +779,10 @@ fn extract_multiedit_activity(
                 new_string: new_str.to_string(),
                 line_start: None,
                 line_end: None,
+                replace_all: edit
+                    .get("replace_all")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
             });
         }
     }
@@ -505,6 +799,7 @@ fn extract_multiedit_activity(
         content_after: None,
         size_before: None,
         size_after: None,
+        partial_read: None,
         changes: Some(changes),
         lines_added: if total_lines_added > 0 {
             Some(total_lines_added)
@@ -582,3 +877,633 @@ fn should_include_activity(activity: &FileActivity, filters: &FileActivityFilter
 
     true
 }
+
+// ============================================================================
+// CROSS-SESSION FILE DIFF
+// ============================================================================
+
+/// Lines of unchanged context around each hunk of `diff_file_across_sessions`
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Largest LCS table (old lines x new lines, after trimming the common prefix
+/// and suffix) computed line by line; bigger spans are diffed as one
+/// delete+insert block
+const DIFF_MAX_LCS_CELLS: usize = 4_000_000;
+
+/// Unified diff of `file_path` between its content as of the end of
+/// `session_a` and of `session_b` (session ids from `get_file_activities`).
+///
+/// The content is replayed from the file activities of each session in order:
+/// a Read, Write or Create snapshot (or an Edit's recorded original file) sets
+/// it, and Edit/MultiEdit changes are applied on top. Returns an empty string
+/// when both versions are identical.
+#[tauri::command]
+pub async fn diff_file_across_sessions(
+    project_path: String,
+    file_path: String,
+    session_a: String,
+    session_b: String,
+) -> Result<String, String> {
+    let mut activities =
+        collect_file_activities(project_path, None, FileActivityFilters::default(), None).await?;
+    activities.retain(|a| a.file_path == file_path);
+    // Oldest first; the sort is stable so same-message activities keep order
+    activities.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    let content_as_of = |session_id: &str| {
+        let session_activities: Vec<&FileActivity> = activities
+            .iter()
+            .filter(|a| a.session_id == session_id)
+            .collect();
+        reconstruct_file_content(&session_activities).ok_or_else(|| {
+            format!(
+                "FILES_RECONSTRUCT_ERROR: Cannot reconstruct {} as of session {}: it has no full \
+                 snapshot of the file or its edits don't apply",
+                file_path, session_id
+            )
+        })
+    };
+    let old = content_as_of(&session_a)?;
+    let new = content_as_of(&session_b)?;
+
+    Ok(unified_diff(
+        &old,
+        &new,
+        &format!("{} ({})", file_path, session_a),
+        &format!("{} ({})", file_path, session_b),
+    ))
+}
+
+/// Replay file activities (oldest first) into the file's final content, or
+/// `None` when no snapshot precedes the edits or an edit doesn't apply.
+/// Partial Reads are skipped: their content is only a slice of the file.
+fn reconstruct_file_content(activities: &[&FileActivity]) -> Option<String> {
+    let mut content: Option<String> = None;
+    for activity in activities {
+        match activity.operation {
+            FileOperation::Read if activity.partial_read == Some(true) => {}
+            FileOperation::Read | FileOperation::Write | FileOperation::Create => {
+                if let Some(ref after) = activity.content_after {
+                    content = Some(after.clone());
+                }
+            }
+            FileOperation::Delete => content = Some(String::new()),
+            FileOperation::Edit | FileOperation::MultiEdit => {
+                if let Some(ref before) = activity.content_before {
+                    content = Some(before.clone());
+                }
+                for change in activity.changes.iter().flatten() {
+                    content = content.and_then(|c| {
+                        c.contains(&change.old_string).then(|| {
+                            if change.replace_all {
+                                c.replace(&change.old_string, &change.new_string)
+                            } else {
+                                c.replacen(&change.old_string, &change.new_string, 1)
+                            }
+                        })
+                    });
+                }
+            }
+            FileOperation::Glob => {}
+        }
+    }
+    content
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line-level edit script turning `old` into `new` (LCS based)
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(DiffOp, &str)> = old[..prefix].iter().map(|l| (DiffOp::Equal, *l)).collect();
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n.saturating_mul(m) > DIFF_MAX_LCS_CELLS {
+        ops.extend(old_mid.iter().map(|l| (DiffOp::Delete, *l)));
+        ops.extend(new_mid.iter().map(|l| (DiffOp::Insert, *l)));
+    } else {
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..]
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * (m + 1) + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_mid[i] == new_mid[j] {
+                ops.push((DiffOp::Equal, old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] {
+                ops.push((DiffOp::Delete, old_mid[i]));
+                i += 1;
+            } else {
+                ops.push((DiffOp::Insert, new_mid[j]));
+                j += 1;
+            }
+        }
+        ops.extend(old_mid[i..].iter().map(|l| (DiffOp::Delete, *l)));
+        ops.extend(new_mid[j..].iter().map(|l| (DiffOp::Insert, *l)));
+    }
+
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|l| (DiffOp::Equal, *l)),
+    );
+    ops
+}
+
+/// Unified diff (`---`/`+++` headers, `@@` hunks with
+/// [`DIFF_CONTEXT_LINES`] of context); empty when the texts have equal lines
+fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let changes: Vec<usize> = (0..ops.len())
+        .filter(|&i| ops[i].0 != DiffOp::Equal)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Line number (0-based) in old/new before each op
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0usize, 0usize);
+    for (op, _) in &ops {
+        positions.push((old_pos, new_pos));
+        match op {
+            DiffOp::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            DiffOp::Delete => old_pos += 1,
+            DiffOp::Insert => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut idx = 0;
+    while idx < changes.len() {
+        let start = changes[idx].saturating_sub(DIFF_CONTEXT_LINES);
+        let mut end = changes[idx] + 1;
+        // Merge changes whose context would overlap into one hunk
+        while idx + 1 < changes.len() && changes[idx + 1] <= end + 2 * DIFF_CONTEXT_LINES {
+            idx += 1;
+            end = changes[idx] + 1;
+        }
+        let end = (end + DIFF_CONTEXT_LINES).min(ops.len());
+        idx += 1;
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        let range = |from: usize, to: usize| {
+            let count = to - from;
+            // Empty ranges name the line before them, per the unified format
+            let first = if count == 0 { from } else { from + 1 };
+            format!("{},{}", first, count)
+        };
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_end),
+            range(new_start, new_end)
+        ));
+        for (op, line) in &ops[start..end] {
+            let marker = match op {
+                DiffOp::Equal => ' ',
+                DiffOp::Delete => '-',
+                DiffOp::Insert => '+',
+            };
+            out.push(marker);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_gemini_project_file_activities() {
+        let temp = TempDir::new().unwrap();
+        let project_dir = temp.path().join(".gemini").join("tmp").join("abc123");
+        let chats = project_dir.join("chats");
+        std::fs::create_dir_all(&chats).unwrap();
+        let session = json!({
+            "sessionId": "s1",
+            "messages": [{
+                "id": "m1",
+                "type": "gemini",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "content": "",
+                "toolCalls": [
+                    {"id": "t1", "name": "read_file", "args": {"absolute_path": "/src/main.rs"}},
+                    {"id": "t2", "name": "write_file", "args": {"file_path": "/src/lib.rs", "content": "fn a() {}"}},
+                    {"id": "t3", "name": "shell", "args": {"command": "ls"}}
+                ]
+            }]
+        });
+        std::fs::write(chats.join("session-1.json"), session.to_string()).unwrap();
+
+        let activities = collect_file_activities(
+            project_dir.to_string_lossy().to_string(),
+            None,
+            FileActivityFilters::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(activities.len(), 2);
+        let read = activities.iter().find(|a| a.tool_name == "Read").unwrap();
+        assert_eq!(read.file_path, "/src/main.rs");
+        assert_eq!(read.project_id, "abc123");
+        let write = activities.iter().find(|a| a.tool_name == "Write").unwrap();
+        assert_eq!(write.size_after, Some(9));
+    }
+
+    #[tokio::test]
+    async fn test_all_projects_reports_progress_per_project() {
+        let temp = TempDir::new().unwrap();
+        for (project, file) in [("-tmp-one", "/one.rs"), ("-tmp-two", "/two.rs")] {
+            let dir = temp.path().join("projects").join(project);
+            std::fs::create_dir_all(&dir).unwrap();
+            let user = json!({
+                "uuid": "u1", "sessionId": "s1", "timestamp": "2025-01-01T00:00:00Z",
+                "type": "user",
+                "message": {"role": "user", "content": "write it"}
+            });
+            let assistant = json!({
+                "uuid": "a1", "sessionId": "s1", "timestamp": "2025-01-01T00:00:01Z",
+                "type": "assistant",
+                "message": {"role": "assistant", "content": [{
+                    "type": "tool_use", "id": "t1", "name": "Write",
+                    "input": {"file_path": file, "content": "fn main() {}"}
+                }]}
+            });
+            std::fs::write(dir.join("s1.jsonl"), format!("{}\n{}\n", user, assistant)).unwrap();
+        }
+
+        let progress = std::sync::Mutex::new(Vec::new());
+        let record = |p: FileActivityProgress| {
+            progress
+                .lock()
+                .unwrap()
+                .push((p.projects_done, p.projects_total, p.activities_found));
+        };
+        let activities = collect_file_activities(
+            "*".to_string(),
+            Some(temp.path().to_string_lossy().to_string()),
+            FileActivityFilters::default(),
+            Some(&record),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(activities.len(), 2);
+        assert_eq!(*progress.lock().unwrap(), vec![(1, 2, 1), (2, 2, 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_project_file_activities_include_gzip_sessions() {
+        use std::io::Write;
+
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("projects").join("-tmp-app");
+        std::fs::create_dir_all(&dir).unwrap();
+        let session = |id: &str, file: &str| {
+            let assistant = json!({
+                "uuid": format!("a-{}", id), "sessionId": id,
+                "timestamp": "2025-01-01T00:00:01Z", "type": "assistant",
+                "message": {"role": "assistant", "content": [{
+                    "type": "tool_use", "id": "t1", "name": "Write",
+                    "input": {"file_path": file, "content": "fn main() {}"}
+                }]}
+            });
+            format!("{}\n", assistant)
+        };
+        std::fs::write(dir.join("live.jsonl"), session("live", "/live.rs")).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(session("archived", "/archived.rs").as_bytes())
+            .unwrap();
+        std::fs::write(dir.join("archived.jsonl.gz"), encoder.finish().unwrap()).unwrap();
+
+        let activities = collect_file_activities(
+            dir.to_string_lossy().to_string(),
+            None,
+            FileActivityFilters::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        let mut files: Vec<&str> = activities.iter().map(|a| a.file_path.as_str()).collect();
+        files.sort_unstable();
+        assert_eq!(files, vec!["/archived.rs", "/live.rs"]);
+
+        // Sessions older than `since` are skipped by mtime
+        let filters = FileActivityFilters {
+            since: Some((Utc::now() + chrono::Duration::hours(1)).to_rfc3339()),
+            ..Default::default()
+        };
+        let activities =
+            collect_file_activities(dir.to_string_lossy().to_string(), None, filters, None)
+                .await
+                .unwrap();
+        assert!(activities.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_since_skips_sessions_modified_before_cutoff() {
+        let temp = TempDir::new().unwrap();
+        let chats = temp
+            .path()
+            .join(".gemini")
+            .join("tmp")
+            .join("p")
+            .join("chats");
+        std::fs::create_dir_all(&chats).unwrap();
+        let session = json!({
+            "messages": [{
+                "type": "gemini",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "content": "",
+                "toolCalls": [{"name": "read_file", "args": {"file_path": "/a.rs"}}]
+            }]
+        });
+        std::fs::write(chats.join("session-1.json"), session.to_string()).unwrap();
+        let project_path = chats.parent().unwrap().to_string_lossy().to_string();
+
+        let mut filters = FileActivityFilters {
+            since: Some((Utc::now() + chrono::Duration::hours(1)).to_rfc3339()),
+            ..Default::default()
+        };
+        let activities = collect_file_activities(project_path.clone(), None, filters.clone(), None)
+            .await
+            .unwrap();
+        assert!(activities.is_empty());
+
+        filters.since = Some((Utc::now() - chrono::Duration::hours(1)).to_rfc3339());
+        let activities = collect_file_activities(project_path.clone(), None, filters, None)
+            .await
+            .unwrap();
+        assert_eq!(activities.len(), 1);
+
+        let invalid = FileActivityFilters {
+            since: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        let err = collect_file_activities(project_path, None, invalid, None)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("FILES_INVALID_ARGUMENT"));
+    }
+
+    #[tokio::test]
+    async fn test_codex_rollout_file_activities() {
+        let temp = TempDir::new().unwrap();
+        let rollout = temp
+            .path()
+            .join("rollout-2025-01-27T14-30-45-a1b2c3d4-e5f6-7890-abcd-ef1234567890.jsonl");
+        let patch = "*** Begin Patch\n*** Update File: /repo/src/main.rs\n@@\n-println!(\"old\");\n+println!(\"new\");\n+println!(\"more\");\n*** End Patch";
+        let events = [
+            json!({
+                "timestamp": "2025-01-27T14:30:46Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "message", "role": "user",
+                    "content": [{"type": "input_text", "text": "fix main"}]
+                }
+            }),
+            json!({
+                "timestamp": "2025-01-27T14:30:47Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call", "name": "shell", "call_id": "call_1",
+                    "arguments": json!({"command": ["apply_patch", patch]}).to_string()
+                }
+            }),
+            json!({
+                "timestamp": "2025-01-27T14:30:48Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call_output", "call_id": "call_1",
+                    "output": "{\"output\":\"Done!\",\"metadata\":{\"exit_code\":0}}"
+                }
+            }),
+        ];
+        let content: Vec<String> = events.iter().map(|e| e.to_string()).collect();
+        std::fs::write(&rollout, content.join("\n")).unwrap();
+
+        assert_eq!(
+            detect_file_activity_source(&rollout),
+            FileActivitySource::Codex
+        );
+        assert_eq!(
+            detect_file_activity_source(temp.path()),
+            FileActivitySource::Codex
+        );
+
+        let activities = collect_file_activities(
+            rollout.to_string_lossy().to_string(),
+            None,
+            FileActivityFilters::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(activities.len(), 1);
+        let edit = &activities[0];
+        assert_eq!(edit.tool_name, "Edit");
+        assert_eq!(edit.file_path, "/repo/src/main.rs");
+        assert_eq!(edit.session_id, "a1b2c3d4-e5f6-7890-abcd-ef1234567890");
+        assert_eq!(edit.lines_added, Some(1));
+    }
+
+    fn activity(
+        operation: FileOperation,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> FileActivity {
+        FileActivity {
+            file_path: "/f.txt".to_string(),
+            operation,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            session_id: "s".to_string(),
+            project_id: "p".to_string(),
+            message_id: "m".to_string(),
+            tool_name: String::new(),
+            content_before: before.map(String::from),
+            content_after: after.map(String::from),
+            size_before: None,
+            size_after: None,
+            partial_read: None,
+            changes: None,
+            lines_added: None,
+            lines_removed: None,
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_file_content_replays_edits() {
+        let read = activity(FileOperation::Read, None, Some("a\nb\n"));
+        let mut edit = activity(FileOperation::Edit, None, None);
+        edit.changes = Some(vec![FileChange {
+            old_string: "b".to_string(),
+            new_string: "c".to_string(),
+            line_start: None,
+            line_end: None,
+            replace_all: false,
+        }]);
+        assert_eq!(
+            reconstruct_file_content(&[&read, &edit]).as_deref(),
+            Some("a\nc\n")
+        );
+        // No snapshot before the edit
+        assert_eq!(reconstruct_file_content(&[&edit]), None);
+        // Edit that doesn't apply to the known content
+        let stale = activity(FileOperation::Read, None, Some("x\n"));
+        assert_eq!(reconstruct_file_content(&[&stale, &edit]), None);
+        // An Edit's recorded original file counts as a snapshot
+        edit.content_before = Some("b".to_string());
+        assert_eq!(reconstruct_file_content(&[&edit]).as_deref(), Some("c"));
+
+        // A partial Read is not a snapshot of the file
+        let mut slice = activity(FileOperation::Read, None, Some("b\n"));
+        slice.partial_read = Some(true);
+        assert_eq!(
+            reconstruct_file_content(&[&read, &slice]).as_deref(),
+            Some("a\nb\n")
+        );
+        assert_eq!(reconstruct_file_content(&[&slice]), None);
+
+        // replace_all replaces every occurrence
+        let repeated = activity(FileOperation::Read, None, Some("b\nb\n"));
+        edit.content_before = None;
+        assert_eq!(
+            reconstruct_file_content(&[&repeated, &edit]).as_deref(),
+            Some("c\nb\n")
+        );
+        edit.changes.as_mut().unwrap()[0].replace_all = true;
+        assert_eq!(
+            reconstruct_file_content(&[&repeated, &edit]).as_deref(),
+            Some("c\nc\n")
+        );
+    }
+
+    #[test]
+    fn test_read_activity_flags_partial_reads() {
+        let message: UniversalMessage = serde_json::from_value(serde_json::json!({
+            "id": "m", "sessionId": "s", "projectId": "p", "sourceId": "src",
+            "providerId": "claude-code", "timestamp": "2025-01-01T00:00:00Z",
+            "sequenceNumber": 0, "role": "assistant", "messageType": "message",
+            "content": [], "originalFormat": "", "providerMetadata": {}
+        }))
+        .unwrap();
+        let read = |input: serde_json::Value, num_lines: u64| {
+            let tool_call: ToolCall = serde_json::from_value(serde_json::json!({
+                "id": "t1", "name": "Read", "input": input,
+                "output": {"file": {
+                    "content": "a", "numLines": num_lines, "startLine": 1, "totalLines": 3
+                }},
+                "status": "success"
+            }))
+            .unwrap();
+            extract_read_activity(&tool_call, &message, "p")
+                .unwrap()
+                .partial_read
+        };
+
+        assert_eq!(read(serde_json::json!({"file_path": "/f"}), 3), None);
+        assert_eq!(
+            read(serde_json::json!({"file_path": "/f", "offset": 2}), 2),
+            Some(true)
+        );
+        assert_eq!(
+            read(serde_json::json!({"file_path": "/f", "limit": 1}), 1),
+            Some(true)
+        );
+        assert_eq!(read(serde_json::json!({"file_path": "/f"}), 2), Some(true));
+    }
+
+    #[test]
+    fn test_multiedit_activity_keeps_replace_all_per_edit() {
+        let message: UniversalMessage = serde_json::from_value(serde_json::json!({
+            "id": "m", "sessionId": "s", "projectId": "p", "sourceId": "src",
+            "providerId": "claude-code", "timestamp": "2025-01-01T00:00:00Z",
+            "sequenceNumber": 0, "role": "assistant", "messageType": "message",
+            "content": [], "originalFormat": "", "providerMetadata": {}
+        }))
+        .unwrap();
+        let tool_call: ToolCall = serde_json::from_value(serde_json::json!({
+            "id": "t1", "name": "MultiEdit",
+            "input": {"file_path": "/f", "edits": [
+                {"old_string": "a", "new_string": "b", "replace_all": true},
+                {"old_string": "c", "new_string": "d"},
+            ]},
+            "status": "success"
+        }))
+        .unwrap();
+
+        let activity = extract_multiedit_activity(&tool_call, &message, "p").unwrap();
+        let replace_all: Vec<bool> = activity
+            .changes
+            .unwrap()
+            .iter()
+            .map(|c| c.replace_all)
+            .collect();
+        assert_eq!(replace_all, vec![true, false]);
+    }
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "x", "y"), "");
+
+        let old: String = (1..=20).map(|i| format!("{}\n", i)).collect();
+        let new: String = (1..=20)
+            .filter(|&i| i != 18)
+            .map(|i| match i {
+                2 => "two\n".to_string(),
+                _ => format!("{}\n", i),
+            })
+            .collect();
+        assert_eq!(
+            unified_diff(&old, &new, "old", "new"),
+            "--- old\n+++ new\n\
+             @@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n\
+             @@ -15,6 +15,5 @@\n 15\n 16\n 17\n-18\n 19\n 20\n"
+        );
+
+        assert_eq!(
+            unified_diff("", "new\n", "old", "new"),
+            "--- old\n+++ new\n@@ -0,0 +1,1 @@\n+new\n"
+        );
+    }
+}
@@ -20,8 +20,8 @@ fn resolve_gemini_base() -> Result<PathBuf, String> {
             return Ok(p);
         }
     }
-    let home_dir =
-        dirs::home_dir().ok_or("HOME_DIRECTORY_NOT_FOUND: Could not determine home directory")?;
+    let home_dir = crate::utils::resolve_home()
+        .ok_or("HOME_DIRECTORY_NOT_FOUND: Could not determine home directory")?;
     Ok(home_dir.join(".gemini"))
 }
 
@@ -152,40 +152,12 @@ pub async fn load_gemini_messages(
     project_id: String,
     source_id: String,
 ) -> Result<Vec<UniversalMessage>, String> {
-    let path = Path::new(&session_path);
-
-    // Read JSON file
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read Gemini session: {}", e))?;
-
-    let session: GeminiSession = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse Gemini session: {}", e))?;
-
-    // Extract messages
-    let messages = session
-        .messages
-        .or(session.history)
-        .unwrap_or_else(Vec::new);
-
-    // Convert to UniversalMessage
-    let mut universal_messages = Vec::new();
-    for (i, msg_value) in messages.iter().enumerate() {
-        match gemini_message_to_universal(
-            msg_value,
-            session_id.clone(),
-            project_id.clone(),
-            source_id.clone(),
-            i as i32,
-        ) {
-            Ok(msg) => universal_messages.push(msg),
-            Err(e) => {
-                eprintln!("Failed to parse Gemini message {}: {}", i, e);
-                // Continue with other messages
-            }
-        }
-    }
-
-    Ok(universal_messages)
+    gemini_file_to_messages(
+        Path::new(&session_path),
+        Some(session_id),
+        project_id,
+        source_id,
+    )
 }
 
 #[tauri::command]
@@ -0,0 +1,225 @@
+//! Leveled logging through `tauri-plugin-log`
+//!
+//! Backend diagnostics go through the `log` macros instead of `println!`, so
+//! release builds stay quiet. The verbosity starts at `$HISTORY_VIEWER_LOG`
+//! (`off`, `error`, `warn`, `info`, `debug` or `trace`), or `info` (`debug`
+//! in debug builds) when unset, and can be changed at runtime with
+//! `set_log_level`.
+//!
+//! Besides stdout, logs are written to `~/.claude/.history-viewer/logs/`
+//! (rotated at [`LOG_MAX_FILE_BYTES`], keeping [`LOG_KEEP_ARCHIVES`] old
+//! files, so about 5 MB in total) for users to attach to bug reports via
+//! `get_log_path` / `export_logs`.
+
+use log::LevelFilter;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Environment variable holding the startup log level.
+pub const LOG_LEVEL_ENV: &str = "HISTORY_VIEWER_LOG";
+
+/// Base name of the log files: `history-viewer.log` is the active file,
+/// `history-viewer_<date>.log` the rotated ones.
+const LOG_FILE_NAME: &str = "history-viewer";
+
+/// Size at which the active log file is rotated.
+const LOG_MAX_FILE_BYTES: u128 = 1_000_000;
+
+/// Rotated log files kept next to the active one.
+const LOG_KEEP_ARCHIVES: usize = 4;
+
+/// Current verbosity as a `LevelFilter` discriminant. Checked by the logger
+/// itself, so it applies even before `log::set_max_level` has been lowered.
+static LOG_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Info as usize);
+
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+/// Parse a level name (case-insensitive, surrounding whitespace ignored).
+pub fn parse_log_level(level: &str) -> Result<LevelFilter, String> {
+    level.trim().parse::<LevelFilter>().map_err(|_| {
+        format!(
+            "LOG_INVALID_ARGUMENT: Unknown log level '{}' (expected off, error, warn, info, debug or trace)",
+            level
+        )
+    })
+}
+
+/// Startup level: `$HISTORY_VIEWER_LOG` when valid, otherwise `info`
+/// (`debug` in debug builds).
+fn startup_log_level() -> LevelFilter {
+    if let Ok(value) = std::env::var(LOG_LEVEL_ENV) {
+        match parse_log_level(&value) {
+            Ok(level) => return level,
+            Err(e) => eprintln!("Ignoring {}: {}", LOG_LEVEL_ENV, e),
+        }
+    }
+    if cfg!(debug_assertions) {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Info
+    }
+}
+
+fn current_log_level() -> LevelFilter {
+    LEVELS[LOG_LEVEL.load(Ordering::Relaxed).min(LEVELS.len() - 1)]
+}
+
+fn apply_log_level(level: LevelFilter) {
+    LOG_LEVEL.store(level as usize, Ordering::Relaxed);
+    log::set_max_level(level);
+}
+
+/// `~/.claude/.history-viewer/logs`, or `None` without a home directory.
+fn log_dir() -> Option<PathBuf> {
+    crate::utils::resolve_home()
+        .map(|home| home.join(".claude").join(".history-viewer").join("logs"))
+}
+
+/// The log plugin: stdout and rotating file output filtered by the current
+/// verbosity. Dependencies are capped at `warn` so their chatter doesn't
+/// drown ours.
+pub fn log_plugin<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    LOG_LEVEL.store(startup_log_level() as usize, Ordering::Relaxed);
+    let mut builder =
+        tauri_plugin_log::Builder::new()
+            .clear_targets()
+            .target(tauri_plugin_log::Target::new(
+                tauri_plugin_log::TargetKind::Stdout,
+            ));
+    if let Some(dir) = log_dir() {
+        builder = builder
+            .target(tauri_plugin_log::Target::new(
+                tauri_plugin_log::TargetKind::Folder {
+                    path: dir,
+                    file_name: Some(LOG_FILE_NAME.to_string()),
+                },
+            ))
+            .max_file_size(LOG_MAX_FILE_BYTES)
+            .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepSome(
+                LOG_KEEP_ARCHIVES,
+            ));
+    }
+    builder
+        .level(LevelFilter::Warn)
+        .level_for(env!("CARGO_CRATE_NAME"), LevelFilter::Trace)
+        .filter(|metadata| metadata.level() <= current_log_level())
+        .build()
+}
+
+/// Change the backend log verbosity for the rest of the session.
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<(), String> {
+    let level = parse_log_level(&level)?;
+    apply_log_level(level);
+    log::info!("Log level set to {}", level);
+    Ok(())
+}
+
+/// Path of the active log file (it may not exist yet).
+#[tauri::command]
+pub async fn get_log_path() -> Result<String, String> {
+    log_dir()
+        .map(|dir| {
+            dir.join(format!("{}.log", LOG_FILE_NAME))
+                .to_string_lossy()
+                .to_string()
+        })
+        .ok_or_else(|| "LOG_PATH_ERROR: Could not find home directory".to_string())
+}
+
+/// Contents of all log files, oldest first, each preceded by a
+/// `==> <file name> <==` header. Empty when nothing has been logged yet.
+#[tauri::command]
+pub async fn export_logs() -> Result<String, String> {
+    let dir =
+        log_dir().ok_or_else(|| "LOG_PATH_ERROR: Could not find home directory".to_string())?;
+    collect_logs(&dir)
+}
+
+fn collect_logs(dir: &Path) -> Result<String, String> {
+    if !dir.exists() {
+        return Ok(String::new());
+    }
+    let active = format!("{}.log", LOG_FILE_NAME);
+    let archive_prefix = format!("{}_", LOG_FILE_NAME);
+    let mut archives: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| format!("LOG_READ_ERROR: Failed to list log directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(&archive_prefix) && name.ends_with(".log"))
+        .collect();
+    // Rotated names embed a `YYYY-MM-DD_HH-MM-SS` stamp, so names sort by age
+    archives.sort();
+
+    let mut out = String::new();
+    for name in archives.iter().chain(std::iter::once(&active)) {
+        let path = dir.join(name);
+        if !path.exists() {
+            continue;
+        }
+        let bytes = fs::read(&path)
+            .map_err(|e| format!("LOG_READ_ERROR: Failed to read {}: {}", name, e))?;
+        out.push_str(&format!("==> {} <==\n", name));
+        out.push_str(&String::from_utf8_lossy(&bytes));
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_level() {
+        assert_eq!(parse_log_level("debug"), Ok(LevelFilter::Debug));
+        assert_eq!(parse_log_level(" WARN "), Ok(LevelFilter::Warn));
+        assert_eq!(parse_log_level("off"), Ok(LevelFilter::Off));
+        assert!(parse_log_level("loud")
+            .unwrap_err()
+            .starts_with("LOG_INVALID_ARGUMENT"));
+    }
+
+    #[test]
+    fn test_level_filter_discriminants_index_levels() {
+        for (i, level) in LEVELS.iter().enumerate() {
+            assert_eq!(*level as usize, i);
+        }
+    }
+
+    #[test]
+    fn test_collect_logs_orders_archives_before_active_file() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(collect_logs(&temp.path().join("missing")).unwrap(), "");
+
+        fs::write(temp.path().join("history-viewer.log"), "current\n").unwrap();
+        fs::write(
+            temp.path().join("history-viewer_2025-02-01_00-00-00.log"),
+            "newer",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("history-viewer_2025-01-01_00-00-00.log"),
+            "older\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("other.log"), "ignored\n").unwrap();
+
+        assert_eq!(
+            collect_logs(temp.path()).unwrap(),
+            "==> history-viewer_2025-01-01_00-00-00.log <==\nolder\n\
+             ==> history-viewer_2025-02-01_00-00-00.log <==\nnewer\n\
+             ==> history-viewer.log <==\ncurrent\n"
+        );
+    }
+}
@@ -41,14 +41,20 @@ impl Default for MetadataState {
 /// Returns the metadata directory.
 ///
 /// Windows: `%LOCALAPPDATA%\claude-history-viewer`  (via `dirs::data_local_dir`)
-/// Other:   `~/.claude-history-viewer`              (via `dirs::home_dir`)
+/// Other:   `~/.claude-history-viewer`              (via `resolve_home`)
+///
+/// A `HISTORY_VIEWER_HOME` override takes precedence on every platform.
 fn get_metadata_dir() -> Result<PathBuf, String> {
+    if let Some(home) = crate::utils::home_override() {
+        return Ok(home.join("claude-history-viewer"));
+    }
+
     #[cfg(target_os = "windows")]
     let base = dirs::data_local_dir()
         .ok_or_else(|| "METADATA_READ_ERROR: Could not resolve %LOCALAPPDATA%".to_string())?;
 
     #[cfg(not(target_os = "windows"))]
-    let base = dirs::home_dir()
+    let base = crate::utils::resolve_home()
         .ok_or_else(|| "METADATA_READ_ERROR: Could not find home directory".to_string())?;
 
     Ok(base.join("claude-history-viewer"))
@@ -376,10 +382,33 @@ pub async fn clear_all_metadata(
         sessions: HashMap::new(),
         projects: HashMap::new(),
         version: 1,
+        custom_stopwords: Vec::new(),
     };
     persist(&state, fresh)
 }
 
+// ============================================================================
+// Stopwords
+// ============================================================================
+
+/// Replace the user's custom stopwords (merged with the built-in lists by the
+/// topic/keyword features). Words are trimmed, lowercased and deduplicated.
+#[tauri::command]
+pub async fn set_custom_stopwords(
+    words: Vec<String>,
+    state: State<'_, MetadataState>,
+) -> Result<(), String> {
+    let mut meta = get_cached(&state)?;
+    meta.custom_stopwords = crate::utils::stopwords::normalize_custom(&words);
+    persist(&state, meta)
+}
+
+/// The user's custom stopwords, for features building a
+/// [`Stopwords`](crate::utils::stopwords::Stopwords) set.
+pub(crate) fn custom_stopwords(state: &State<'_, MetadataState>) -> Result<Vec<String>, String> {
+    Ok(get_cached(state)?.custom_stopwords)
+}
+
 // ============================================================================
 // Upstream-compatible commands (kept for compatibility with load_user_metadata
 // / update_session_metadata / update_project_metadata patterns)
@@ -1,22 +1,31 @@
 pub mod adapters; // v2.0.0 - Universal provider adapters
 pub mod claude_settings; // Settings CRUD and MCP server management
 pub mod codex; // v1.8.0 - Codex CLI support
+pub mod context_usage; // Duplicate tool results and other context usage analysis
 pub mod cursor; // v2.0.0 - Cursor IDE support
 pub mod edits; // Recent file edits tracking and restore
+pub mod export; // Session export (Anthropic Messages API request JSON)
 pub mod feedback;
 pub mod fs_utils; // Cross-platform filesystem utilities
 pub mod files; // v1.5.0+ - File activity tracking
 pub mod gemini; // v1.7.0 - Gemini CLI support
+pub mod logging; // Leveled logging (tauri-plugin-log) and runtime verbosity
 pub mod mcp_presets; // MCP server presets
+pub mod productivity; // Productivity dashboard (active time, files touched, lines changed)
 pub mod project;
 pub mod rename; // Native session renaming
 pub mod resume; // Session resume functionality
 pub mod secure_update;
 pub mod session;
+pub mod session_archive; // Archive sessions to .tar.gz
+pub mod session_cache; // mtime/size cache of parsed sessions for load_project_sessions
 pub mod session_delete; // Delete session (move JSONL + associated folder to trash)
 pub mod session_writer; // v1.6.0+ - Session creation and writing
+pub mod session_templates; // Session templates (store plugin)
 pub mod settings; // Settings presets
 pub mod stats;
+pub mod time_format; // Localized relative time strings ("3 hours ago")
+pub mod topics; // Session keyword/topic extraction
 pub mod unified_presets; // Unified presets (settings + MCP)
 pub mod update;
 pub mod metadata; // v1.9.0 - Session/project metadata persistence
@@ -27,5 +36,6 @@ pub mod forgecode; // v1.9.x - ForgeCode support
 pub mod antigravity; // v1.9.x - Antigravity support
 pub mod multi_provider; // v1.9.0 - Unified multi-provider facade
 pub mod search_match; // Shared search primitives: matcher, top-k, cache invalidation
+pub mod search_history; // Persisted recent search queries (store plugin)
 pub mod watcher; // File watcher for real-time session detection
 pub mod wsl; // WSL distro detection + AI-tool directory resolution (Windows)
@@ -35,17 +35,26 @@ use crate::commands::adapters::forgecode::{
     parse_session_path as forgecode_parse_session_path, scan_forgecode_projects as forgecode_scan_projects,
 };
 use crate::commands::adapters::gemini::GeminiHashResolver;
-use crate::commands::adapters::opencode::{get_opencode_base_path, scan_opencode_projects_impl};
+use crate::commands::adapters::opencode::{
+    get_opencode_base_path, opencode_db_path, scan_opencode_projects_impl,
+};
 use crate::commands::search_match::{
-    cache_key, current_search_generation, take_matching, top_k_by, QueryMatcher,
+    cache_key, current_search_generation, message_matches, take_matching, top_k_by, MessageFilter,
+    QueryMatcher,
+};
+use crate::models::universal::{
+    ContentType, MessageRole, MessageType, UniversalMessage, UniversalProject, UniversalSession,
+};
+use crate::models::{MessageIncludeFilters, SearchFilters, UniversalMessagePage};
+use crate::utils::{
+    cap_message_content, hash_message_content, set_message_source, strip_raw_metadata,
 };
-use crate::models::universal::{UniversalMessage, UniversalProject, UniversalSession};
-use crate::models::SearchFilters;
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 // ============================================================================
@@ -67,6 +76,29 @@ pub struct DetectedProvider {
     pub error: Option<String>,
 }
 
+/// One provider install to search with [`search_all_providers`]. `path` is
+/// the base directory the provider's own commands take (the Claude folder,
+/// `~/.codex/sessions`, the Cursor user-data folder); the other providers
+/// resolve their data directories themselves and ignore it.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct ProviderSource {
+    pub provider_id: String,
+    pub path: String,
+}
+
+/// Ids of the providers this build can load, in display order.
+pub const SUPPORTED_PROVIDERS: &[&str] = &[
+    "claude-code",
+    "codex",
+    "gemini",
+    "cursor",
+    "opencode",
+    "cline",
+    "aider",
+    "forgecode",
+    "antigravity",
+];
+
 // ============================================================================
 // PROVIDER DETECTION
 // ============================================================================
@@ -217,6 +249,91 @@ pub async fn detect_providers() -> Result<Vec<DetectedProvider>, String> {
     Ok(providers)
 }
 
+// ============================================================================
+// PROVIDER ACTIVITY
+// ============================================================================
+
+/// Session totals of one detected provider, for the onboarding/home screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderActivity {
+    /// Provider id as in [`DetectedProvider::id`]
+    pub id: String,
+    pub display_name: String,
+    pub session_count: usize,
+    /// Timestamp of the most recent session (None when there are none)
+    pub last_activity_at: Option<String>,
+}
+
+/// Session count and most recent activity for every available provider.
+///
+/// Providers are scanned concurrently, each through its project scan (Cursor
+/// through its workspace scan), and returned in detection order. A provider
+/// whose scan fails is reported with no sessions.
+#[tauri::command]
+pub async fn get_provider_last_activity() -> Result<Vec<ProviderActivity>, String> {
+    let scans: Vec<_> = detect_providers()
+        .await?
+        .into_iter()
+        .filter(|p| p.is_available)
+        .map(|p| tauri::async_runtime::spawn(provider_activity(p)))
+        .collect();
+
+    let mut activity = Vec::with_capacity(scans.len());
+    for scan in scans {
+        activity.push(
+            scan.await
+                .map_err(|e| format!("PROVIDER_ACTIVITY_ERROR: Scan task failed: {}", e))?,
+        );
+    }
+    Ok(activity)
+}
+
+async fn provider_activity(provider: DetectedProvider) -> ProviderActivity {
+    let totals: Vec<(usize, Option<String>)> = if provider.id == "cursor" {
+        let cursor_path = provider.base_path.clone().unwrap_or_default();
+        crate::commands::cursor::scan_cursor_workspaces(cursor_path)
+            .await
+            .map(|workspaces| {
+                workspaces
+                    .into_iter()
+                    .map(|w| (w.session_count, w.last_activity))
+                    .collect()
+            })
+    } else {
+        scan_all_projects(None, Some(vec![provider.id.clone()]), None, None)
+            .await
+            .map(|projects| {
+                projects
+                    .into_iter()
+                    .map(|p| (p.session_count, p.last_activity_at))
+                    .collect()
+            })
+    }
+    .unwrap_or_else(|e| {
+        eprintln!(
+            "[multi_provider] {} activity scan failed: {}",
+            provider.id, e
+        );
+        Vec::new()
+    });
+
+    let session_count = totals.iter().map(|(count, _)| count).sum();
+    let last_activity_at = latest_timestamp(totals.into_iter().filter_map(|(_, ts)| ts));
+    ProviderActivity {
+        id: provider.id,
+        display_name: provider.display_name,
+        session_count,
+        last_activity_at,
+    }
+}
+
+/// Most recent of `timestamps`. Providers format RFC 3339 differently
+/// (`Z` vs offsets, varying precision), so they are compared as instants;
+/// unparseable values only win over nothing.
+fn latest_timestamp(timestamps: impl Iterator<Item = String>) -> Option<String> {
+    timestamps.max_by_key(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+}
+
 // ============================================================================
 // SCAN ALL PROJECTS
 // ============================================================================
@@ -258,7 +375,7 @@ pub async fn scan_all_projects(
             futures_lite_workaround_get_claude_path()
         });
         if let Some(base_path) = base {
-            match crate::commands::project::scan_projects(base_path.clone()).await {
+            match crate::commands::project::scan_projects(base_path.clone(), None).await {
                 Ok(claude_projects) => {
                     // Convert ClaudeProject → UniversalProject
                     let universal: Vec<UniversalProject> = claude_projects
@@ -376,7 +493,7 @@ pub async fn scan_all_projects(
     if wsl_enabled.unwrap_or(false) && wanted.iter().any(|p| p == "claude-code") {
         let excluded = wsl_excluded_distros.clone().unwrap_or_default();
         for (distro, claude_unc) in crate::commands::wsl::resolve_active_claude_dirs(&excluded) {
-            match crate::commands::project::scan_projects(claude_unc.clone()).await {
+            match crate::commands::project::scan_projects(claude_unc.clone(), None).await {
                 Ok(projects) => {
                     let label = format!("WSL: {}", distro);
                     let universal: Vec<UniversalProject> = projects
@@ -429,9 +546,14 @@ pub async fn load_provider_sessions(
         "claude-code" => {
             // Claude sessions are loaded per JSONL file; the project_path is the
             // directory containing the JSONL files.
-            let claude_sessions =
-                crate::commands::session::load_project_sessions(project_path.clone(), Some(false), None)
-                    .await?;
+            let claude_sessions = crate::commands::session::collect_project_sessions(
+                project_path.clone(),
+                Some(false),
+                None,
+                None,
+                None,
+            )
+            .await?;
 
             // Convert ClaudeSession → UniversalSession
             let universal = claude_sessions
@@ -528,18 +650,30 @@ pub async fn load_provider_sessions(
 /// `provider`: one of "claude", "codex", "gemini", "cursor", "opencode"
 /// `session_path`: the session path/identifier as returned by load_provider_sessions
 /// `offset` / `limit`: pagination parameters
+/// `max_content_bytes`: optional per-item content cap (see `cap_message_content`);
+/// `None` returns content untruncated
+/// `include_hashes`: fill `UniversalContent.hash` (see `content_hash`) so the
+/// UI can spot identical content; off by default to skip the hashing cost
+/// `include_raw_metadata`: keep the bulky `raw_*` keys of `provider_metadata`
+/// (default true); fetch them later with `get_message_metadata` when off
 #[tauri::command]
 pub async fn load_provider_messages(
     provider: String,
     session_path: String,
     offset: usize,
     limit: usize,
+    max_content_bytes: Option<usize>,
+    include_hashes: Option<bool>,
+    include_raw_metadata: Option<bool>,
 ) -> Result<Vec<UniversalMessage>, String> {
-    match provider.as_str() {
+    let message_source = provider_message_source(&provider, &session_path);
+    let mut messages = match provider.as_str() {
         "claude-code" => {
             // load_session_messages returns Vec<UniversalMessage> directly;
             // apply manual offset/limit pagination after loading.
-            let all = crate::commands::session::load_session_messages(session_path, None).await?;
+            let all =
+                crate::commands::session::load_session_messages(session_path, None, None, None)
+                    .await?;
             let total = all.len();
             let start = offset.min(total);
             let end = (offset + limit).min(total);
@@ -676,6 +810,312 @@ pub async fn load_provider_messages(
             "MULTI_PROVIDER_UNKNOWN_PROVIDER: Unknown provider '{}'",
             other
         )),
+    }?;
+
+    if let Some((source_path, provider_root)) = message_source {
+        set_message_source(&mut messages, &source_path, &provider_root);
+    }
+
+    // Hash before capping so the hash covers the full content
+    if include_hashes.unwrap_or(false) {
+        for msg in messages.iter_mut() {
+            hash_message_content(msg);
+        }
+    }
+
+    if !include_raw_metadata.unwrap_or(true) {
+        for msg in messages.iter_mut() {
+            strip_raw_metadata(msg);
+        }
+    }
+
+    if let Some(max_bytes) = max_content_bytes {
+        for msg in messages.iter_mut() {
+            cap_message_content(msg, max_bytes);
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Full `provider_metadata` of one message, including the `raw_*` keys.
+///
+/// Companion to `include_raw_metadata: false` on `load_provider_messages`:
+/// `source` is the session path/identifier the messages were loaded from and
+/// `uuid` the message id.
+#[tauri::command]
+pub async fn get_message_metadata(
+    provider_id: String,
+    source: String,
+    uuid: String,
+) -> Result<HashMap<String, serde_json::Value>, String> {
+    let messages =
+        load_provider_messages(provider_id, source.clone(), 0, usize::MAX, None, None, None)
+            .await?;
+    messages
+        .into_iter()
+        .find(|msg| msg.id == uuid)
+        .map(|msg| msg.provider_metadata)
+        .ok_or_else(|| {
+            format!(
+                "MESSAGE_NOT_FOUND: Message {} not found in {}",
+                uuid, source
+            )
+        })
+}
+
+/// Nearest ancestor of `path` named `dir_name`, returning that ancestor's
+/// parent (e.g. `~/.codex` for a file under `~/.codex/sessions`). Falls back
+/// to the file's own directory.
+fn root_above(path: &str, dir_name: &str) -> String {
+    let path = Path::new(path);
+    path.ancestors()
+        .find(|p| p.file_name().and_then(|n| n.to_str()) == Some(dir_name))
+        .and_then(Path::parent)
+        .or_else(|| path.parent())
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// `(source_path, provider_root)` for messages loaded from `session_path`:
+/// the concrete file or database backing the session, and the provider's
+/// base directory. Claude Code messages are stamped by the session loader.
+fn provider_message_source(provider: &str, session_path: &str) -> Option<(String, String)> {
+    let lossy = |p: &Path| p.to_string_lossy().to_string();
+    match provider {
+        "codex" => Some((
+            session_path.to_string(),
+            root_above(session_path, "sessions"),
+        )),
+        "gemini" => Some((session_path.to_string(), root_above(session_path, "tmp"))),
+        "opencode" => {
+            let base = get_opencode_base_path()?;
+            let db_path = opencode_db_path(&base);
+            let source = if db_path.is_file() {
+                db_path
+            } else {
+                base.join("storage")
+                    .join("message")
+                    .join(extract_last_segment(session_path))
+            };
+            Some((lossy(&source), lossy(&base)))
+        }
+        "cline" => {
+            let (base, task_id) = cline_parse_scheme_path(session_path).ok()?;
+            let source = base.join("tasks").join(task_id).join("ui_messages.json");
+            Some((lossy(&source), lossy(&base)))
+        }
+        "aider" => {
+            let history_path = aider_parse_scheme_path(session_path).ok()?;
+            let root = history_path.parent().map(lossy).unwrap_or_default();
+            Some((lossy(&history_path), root))
+        }
+        "forgecode" => {
+            let base = get_forgecode_base_path()?;
+            Some((lossy(&base.join(".forge.db")), lossy(&base)))
+        }
+        "antigravity" => {
+            let (root, session_id) = antigravity_parse_scheme_path(session_path).ok()?;
+            let conversations = root.join("conversations");
+            let session_dir = conversations.join(&session_id);
+            let source = if session_dir.is_dir() {
+                session_dir
+            } else {
+                conversations.join(format!("{}.pb", session_id))
+            };
+            Some((lossy(&source), lossy(&root)))
+        }
+        _ => None,
+    }
+}
+
+/// Page of `all` starting at `offset`, with the total known up front.
+fn slice_universal_page(
+    all: Vec<UniversalMessage>,
+    offset: usize,
+    limit: usize,
+) -> UniversalMessagePage {
+    let total = all.len();
+    let start = offset.min(total);
+    let end = offset.saturating_add(limit).min(total);
+    let messages: Vec<UniversalMessage> = all.into_iter().skip(start).take(end - start).collect();
+    UniversalMessagePage {
+        messages,
+        total_count: Some(total),
+        has_more: end < total,
+        next_offset: end,
+    }
+}
+
+/// Sidechain-like content: Claude sidechains, plus records holding nothing
+/// but reasoning (Codex `reasoning` events, Gemini thoughts) that sit beside
+/// the main conversation rather than in it.
+fn is_sidechain_like(msg: &UniversalMessage) -> bool {
+    msg.message_type == MessageType::Sidechain
+        || msg
+            .provider_metadata
+            .get("is_sidechain")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        || (!msg.content.is_empty()
+            && msg
+                .content
+                .iter()
+                .all(|c| c.content_type == ContentType::Thinking))
+}
+
+/// A message that only calls tools or carries their results: every content
+/// item is a tool block or blank text, and at least one tool is involved.
+fn is_tool_only(msg: &UniversalMessage) -> bool {
+    let has_tool = msg
+        .tool_calls
+        .as_ref()
+        .is_some_and(|calls| !calls.is_empty())
+        || msg.content.iter().any(|c| {
+            matches!(
+                c.content_type,
+                ContentType::ToolUse | ContentType::ToolResult
+            )
+        });
+    has_tool
+        && msg.content.iter().all(|c| match c.content_type {
+            ContentType::ToolUse | ContentType::ToolResult => true,
+            ContentType::Text => c
+                .data
+                .get("text")
+                .and_then(|t| t.as_str())
+                .map_or(true, |t| t.trim().is_empty()),
+            _ => false,
+        })
+}
+
+fn passes_include_filters(msg: &UniversalMessage, filters: &MessageIncludeFilters) -> bool {
+    (filters.sidechain != Some(false) || !is_sidechain_like(msg))
+        && (filters.tool_only != Some(false) || !is_tool_only(msg))
+        && (filters.system != Some(false) || msg.role != MessageRole::System)
+}
+
+/// Every message of a session, for callers that must filter before paging.
+async fn load_all_universal_messages(
+    provider_id: String,
+    source_path: String,
+    session_ref: String,
+) -> Result<Vec<UniversalMessage>, String> {
+    match provider_id.as_str() {
+        "cursor" => crate::commands::cursor::load_cursor_messages(source_path, session_ref).await,
+        _ => {
+            load_provider_messages(provider_id, session_ref, 0, usize::MAX, None, None, None).await
+        }
+    }
+}
+
+/// Load one page of a session from any provider.
+///
+/// `source_path`: the provider's base path (used by Cursor to validate the DB path)
+/// `session_ref`: the session path/identifier as returned by load_provider_sessions
+/// `include_filters`: kinds of message to drop; offsets and totals count only
+/// the messages that are kept
+///
+/// Claude Code uses the streaming paginated loader. Cursor and Gemini
+/// sessions are loaded whole and sliced: Cursor's estimated timestamps span
+/// the full rowid range and empty bubbles are dropped, so a SQL window would
+/// not line up with message offsets. Other providers read `limit + 1`
+/// messages to tell whether another page follows; their `total_count` is `None`.
+/// Filtered requests load the whole session so pages stay contiguous.
+#[tauri::command]
+pub async fn load_session_universal_paginated(
+    provider_id: String,
+    source_path: String,
+    session_ref: String,
+    offset: usize,
+    limit: usize,
+    include_filters: Option<MessageIncludeFilters>,
+) -> Result<UniversalMessagePage, String> {
+    let filters = include_filters.unwrap_or_default();
+    if filters.is_active() {
+        let mut all = load_all_universal_messages(provider_id, source_path, session_ref).await?;
+        all.retain(|msg| passes_include_filters(msg, &filters));
+        return Ok(slice_universal_page(all, offset, limit));
+    }
+
+    match provider_id.as_str() {
+        "claude-code" => {
+            let page = crate::commands::session::load_session_messages_paginated(
+                session_ref,
+                offset,
+                limit,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?;
+            Ok(UniversalMessagePage {
+                messages: page.messages,
+                total_count: Some(page.total_count),
+                has_more: page.has_more,
+                next_offset: page.next_offset,
+            })
+        }
+
+        "cursor" => {
+            let all =
+                crate::commands::cursor::load_cursor_messages(source_path, session_ref).await?;
+            Ok(slice_universal_page(all, offset, limit))
+        }
+
+        "gemini" => {
+            // Gemini's loader ignores offset/limit and returns the whole session
+            let all =
+                load_provider_messages(provider_id, session_ref, 0, usize::MAX, None, None, None)
+                    .await?;
+            Ok(slice_universal_page(all, offset, limit))
+        }
+
+        _ => {
+            let mut messages = load_provider_messages(
+                provider_id,
+                session_ref,
+                offset,
+                limit.saturating_add(1),
+                None,
+                None,
+                None,
+            )
+            .await?;
+            let has_more = messages.len() > limit;
+            messages.truncate(limit);
+            let next_offset = offset + messages.len();
+            Ok(UniversalMessagePage {
+                messages,
+                total_count: None,
+                has_more,
+                next_offset,
+            })
+        }
+    }
+}
+
+/// Count the messages of a session without returning them, for the
+/// pagination total.
+///
+/// Claude Code sessions are counted line by line and Cursor sessions with a
+/// `COUNT(*)` over the session's bubble rows. Other providers do not store
+/// one message per record, so their sessions are loaded and counted.
+#[tauri::command]
+pub async fn get_universal_message_count(
+    provider_id: String,
+    source_path: String,
+    session_ref: String,
+) -> Result<usize, String> {
+    match provider_id.as_str() {
+        "claude-code" => {
+            crate::commands::session::get_session_message_count(session_ref, None, None).await
+        }
+        "cursor" => crate::commands::cursor::count_cursor_messages(&source_path, &session_ref),
+        _ => load_provider_messages(provider_id, session_ref, 0, usize::MAX, None, None, None)
+            .await
+            .map(|messages| messages.len()),
     }
 }
 
@@ -802,22 +1242,41 @@ lazy_static::lazy_static! {
 
 /// Search messages across all providers (or a subset).
 ///
+/// `sources`: provider installs to search; if None, every detected provider
+///   (narrowed by `active_providers`) at its default location
 /// `query`: full-text search query
-/// `active_providers`: optional filter — if None, search all detected providers
+/// `filters`: applied natively by the Claude Code, Cursor and Codex searches;
+///   the other providers apply all but `projects` to their converted messages
 /// `limit`: maximum number of results to return (default 100)
+///
+/// Results of every provider are merged as `UniversalMessage`s (each tagged
+/// with its `providerId`) and returned newest first.
 #[tauri::command]
 pub async fn search_all_providers(
+    sources: Option<Vec<ProviderSource>>,
     query: String,
+    filters: Option<SearchFilters>,
     active_providers: Option<Vec<String>>,
     limit: Option<usize>,
     wsl_enabled: Option<bool>,
     wsl_excluded_distros: Option<Vec<String>>,
 ) -> Result<Vec<UniversalMessage>, String> {
     let max_results = limit.unwrap_or(100);
+    let filters = filters.unwrap_or_default();
 
     // ---- Result cache (generation-gated) -----------------------------------
+    // SearchFilters is not Hash; its JSON form stands in for it in the key
     let generation = current_search_generation();
-    let key = cache_key(&(&query, &active_providers, max_results));
+    let filters_key = serde_json::to_string(&filters).unwrap_or_default();
+    let key = cache_key(&(
+        &query,
+        &sources,
+        &filters_key,
+        &active_providers,
+        max_results,
+        wsl_enabled,
+        &wsl_excluded_distros,
+    ));
     if let Ok(mut cache) = SEARCH_CACHE.lock() {
         if let Some((cached_gen, cached)) = cache.get(&key) {
             if *cached_gen == generation {
@@ -831,95 +1290,110 @@ pub async fn search_all_providers(
     if matcher.is_empty() {
         return Ok(Vec::new());
     }
+    let message_filter = MessageFilter::new(&filters);
+    let matches = |m: &UniversalMessage| message_matches(m, &matcher) && message_filter.matches(m);
 
-    let wanted: Vec<String> = match active_providers {
+    let mut sources = match sources {
         Some(list) => list,
-        None => {
-            detect_providers()
-                .await?
-                .into_iter()
-                .filter(|p| p.is_available)
-                .map(|p| p.id)
-                .collect()
+        None => default_search_sources(active_providers).await?,
+    };
+
+    // ---- WSL (Claude Code only) -------------------------------------------
+    if wsl_enabled.unwrap_or(false) && sources.iter().any(|s| s.provider_id == "claude-code") {
+        let excluded = wsl_excluded_distros.unwrap_or_default();
+        for (_distro, claude_unc) in crate::commands::wsl::resolve_active_claude_dirs(&excluded) {
+            sources.push(ProviderSource {
+                provider_id: "claude-code".to_string(),
+                path: claude_unc,
+            });
         }
+    }
+
+    let wants = |provider_id: &str| sources.iter().any(|s| s.provider_id == provider_id);
+    let paths_of = |provider_id: &'static str| -> Vec<String> {
+        sources
+            .iter()
+            .filter(|s| s.provider_id == provider_id)
+            .map(|s| s.path.clone())
+            .collect()
     };
 
     let mut all_results: Vec<UniversalMessage> = Vec::new();
 
     // ---- Claude Code -------------------------------------------------------
-    if wanted.iter().any(|p| p == "claude-code") {
-        if let Some(claude_base) = futures_lite_workaround_get_claude_path() {
-            let filters = SearchFilters {
-                date_range: None,
-                projects: None,
-                session_id: None,
-                message_type: None,
-                has_tool_calls: None,
-                has_errors: None,
-                has_file_changes: None,
-            };
-            match crate::commands::session::search_messages(
-                claude_base.clone(),
-                query.clone(),
-                filters,
-            )
-            .await
-            {
-                Ok(results) => all_results.extend(results),
-                Err(e) => {
-                    eprintln!("[multi_provider] Claude search failed ({}): {}", claude_base, e);
-                }
+    for claude_base in paths_of("claude-code") {
+        match crate::commands::session::search_all_messages(
+            claude_base.clone(),
+            query.clone(),
+            filters.clone(),
+            None,
+        )
+        .await
+        {
+            Ok(results) => all_results.extend(results),
+            Err(e) => {
+                log::warn!(
+                    "[multi_provider] Claude search failed ({}): {}",
+                    claude_base,
+                    e
+                );
+            }
+        }
+    }
+
+    // ---- Cursor IDE --------------------------------------------------------
+    for cursor_path in paths_of("cursor") {
+        // Cursor takes its own, narrower filter set
+        let cursor_filters = crate::commands::cursor::SearchFilters {
+            date_range: filters.date_range.clone(),
+            message_type: filters.message_type.clone(),
+            has_tool_calls: filters.has_tool_calls,
+            has_errors: filters.has_errors,
+        };
+        match crate::commands::cursor::search_cursor_messages(
+            cursor_path.clone(),
+            query.clone(),
+            cursor_filters,
+            Some(max_results),
+            None,
+        )
+        .await
+        {
+            Ok(result) => all_results.extend(result.messages),
+            Err(e) => {
+                log::warn!(
+                    "[multi_provider] Cursor search failed ({}): {}",
+                    cursor_path,
+                    e
+                );
             }
         }
     }
 
     // ---- Codex CLI ---------------------------------------------------------
-    if wanted.iter().any(|p| p == "codex") {
-        if let Ok(codex_path) = crate::commands::codex::get_codex_path().await {
-            let source_id = format!("codex:{}", codex_path);
-            // Load all projects then search messages (Codex has no dedicated search command)
-            if let Ok(projects) =
-                crate::commands::codex::scan_codex_projects(codex_path, source_id.clone()).await
-            {
-                'codex_search: for project in projects {
-                    if let Ok(sessions) = crate::commands::codex::load_codex_sessions(
-                        String::new(),
-                        project.path.clone(),
-                        project.id.clone(),
-                        source_id.clone(),
-                    )
-                    .await
-                    {
-                        for session in sessions {
-                            if let Ok(msgs) = crate::commands::codex::load_codex_messages(
-                                session
-                                    .metadata
-                                    .get("filePath")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or(&project.path)
-                                    .to_string(),
-                                0,
-                                SEARCH_MAX_MESSAGES_PER_SESSION,
-                            )
-                            .await
-                            {
-                                let remaining = max_results.saturating_sub(all_results.len());
-                                let matching =
-                                    take_matching(msgs, remaining, |m| message_matches(m, &matcher));
-                                all_results.extend(matching);
-                                if all_results.len() >= max_results {
-                                    break 'codex_search;
-                                }
-                            }
-                        }
-                    }
-                }
+    for codex_path in paths_of("codex") {
+        match crate::commands::codex::search_codex_messages(
+            codex_path.clone(),
+            query.clone(),
+            filters.clone(),
+            Some(max_results),
+            None,
+        )
+        .await
+        {
+            Ok(result) => all_results.extend(result.messages),
+            Err(e) => {
+                log::warn!(
+                    "[multi_provider] Codex search failed ({}): {}",
+                    codex_path,
+                    e
+                );
             }
         }
     }
 
     // ---- OpenCode ----------------------------------------------------------
-    if wanted.iter().any(|p| p == "opencode") {
+    if wants("opencode") {
         if let Some(opencode_base) = get_opencode_base_path() {
             let source_id = format!("opencode:{}", opencode_base.display());
             if let Ok(projects) = scan_opencode_projects_impl(&opencode_base, &source_id) {
@@ -944,8 +1418,7 @@ pub async fn search_all_providers(
                             .await
                             {
                                 let remaining = max_results.saturating_sub(all_results.len());
-                                let matching =
-                                    take_matching(msgs, remaining, |m| message_matches(m, &matcher));
+                                let matching = take_matching(msgs, remaining, matches);
                                 all_results.extend(matching);
                                 if all_results.len() >= max_results {
                                     break 'opencode_search;
@@ -959,7 +1432,7 @@ pub async fn search_all_providers(
     }
 
     // ---- Cline / Roo Code --------------------------------------------------
-    if wanted.iter().any(|p| p == "cline") {
+    if wants("cline") {
         'cline_search: for (base, _label) in get_all_cline_base_paths() {
             let source_id = format!("cline:{}", base.display());
             if let Ok(projects) = cline_scan_projects(&base, &source_id) {
@@ -985,10 +1458,8 @@ pub async fn search_all_providers(
                                 0,
                                 SEARCH_MAX_MESSAGES_PER_SESSION,
                             ) {
-                                let matching: Vec<UniversalMessage> = msgs
-                                    .into_iter()
-                                    .filter(|m| message_matches(m, &matcher))
-                                    .collect();
+                                let matching: Vec<UniversalMessage> =
+                                    msgs.into_iter().filter(|m| matches(m)).collect();
                                 all_results.extend(matching);
                                 if all_results.len() >= max_results {
                                     break 'cline_search;
@@ -1002,7 +1473,7 @@ pub async fn search_all_providers(
     }
 
     // ---- Aider -------------------------------------------------------------
-    if wanted.iter().any(|p| p == "aider") {
+    if wants("aider") {
         let base = get_aider_base_path()
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default();
@@ -1029,10 +1500,8 @@ pub async fn search_all_providers(
                             0,
                             SEARCH_MAX_MESSAGES_PER_SESSION,
                         ) {
-                            let matching: Vec<UniversalMessage> = msgs
-                                .into_iter()
-                                .filter(|m| message_matches(m, &matcher))
-                                .collect();
+                            let matching: Vec<UniversalMessage> =
+                                msgs.into_iter().filter(|m| matches(m)).collect();
                             all_results.extend(matching);
                             if all_results.len() >= max_results {
                                 break 'aider_search;
@@ -1045,7 +1514,7 @@ pub async fn search_all_providers(
     }
 
     // ---- ForgeCode ---------------------------------------------------------
-    if wanted.iter().any(|p| p == "forgecode") {
+    if wants("forgecode") {
         'forgecode_search: {
             let Some(forge_base) = get_forgecode_base_path() else {
                 break 'forgecode_search;
@@ -1077,10 +1546,8 @@ pub async fn search_all_providers(
                                 0,
                                 SEARCH_MAX_MESSAGES_PER_SESSION,
                             ) {
-                                let matching: Vec<UniversalMessage> = msgs
-                                    .into_iter()
-                                    .filter(|m| message_matches(m, &matcher))
-                                    .collect();
+                                let matching: Vec<UniversalMessage> =
+                                    msgs.into_iter().filter(|m| matches(m)).collect();
                                 all_results.extend(matching);
                                 if all_results.len() >= max_results {
                                     break 'forgecode_search;
@@ -1094,7 +1561,7 @@ pub async fn search_all_providers(
     }
 
     // ---- Antigravity -------------------------------------------------------
-    if wanted.iter().any(|p| p == "antigravity") {
+    if wants("antigravity") {
         if let Some(root) = get_antigravity_base_path() {
             let source_id = format!("antigravity:{}", root.display());
             if let Ok(sessions) = antigravity_load_sessions(&root, &source_id) {
@@ -1113,10 +1580,8 @@ pub async fn search_all_providers(
                         0,
                         SEARCH_MAX_MESSAGES_PER_SESSION,
                     ) {
-                        let matching: Vec<UniversalMessage> = msgs
-                            .into_iter()
-                            .filter(|m| message_matches(m, &matcher))
-                            .collect();
+                        let matching: Vec<UniversalMessage> =
+                            msgs.into_iter().filter(|m| matches(m)).collect();
                         all_results.extend(matching);
                         if all_results.len() >= max_results {
                             break 'antigravity_search;
@@ -1127,29 +1592,6 @@ pub async fn search_all_providers(
         }
     }
 
-    // ---- WSL (Claude Code only) -------------------------------------------
-    if wsl_enabled.unwrap_or(false) && wanted.iter().any(|p| p == "claude-code") {
-        let excluded = wsl_excluded_distros.clone().unwrap_or_default();
-        for (distro, claude_unc) in crate::commands::wsl::resolve_active_claude_dirs(&excluded) {
-            let filters = SearchFilters {
-                date_range: None,
-                projects: None,
-                session_id: None,
-                message_type: None,
-                has_tool_calls: None,
-                has_errors: None,
-                has_file_changes: None,
-            };
-            match crate::commands::session::search_messages(claude_unc, query.clone(), filters).await
-            {
-                Ok(results) => all_results.extend(results),
-                Err(e) => {
-                    eprintln!("[multi_provider] WSL Claude search failed for '{}': {}", distro, e);
-                }
-            }
-        }
-    }
-
     // Top-k by timestamp descending: O(n) partial selection instead of an
     // O(n log n) full sort when there are more candidates than requested.
     let results = top_k_by(all_results, max_results, |a, b| b.timestamp.cmp(&a.timestamp));
@@ -1188,6 +1630,38 @@ fn result_to_probe(result: Result<String, String>) -> (bool, Option<String>, Opt
     }
 }
 
+/// Sources searched when `search_all_providers` is given none: the
+/// `active_providers` (default: every detected provider) at their default
+/// locations. Claude Code, Codex and Cursor are skipped when their folder
+/// cannot be resolved.
+async fn default_search_sources(
+    active_providers: Option<Vec<String>>,
+) -> Result<Vec<ProviderSource>, String> {
+    let wanted: Vec<String> = match active_providers {
+        Some(list) => list,
+        None => detect_providers()
+            .await?
+            .into_iter()
+            .filter(|p| p.is_available)
+            .map(|p| p.id)
+            .collect(),
+    };
+
+    let mut sources = Vec::with_capacity(wanted.len());
+    for provider_id in wanted {
+        let path = match provider_id.as_str() {
+            "claude-code" => futures_lite_workaround_get_claude_path(),
+            "codex" => crate::commands::codex::get_codex_path().await.ok(),
+            "cursor" => crate::commands::cursor::get_cursor_path().await.ok(),
+            _ => Some(String::new()),
+        };
+        if let Some(path) = path {
+            sources.push(ProviderSource { provider_id, path });
+        }
+    }
+    Ok(sources)
+}
+
 /// Synchronously resolve the Claude folder path without going through Tauri
 /// command machinery (which requires a running event-loop context).
 ///
@@ -1197,7 +1671,7 @@ fn futures_lite_workaround_get_claude_path() -> Option<String> {
     if let Some(config_dir) = crate::utils::resolve_claude_config_dir() {
         return Some(config_dir);
     }
-    let home = dirs::home_dir()?;
+    let home = crate::utils::resolve_home()?;
     let p = home.join(".claude");
     if p.exists() && std::fs::read_dir(&p).is_ok() {
         Some(p.to_string_lossy().to_string())
@@ -1237,7 +1711,6 @@ fn claude_project_to_universal(
     p: crate::models::ClaudeProject,
     claude_base: &str,
 ) -> UniversalProject {
-    use std::collections::HashMap;
     let source_id = format!("claude-code:{}", claude_base);
     UniversalProject {
         id: p.path.clone(),
@@ -1259,7 +1732,6 @@ fn claude_session_to_universal(
     project_path: &str,
     source_id: &str,
 ) -> UniversalSession {
-    use std::collections::HashMap;
     let mut metadata = HashMap::new();
     metadata.insert(
         "filePath".to_string(),
@@ -1268,6 +1740,10 @@ fn claude_session_to_universal(
     if let Some(ref branch) = s.git_branch {
         metadata.insert("gitBranch".to_string(), serde_json::json!(branch));
     }
+    metadata.insert(
+        "activityScore".to_string(),
+        serde_json::json!(s.activity_score),
+    );
 
     UniversalSession {
         id: s.session_id.clone(),
@@ -1356,18 +1832,196 @@ fn parse_gemini_session_path(path: &str) -> (String, String, String, String) {
     (actual_path, session_id, project_id, source_id)
 }
 
-/// Check whether any content in a UniversalMessage matches the search query.
-///
-/// A message matches when its serialized content contains all query terms
-/// (ASCII case-insensitive, order-independent), as determined by the shared
-/// [`QueryMatcher`]. The matcher is built once per search and reused here.
-fn message_matches(msg: &UniversalMessage, matcher: &QueryMatcher) -> bool {
-    for content in &msg.content {
-        if let Ok(json) = serde_json::to_string(content) {
-            if matcher.is_match(&json) {
-                return true;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_raw_metadata_is_optional_and_fetchable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        let line = json!({
+            "uuid": "u1", "sessionId": "s", "timestamp": "2025-01-01T00:00:00Z",
+            "type": "user", "message": {"role": "user", "content": "hello"}
+        });
+        std::fs::write(&path, format!("{}\n", line)).unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let load = |raw| {
+            load_provider_messages("claude-code".into(), path.clone(), 0, 10, None, None, raw)
+        };
+        let full = load(None).await.unwrap();
+        assert!(full[0].provider_metadata.contains_key("raw_content"));
+        let slim = load(Some(false)).await.unwrap();
+        assert!(slim[0]
+            .provider_metadata
+            .keys()
+            .all(|k| !k.starts_with("raw_")));
+
+        let metadata = get_message_metadata("claude-code".into(), path.clone(), "u1".into())
+            .await
+            .unwrap();
+        assert_eq!(metadata, full[0].provider_metadata);
+        let err = get_message_metadata("claude-code".into(), path, "nope".into())
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("MESSAGE_NOT_FOUND"));
+    }
+
+    fn message(role: &str, message_type: &str, content: serde_json::Value) -> UniversalMessage {
+        serde_json::from_value(json!({
+            "id": "m", "sessionId": "s", "projectId": "p", "sourceId": "src",
+            "providerId": "codex", "timestamp": "2025-01-01T00:00:00Z",
+            "sequenceNumber": 0, "role": role, "messageType": message_type,
+            "content": content, "originalFormat": "", "providerMetadata": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn include_filters_drop_requested_kinds() {
+        let text = message(
+            "assistant",
+            "message",
+            json!([{"type": "text", "data": {"text": "hi"}}]),
+        );
+        let sidechain = message(
+            "user",
+            "sidechain",
+            json!([{"type": "text", "data": {"text": "hi"}}]),
+        );
+        let reasoning = message(
+            "assistant",
+            "message",
+            json!([{"type": "thinking", "data": {}}]),
+        );
+        let tool_only = message(
+            "assistant",
+            "message",
+            json!([{"type": "text", "data": {"text": " "}}, {"type": "tool_use", "data": {}}]),
+        );
+        let system = message(
+            "system",
+            "message",
+            json!([{"type": "text", "data": {"text": "info"}}]),
+        );
+
+        let filters = MessageIncludeFilters {
+            sidechain: Some(false),
+            tool_only: Some(false),
+            system: Some(false),
+        };
+        assert!(filters.is_active());
+        assert!(passes_include_filters(&text, &filters));
+        for dropped in [&sidechain, &reasoning, &tool_only, &system] {
+            assert!(!passes_include_filters(dropped, &filters));
         }
+
+        let keep_all = MessageIncludeFilters::default();
+        assert!(!keep_all.is_active());
+        assert!(passes_include_filters(&tool_only, &keep_all));
+    }
+    #[tokio::test]
+    async fn search_all_providers_merges_sources_newest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let claude_base = tmp.path().join("claude");
+        let project_dir = claude_base.join("projects").join("-work-app");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let claude_line = |uuid: &str, ts: &str, text: &str| {
+            json!({
+                "uuid": uuid, "sessionId": "s1", "timestamp": ts, "type": "user",
+                "message": {"role": "user", "content": text}
+            })
+            .to_string()
+        };
+        std::fs::write(
+            project_dir.join("s1.jsonl"),
+            [
+                claude_line("c1", "2025-06-01T08:00:00Z", "migrate the schema"),
+                claude_line("c2", "2025-06-01T12:00:00Z", "migrate again"),
+                claude_line("c3", "2025-06-01T13:00:00Z", "unrelated"),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let codex_base = tmp.path().join("codex");
+        let day = codex_base.join("2025/06/01");
+        std::fs::create_dir_all(&day).unwrap();
+        let codex_line = json!({
+            "type": "response_item", "timestamp": "2025-06-01T10:00:00Z",
+            "payload": {
+                "type": "message", "role": "user",
+                "content": [{"type": "input_text", "text": "migrate from codex"}]
+            }
+        });
+        std::fs::write(
+            day.join("rollout-2025-06-01T10-00-00-aaaaaaaa-0000-0000-0000-000000000001.jsonl"),
+            codex_line.to_string(),
+        )
+        .unwrap();
+
+        let source = |provider_id: &str, path: &Path| ProviderSource {
+            provider_id: provider_id.to_string(),
+            path: path.to_string_lossy().to_string(),
+        };
+        let results = search_all_providers(
+            Some(vec![
+                source("claude-code", &claude_base),
+                source("codex", &codex_base),
+            ]),
+            "migrate".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let hits: Vec<(&str, &str)> = results
+            .iter()
+            .map(|m| (m.provider_id.as_str(), m.timestamp.as_str()))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![
+                ("claude-code", "2025-06-01T12:00:00Z"),
+                ("codex", "2025-06-01T10:00:00Z"),
+                ("claude-code", "2025-06-01T08:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn root_above_finds_provider_directory() {
+        assert_eq!(
+            root_above(
+                "/home/me/.codex/sessions/2025/01/02/rollout-x.jsonl",
+                "sessions"
+            ),
+            "/home/me/.codex"
+        );
+        assert_eq!(
+            root_above("/home/me/.gemini/tmp/abc/chats/session-1.json", "tmp"),
+            "/home/me/.gemini"
+        );
+        assert_eq!(root_above("/elsewhere/session.json", "tmp"), "/elsewhere");
+    }
+
+    #[test]
+    fn latest_timestamp_compares_instants() {
+        let ts = |values: &[&str]| latest_timestamp(values.iter().map(|v| v.to_string()));
+        assert_eq!(
+            ts(&["2025-06-01T10:00:00Z", "2025-06-01T11:30:00+02:00"]),
+            Some("2025-06-01T10:00:00Z".to_string())
+        );
+        assert_eq!(
+            ts(&["garbage", "2025-06-01T10:00:00.123Z"]),
+            Some("2025-06-01T10:00:00.123Z".to_string())
+        );
+        assert_eq!(ts(&[]), None);
     }
-    false
 }
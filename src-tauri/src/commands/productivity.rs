@@ -0,0 +1,150 @@
+//! Project productivity dashboard
+//!
+//! `get_productivity_metrics` combines the session scan (`load_project_sessions`),
+//! message timestamps and file activities (`get_file_activities`) into one
+//! aggregate. See [`ProductivityMetrics`] for how each metric is derived.
+
+use crate::commands::files::collect_file_activities;
+use crate::commands::session::collect_project_sessions;
+use crate::models::{FileActivity, FileActivityFilters, FileOperation, ProductivityMetrics};
+use crate::utils::open_session_reader;
+use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+
+/// Longest gap between two messages that still counts as active time.
+const IDLE_GAP_CAP_SECS: i64 = 15 * 60;
+
+/// Only the timestamp of a session line is needed for active time.
+#[derive(Deserialize)]
+struct TimestampEntry {
+    timestamp: Option<String>,
+}
+
+/// Active seconds of a session: gaps between consecutive message timestamps,
+/// each capped at [`IDLE_GAP_CAP_SECS`]. Unreadable files count as zero.
+fn session_active_seconds(path: &Path) -> i64 {
+    let Ok(reader) = open_session_reader(path) else {
+        return 0;
+    };
+    let mut timestamps: Vec<DateTime<Utc>> = reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<TimestampEntry>(&line).ok())
+        .filter_map(|entry| DateTime::parse_from_rfc3339(&entry.timestamp?).ok())
+        .map(|t| t.with_timezone(&Utc))
+        .collect();
+    timestamps.sort();
+    active_seconds(&timestamps)
+}
+
+/// Sum of the gaps between sorted `timestamps`, each capped at [`IDLE_GAP_CAP_SECS`].
+fn active_seconds(timestamps: &[DateTime<Utc>]) -> i64 {
+    timestamps
+        .windows(2)
+        .map(|pair| {
+            (pair[1] - pair[0])
+                .num_seconds()
+                .clamp(0, IDLE_GAP_CAP_SECS)
+        })
+        .sum()
+}
+
+/// Files touched and lines added/removed by the write and edit activities.
+fn edit_totals(activities: &[FileActivity]) -> (usize, usize, usize) {
+    let edits = activities.iter().filter(|a| {
+        matches!(
+            a.operation,
+            FileOperation::Write | FileOperation::Edit | FileOperation::MultiEdit
+        )
+    });
+    let mut files: HashSet<&str> = HashSet::new();
+    let (mut added, mut removed) = (0, 0);
+    for activity in edits {
+        files.insert(&activity.file_path);
+        added += activity.lines_added.unwrap_or(0);
+        removed += activity.lines_removed.unwrap_or(0);
+    }
+    (files.len(), added, removed)
+}
+
+/// Productivity aggregate of one Claude Code project.
+#[tauri::command]
+pub async fn get_productivity_metrics(project_path: String) -> Result<ProductivityMetrics, String> {
+    let sessions = collect_project_sessions(project_path.clone(), None, None, None, None).await?;
+    let activities =
+        collect_file_activities(project_path, None, FileActivityFilters::default(), None).await?;
+
+    let active_secs: i64 = sessions
+        .par_iter()
+        .map(|session| session_active_seconds(Path::new(&session.file_path)))
+        .sum();
+    let active_days: HashSet<String> = sessions
+        .iter()
+        .filter_map(|s| DateTime::parse_from_rfc3339(&s.last_message_time).ok())
+        .map(|t| t.with_timezone(&Utc).format("%Y-%m-%d").to_string())
+        .collect();
+    let (files_touched, lines_added, lines_removed) = edit_totals(&activities);
+
+    Ok(ProductivityMetrics {
+        session_count: sessions.len(),
+        active_hours: active_secs as f64 / 3600.0,
+        files_touched,
+        lines_added,
+        lines_removed,
+        active_days: active_days.len(),
+        sessions_per_day: if active_days.is_empty() {
+            0.0
+        } else {
+            sessions.len() as f64 / active_days.len() as f64
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn active_seconds_caps_idle_gaps() {
+        let timestamps = [
+            at("2025-06-01T10:00:00Z"),
+            at("2025-06-01T10:05:00Z"),
+            at("2025-06-01T13:00:00Z"), // 3h idle, counted as 15 min
+            at("2025-06-01T13:01:00Z"),
+        ];
+        assert_eq!(active_seconds(&timestamps), 5 * 60 + 15 * 60 + 60);
+        assert_eq!(active_seconds(&timestamps[..1]), 0);
+    }
+
+    #[tokio::test]
+    async fn metrics_combine_sessions_and_file_activity() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lines = [
+            r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"edit it"}}"#,
+            r#"{"uuid":"a1","sessionId":"s1","timestamp":"2025-06-01T10:10:00Z","type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/p/a.rs","old_string":"a","new_string":"a\nb\nc"}},{"type":"tool_use","id":"t2","name":"Read","input":{"file_path":"/p/b.rs"}}]}}"#,
+        ];
+        std::fs::write(temp_dir.path().join("s1.jsonl"), lines.join("\n")).unwrap();
+
+        let metrics = get_productivity_metrics(temp_dir.path().to_string_lossy().to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(metrics.session_count, 1);
+        assert!((metrics.active_hours - 10.0 / 60.0).abs() < 1e-9);
+        assert_eq!(metrics.files_touched, 1);
+        assert_eq!(metrics.lines_added, 2);
+        assert_eq!(metrics.lines_removed, 0);
+        assert_eq!(metrics.active_days, 1);
+        assert_eq!(metrics.sessions_per_day, 1.0);
+    }
+}
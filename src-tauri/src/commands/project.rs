@@ -1,9 +1,14 @@
+use crate::commands::stats::count_session_file_tokens;
 use crate::models::*;
-use crate::utils::{estimate_message_count_from_size, extract_project_name};
+use crate::utils::{
+    decode_project_path, estimate_message_count_from_size, is_session_file,
+    project_folder_display_name, session_content_size, IgnoreRules,
+};
 use chrono::{DateTime, Utc};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 use walkdir::WalkDir;
 
 #[tauri::command]
@@ -65,8 +70,8 @@ pub async fn get_claude_folder_path() -> Result<String, String> {
         return Ok(config_dir);
     }
 
-    let home_dir =
-        dirs::home_dir().ok_or("HOME_DIRECTORY_NOT_FOUND: Could not determine home directory")?;
+    let home_dir = crate::utils::resolve_home()
+        .ok_or("HOME_DIRECTORY_NOT_FOUND: Could not determine home directory")?;
     let claude_path = home_dir.join(".claude");
 
     if !claude_path.exists() {
@@ -117,10 +122,39 @@ pub async fn validate_claude_folder(path: String) -> Result<bool, String> {
     Ok(false)
 }
 
+/// Scan the Claude `projects/` directory, newest activity first.
+///
+/// `include_tokens` additionally sums token usage per project into
+/// `total_tokens`. It reads every session file, so it is off by default.
 #[tauri::command]
-pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, String> {
+pub async fn scan_projects(
+    claude_path: String,
+    include_tokens: Option<bool>,
+) -> Result<Vec<ClaudeProject>, String> {
+    scan_projects_impl(&claude_path, None, include_tokens.unwrap_or(false))
+}
+
+/// Like `scan_projects`, but only returns projects whose `last_modified` is at
+/// or after `since` (RFC3339). Activity comes from the same file metadata
+/// `scan_projects` uses, so no session file is read; projects without any
+/// session file are left out.
+#[tauri::command]
+pub async fn scan_projects_since(
+    claude_path: String,
+    since: String,
+) -> Result<Vec<ClaudeProject>, String> {
+    let since = DateTime::parse_from_rfc3339(&since)
+        .map_err(|e| format!("PROJECT_INVALID_ARGUMENT: Invalid since timestamp: {e}"))?;
+    scan_projects_impl(&claude_path, Some(since.with_timezone(&Utc).into()), false)
+}
+
+fn scan_projects_impl(
+    claude_path: &str,
+    since: Option<SystemTime>,
+    include_tokens: bool,
+) -> Result<Vec<ClaudeProject>, String> {
     let start_time = std::time::Instant::now();
-    let projects_path = PathBuf::from(&claude_path).join("projects");
+    let projects_path = PathBuf::from(claude_path).join("projects");
 
     if !projects_path.exists() {
         return Ok(vec![]);
@@ -128,6 +162,7 @@ pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, St
 
     let mut projects = Vec::new();
     let mut seen_canonical = std::collections::HashSet::new();
+    let ignore = IgnoreRules::load(Path::new(claude_path));
 
     let mut entries: Vec<_> = WalkDir::new(&projects_path)
         .min_depth(1)
@@ -140,6 +175,7 @@ pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, St
             // so there is no risk of traversing outside the projects/ tree.
             e.file_type().is_dir() || (e.file_type().is_symlink() && e.path().is_dir())
         })
+        .filter(|e| !ignore.is_ignored(e.path()))
         .collect();
     // Prefer real directories over symlinks so canonical-path dedup picks a
     // stable winner instead of relying on WalkDir iteration order (which varies
@@ -161,16 +197,17 @@ pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, St
 
         let raw_project_name = entry.file_name().to_string_lossy().to_string();
         let project_path = entry.path().to_string_lossy().to_string();
-        let project_name = extract_project_name(&raw_project_name);
+        let project_name = project_folder_display_name(&raw_project_name);
 
         let mut session_count = 0;
         let mut message_count = 0;
         let mut last_modified = None;
+        let mut total_tokens = 0u64;
 
         for jsonl_entry in WalkDir::new(entry.path())
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+            .filter(|e| is_session_file(e.path()) && !ignore.is_ignored(e.path()))
         {
             session_count += 1;
 
@@ -188,14 +225,26 @@ pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, St
                 }
 
                 // Estimate message count from file size - much faster
-                let estimated_messages = estimate_message_count_from_size(metadata.len());
+                // (uncompressed size for `.jsonl.gz`)
+                let content_size = session_content_size(jsonl_entry.path(), &metadata);
+                let estimated_messages = estimate_message_count_from_size(content_size);
                 message_count += estimated_messages;
+
+                if include_tokens {
+                    total_tokens += count_session_file_tokens(jsonl_entry.path());
+                }
             } else {
                 #[cfg(debug_assertions)]
                 eprintln!("⚠️ Failed to get metadata for: {:?}", jsonl_entry.path());
             }
         }
 
+        if let Some(since) = since {
+            if !last_modified.is_some_and(|lm| lm >= since) {
+                continue;
+            }
+        }
+
         let last_modified_str = last_modified
             .map(|lm| {
                 let dt: DateTime<Utc> = lm.into();
@@ -217,6 +266,8 @@ pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, St
             session_count,
             message_count,
             last_modified: last_modified_str,
+            total_tokens: include_tokens.then_some(total_tokens),
+            cwd: decode_project_path(&raw_project_name),
         });
     }
 
@@ -262,7 +313,7 @@ mod tests {
         let link_path = projects_dir.join("shared-project");
         symlink(&shared_dir, &link_path).unwrap();
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -290,7 +341,7 @@ mod tests {
         let dangling_link = projects_dir.join("dangling-project");
         symlink(&dangling_target, &dangling_link).unwrap();
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -316,7 +367,7 @@ mod tests {
         let alias_link = projects_dir.join("my-project-alias");
         symlink(&real_dir, &alias_link).unwrap();
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -326,4 +377,61 @@ mod tests {
         // project name stays stable across scans regardless of iteration order.
         assert_eq!(projects[0].name, "my-project");
     }
+
+    #[tokio::test]
+    async fn test_scan_projects_include_tokens_sums_usage() {
+        let temp_dir = TempDir::new().unwrap();
+        let claude_dir = temp_dir.path().join(".claude");
+        let project_dir = claude_dir.join("projects").join("my-project");
+        fs::create_dir_all(&project_dir).unwrap();
+        let line = r#"{"type":"assistant","timestamp":"2025-06-01T10:00:00Z","message":{"role":"assistant","content":[],"usage":{"input_tokens":10,"output_tokens":5,"cache_read_input_tokens":100}}}"#;
+        create_test_jsonl_file(&project_dir, "a.jsonl", &format!("{line}\n{line}\n"));
+        create_test_jsonl_file(&project_dir, "b.jsonl", &format!("{line}\n"));
+
+        let claude_path = claude_dir.to_string_lossy().to_string();
+        let projects = scan_projects(claude_path.clone(), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(projects[0].total_tokens, Some(3 * 115));
+
+        let projects = scan_projects(claude_path, None).await.unwrap();
+        assert_eq!(projects[0].total_tokens, None);
+    }
+
+    #[tokio::test]
+    async fn test_scan_projects_since_filters_by_last_activity() {
+        let temp_dir = TempDir::new().unwrap();
+        let claude_dir = temp_dir.path().join(".claude");
+        let projects_dir = claude_dir.join("projects");
+
+        let old_dir = projects_dir.join("old-project");
+        fs::create_dir_all(&old_dir).unwrap();
+        create_test_jsonl_file(&old_dir, "session.jsonl", "{}");
+        let old_time = SystemTime::now() - std::time::Duration::from_secs(30 * 24 * 3600);
+        fs::File::options()
+            .write(true)
+            .open(old_dir.join("session.jsonl"))
+            .unwrap()
+            .set_modified(old_time)
+            .unwrap();
+
+        let recent_dir = projects_dir.join("recent-project");
+        fs::create_dir_all(&recent_dir).unwrap();
+        create_test_jsonl_file(&recent_dir, "session.jsonl", "{}");
+
+        // Empty projects have no activity and never match a window
+        fs::create_dir_all(projects_dir.join("empty-project")).unwrap();
+
+        let week_ago = (Utc::now() - chrono::Duration::days(7)).to_rfc3339();
+        let projects = scan_projects_since(claude_dir.to_string_lossy().to_string(), week_ago)
+            .await
+            .unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "recent-project");
+
+        let err = scan_projects_since(claude_dir.to_string_lossy().to_string(), "soon".into())
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("PROJECT_INVALID_ARGUMENT"));
+    }
 }
@@ -231,7 +231,7 @@ fn validate_claude_path(file_path: &str) -> Result<(), String> {
         .map_err(|e| RenameError::IoError(e.to_string()).to_string())?;
 
     // Get home directory
-    let home_dir = dirs::home_dir().ok_or_else(|| {
+    let home_dir = crate::utils::resolve_home().ok_or_else(|| {
         RenameError::IoError("Cannot determine home directory".to_string()).to_string()
     })?;
 
@@ -664,7 +664,7 @@ mod tests {
 
     #[test]
     fn test_validate_claude_path_valid_path() {
-        if let Some(home) = dirs::home_dir() {
+        if let Some(home) = crate::utils::resolve_home() {
             let claude_projects = home.join(".claude/projects");
             if claude_projects.exists() {
                 if let Ok(projects) = fs::read_dir(&claude_projects) {
@@ -5,93 +5,157 @@
 // Supports different providers (Claude Code, Codex, etc.)
 
 use crate::commands::adapters::provider_capabilities::ProviderCapabilities;
+use crate::commands::session_writer::{fork_session_until, CreateSessionResponse};
+use crate::utils::{is_session_file, open_session_reader};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command};
+use std::time::Duration;
 use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
 
 #[cfg(target_os = "windows")]
 #[allow(unused_imports)]
 use std::os::windows::process::CommandExt;
 
-#[tauri::command]
-pub async fn resume_session(
-    session_id: String,
-    cwd: Option<String>,
-    provider_id: String,
-) -> Result<(), String> {
-    let working_directory = cwd.ok_or("No working directory found for this session")?;
+/// Store (app data directory) holding user-set working directories for
+/// projects whose repository was moved after the sessions were recorded.
+const CWD_OVERRIDES_STORE: &str = "project-cwd-overrides.json";
+const CWD_OVERRIDES_KEY: &str = "overrides";
 
-    println!("DEBUG: Resume session {} in directory: {}", session_id, working_directory);
-    println!("DEBUG: Provider: {}", provider_id);
+/// How long a freshly spawned terminal is watched for an immediate failure
+const LAUNCH_CHECK_DELAY: Duration = Duration::from_millis(300);
 
-    // Extract UUID from filename if session_id is a full path
-    // e.g., "C:\...\22d84a97-2a19-47b8-a4d0-d83643076649.jsonl" -> "22d84a97-2a19-47b8-a4d0-d83643076649"
-    let session_uuid = if session_id.ends_with(".jsonl") {
-        Path::new(&session_id)
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .ok_or("Failed to extract session UUID from filename")?
-            .to_string()
+/// Override keys are compared without trailing separators, so `/repo/` and
+/// `/repo` name the same project.
+fn override_key(path: &str) -> &str {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        path
     } else {
-        session_id.clone()
-    };
+        trimmed
+    }
+}
 
-    println!("DEBUG: Extracted session UUID: {}", session_uuid);
+/// First override registered for any of `keys` whose directory still exists.
+fn lookup_cwd_override<'a>(
+    overrides: &HashMap<String, String>,
+    keys: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    keys.into_iter()
+        .filter_map(|key| overrides.get(override_key(key)))
+        .find(|cwd| Path::new(cwd).is_dir())
+        .cloned()
+}
 
-    // Get provider capabilities
-    let capabilities = ProviderCapabilities::for_provider(&provider_id);
+fn read_cwd_overrides(app_handle: &AppHandle) -> Result<HashMap<String, String>, String> {
+    let store = app_handle
+        .store(CWD_OVERRIDES_STORE)
+        .map_err(|e| format!("CWD_OVERRIDE_READ_ERROR: Failed to open store: {e}"))?;
 
-    // Check if provider supports resume
-    if !capabilities.supports_resume {
+    // A missing or malformed value is treated as no overrides
+    Ok(store
+        .get(CWD_OVERRIDES_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+/// Prefer the override registered for a recorded working directory.
+fn apply_cwd_override(app_handle: &AppHandle, cwd: Option<String>) -> Option<String> {
+    let cwd = cwd?;
+    let overrides = read_cwd_overrides(app_handle).unwrap_or_default();
+    Some(lookup_cwd_override(&overrides, [cwd.as_str()]).unwrap_or(cwd))
+}
+
+/// Re-link a moved project: sessions of `project_path` resume in `new_cwd`
+/// instead of the directory recorded in their JSONL files.
+///
+/// `project_path` is either the project's session folder (e.g.
+/// `~/.claude/projects/-home-me-app`) or the stale working directory itself.
+/// `new_cwd` must be an existing directory.
+#[tauri::command]
+pub async fn set_project_cwd_override(
+    app_handle: AppHandle,
+    project_path: String,
+    new_cwd: String,
+) -> Result<(), String> {
+    if project_path.trim().is_empty() {
+        return Err("CWD_OVERRIDE_INVALID: Project path is empty".to_string());
+    }
+    if !Path::new(&new_cwd).is_dir() {
         return Err(format!(
-            "Resume not supported for provider: {} (Provider does not have CLI resume capability)",
-            provider_id
+            "CWD_OVERRIDE_INVALID: Directory does not exist: {new_cwd}"
         ));
     }
 
-    // Build resume command from provider capabilities
-    let resume_command = capabilities
-        .build_resume_command(&session_uuid)
-        .ok_or_else(|| format!("Failed to build resume command for provider: {}", provider_id))?;
+    let mut overrides = read_cwd_overrides(&app_handle)?;
+    overrides.insert(override_key(&project_path).to_string(), new_cwd);
+
+    let store = app_handle
+        .store(CWD_OVERRIDES_STORE)
+        .map_err(|e| format!("CWD_OVERRIDE_WRITE_ERROR: Failed to open store: {e}"))?;
+    let value = serde_json::to_value(&overrides)
+        .map_err(|e| format!("CWD_OVERRIDE_WRITE_ERROR: Failed to serialize overrides: {e}"))?;
+    store.set(CWD_OVERRIDES_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("CWD_OVERRIDE_WRITE_ERROR: Failed to save store: {e}"))
+}
+
+/// Open a terminal running the provider's resume command. Returns the name
+/// of the terminal that was launched.
+#[tauri::command]
+pub async fn resume_session(
+    app_handle: AppHandle,
+    session_id: String,
+    cwd: Option<String>,
+    provider_id: String,
+) -> Result<String, String> {
+    let working_directory = apply_cwd_override(&app_handle, cwd)
+        .ok_or("No working directory found for this session")?;
+
+    log::debug!(
+        "Resume session {} in directory: {}",
+        session_id,
+        working_directory
+    );
+    log::debug!("Provider: {}", provider_id);
+
+    let session_uuid = session_uuid(&session_id)?;
+    log::debug!("Extracted session UUID: {}", session_uuid);
+
+    let capabilities = resume_capabilities(&provider_id)?;
+    let resume_command = build_resume_command(&capabilities, &session_uuid, &provider_id)?;
 
-    println!("DEBUG: Executing command: {}", resume_command);
-    open_terminal_with_command(&working_directory, &resume_command)?;
-    println!("DEBUG: Terminal launched successfully");
+    log::debug!("Executing command: {}", resume_command);
+    // Launching waits briefly on each terminal it tries, so keep it off the
+    // async runtime
+    let terminal = tauri::async_runtime::spawn_blocking(move || {
+        open_terminal_with_command(&working_directory, &resume_command)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))??;
+    log::debug!("Terminal launched successfully: {}", terminal);
 
-    Ok(())
+    Ok(terminal)
 }
 
 #[tauri::command]
 pub async fn get_resume_command(
+    app_handle: AppHandle,
     session_id: String,
     cwd: Option<String>,
     provider_id: String,
 ) -> Result<String, String> {
-    let working_directory = cwd.unwrap_or_else(|| ".".to_string());
+    let working_directory = apply_cwd_override(&app_handle, cwd).unwrap_or_else(|| ".".to_string());
 
-    // Extract UUID from filename if session_id is a full path
-    let session_uuid = if session_id.ends_with(".jsonl") {
-        Path::new(&session_id)
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .ok_or("Failed to extract session UUID from filename")?
-            .to_string()
-    } else {
-        session_id.clone()
-    };
-
-    // Get provider capabilities
-    let capabilities = ProviderCapabilities::for_provider(&provider_id);
-
-    // Check if provider supports resume
-    if !capabilities.supports_resume {
-        return Err(format!(
-            "Resume not supported for provider: {}",
-            provider_id
-        ));
-    }
+    let session_uuid = session_uuid(&session_id)?;
+    let capabilities = resume_capabilities(&provider_id)?;
 
     // For interactive providers (like Gemini), return the interactive command
     if let Some(interactive_cmd) = capabilities.get_interactive_command(&session_uuid) {
@@ -100,9 +164,7 @@ pub async fn get_resume_command(
     }
 
     // For direct flag providers, build full command with cd
-    let resume_command = capabilities
-        .build_resume_command(&session_uuid)
-        .ok_or_else(|| format!("Failed to build resume command for provider: {}", provider_id))?;
+    let resume_command = build_resume_command(&capabilities, &session_uuid, &provider_id)?;
 
     Ok(format!(
         "cd {} && {}",
@@ -110,6 +172,146 @@ pub async fn get_resume_command(
     ))
 }
 
+/// Rewind a Claude Code session: copy it up to and including `message_id`
+/// into a new session, then resume that copy so the conversation can
+/// continue differently from there. Returns the new session.
+#[tauri::command]
+pub async fn resume_from_message(
+    app_handle: AppHandle,
+    session_path: String,
+    message_id: String,
+    cwd: Option<String>,
+    provider_id: String,
+) -> Result<CreateSessionResponse, String> {
+    resume_capabilities(&provider_id)?;
+    // The session writer only produces Claude Code JSONL
+    if provider_id != "claude-code" {
+        return Err(format!(
+            "Resume from message not supported for provider: {}",
+            provider_id
+        ));
+    }
+
+    let forked = fork_session_until(&session_path, &message_id, cwd.clone()).await?;
+    let working_directory = match cwd {
+        Some(cwd) => Some(cwd),
+        None => read_session_cwd(&forked.session_path, &provider_id).ok(),
+    };
+    log::debug!(
+        "Forked session {} at message {} into {}",
+        session_path,
+        message_id,
+        forked.session_path
+    );
+
+    resume_session(
+        app_handle,
+        forked.session_path.clone(),
+        working_directory,
+        provider_id,
+    )
+    .await?;
+    Ok(forked)
+}
+
+/// What resuming a session would do, for a confirmation dialog
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResumePreview {
+    /// Working directory after overrides; `None` when it cannot be determined
+    pub cwd: Option<String>,
+    /// Command run in `cwd` by `resume_session`
+    pub command: String,
+    /// Command to type inside the CLI afterwards (Gemini's `/chat resume`)
+    pub interactive_command: Option<String>,
+    pub cli_name: Option<String>,
+    /// Whether `cli_name` was found on `PATH`
+    pub cli_installed: bool,
+    pub directory_exists: bool,
+}
+
+/// Resolve everything `resume_session` needs without launching a terminal.
+/// Fails only when the provider cannot resume at all; a missing CLI or
+/// working directory is reported in the preview.
+#[tauri::command]
+pub async fn preview_resume(
+    app_handle: AppHandle,
+    session_path: String,
+    provider_id: String,
+) -> Result<ResumePreview, String> {
+    let capabilities = resume_capabilities(&provider_id)?;
+    let session_uuid = session_uuid(&session_path)?;
+    let command = build_resume_command(&capabilities, &session_uuid, &provider_id)?;
+
+    let cwd = resolve_session_cwd(&app_handle, &session_path, &provider_id, false).ok();
+    let cli_installed = match (&capabilities.cli_name, std::env::var_os("PATH")) {
+        (Some(cli), Some(path)) => cli_on_path(cli, &path),
+        _ => false,
+    };
+
+    Ok(ResumePreview {
+        directory_exists: cwd.as_deref().is_some_and(|dir| Path::new(dir).is_dir()),
+        cwd,
+        command,
+        interactive_command: capabilities.get_interactive_command(&session_uuid),
+        cli_name: capabilities.cli_name,
+        cli_installed,
+    })
+}
+
+/// Session UUID from either a bare id or a session file path
+/// e.g., "C:\...\22d84a97-2a19-47b8-a4d0-d83643076649.jsonl" -> "22d84a97-2a19-47b8-a4d0-d83643076649"
+fn session_uuid(session_id: &str) -> Result<String, String> {
+    if session_id.ends_with(".jsonl") {
+        Path::new(session_id)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+            .ok_or_else(|| "Failed to extract session UUID from filename".to_string())
+    } else {
+        Ok(session_id.to_string())
+    }
+}
+
+/// Capabilities of a provider that can resume sessions
+fn resume_capabilities(provider_id: &str) -> Result<ProviderCapabilities, String> {
+    let capabilities = ProviderCapabilities::for_provider(provider_id);
+    if !capabilities.supports_resume {
+        return Err(format!(
+            "Resume not supported for provider: {} (Provider does not have CLI resume capability)",
+            provider_id
+        ));
+    }
+    Ok(capabilities)
+}
+
+fn build_resume_command(
+    capabilities: &ProviderCapabilities,
+    session_uuid: &str,
+    provider_id: &str,
+) -> Result<String, String> {
+    capabilities
+        .build_resume_command(session_uuid)
+        .ok_or_else(|| {
+            format!(
+                "Failed to build resume command for provider: {}",
+                provider_id
+            )
+        })
+}
+
+/// Whether an executable named `cli` exists in one of the `path` entries
+fn cli_on_path(cli: &str, path: &OsStr) -> bool {
+    let names: Vec<String> = if cfg!(windows) {
+        ["exe", "cmd", "bat"]
+            .iter()
+            .map(|ext| format!("{}.{}", cli, ext))
+            .collect()
+    } else {
+        vec![cli.to_string()]
+    };
+    std::env::split_paths(path).any(|dir| names.iter().any(|name| dir.join(name).is_file()))
+}
+
 /// Check if a provider supports resume functionality
 #[tauri::command]
 pub async fn provider_supports_resume(provider_id: String) -> Result<bool, String> {
@@ -118,26 +320,135 @@ pub async fn provider_supports_resume(provider_id: String) -> Result<bool, Strin
 }
 
 /// Extract CWD from a session file
-/// Reads the first message to find the working directory
+/// Reads the first message to find the working directory. A project override
+/// (see [`set_project_cwd_override`]) for the session's folder or for the
+/// recorded directory takes precedence.
 #[tauri::command]
 pub async fn get_session_cwd(
+    app_handle: AppHandle,
     session_file_path: String,
     provider_id: String,
 ) -> Result<String, String> {
-    println!("DEBUG: get_session_cwd called - provider_id: {}, session_file_path: {}", provider_id, &session_file_path[..session_file_path.len().min(100)]);
+    resolve_session_cwd(&app_handle, &session_file_path, &provider_id, true)
+}
+
+/// Reveal a project in the system file manager. Opens the project's real
+/// working directory (after overrides) when one is recorded and still
+/// exists, otherwise its session folder.
+#[tauri::command]
+pub async fn open_project_folder(
+    app_handle: AppHandle,
+    project_path: String,
+) -> Result<(), String> {
+    let overrides = read_cwd_overrides(&app_handle).unwrap_or_default();
+    let dir = project_open_dir(&overrides, &project_path)?;
+    log::debug!("Opening project folder: {}", dir);
+    tauri_plugin_opener::open_path(&dir, None::<&str>)
+        .map_err(|e| format!("OPEN_FOLDER_ERROR: Failed to open {}: {}", dir, e))
+}
+
+/// Directory [`open_project_folder`] opens for the session folder `project_path`.
+fn project_open_dir(
+    overrides: &HashMap<String, String>,
+    project_path: &str,
+) -> Result<String, String> {
+    let project_dir = Path::new(project_path);
+    if !project_dir.is_dir() {
+        return Err(format!(
+            "OPEN_FOLDER_ERROR: Project folder does not exist: {}",
+            project_path
+        ));
+    }
+
+    let mut sessions: Vec<_> = fs::read_dir(project_dir)
+        .map_err(|e| format!("OPEN_FOLDER_ERROR: Failed to read project folder: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_session_file(path))
+        .collect();
+    sessions.sort();
+    let recorded = sessions
+        .iter()
+        .find_map(|path| read_session_cwd(&path.to_string_lossy(), "claude-code").ok());
+
+    let keys = std::iter::once(project_path).chain(recorded.as_deref());
+    Ok(lookup_cwd_override(overrides, keys)
+        .filter(|cwd| Path::new(cwd).is_dir())
+        .or_else(|| recorded.filter(|cwd| Path::new(cwd).is_dir()))
+        .unwrap_or_else(|| project_path.to_string()))
+}
+
+/// Whether the Resume action can work for this session: the provider has a
+/// CLI resume command and the session's working directory (after overrides)
+/// is an existing directory. Cursor sessions whose workspace path cannot be
+/// recovered are not resumable, rather than resuming in the home directory.
+#[tauri::command]
+pub async fn session_supports_resume(
+    app_handle: AppHandle,
+    session_path: String,
+    provider_id: String,
+) -> Result<bool, String> {
+    if !ProviderCapabilities::for_provider(&provider_id).supports_resume {
+        return Ok(false);
+    }
+
+    Ok(
+        resolve_session_cwd(&app_handle, &session_path, &provider_id, false)
+            .is_ok_and(|cwd| Path::new(&cwd).is_dir()),
+    )
+}
+
+/// Working directory of a session, preferring a project override. With
+/// `cursor_home_fallback`, a Cursor session without a recoverable workspace
+/// resolves to the home directory instead of failing.
+fn resolve_session_cwd(
+    app_handle: &AppHandle,
+    session_file_path: &str,
+    provider_id: &str,
+    cursor_home_fallback: bool,
+) -> Result<String, String> {
+    let recorded = if provider_id == "cursor" && !cursor_home_fallback {
+        find_cursor_workspace_path(session_file_path).and_then(|path| {
+            path.ok_or_else(|| "Could not determine workspace directory".to_string())
+        })
+    } else {
+        read_session_cwd(session_file_path, provider_id)
+    };
+
+    let overrides = read_cwd_overrides(app_handle).unwrap_or_default();
+    // Cursor session ids are composite DB references, not files in a project folder
+    let project_dir = (provider_id != "cursor")
+        .then(|| Path::new(session_file_path).parent())
+        .flatten()
+        .map(|dir| dir.to_string_lossy().to_string());
+    let keys = project_dir
+        .as_deref()
+        .into_iter()
+        .chain(recorded.as_deref().ok());
+    if let Some(cwd) = lookup_cwd_override(&overrides, keys) {
+        return Ok(cwd);
+    }
+
+    recorded
+}
+
+fn read_session_cwd(session_file_path: &str, provider_id: &str) -> Result<String, String> {
+    log::debug!(
+        "get_session_cwd called - provider_id: {}, session_file_path: {}",
+        provider_id,
+        &session_file_path[..session_file_path.len().min(100)]
+    );
 
     // Handle Cursor separately - it uses SQLite DB, not JSONL files
     if provider_id == "cursor" {
-        println!("DEBUG: Calling get_cursor_session_cwd for Cursor provider");
-        return get_cursor_session_cwd(&session_file_path);
+        log::debug!("Calling get_cursor_session_cwd for Cursor provider");
+        return get_cursor_session_cwd(session_file_path);
     }
 
     // For JSONL-based providers (Claude Code, Codex, Gemini)
-    let file = fs::File::open(&session_file_path)
+    let reader = open_session_reader(Path::new(session_file_path))
         .map_err(|e| format!("Failed to open session file: {}", e))?;
 
-    let reader = BufReader::new(file);
-
     // Read lines and look for CWD in first few messages
     for line in reader.lines().take(20) {
         let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
@@ -179,12 +490,15 @@ pub async fn get_session_cwd(
 }
 
 /// Open terminal with command based on platform
-fn open_terminal_with_command(cwd: &str, command: &str) -> Result<(), String> {
+/// Returns the launched terminal. A terminal that exits with an error right
+/// after spawning counts as a failed launch; failures of the command inside
+/// a running terminal cannot be observed from here.
+fn open_terminal_with_command(cwd: &str, command: &str) -> Result<String, String> {
     #[cfg(target_os = "windows")]
     {
         // Windows: Use 'start' command to properly launch interactive terminal app
-        println!("DEBUG: Launching terminal in directory: {}", cwd);
-        println!("DEBUG: Command to execute: {}", command);
+        log::debug!("Launching terminal in directory: {}", cwd);
+        log::debug!("Command to execute: {}", command);
 
         // On Windows, convert forward slashes to backslashes if needed
         let windows_path = cwd.replace("/", "\\");
@@ -199,15 +513,17 @@ fn open_terminal_with_command(cwd: &str, command: &str) -> Result<(), String> {
             command.to_string(),
         ];
 
-        println!("DEBUG: start /D \"{}\" cmd /K {}", windows_path, command);
+        log::debug!("start /D \"{}\" cmd /K {}", windows_path, command);
 
-        Command::new("cmd.exe")
+        let mut child = Command::new("cmd.exe")
             .arg("/C")
             .arg("start")
             .arg("Claude Code Session") // Window title
             .args(&start_args)
             .spawn()
             .map_err(|e| format!("Failed to launch terminal: {}", e))?;
+        confirm_launch(&mut child, "cmd.exe")?;
+        Ok("cmd.exe".to_string())
     }
 
     #[cfg(target_os = "macos")]
@@ -224,10 +540,12 @@ fn open_terminal_with_command(cwd: &str, command: &str) -> Result<(), String> {
             safe_cwd, safe_command
         );
 
-        Command::new("osascript")
+        let mut child = Command::new("osascript")
             .args(&["-e", &script])
             .spawn()
             .map_err(|e| format!("Failed to open terminal: {}", e))?;
+        confirm_launch(&mut child, "Terminal.app")?;
+        Ok("Terminal.app".to_string())
     }
 
     #[cfg(target_os = "linux")]
@@ -247,30 +565,74 @@ fn open_terminal_with_command(cwd: &str, command: &str) -> Result<(), String> {
             ("xterm", vec!["-e", "bash", "-c", xterm_cmd.as_str()]),
         ];
 
-        let mut launched = false;
+        // A terminal that is installed but fails at once falls through to the next one
+        let mut failures = Vec::new();
         for (terminal, args) in terminals {
-            if let Ok(_) = Command::new(terminal).args(&args).spawn() {
-                launched = true;
-                break;
+            match Command::new(terminal).args(&args).spawn() {
+                Ok(mut child) => match confirm_launch(&mut child, terminal) {
+                    Ok(()) => return Ok(terminal.to_string()),
+                    Err(e) => {
+                        log::warn!("{}", e);
+                        failures.push(e);
+                    }
+                },
+                Err(e) => failures.push(format!("{}: {}", terminal, e)),
             }
         }
 
-        if !launched {
-            return Err("No supported terminal found. Please install gnome-terminal, konsole, or xterm.".to_string());
-        }
+        Err(format!(
+            "No supported terminal could be launched ({}). Please install gnome-terminal, konsole, or xterm.",
+            failures.join("; ")
+        ))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        let _ = (cwd, command);
+        Err("Resuming in a terminal is not supported on this platform".to_string())
     }
+}
 
-    Ok(())
+/// Fail if `child` exits unsuccessfully within [`LAUNCH_CHECK_DELAY`].
+/// Launchers that hand off to a running terminal and exit cleanly pass.
+/// Blocks the calling thread; call from a blocking task.
+fn confirm_launch(child: &mut Child, terminal: &str) -> Result<(), String> {
+    std::thread::sleep(LAUNCH_CHECK_DELAY);
+    match child.try_wait() {
+        Ok(Some(status)) if !status.success() => Err(format!(
+            "Failed to launch terminal: {} exited immediately ({})",
+            terminal, status
+        )),
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to check terminal {}: {}", terminal, e)),
+    }
 }
 
-/// Extract working directory for Cursor sessions
+/// Extract working directory for Cursor sessions, falling back to the home
+/// directory when the workspace path cannot be recovered
+fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String> {
+    if let Some(workspace_path) = find_cursor_workspace_path(composite_session_id)? {
+        return Ok(workspace_path);
+    }
+
+    // Fallback: return home directory
+    log::debug!("Falling back to home directory");
+    crate::utils::resolve_home()
+        .map(|p| p.to_string_lossy().to_string())
+        .ok_or("Could not determine workspace directory".to_string())
+}
+
+/// Recover the workspace path of a Cursor session
 /// Cursor format: <db-path>#session=<session-id>#timestamp=<iso-timestamp>
 /// We need to parse this and extract the workspace path from the workspace storage
-fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String> {
+fn find_cursor_workspace_path(composite_session_id: &str) -> Result<Option<String>, String> {
     use rusqlite::{params, Connection};
     use std::path::PathBuf;
 
-    println!("DEBUG: get_cursor_session_cwd called with: {}", composite_session_id);
+    log::debug!(
+        "get_cursor_session_cwd called with: {}",
+        composite_session_id
+    );
 
     // Parse the composite session ID
     // Format: C:\...\state.vscdb#session=<session-id>#workspace=<workspace-id>#timestamp=<iso-timestamp>
@@ -294,9 +656,9 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
         .ok_or("Invalid Cursor session format: missing #timestamp=")?;
     let workspace_id = &after_workspace[..timestamp_pos];
 
-    println!("DEBUG: Cursor session ID: {}", session_id);
-    println!("DEBUG: Cursor workspace ID: {}", workspace_id);
-    println!("DEBUG: Cursor DB path: {}", db_path_str);
+    log::debug!("Cursor session ID: {}", session_id);
+    log::debug!("Cursor workspace ID: {}", workspace_id);
+    log::debug!("Cursor DB path: {}", db_path_str);
 
     // The workspace ID corresponds to a workspace storage folder
     // We need to find the Cursor base path and look up the workspace
@@ -307,7 +669,7 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
         .and_then(|p| p.parent()) // Remove User
         .ok_or("Failed to determine Cursor base path")?;
 
-    println!("DEBUG: Cursor base: {}", cursor_base.display());
+    log::debug!("Cursor base: {}", cursor_base.display());
 
     // Try to open the workspace's state.vscdb to extract project root
     let workspace_db = cursor_base
@@ -316,17 +678,14 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
         .join(workspace_id)
         .join("state.vscdb");
 
-    println!("DEBUG: Workspace DB: {}", workspace_db.display());
+    log::debug!("Workspace DB: {}", workspace_db.display());
 
     if !workspace_db.exists() {
-        println!("DEBUG: Workspace DB does not exist! Falling back to home directory.");
-        // Fallback: return home directory or current directory
-        return dirs::home_dir()
-            .map(|p| p.to_string_lossy().to_string())
-            .ok_or("Could not determine workspace directory".to_string());
+        log::warn!("Workspace DB does not exist!");
+        return Ok(None);
     }
 
-    println!("DEBUG: Workspace DB exists, opening...");
+    log::debug!("Workspace DB exists, opening...");
 
     // Open workspace database and extract project root
     let conn = Connection::open(&workspace_db)
@@ -342,10 +701,13 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
 
     match history_result {
         Ok(ref history_json) => {
-            println!("DEBUG: Found history.entries, parsing {} chars...", history_json.len());
+            log::debug!(
+                "Found history.entries, parsing {} chars...",
+                history_json.len()
+            );
         }
         Err(ref e) => {
-            println!("DEBUG: Failed to read history.entries: {}", e);
+            log::warn!("Failed to read history.entries: {}", e);
         }
     }
 
@@ -364,14 +726,14 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
                 }
             }
 
-            println!("DEBUG: Found {} file paths in history", file_paths.len());
+            log::debug!("Found {} file paths in history", file_paths.len());
 
             if !file_paths.is_empty() {
                 // Find common prefix (project root)
                 let common_prefix = find_common_file_prefix(&file_paths);
                 let root_path = common_prefix.trim_end_matches('/');
 
-                println!("DEBUG: Common prefix before conversion: {}", root_path);
+                log::debug!("Common prefix before conversion: {}", root_path);
 
                 // Convert URI path to Windows path
                 // URI format examples:
@@ -392,18 +754,18 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
                     decoded_path.to_string()
                 };
 
-                println!("DEBUG: Extracted project root: {}", windows_path);
-                return Ok(windows_path);
+                log::debug!("Extracted project root: {}", windows_path);
+                return Ok(Some(windows_path));
             } else {
-                println!("DEBUG: File paths list is empty after parsing");
+                log::debug!("File paths list is empty after parsing");
             }
         } else {
-            println!("DEBUG: Failed to parse history.entries JSON");
+            log::debug!("Failed to parse history.entries JSON");
         }
     }
 
     // Try workspace.folderUri as alternative
-    println!("DEBUG: Trying workspace.folderUri...");
+    log::debug!("Trying workspace.folderUri...");
     let folder_uri_result: Result<String, _> = conn.query_row(
         "SELECT value FROM ItemTable WHERE key = 'workspace.folderUri'",
         params![],
@@ -411,7 +773,10 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
     );
 
     if let Ok(folder_uri_json) = folder_uri_result {
-        println!("DEBUG: Found workspace.folderUri: {}", &folder_uri_json[..folder_uri_json.len().min(200)]);
+        log::debug!(
+            "Found workspace.folderUri: {}",
+            &folder_uri_json[..folder_uri_json.len().min(200)]
+        );
 
         if let Ok(uri_obj) = serde_json::from_str::<serde_json::Value>(&folder_uri_json) {
             if let Some(path) = uri_obj.get("path").and_then(|v| v.as_str()) {
@@ -427,31 +792,30 @@ fn get_cursor_session_cwd(composite_session_id: &str) -> Result<String, String>
                     path.to_string()
                 };
 
-                println!("DEBUG: Extracted project root from workspace.folderUri: {}", windows_path);
-                return Ok(windows_path);
+                log::debug!(
+                    "Extracted project root from workspace.folderUri: {}",
+                    windows_path
+                );
+                return Ok(Some(windows_path));
             }
         }
     } else {
-        println!("DEBUG: workspace.folderUri not found");
+        log::debug!("workspace.folderUri not found");
     }
 
     // Last resort: list all keys to help debug
-    println!("DEBUG: Listing first 10 keys in workspace DB:");
+    log::debug!("Listing first 10 keys in workspace DB:");
     if let Ok(mut stmt) = conn.prepare("SELECT key FROM ItemTable LIMIT 10") {
         if let Ok(rows) = stmt.query_map(params![], |row| row.get::<_, String>(0)) {
             for (i, key_result) in rows.enumerate() {
                 if let Ok(key) = key_result {
-                    println!("  [{}] {}", i, key);
+                    log::debug!("  [{}] {}", i, key);
                 }
             }
         }
     }
 
-    // Fallback: return home directory
-    println!("DEBUG: Falling back to home directory");
-    dirs::home_dir()
-        .map(|p| p.to_string_lossy().to_string())
-        .ok_or("Could not determine workspace directory".to_string())
+    Ok(None)
 }
 
 /// Find common prefix among file paths to determine project root
@@ -491,3 +855,157 @@ fn find_common_file_prefix(paths: &[String]) -> String {
         String::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cwd_override_lookup_prefers_first_existing_key() {
+        let moved = tempfile::TempDir::new().unwrap();
+        let moved_path = moved.path().to_string_lossy().to_string();
+        let overrides = HashMap::from([
+            ("/old/repo".to_string(), moved_path.clone()),
+            ("/gone/repo".to_string(), "/no/such/dir".to_string()),
+        ]);
+
+        assert_eq!(
+            lookup_cwd_override(&overrides, ["/unknown", "/old/repo/"]),
+            Some(moved_path)
+        );
+        // An override whose directory has since disappeared is ignored
+        assert_eq!(lookup_cwd_override(&overrides, ["/gone/repo"]), None);
+        assert_eq!(lookup_cwd_override(&overrides, ["/unknown"]), None);
+    }
+
+    #[test]
+    fn override_key_ignores_trailing_separators() {
+        assert_eq!(override_key("/old/repo/"), "/old/repo");
+        assert_eq!(override_key("C:\\old\\repo\\"), "C:\\old\\repo");
+        assert_eq!(override_key("/"), "/");
+    }
+
+    #[test]
+    fn cursor_workspace_path_is_none_without_workspace_db() {
+        let base = tempfile::TempDir::new().unwrap();
+        let db = base.path().join("User/globalStorage/state.vscdb");
+        let composite = format!(
+            "{}#session=abc#workspace=missing#timestamp=2025-06-01T10:00:00Z",
+            db.display()
+        );
+
+        assert_eq!(find_cursor_workspace_path(&composite), Ok(None));
+        assert!(find_cursor_workspace_path("no-markers").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn confirm_launch_rejects_immediate_failure() {
+        let mut failed = Command::new("false").spawn().unwrap();
+        let err = confirm_launch(&mut failed, "false").unwrap_err();
+        assert!(err.contains("exited immediately"), "{}", err);
+
+        let mut handed_off = Command::new("true").spawn().unwrap();
+        assert_eq!(confirm_launch(&mut handed_off, "true"), Ok(()));
+
+        let mut running = Command::new("sleep").arg("2").spawn().unwrap();
+        assert_eq!(confirm_launch(&mut running, "sleep"), Ok(()));
+        running.kill().unwrap();
+        running.wait().unwrap();
+    }
+
+    #[test]
+    fn project_open_dir_prefers_existing_recorded_cwd() {
+        use std::io::Write;
+
+        let project = tempfile::TempDir::new().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+        let real = tempfile::TempDir::new().unwrap();
+        let real_path = real.path().to_string_lossy().to_string();
+        let no_overrides = HashMap::new();
+
+        // No sessions yet: the session folder itself
+        assert_eq!(
+            project_open_dir(&no_overrides, &project_path),
+            Ok(project_path.clone())
+        );
+
+        let session = project.path().join("s1.jsonl");
+        fs::write(
+            &session,
+            format!("{}\n", serde_json::json!({"cwd": "/no/such/dir"})),
+        )
+        .unwrap();
+        assert_eq!(
+            project_open_dir(&no_overrides, &project_path),
+            Ok(project_path.clone())
+        );
+
+        fs::write(
+            &session,
+            format!("{}\n", serde_json::json!({"cwd": real_path})),
+        )
+        .unwrap();
+        assert_eq!(
+            project_open_dir(&no_overrides, &project_path),
+            Ok(real_path.clone())
+        );
+
+        let moved = tempfile::TempDir::new().unwrap();
+        let moved_path = moved.path().to_string_lossy().to_string();
+        let overrides = HashMap::from([(real_path.clone(), moved_path.clone())]);
+        assert_eq!(project_open_dir(&overrides, &project_path), Ok(moved_path));
+
+        // An override pointing at a missing directory is ignored
+        let stale = HashMap::from([(real_path.clone(), "/no/such/dir".to_string())]);
+        assert_eq!(
+            project_open_dir(&stale, &project_path),
+            Ok(real_path.clone())
+        );
+
+        // Gzip-archived sessions are read too
+        fs::remove_file(&session).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(format!("{}\n", serde_json::json!({"cwd": real_path})).as_bytes())
+            .unwrap();
+        fs::write(
+            project.path().join("s1.jsonl.gz"),
+            encoder.finish().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            project_open_dir(&no_overrides, &project_path),
+            Ok(real_path)
+        );
+
+        let err = project_open_dir(&no_overrides, "/no/such/project").unwrap_err();
+        assert!(err.starts_with("OPEN_FOLDER_ERROR"), "{}", err);
+    }
+
+    #[test]
+    fn session_uuid_strips_jsonl_path() {
+        assert_eq!(
+            session_uuid("/home/me/.claude/projects/-app/22d84a97.jsonl"),
+            Ok("22d84a97".to_string())
+        );
+        assert_eq!(session_uuid("22d84a97"), Ok("22d84a97".to_string()));
+        assert!(resume_capabilities("unknown").is_err());
+    }
+
+    #[test]
+    fn cli_on_path_finds_executable_in_any_entry() {
+        let empty = tempfile::TempDir::new().unwrap();
+        let bin = tempfile::TempDir::new().unwrap();
+        let file_name = if cfg!(windows) {
+            "claude.exe"
+        } else {
+            "claude"
+        };
+        fs::write(bin.path().join(file_name), "").unwrap();
+        let path = std::env::join_paths([empty.path(), bin.path()]).unwrap();
+
+        assert!(cli_on_path("claude", &path));
+        assert!(!cli_on_path("codex", &path));
+    }
+}
@@ -0,0 +1,129 @@
+//! Tauri commands for persisted search history
+//!
+//! Recent search queries are kept in the store plugin's `search-history.json`
+//! (app data directory), newest first. Re-running a query moves it back to the
+//! top instead of adding a duplicate, and the list is capped at
+//! [`MAX_SEARCH_HISTORY`] entries.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const SEARCH_HISTORY_STORE: &str = "search-history.json";
+const SEARCH_HISTORY_KEY: &str = "entries";
+
+/// Maximum number of queries kept in the history.
+pub const MAX_SEARCH_HISTORY: usize = 50;
+
+/// A single recorded search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHistoryEntry {
+    pub query: String,
+    pub searched_at: String, // ISO 8601 timestamp
+}
+
+/// Record `query` at the front of `entries`, replacing an earlier identical
+/// query and dropping the oldest entries beyond [`MAX_SEARCH_HISTORY`].
+/// Blank queries are ignored.
+fn push_search_history(entries: &mut Vec<SearchHistoryEntry>, query: &str, searched_at: String) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+
+    entries.retain(|entry| entry.query != query);
+    entries.insert(
+        0,
+        SearchHistoryEntry {
+            query: query.to_string(),
+            searched_at,
+        },
+    );
+    entries.truncate(MAX_SEARCH_HISTORY);
+}
+
+fn read_search_history(app_handle: &AppHandle) -> Result<Vec<SearchHistoryEntry>, String> {
+    let store = app_handle
+        .store(SEARCH_HISTORY_STORE)
+        .map_err(|e| format!("SEARCH_HISTORY_READ_ERROR: Failed to open store: {e}"))?;
+
+    // A missing or malformed value is treated as an empty history
+    Ok(store
+        .get(SEARCH_HISTORY_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+/// Record a search query in the persisted history.
+#[tauri::command]
+pub async fn add_search_history(app_handle: AppHandle, query: String) -> Result<(), String> {
+    let mut entries = read_search_history(&app_handle)?;
+    push_search_history(&mut entries, &query, Utc::now().to_rfc3339());
+
+    let store = app_handle
+        .store(SEARCH_HISTORY_STORE)
+        .map_err(|e| format!("SEARCH_HISTORY_WRITE_ERROR: Failed to open store: {e}"))?;
+    let value = serde_json::to_value(&entries)
+        .map_err(|e| format!("SEARCH_HISTORY_WRITE_ERROR: Failed to serialize history: {e}"))?;
+    store.set(SEARCH_HISTORY_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("SEARCH_HISTORY_WRITE_ERROR: Failed to save store: {e}"))
+}
+
+/// Get up to `limit` recent searches, newest first.
+#[tauri::command]
+pub async fn get_search_history(
+    app_handle: AppHandle,
+    limit: usize,
+) -> Result<Vec<SearchHistoryEntry>, String> {
+    let mut entries = read_search_history(&app_handle)?;
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queries(entries: &[SearchHistoryEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.query.as_str()).collect()
+    }
+
+    #[test]
+    fn push_adds_newest_first() {
+        let mut entries = Vec::new();
+        push_search_history(&mut entries, "timeout", "2025-06-01T10:00:00Z".to_string());
+        push_search_history(&mut entries, "panic", "2025-06-01T11:00:00Z".to_string());
+        assert_eq!(queries(&entries), vec!["panic", "timeout"]);
+    }
+
+    #[test]
+    fn push_deduplicates_and_refreshes_timestamp() {
+        let mut entries = Vec::new();
+        push_search_history(&mut entries, "timeout", "2025-06-01T10:00:00Z".to_string());
+        push_search_history(&mut entries, "panic", "2025-06-01T11:00:00Z".to_string());
+        push_search_history(&mut entries, "  timeout ", "2025-06-01T12:00:00Z".to_string());
+        assert_eq!(queries(&entries), vec!["timeout", "panic"]);
+        assert_eq!(entries[0].searched_at, "2025-06-01T12:00:00Z");
+    }
+
+    #[test]
+    fn push_ignores_blank_queries() {
+        let mut entries = Vec::new();
+        push_search_history(&mut entries, "   ", "2025-06-01T10:00:00Z".to_string());
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn push_caps_history() {
+        let mut entries = Vec::new();
+        for i in 0..(MAX_SEARCH_HISTORY + 5) {
+            push_search_history(&mut entries, &format!("q{i}"), "t".to_string());
+        }
+        assert_eq!(entries.len(), MAX_SEARCH_HISTORY);
+        assert_eq!(entries[0].query, format!("q{}", MAX_SEARCH_HISTORY + 4));
+    }
+}
@@ -2,13 +2,20 @@
 //!
 //! - [`QueryMatcher`]: SIMD-accelerated, ASCII case-insensitive multi-term
 //!   matcher built once per query and shared across parallel scans.
+//! - [`FuzzyWords`]: typo-tolerant word lookup (Levenshtein distance) for
+//!   fuzzy search mode.
 //! - [`top_k_by`]: O(n) partial selection (top-k) instead of O(n log n) full
 //!   sort before truncation.
 //! - [`take_matching`]: early-termination collector that stops scanning once
 //!   `limit` matches have been gathered.
+//! - [`message_matches`] / [`MessageFilter`]: query and [`SearchFilters`]
+//!   checks on converted `UniversalMessage`s.
 //! - Generation-based cache invalidation hooks ([`bump_search_generation`]).
 
+use crate::models::universal::{ContentType, MessageRole, UniversalMessage};
+use crate::models::SearchFilters;
 use aho_corasick::AhoCorasick;
+use chrono::{DateTime, FixedOffset};
 use std::cmp::Ordering;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -90,6 +97,72 @@ impl QueryMatcher {
         }
         remaining == 0
     }
+
+    /// Report which patterns occur in `haystack`, indexed in construction
+    /// order (empty patterns dropped by [`QueryMatcher::from_patterns`] do not
+    /// take a slot). Used to evaluate boolean queries over a single scan.
+    pub fn matched_patterns(&self, haystack: &str) -> Vec<bool> {
+        let mut seen = vec![false; self.term_count];
+        let Some(ac) = &self.ac else {
+            return seen;
+        };
+
+        let mut remaining = self.term_count;
+        for m in ac.find_overlapping_iter(haystack) {
+            let pid = m.pattern().as_usize();
+            if !seen[pid] {
+                seen[pid] = true;
+                remaining -= 1;
+                if remaining == 0 {
+                    break;
+                }
+            }
+        }
+        seen
+    }
+}
+
+/// Lowercased words of a haystack, for typo-tolerant term lookup.
+///
+/// Words are maximal runs of alphanumerics and `_`. Built lazily by callers —
+/// only when a term was not found verbatim — since splitting allocates.
+pub struct FuzzyWords {
+    words: Vec<String>,
+}
+
+impl FuzzyWords {
+    pub fn new(haystack: &str) -> Self {
+        let mut words: Vec<String> = haystack
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+        Self { words }
+    }
+
+    /// Smallest Levenshtein distance between `term` and any word, if within
+    /// the distance allowed for `term` (see [`allowed_edit_distance`]).
+    pub fn closest(&self, term: &str, max_distance: usize) -> Option<usize> {
+        let term = term.to_lowercase();
+        let term_len = term.chars().count();
+        let allowed = allowed_edit_distance(term_len, max_distance);
+        self.words
+            .iter()
+            // Length difference is a lower bound on the edit distance
+            .filter(|w| w.chars().count().abs_diff(term_len) <= allowed)
+            .map(|w| strsim::levenshtein(&term, w))
+            .filter(|d| *d <= allowed)
+            .min()
+    }
+}
+
+/// Edit distance tolerated for a term of `term_len` characters: at most
+/// `max_distance`, and less than half the term so short words (`ls`, `bug`)
+/// do not match nearly everything.
+pub fn allowed_edit_distance(term_len: usize, max_distance: usize) -> usize {
+    max_distance.min(term_len.saturating_sub(1) / 2)
 }
 
 /// Reduce `items` to its top `limit` entries by `cmp` using an O(n) partial
@@ -136,6 +209,104 @@ where
     out
 }
 
+/// Check whether any content in a UniversalMessage matches the search query.
+///
+/// A message matches when its serialized content contains all query terms
+/// (ASCII case-insensitive, order-independent), as determined by the shared
+/// [`QueryMatcher`]. The matcher is built once per search and reused here.
+pub fn message_matches(msg: &UniversalMessage, matcher: &QueryMatcher) -> bool {
+    for content in &msg.content {
+        if let Ok(json) = serde_json::to_string(content) {
+            if matcher.is_match(&json) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// [`SearchFilters`] checked against converted messages, for providers whose
+/// search runs over `UniversalMessage`s rather than raw records.
+///
+/// Applies `dateRange`, `sessionId`, `messageType`, `hasToolCalls` and
+/// `hasErrors`. `projects` is provider-specific and left to the caller.
+pub struct MessageFilter<'a> {
+    filters: &'a SearchFilters,
+    date_range: Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+}
+
+impl<'a> MessageFilter<'a> {
+    pub fn new(filters: &'a SearchFilters) -> Self {
+        // Same parsing as the Claude search: a range needs two RFC 3339 bounds
+        let date_range = match filters.date_range.as_deref() {
+            Some([start, end]) => DateTime::parse_from_rfc3339(start)
+                .ok()
+                .zip(DateTime::parse_from_rfc3339(end).ok()),
+            _ => None,
+        };
+        Self {
+            filters,
+            date_range,
+        }
+    }
+
+    pub fn matches(&self, msg: &UniversalMessage) -> bool {
+        if let Some((start, end)) = self.date_range {
+            match DateTime::parse_from_rfc3339(&msg.timestamp) {
+                Ok(time) if time >= start && time <= end => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(ref session_id) = self.filters.session_id {
+            if !msg.session_id.contains(session_id.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(ref message_type) = self.filters.message_type {
+            let role = match msg.role {
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+                MessageRole::System => "system",
+                MessageRole::Function => "function",
+            };
+            if message_type != "all" && message_type != role {
+                return false;
+            }
+        }
+
+        if let Some(has_tool_calls) = self.filters.has_tool_calls {
+            let has_tools = msg
+                .tool_calls
+                .as_ref()
+                .is_some_and(|calls| !calls.is_empty())
+                || msg.content.iter().any(|content| {
+                    matches!(
+                        content.content_type,
+                        ContentType::ToolUse | ContentType::ToolResult
+                    )
+                });
+            if has_tools != has_tool_calls {
+                return false;
+            }
+        }
+
+        if let Some(has_errors) = self.filters.has_errors {
+            let errored = msg.errors.as_ref().is_some_and(|errors| !errors.is_empty())
+                || msg.content.iter().any(|content| {
+                    matches!(content.content_type, ContentType::ToolResult)
+                        && content.data.get("is_error").and_then(|v| v.as_bool()) == Some(true)
+                });
+            if errored != has_errors {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 /// Monotonic generation counter for search-result caches. Any change that could
 /// affect search output (e.g. a session file write) should call
 /// [`bump_search_generation`]; cached entries tagged with an older generation
@@ -204,6 +375,16 @@ mod tests {
         assert!(!m.is_match("hello rust world")); // phrase not contiguous
     }
 
+    #[test]
+    fn matcher_reports_each_present_pattern() {
+        let m = QueryMatcher::from_patterns(&["error", "panic", "warn"]);
+        assert_eq!(
+            m.matched_patterns("an ERROR then a Panic"),
+            vec![true, true, false]
+        );
+        assert_eq!(m.matched_patterns("nothing"), vec![false, false, false]);
+    }
+
     #[test]
     fn matcher_empty_query_is_empty() {
         let m = QueryMatcher::from_query("   ");
@@ -274,4 +455,25 @@ mod tests {
         assert_eq!(a, b);
         assert_ne!(a, c);
     }
+
+    #[test]
+    fn fuzzy_words_tolerates_small_typos() {
+        let words = FuzzyWords::new("The deployment TIMEOUT was hit, see config_file.");
+        assert_eq!(words.closest("timeout", 2), Some(0));
+        assert_eq!(words.closest("timout", 2), Some(1));
+        assert_eq!(words.closest("deploymnet", 2), Some(2));
+        assert_eq!(words.closest("config_fle", 2), Some(1));
+        assert_eq!(words.closest("timeout", 0), Some(0));
+        assert_eq!(words.closest("timout", 0), None);
+        assert_eq!(words.closest("database", 2), None);
+    }
+
+    #[test]
+    fn fuzzy_words_short_terms_need_exact_words() {
+        let words = FuzzyWords::new("cat hat");
+        assert_eq!(allowed_edit_distance(3, 2), 1);
+        assert_eq!(allowed_edit_distance(2, 2), 0);
+        assert_eq!(words.closest("bat", 2), Some(1));
+        assert_eq!(words.closest("ct", 2), None);
+    }
 }
@@ -1,16 +1,22 @@
 use crate::commands::adapters::claude_code::claude_message_to_universal;
-use crate::commands::search_match::QueryMatcher;
-use crate::models::universal::UniversalMessage;
+use crate::commands::search_match::{FuzzyWords, QueryMatcher};
+use crate::commands::session_cache::{SessionCacheState, SessionParseOptions};
+use crate::models::universal::{ContentType, UniversalContent, UniversalMessage};
 use crate::models::*;
 use crate::utils::{
-    extract_git_info, extract_project_name, filter_preamble_from_title, find_line_ranges,
+    cap_message_content, extract_git_info, filter_preamble_from_title, find_fenced_code_blocks,
+    find_line_ranges, is_session_file, open_session_reader, project_folder_display_name,
+    read_session_bytes, read_session_to_string, resolve_project_name, strip_bom, IgnoreRules,
 };
 use chrono::{DateTime, Utc};
-use memmap2::Mmap;
 use rayon::prelude::*;
+use regex::Regex;
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::HashSet;
 use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
 use uuid::Uuid;
 use walkdir::WalkDir;
 
@@ -109,14 +115,111 @@ fn normalize_windows_path(path: &str) -> String {
     path.to_string()
 }
 
+/// Whether a raw tool result (an object, or a content array of tool_result
+/// blocks) carries `"is_error": true`. Only parses when the key is present.
+fn raw_reports_error(raw: &str) -> bool {
+    if !raw.contains("\"is_error\"") {
+        return false;
+    }
+    let is_error =
+        |v: &serde_json::Value| v.get("is_error").and_then(|e| e.as_bool()) == Some(true);
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(serde_json::Value::Array(items)) => items.iter().any(is_error),
+        Ok(value) => is_error(&value),
+        Err(_) => false,
+    }
+}
+
+/// Maximum number of parse warnings kept per session file.
+const MAX_WARNINGS_PER_FILE: usize = 100;
+
+/// Default cap on a single JSONL line when scanning sessions. Longer lines
+/// (e.g. a giant base64 image) are skipped instead of being buffered whole.
+pub const DEFAULT_MAX_LINE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Read the next line of `reader` into `buf` (without the `\n` / `\r\n`),
+/// buffering at most `max_bytes`. Returns the full line length, or `None` at
+/// end of input. When the length exceeds `max_bytes`, the rest of the line is
+/// consumed without being stored and `buf` is left empty.
+fn read_capped_line<R: std::io::BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    max_bytes: usize,
+) -> std::io::Result<Option<usize>> {
+    buf.clear();
+    let mut total = 0usize;
+    let mut read_any = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break;
+        }
+        read_any = true;
+        let (chunk_len, consumed, done) = match memchr::memchr(b'\n', available) {
+            Some(pos) => (pos, pos + 1, true),
+            None => (available.len(), available.len(), false),
+        };
+        if total + chunk_len <= max_bytes {
+            buf.extend_from_slice(&available[..chunk_len]);
+        } else {
+            buf.clear();
+        }
+        total += chunk_len;
+        reader.consume(consumed);
+        if done {
+            break;
+        }
+    }
+
+    if !read_any {
+        return Ok(None);
+    }
+    if total <= max_bytes && buf.last() == Some(&b'\r') {
+        buf.pop();
+        total -= 1;
+    }
+    Ok(Some(total))
+}
+
 /// Process a single JSONL file into a `ClaudeSession` using lightweight `SessionScanEntry`.
 /// Returns `None` if the file has no valid messages.
 fn process_session_file(
     entry: &walkdir::DirEntry,
     exclude_sidechain: bool,
     include_noise: bool,
+    max_line_bytes: usize,
+) -> Option<ClaudeSession> {
+    process_session_file_with_warnings(
+        entry,
+        exclude_sidechain,
+        include_noise,
+        max_line_bytes,
+        None,
+    )
+}
+
+/// [`process_session_file`] that also records data-quality problems into
+/// `warnings` (at most [`MAX_WARNINGS_PER_FILE`] per file) when given.
+fn process_session_file_with_warnings(
+    entry: &walkdir::DirEntry,
+    exclude_sidechain: bool,
+    include_noise: bool,
+    max_line_bytes: usize,
+    mut warnings: Option<&mut Vec<SessionParseWarning>>,
 ) -> Option<ClaudeSession> {
     let file_path = entry.path().to_string_lossy().to_string();
+    let mut warn = |line_num: usize, kind: &str, message: &str| {
+        if let Some(warnings) = warnings.as_deref_mut() {
+            if warnings.len() < MAX_WARNINGS_PER_FILE {
+                warnings.push(SessionParseWarning {
+                    file_path: file_path.clone(),
+                    line: line_num + 1,
+                    kind: kind.to_string(),
+                    message: message.to_string(),
+                });
+            }
+        }
+    };
 
     let last_modified = entry
         .metadata()
@@ -128,9 +231,8 @@ fn process_session_file(
         })
         .unwrap_or_else(|| Utc::now().to_rfc3339());
 
-    let file = std::fs::File::open(entry.path()).ok()?;
-    use std::io::{BufRead, BufReader};
-    let reader = BufReader::new(file);
+    // `.jsonl.gz` sessions are decompressed while reading
+    let mut reader = open_session_reader(entry.path()).ok()?;
 
     // Lightweight scan state — no ClaudeMessage allocation
     let mut session_summary: Option<String> = None;
@@ -141,6 +243,7 @@ fn process_session_file(
     let mut first_message_time: Option<String> = None;
     let mut last_message_time: Option<String> = None;
     let mut has_tool_use = false;
+    let mut tool_use_count: usize = 0;
     let mut has_errors = false;
     // Originating client (entrypoint): lock in the first non-empty value seen.
     let mut entrypoint: Option<String> = None;
@@ -157,25 +260,60 @@ fn process_session_file(
     let mut tool_result_raws: Vec<String> = Vec::new();
     let mut git_info_found = false;
 
-    for (_line_num, line_result) in reader.lines().enumerate() {
-        let line = match line_result {
+    let mut buf: Vec<u8> = Vec::new();
+    let mut next_line_num = 0usize;
+    loop {
+        let line_num = next_line_num;
+        next_line_num += 1;
+        let line_len = match read_capped_line(&mut reader, &mut buf, max_line_bytes) {
+            Ok(Some(len)) => len,
+            Ok(None) => break,
+            Err(e) => {
+                warn(line_num, "unparseable_line", &e.to_string());
+                break;
+            }
+        };
+        if line_len > max_line_bytes {
+            log::debug!(
+                "Skipping line {} of {} ({} bytes exceeds the {} byte limit)",
+                line_num + 1,
+                file_path,
+                line_len,
+                max_line_bytes
+            );
+            warn(
+                line_num,
+                "line_too_long",
+                &format!(
+                    "Line of {} bytes exceeds the {} byte limit",
+                    line_len, max_line_bytes
+                ),
+            );
+            continue;
+        }
+        let line = match std::str::from_utf8(&buf) {
             Ok(l) => l,
-            Err(_) => continue,
+            Err(e) => {
+                warn(line_num, "unparseable_line", &e.to_string());
+                continue;
+            }
         };
+        let line = if line_num == 0 { strip_bom(line) } else { line };
         if line.trim().is_empty() {
             continue;
         }
 
-        let scan_entry: SessionScanEntry = match serde_json::from_str(&line) {
+        let scan_entry: SessionScanEntry = match serde_json::from_str(line) {
             Ok(e) => e,
-            Err(_e) => {
+            Err(e) => {
                 #[cfg(debug_assertions)]
                 eprintln!(
                     "Error: Failed to parse JSONL at line {} in {}: {}",
-                    _line_num + 1,
+                    line_num + 1,
                     file_path,
-                    _e
+                    e
                 );
+                warn(line_num, "unparseable_line", &e.to_string());
                 continue;
             }
         };
@@ -218,6 +356,16 @@ fn process_session_file(
             continue;
         }
 
+        if scan_entry.uuid.is_none() {
+            warn(line_num, "missing_uuid", "Message has no uuid");
+        }
+        if scan_entry.session_id.is_none() {
+            warn(line_num, "missing_session_id", "Message has no sessionId");
+        }
+        if scan_entry.timestamp.is_none() {
+            warn(line_num, "missing_timestamp", "Message has no timestamp");
+        }
+
         let is_sidechain = scan_entry.is_sidechain.unwrap_or(false);
         if exclude_sidechain && is_sidechain {
             // Still count for has_tool_use/has_errors even if filtered from display count
@@ -238,6 +386,14 @@ fn process_session_file(
             }
         }
 
+        // --- Tool call count for activity_score (raw string scan) ---
+        if scan_entry.message_type == "assistant" {
+            let content_raw = scan_entry.message.as_ref().and_then(|m| m.content.as_ref());
+            if let Some(content_raw) = content_raw {
+                tool_use_count += content_raw.get().matches("\"type\":\"tool_use\"").count();
+            }
+        }
+
         // --- has_tool_use check (lightweight, using RawValue strings) ---
         if !has_tool_use {
             // Check top-level tool_use/tool_use_result presence
@@ -256,7 +412,7 @@ fn process_session_file(
             }
         }
 
-        // --- has_errors check (lazy parse only tool_use_result with stderr) ---
+        // --- has_errors check (lazy parse only tool results with stderr / is_error) ---
         if !has_errors {
             if let Some(ref raw) = scan_entry.tool_use_result {
                 let raw_str = raw.get();
@@ -270,6 +426,15 @@ fn process_session_file(
                         }
                     }
                 }
+                if !has_errors && raw_reports_error(raw_str) {
+                    has_errors = true;
+                }
+            }
+        }
+        if !has_errors && scan_entry.message_type == "user" {
+            let content_raw = scan_entry.message.as_ref().and_then(|m| m.content.as_ref());
+            if content_raw.is_some_and(|raw| raw_reports_error(raw.get())) {
+                has_errors = true;
             }
         }
 
@@ -284,7 +449,7 @@ fn process_session_file(
                 .map(|c| c.get().to_string());
         }
 
-        // --- Track first user message content for summary fallback ---
+        // --- Track first user message content (first prompt, summary fallback) ---
         if first_user_content_raw.is_none() && scan_entry.message_type == "user" {
             if let Some(ref msg) = scan_entry.message {
                 if let Some(ref content_raw) = msg.content {
@@ -332,6 +497,8 @@ fn process_session_file(
         false
     };
 
+    let first_user_prompt = extract_first_user_prompt(first_user_content_raw.as_deref());
+
     // --- Summary from first user message (only if no summary message found) ---
     let final_summary = if session_summary.is_some() {
         session_summary
@@ -368,11 +535,19 @@ fn process_session_file(
         .and_then(|n| n.to_str())
         .unwrap_or("Unknown")
         .to_string();
-    let project_name = extract_project_name(&raw_project_name);
+    let project_name = project_folder_display_name(&raw_project_name);
+
+    let activity_score = activity_score(
+        last_message_time.as_deref().unwrap_or(&last_modified),
+        message_count,
+        tool_use_count,
+        Utc::now(),
+    );
 
     Some(ClaudeSession {
         session_id,
         actual_session_id: actual_session_id.unwrap_or_else(|| "unknown-session".to_string()),
+        file_paths: vec![file_path.clone()],
         file_path,
         project_name,
         message_count,
@@ -382,45 +557,90 @@ fn process_session_file(
         has_tool_use,
         has_errors,
         is_problematic,
+        tool_use_count,
         summary: final_summary,
+        first_user_prompt,
         git_branch: final_git_branch,
         git_commit: final_git_commit,
         entrypoint,
+        activity_score,
     })
 }
 
-/// Extract a summary string from raw JSON content of the first user message.
+/// First non-blank text in raw JSON message content.
 /// The content may be a JSON string or an array with `{"type":"text","text":"..."}` items.
-fn extract_summary_from_raw_content(raw_content: Option<&str>) -> Option<String> {
+fn extract_text_from_raw_content(raw_content: Option<&str>) -> Option<String> {
     let raw = raw_content?;
     // Try parsing as serde_json::Value to handle both string and array forms
     let val: serde_json::Value = serde_json::from_str(raw).ok()?;
     match val {
-        serde_json::Value::String(text) => {
-            if text.trim().is_empty() {
-                None
-            } else {
-                let filtered = filter_preamble_from_title(&text);
-                Some(truncate_summary(&filtered))
-            }
-        }
-        serde_json::Value::Array(arr) => {
-            for item in &arr {
-                if item.get("type").and_then(|v| v.as_str()) == Some("text") {
-                    if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
-                        if !text.trim().is_empty() {
-                            let filtered = filter_preamble_from_title(text);
-                            return Some(truncate_summary(&filtered));
-                        }
-                    }
-                }
-            }
-            None
-        }
+        serde_json::Value::String(text) => Some(text).filter(|t| !t.trim().is_empty()),
+        serde_json::Value::Array(arr) => arr
+            .iter()
+            .filter(|item| item.get("type").and_then(|v| v.as_str()) == Some("text"))
+            .filter_map(|item| item.get("text").and_then(|v| v.as_str()))
+            .find(|text| !text.trim().is_empty())
+            .map(str::to_string),
         _ => None,
     }
 }
 
+/// Extract a summary string from raw JSON content of the first user message.
+fn extract_summary_from_raw_content(raw_content: Option<&str>) -> Option<String> {
+    extract_text_from_raw_content(raw_content)
+        .map(|text| truncate_summary(&filter_preamble_from_title(&text)))
+}
+
+/// Longest `first_user_prompt` kept on a session, in characters.
+const FIRST_USER_PROMPT_MAX_CHARS: usize = 2000;
+
+/// The opening prompt of a session: the first user message's text, capped at
+/// [`FIRST_USER_PROMPT_MAX_CHARS`] independently of the summary.
+fn extract_first_user_prompt(raw_content: Option<&str>) -> Option<String> {
+    let text = extract_text_from_raw_content(raw_content)?;
+    let text = text.trim();
+    if text.chars().count() > FIRST_USER_PROMPT_MAX_CHARS {
+        let truncated: String = text.chars().take(FIRST_USER_PROMPT_MAX_CHARS).collect();
+        Some(format!("{}...", truncated))
+    } else {
+        Some(text.to_string())
+    }
+}
+
+// Weights of `ClaudeSession::activity_score`. Each term is scaled so a
+// typical substantial session contributes a few points per term.
+
+/// Points for a session active right now; halves every
+/// [`ACTIVITY_RECENCY_HALF_LIFE_DAYS`].
+const ACTIVITY_RECENCY_WEIGHT: f64 = 10.0;
+const ACTIVITY_RECENCY_HALF_LIFE_DAYS: f64 = 7.0;
+/// Points per natural-log unit of message count (100 messages ≈ 4.6 units).
+const ACTIVITY_MESSAGE_WEIGHT: f64 = 1.0;
+/// Points per natural-log unit of tool calls; tool use marks sessions where
+/// real work happened, so it weighs more than plain conversation.
+const ACTIVITY_TOOL_WEIGHT: f64 = 1.5;
+
+/// Sortable significance of a session: exponentially decaying recency plus
+/// log-scaled message and tool call counts (so one huge session does not
+/// dwarf everything else). Unparseable timestamps contribute no recency.
+fn activity_score(
+    last_activity: &str,
+    message_count: usize,
+    tool_use_count: usize,
+    now: DateTime<Utc>,
+) -> f64 {
+    let recency = DateTime::parse_from_rfc3339(last_activity)
+        .map(|t| {
+            let age_days = (now - t.with_timezone(&Utc)).num_seconds().max(0) as f64 / 86_400.0;
+            0.5_f64.powf(age_days / ACTIVITY_RECENCY_HALF_LIFE_DAYS)
+        })
+        .unwrap_or(0.0);
+
+    ACTIVITY_RECENCY_WEIGHT * recency
+        + ACTIVITY_MESSAGE_WEIGHT * (message_count as f64).ln_1p()
+        + ACTIVITY_TOOL_WEIGHT * (tool_use_count as f64).ln_1p()
+}
+
 /// Truncate a summary string to 100 characters with ellipsis.
 fn truncate_summary(s: &str) -> String {
     if s.chars().count() > 100 {
@@ -431,29 +651,326 @@ fn truncate_summary(s: &str) -> String {
     }
 }
 
+/// Sessions of a project, newest first. Files whose mtime and size are
+/// unchanged since the previous call are served from [`SessionCacheState`]
+/// instead of being re-parsed.
+///
+/// With `group_by_session_id`, the files of a resumed conversation (which
+/// share `actual_session_id`) are merged into one session; see
+/// [`group_resumed_sessions`].
 #[tauri::command]
 pub async fn load_project_sessions(
     project_path: String,
     exclude_sidechain: Option<bool>,
     include_noise: Option<bool>,
+    max_line_bytes: Option<usize>,
+    group_by_session_id: Option<bool>,
+    cache: State<'_, SessionCacheState>,
 ) -> Result<Vec<ClaudeSession>, String> {
-    let start_time = std::time::Instant::now();
-    let exclude = exclude_sidechain.unwrap_or(false);
-    let noise = include_noise.unwrap_or(false);
+    let sessions = collect_project_sessions(
+        project_path,
+        exclude_sidechain,
+        include_noise,
+        max_line_bytes,
+        Some(&cache),
+    )
+    .await?;
+    if group_by_session_id.unwrap_or(false) {
+        Ok(group_resumed_sessions(sessions, Utc::now()))
+    } else {
+        Ok(sessions)
+    }
+}
 
-    // Collect file entries first for parallel processing
-    let file_entries: Vec<_> = WalkDir::new(&project_path)
+/// Merge sessions whose files share an `actual_session_id` into one entry,
+/// newest first, scoring merged entries against `now`. Sessions without a
+/// known ID are left as they are.
+fn group_resumed_sessions(sessions: Vec<ClaudeSession>, now: DateTime<Utc>) -> Vec<ClaudeSession> {
+    let mut groups: Vec<Vec<ClaudeSession>> = Vec::new();
+    let mut group_of: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for session in sessions {
+        if session.actual_session_id == "unknown-session" {
+            groups.push(vec![session]);
+            continue;
+        }
+        match group_of.get(&session.actual_session_id) {
+            Some(&idx) => groups[idx].push(session),
+            None => {
+                group_of.insert(session.actual_session_id.clone(), groups.len());
+                groups.push(vec![session]);
+            }
+        }
+    }
+
+    let mut merged: Vec<ClaudeSession> = groups
         .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .map(|files| merge_session_files(files, now))
         .collect();
+    merged.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    merged
+}
+
+/// One session from the files of a resumed conversation. The most recently
+/// active file supplies the identity, summary and end state (`is_problematic`,
+/// git info); message and tool call counts are summed, the time range spans
+/// every file, and `file_paths` lists the files oldest first. The activity
+/// score is recomputed from the merged totals against `now`.
+fn merge_session_files(mut files: Vec<ClaudeSession>, now: DateTime<Utc>) -> ClaudeSession {
+    if files.len() == 1 {
+        return files.remove(0);
+    }
+    files.sort_by(|a, b| {
+        a.first_message_time
+            .cmp(&b.first_message_time)
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+
+    let latest = files
+        .iter()
+        .max_by(|a, b| {
+            a.last_message_time
+                .cmp(&b.last_message_time)
+                .then_with(|| a.last_modified.cmp(&b.last_modified))
+        })
+        .expect("a session group is never empty");
+    let mut merged = latest.clone();
+
+    merged.message_count = files.iter().map(|s| s.message_count).sum();
+    merged.tool_use_count = files.iter().map(|s| s.tool_use_count).sum();
+    merged.first_message_time = files
+        .iter()
+        .map(|s| &s.first_message_time)
+        .filter(|t| !t.is_empty())
+        .min()
+        .cloned()
+        .unwrap_or_default();
+    merged.last_message_time = files
+        .iter()
+        .map(|s| &s.last_message_time)
+        .max()
+        .cloned()
+        .unwrap_or_default();
+    merged.last_modified = files
+        .iter()
+        .map(|s| &s.last_modified)
+        .max()
+        .cloned()
+        .unwrap_or_default();
+    merged.has_tool_use = files.iter().any(|s| s.has_tool_use);
+    merged.has_errors = files.iter().any(|s| s.has_errors);
+    merged.first_user_prompt = files.iter().find_map(|s| s.first_user_prompt.clone());
+    score_session(&mut merged, now);
+    merged.file_paths = files.into_iter().map(|s| s.file_path).collect();
+    merged
+}
+
+/// Session files (`.jsonl` / `.jsonl.gz`) below a project directory that
+/// `.history-viewer-ignore` does not exclude.
+pub(crate) fn project_session_entries(project_path: &Path) -> Vec<walkdir::DirEntry> {
+    let ignore = IgnoreRules::for_project(project_path);
+    WalkDir::new(project_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_session_file(e.path()) && !ignore.is_ignored(e.path()))
+        .collect()
+}
+
+/// [`load_project_sessions`] for callers outside the command layer; `cache`
+/// is optional so they can parse without managed state.
+pub(crate) async fn collect_project_sessions(
+    project_path: String,
+    exclude_sidechain: Option<bool>,
+    include_noise: Option<bool>,
+    max_line_bytes: Option<usize>,
+    cache: Option<&SessionCacheState>,
+) -> Result<Vec<ClaudeSession>, String> {
+    let start_time = std::time::Instant::now();
+    let options = SessionParseOptions {
+        exclude_sidechain: exclude_sidechain.unwrap_or(false),
+        include_noise: include_noise.unwrap_or(false),
+        max_line_bytes: max_line_bytes.unwrap_or(DEFAULT_MAX_LINE_BYTES),
+    };
+
+    // Collect file entries first for parallel processing
+    let file_entries = project_session_entries(Path::new(&project_path));
+
+    let parse = |entry: &walkdir::DirEntry| {
+        process_session_file(
+            entry,
+            options.exclude_sidechain,
+            options.include_noise,
+            options.max_line_bytes,
+        )
+    };
 
     // Process files in parallel using rayon
-    let mut sessions: Vec<ClaudeSession> = file_entries
+    let mut sessions: Vec<ClaudeSession> = match cache {
+        None => file_entries.par_iter().filter_map(parse).collect(),
+        Some(cache) => {
+            let sessions = file_entries
+                .par_iter()
+                .filter_map(|entry| {
+                    let Ok(metadata) = fs::metadata(entry.path()) else {
+                        return parse(entry);
+                    };
+                    if let Some(cached) = cache.get(entry.path(), &metadata, options) {
+                        return cached;
+                    }
+                    let session = parse(entry);
+                    cache.insert(
+                        entry.path().to_path_buf(),
+                        &metadata,
+                        options,
+                        session.clone(),
+                    );
+                    session
+                })
+                .collect();
+            let present: HashSet<PathBuf> = file_entries
+                .iter()
+                .map(|e| e.path().to_path_buf())
+                .collect();
+            cache.retain_present(Path::new(&project_path), &present);
+            sessions
+        }
+    };
+
+    finalize_project_sessions(&mut sessions, Utc::now());
+
+    let _elapsed = start_time.elapsed();
+    #[cfg(debug_assertions)]
+    println!(
+        "load_project_sessions performance: {} sessions loaded in {}ms",
+        sessions.len(),
+        _elapsed.as_millis()
+    );
+
+    Ok(sessions)
+}
+
+/// Sessions of a project that contain tool errors (`is_error` tool results or
+/// non-empty stderr), newest first. A focused triage view.
+#[tauri::command]
+pub async fn list_error_sessions(project_path: String) -> Result<Vec<ClaudeSession>, String> {
+    let sessions = collect_project_sessions(project_path, None, None, None, None).await?;
+    Ok(sessions.into_iter().filter(|s| s.has_errors).collect())
+}
+
+/// Sessions of a project bucketed by the UTC date of their last message,
+/// newest day first, with sessions that have no parseable time in a trailing
+/// "unknown" group. Token totals come from the usage fields of each session
+/// (the same count `scan_projects` uses); message bodies are not parsed.
+#[tauri::command]
+pub async fn get_sessions_by_day(project_path: String) -> Result<Vec<DayGroup>, String> {
+    let sessions = collect_project_sessions(project_path, None, None, None, None).await?;
+    Ok(group_sessions_by_day(sessions))
+}
+
+fn group_sessions_by_day(sessions: Vec<ClaudeSession>) -> Vec<DayGroup> {
+    let mut days: std::collections::BTreeMap<String, DayGroup> = std::collections::BTreeMap::new();
+    let tokens: Vec<u64> = sessions
+        .par_iter()
+        .map(|session| {
+            crate::commands::stats::count_session_file_tokens(std::path::Path::new(
+                &session.file_path,
+            ))
+        })
+        .collect();
+
+    for (session, session_tokens) in sessions.into_iter().zip(tokens) {
+        let date = DateTime::parse_from_rfc3339(&session.last_message_time)
+            .or_else(|_| DateTime::parse_from_rfc3339(&session.last_modified))
+            .map(|t| t.with_timezone(&Utc).format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|_| "unknown".to_string());
+        let day = days.entry(date.clone()).or_insert_with(|| DayGroup {
+            date,
+            session_count: 0,
+            total_tokens: 0,
+            sessions: Vec::new(),
+        });
+        day.session_count += 1;
+        day.total_tokens += session_tokens;
+        day.sessions.push(session);
+    }
+
+    let unknown = days.remove("unknown");
+    days.into_values()
+        .rev()
+        .chain(unknown)
+        .map(|mut day| {
+            day.sessions
+                .sort_by(|a, b| b.last_message_time.cmp(&a.last_message_time));
+            day
+        })
+        .collect()
+}
+
+/// Like `load_project_sessions`, but also returns the data-quality warnings
+/// (unparseable or oversized lines, messages missing uuid / sessionId /
+/// timestamp) found while scanning, so the UI can flag projects with broken files.
+#[tauri::command]
+pub async fn load_project_sessions_verbose(
+    project_path: String,
+    exclude_sidechain: Option<bool>,
+    include_noise: Option<bool>,
+    max_line_bytes: Option<usize>,
+) -> Result<ProjectSessionsReport, String> {
+    let exclude = exclude_sidechain.unwrap_or(false);
+    let noise = include_noise.unwrap_or(false);
+    let max_line_bytes = max_line_bytes.unwrap_or(DEFAULT_MAX_LINE_BYTES);
+
+    let file_entries = project_session_entries(Path::new(&project_path));
+
+    let results: Vec<(Option<ClaudeSession>, Vec<SessionParseWarning>)> = file_entries
         .par_iter()
-        .filter_map(|entry| process_session_file(entry, exclude, noise))
+        .map(|entry| {
+            let mut warnings = Vec::new();
+            let session = process_session_file_with_warnings(
+                entry,
+                exclude,
+                noise,
+                max_line_bytes,
+                Some(&mut warnings),
+            );
+            (session, warnings)
+        })
         .collect();
 
+    let mut sessions = Vec::new();
+    let mut warnings = Vec::new();
+    for (session, file_warnings) in results {
+        sessions.extend(session);
+        warnings.extend(file_warnings);
+    }
+    warnings.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+
+    finalize_project_sessions(&mut sessions, Utc::now());
+    Ok(ProjectSessionsReport { sessions, warnings })
+}
+
+/// Set a session's `activity_score` from its counts and last activity
+/// (the file mtime when no message has a timestamp).
+fn score_session(session: &mut ClaudeSession, now: DateTime<Utc>) {
+    let last_activity = if session.last_message_time.is_empty() {
+        &session.last_modified
+    } else {
+        &session.last_message_time
+    };
+    session.activity_score = activity_score(
+        last_activity,
+        session.message_count,
+        session.tool_use_count,
+        now,
+    );
+}
+
+/// Score sessions against `now`, sort them newest first and share summaries
+/// between sessions of the same conversation. Scoring here rather than only
+/// at parse time keeps cached sessions comparable with freshly parsed ones.
+fn finalize_project_sessions(sessions: &mut [ClaudeSession], now: DateTime<Utc>) {
+    for session in sessions.iter_mut() {
+        score_session(session, now);
+    }
     sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
 
     // Summary propagation logic:
@@ -469,7 +986,7 @@ pub async fn load_project_sessions(
         std::collections::HashMap::new();
 
     // First pass: collect all summaries
-    for session in &sessions {
+    for session in sessions.iter() {
         if let Some(ref summary) = session.summary {
             if !summary.is_empty() {
                 summary_map.insert(session.actual_session_id.clone(), summary.clone());
@@ -478,7 +995,7 @@ pub async fn load_project_sessions(
     }
 
     // Second pass: apply summaries to sessions that don't have them
-    for session in &mut sessions {
+    for session in sessions.iter_mut() {
         if session.summary.is_none()
             || session
                 .summary
@@ -491,20 +1008,96 @@ pub async fn load_project_sessions(
             }
         }
     }
+}
 
-    let _elapsed = start_time.elapsed();
-    #[cfg(debug_assertions)]
-    println!(
-        "load_project_sessions performance: {} sessions loaded in {}ms",
-        sessions.len(),
-        _elapsed.as_millis()
-    );
+/// Derive `(project_id, provider_root)` for a Claude session file.
+///
+/// The project id is the full project directory path, for consistency with
+/// `search_messages`; e.g. "/path/to/.claude/projects/my-project/session.jsonl"
+/// -> "/path/to/.claude/projects/my-project". The provider root is the Claude
+/// folder the `projects` directory lives in; the session file itself is the
+/// messages' `source_id`.
+pub(crate) fn session_project_id_and_root(session_path: &str) -> (String, String) {
+    let project_id = if let Some(projects_idx) = session_path.find("projects") {
+        let after_projects = &session_path[projects_idx + "projects".len()..];
+        let parts: Vec<&str> = after_projects
+            .split(|c| c == '/' || c == '\\')
+            .filter(|s| !s.is_empty())
+            .collect();
+        if !parts.is_empty() {
+            // Reconstruct full path up to project directory
+            let up_to_projects = &session_path[..projects_idx + "projects".len()];
+            normalize_windows_path(&format!("{}/{}", up_to_projects, parts[0]))
+        } else {
+            "unknown".to_string()
+        }
+    } else {
+        "unknown".to_string()
+    };
 
-    Ok(sessions)
+    let provider_root = session_path
+        .split("projects")
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('/')
+        .to_string();
+
+    (project_id, provider_root)
+}
+
+/// Build a `ClaudeMessage` from a parsed non-summary JSONL entry.
+/// `line_idx` (0-based) seeds the fallback uuid when the entry has none.
+fn log_entry_to_claude_message(log_entry: RawLogEntry, line_idx: usize) -> ClaudeMessage {
+    let (role, message_id, model, stop_reason, usage) = if let Some(ref msg) = log_entry.message {
+        (
+            Some(msg.role.clone()),
+            msg.id.clone(),
+            msg.model.clone(),
+            msg.stop_reason.clone(),
+            msg.usage.clone(),
+        )
+    } else {
+        (None, None, None, None, None)
+    };
+
+    let subtype = log_entry.subtype.clone();
+    let system_metadata = build_system_metadata(&log_entry);
+    ClaudeMessage {
+        uuid: log_entry
+            .uuid
+            .unwrap_or_else(|| format!("{}-line-{}", Uuid::new_v4(), line_idx + 1)),
+        parent_uuid: log_entry.parent_uuid,
+        session_id: log_entry
+            .session_id
+            .unwrap_or_else(|| "unknown-session".to_string()),
+        timestamp: log_entry
+            .timestamp
+            .unwrap_or_else(|| Utc::now().to_rfc3339()),
+        message_type: log_entry.message_type.clone(),
+        content: log_entry.message.map(|m| m.content),
+        tool_use: log_entry.tool_use,
+        tool_use_result: log_entry.tool_use_result,
+        is_sidechain: log_entry.is_sidechain,
+        usage,
+        role,
+        message_id,
+        model,
+        stop_reason,
+        project_path: None,
+        subtype,
+        system_metadata,
+    }
 }
 
 #[tauri::command]
-pub async fn load_session_messages(session_path: String, include_noise: Option<bool>) -> Result<Vec<UniversalMessage>, String> {
+pub async fn load_session_messages(
+    session_path: String,
+    include_noise: Option<bool>,
+    max_content_bytes: Option<usize>,
+    parse_mode: Option<ParseMode>,
+) -> Result<Vec<UniversalMessage>, String> {
+    let strict = parse_mode.unwrap_or_default() == ParseMode::Strict;
+
     // Use memory-mapped I/O for zero-copy file access (faster than read_to_string for large files)
     let file = fs::File::open(&session_path)
         .map_err(|e| format!("SESSION_READ_ERROR: Failed to open session file: {}", e))?;
@@ -518,19 +1111,20 @@ pub async fn load_session_messages(session_path: String, include_noise: Option<b
         return Ok(Vec::new());
     }
 
-    // SAFETY: The file is opened read-only. We hold the File handle for the duration
-    // of processing. The file content is treated as immutable bytes.
-    let mmap = unsafe {
-        Mmap::map(&file)
-            .map_err(|e| format!("SESSION_READ_ERROR: Failed to memory-map session file: {}", e))?
-    };
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let mmap = read_session_bytes(&file, std::path::Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
 
     // Use SIMD-accelerated line splitting
     let line_ranges = find_line_ranges(&mmap);
     let mut messages = Vec::new();
 
     for (line_num, &(start, end)) in line_ranges.iter().enumerate() {
-        let line = std::str::from_utf8(&mmap[start..end]).unwrap_or_default();
+        let line = match std::str::from_utf8(&mmap[start..end]) {
+            Ok(line) => line,
+            Err(e) if strict => return Err(malformed_line_error(&session_path, line_num, e)),
+            Err(_) => continue,
+        };
         if line.trim().is_empty() {
             continue;
         }
@@ -642,6 +1236,7 @@ pub async fn load_session_messages(session_path: String, include_noise: Option<b
                     messages.push(claude_message);
                 }
             }
+            Err(e) if strict => return Err(malformed_line_error(&session_path, line_num, e)),
             Err(e) => {
                 eprintln!(
                     "Failed to parse line {} in {}: {}. Line: {}",
@@ -655,54 +1250,247 @@ pub async fn load_session_messages(session_path: String, include_noise: Option<b
     }
 
     // Convert ClaudeMessages to UniversalMessages
-    // Extract full project path from session path for consistency with search_messages
-    // E.g., "/path/to/.claude/projects/my-project/session.jsonl" -> "/path/to/.claude/projects/my-project"
-    let project_id = if let Some(projects_idx) = session_path.find("projects") {
-        let after_projects = &session_path[projects_idx + "projects".len()..];
-        let parts: Vec<&str> = after_projects
-            .split(|c| c == '/' || c == '\\')
-            .filter(|s| !s.is_empty())
-            .collect();
-        if !parts.is_empty() {
-            // Reconstruct full path up to project directory
-            let up_to_projects = &session_path[..projects_idx + "projects".len()];
-            normalize_windows_path(&format!("{}/{}", up_to_projects, parts[0]))
-        } else {
-            "unknown".to_string()
-        }
-    } else {
-        "unknown".to_string()
-    };
+    let (project_id, provider_root) = session_project_id_and_root(&session_path);
 
-    let source_id = session_path
-        .split("projects")
-        .next()
-        .unwrap_or("")
-        .trim_end_matches('/')
-        .to_string();
-
-    let universal_messages: Vec<UniversalMessage> = messages
+    let mut universal_messages: Vec<UniversalMessage> = messages
         .iter()
         .enumerate()
         .map(|(i, msg)| {
-            claude_message_to_universal(msg, project_id.clone(), source_id.clone(), i as i32)
+            let mut universal = claude_message_to_universal(
+                msg,
+                project_id.clone(),
+                session_path.clone(),
+                i as i32,
+            );
+            universal.provider_root = Some(provider_root.clone());
+            universal
         })
         .collect();
 
+    if let Some(max_bytes) = max_content_bytes {
+        for msg in universal_messages.iter_mut() {
+            cap_message_content(msg, max_bytes);
+        }
+    }
+
     Ok(universal_messages)
 }
 
+/// Like `load_session_messages`, keeping only the messages whose content
+/// passes `filter` (e.g. "only messages with code").
 #[tauri::command]
-pub async fn load_session_messages_paginated(
+pub async fn load_session_messages_filtered(
     session_path: String,
-    offset: usize,
-    limit: usize,
-    exclude_sidechain: Option<bool>,
-    include_noise: Option<bool>,
-) -> Result<MessagePage, String> {
+    filter: MessageContentFilter,
+) -> Result<Vec<UniversalMessage>, String> {
+    let mut messages = load_session_messages(session_path, None, None, None).await?;
+    messages.retain(|msg| passes_content_filter(msg, &filter));
+    Ok(messages)
+}
+
+/// Text and code of a message's content items, joined by newlines.
+fn message_text(msg: &UniversalMessage) -> String {
+    msg.content
+        .iter()
+        .filter_map(|item| match item.content_type {
+            ContentType::Text => item.data.get("text").and_then(|t| t.as_str()),
+            ContentType::Code => item.data.get("code").and_then(|c| c.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn passes_content_filter(msg: &UniversalMessage, filter: &MessageContentFilter) -> bool {
+    if filter.role.as_ref().is_some_and(|role| *role != msg.role) {
+        return false;
+    }
+
+    if let Some(wanted) = filter.has_tool_use {
+        let has_tool_use = msg
+            .tool_calls
+            .as_ref()
+            .is_some_and(|calls| !calls.is_empty())
+            || msg
+                .content
+                .iter()
+                .any(|item| item.content_type == ContentType::ToolUse);
+        if has_tool_use != wanted {
+            return false;
+        }
+    }
+
+    let text = message_text(msg);
+    if let Some(min_length) = filter.min_length {
+        if text.chars().count() < min_length {
+            return false;
+        }
+    }
+
+    if let Some(wanted) = filter.has_code_block {
+        let has_code = msg
+            .content
+            .iter()
+            .any(|item| item.content_type == ContentType::Code)
+            || !find_fenced_code_blocks(&text).is_empty();
+        if has_code != wanted {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[tauri::command]
+pub async fn load_session_messages_paginated(
+    session_path: String,
+    offset: usize,
+    limit: usize,
+    exclude_sidechain: Option<bool>,
+    include_noise: Option<bool>,
+    max_content_bytes: Option<usize>,
+    parse_mode: Option<ParseMode>,
+) -> Result<MessagePage, String> {
+    let options = PageOptions {
+        exclude_sidechain: exclude_sidechain.unwrap_or(false),
+        include_noise: include_noise.unwrap_or(false),
+        max_content_bytes,
+        omit_tool_results: false,
+        parse_mode: parse_mode.unwrap_or_default(),
+    };
+    load_message_page(&session_path, offset, limit, &options)
+}
+
+/// Like `load_session_messages_paginated`, but every `tool_use_result`, and
+/// the `content` of every `tool_result` block, is replaced by a
+/// `{ omitted: true, size }` placeholder (`size` = serialized bytes).
+/// Tool-heavy sessions render much faster this way; the full result is
+/// fetched on demand with `get_tool_result`.
+#[tauri::command]
+pub async fn load_session_messages_light(
+    session_path: String,
+    offset: usize,
+    limit: usize,
+) -> Result<MessagePage, String> {
+    let options = PageOptions {
+        omit_tool_results: true,
+        ..PageOptions::default()
+    };
+    load_message_page(&session_path, offset, limit, &options)
+}
+
+/// Fetch a result omitted by `load_session_messages_light` from the message
+/// with `uuid`: the `content` of its `tool_result` block for `tool_use_id`
+/// when given, otherwise its full `toolUseResult`.
+#[tauri::command]
+pub async fn get_tool_result(
+    session_path: String,
+    uuid: String,
+    tool_use_id: Option<String>,
+) -> Result<serde_json::Value, String> {
+    use std::io::BufRead;
+
+    let reader = open_session_reader(Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line =
+            line.map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+        let line = if idx == 0 { strip_bom(&line) } else { &line };
+        // Cheap substring probe before a full parse
+        if !line.contains(uuid.as_str()) {
+            continue;
+        }
+        let Ok(log_entry) = serde_json::from_str::<RawLogEntry>(line) else {
+            continue;
+        };
+        if log_entry.uuid.as_deref() != Some(uuid.as_str()) {
+            continue;
+        }
+
+        let result = match tool_use_id {
+            Some(ref tool_use_id) => log_entry.message.and_then(|message| {
+                tool_result_blocks(message.content)
+                    .find(|block| block["tool_use_id"] == tool_use_id.as_str())
+                    .and_then(|mut block| block.get_mut("content").map(serde_json::Value::take))
+            }),
+            None => log_entry.tool_use_result,
+        };
+        return result
+            .ok_or_else(|| format!("TOOL_RESULT_NOT_FOUND: Message {} has no tool result", uuid));
+    }
+
+    Err(format!(
+        "MESSAGE_NOT_FOUND: Message {} not found in {}",
+        uuid, session_path
+    ))
+}
+
+/// `tool_result` blocks of a message's content array.
+fn tool_result_blocks(content: serde_json::Value) -> impl Iterator<Item = serde_json::Value> {
+    let blocks = match content {
+        serde_json::Value::Array(blocks) => blocks,
+        _ => Vec::new(),
+    };
+    blocks
+        .into_iter()
+        .filter(|block| block["type"] == "tool_result")
+}
+
+/// `{ omitted: true, size }` placeholder for a tool result.
+fn omitted_tool_result(result: &serde_json::Value) -> serde_json::Value {
+    let size = serde_json::to_string(result).map(|s| s.len()).unwrap_or(0);
+    serde_json::json!({ "omitted": true, "size": size })
+}
+
+/// Replace a message's `tool_use_result`, and the `content` of its
+/// `tool_result` blocks, with `{ omitted: true, size }` placeholders.
+fn omit_tool_results(msg: &mut ClaudeMessage) {
+    if let Some(result) = msg.tool_use_result.take() {
+        msg.tool_use_result = Some(omitted_tool_result(&result));
+    }
+    if let Some(serde_json::Value::Array(blocks)) = msg.content.as_mut() {
+        for block in blocks.iter_mut().filter(|b| b["type"] == "tool_result") {
+            if let Some(content) = block.get_mut("content") {
+                *content = omitted_tool_result(content);
+            }
+        }
+    }
+}
+
+/// Error for the first malformed line of a session in `ParseMode::Strict`.
+fn malformed_line_error(session_path: &str, line_idx: usize, e: impl std::fmt::Display) -> String {
+    format!(
+        "SESSION_PARSE_ERROR: Malformed JSONL at line {} of {}: {}",
+        line_idx + 1,
+        session_path,
+        e
+    )
+}
+
+/// Options of [`load_message_page`].
+#[derive(Default)]
+struct PageOptions {
+    exclude_sidechain: bool,
+    include_noise: bool,
+    max_content_bytes: Option<usize>,
+    omit_tool_results: bool,
+    parse_mode: ParseMode,
+}
+
+/// Shared two-pass pagination behind the paginated loaders.
+fn load_message_page(
+    session_path: &str,
+    offset: usize,
+    limit: usize,
+    options: &PageOptions,
+) -> Result<MessagePage, String> {
     let start_time = std::time::Instant::now();
+    let exclude = options.exclude_sidechain;
+    let noise = options.include_noise;
+    let strict = options.parse_mode == ParseMode::Strict;
 
-    let file = fs::File::open(&session_path)
+    let file = fs::File::open(session_path)
         .map_err(|e| format!("SESSION_FILE_ERROR: Failed to open session file: {}", e))?;
 
     let metadata = file
@@ -719,24 +1507,23 @@ pub async fn load_session_messages_paginated(
         });
     }
 
-    // Use memory-mapped I/O for zero-copy access
-    let mmap = unsafe {
-        Mmap::map(&file)
-            .map_err(|e| format!("SESSION_READ_ERROR: Failed to memory-map session file: {}", e))?
-    };
+    // Use memory-mapped I/O for zero-copy access (`.jsonl.gz` is decompressed)
+    let mmap = read_session_bytes(&file, std::path::Path::new(session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
 
     // Use SIMD-accelerated line splitting
     let line_ranges = find_line_ranges(&mmap);
 
-    let exclude = exclude_sidechain.unwrap_or(false);
-    let noise = include_noise.unwrap_or(false);
-
     // === PASS 1: Lightweight scan for pagination ===
     // Only parse 4 fields per line to determine which lines are valid displayable messages.
     let mut valid_line_indices: Vec<usize> = Vec::with_capacity(line_ranges.len());
 
     for (line_idx, &(start, end)) in line_ranges.iter().enumerate() {
-        let line = std::str::from_utf8(&mmap[start..end]).unwrap_or_default();
+        let line = match std::str::from_utf8(&mmap[start..end]) {
+            Ok(line) => line,
+            Err(e) if strict => return Err(malformed_line_error(session_path, line_idx, e)),
+            Err(_) => continue,
+        };
         if line.trim().is_empty() {
             continue;
         }
@@ -757,6 +1544,7 @@ pub async fn load_session_messages_paginated(
                 }
                 valid_line_indices.push(line_idx);
             }
+            Err(e) if strict => return Err(malformed_line_error(session_path, line_idx, e)),
             Err(_e) => {
                 #[cfg(debug_assertions)]
                 eprintln!(
@@ -825,29 +1613,7 @@ pub async fn load_session_messages_paginated(
     };
 
     // === PASS 2: Full parse only the messages in the requested page ===
-    // Extract project_id and source_id from session path (same logic as before)
-    let project_id = if let Some(projects_idx) = session_path.find("projects") {
-        let after_projects = &session_path[projects_idx + "projects".len()..];
-        let parts: Vec<&str> = after_projects
-            .split(|c| c == '/' || c == '\\')
-            .filter(|s| !s.is_empty())
-            .collect();
-        if !parts.is_empty() {
-            let up_to_projects = &session_path[..projects_idx + "projects".len()];
-            normalize_windows_path(&format!("{}/{}", up_to_projects, parts[0]))
-        } else {
-            "unknown".to_string()
-        }
-    } else {
-        "unknown".to_string()
-    };
-
-    let source_id = session_path
-        .split("projects")
-        .next()
-        .unwrap_or("")
-        .trim_end_matches('/')
-        .to_string();
+    let (project_id, provider_root) = session_project_id_and_root(session_path);
 
     let page_indices = &valid_line_indices[start_idx..end_idx];
     let mut messages: Vec<UniversalMessage> = Vec::with_capacity(page_indices.len());
@@ -858,57 +1624,26 @@ pub async fn load_session_messages_paginated(
 
         match serde_json::from_str::<RawLogEntry>(line) {
             Ok(log_entry) => {
-                let (role, message_id, model, stop_reason, usage) =
-                    if let Some(ref msg) = log_entry.message {
-                        (
-                            Some(msg.role.clone()),
-                            msg.id.clone(),
-                            msg.model.clone(),
-                            msg.stop_reason.clone(),
-                            msg.usage.clone(),
-                        )
-                    } else {
-                        (None, None, None, None, None)
-                    };
-
-                let subtype = log_entry.subtype.clone();
-                let system_metadata = build_system_metadata(&log_entry);
-                let claude_message = ClaudeMessage {
-                    uuid: log_entry.uuid.unwrap_or_else(|| {
-                        format!("{}-line-{}", Uuid::new_v4().to_string(), line_idx + 1)
-                    }),
-                    parent_uuid: log_entry.parent_uuid,
-                    session_id: log_entry
-                        .session_id
-                        .unwrap_or_else(|| "unknown-session".to_string()),
-                    timestamp: log_entry
-                        .timestamp
-                        .unwrap_or_else(|| Utc::now().to_rfc3339()),
-                    message_type: log_entry.message_type.clone(),
-                    content: log_entry.message.map(|m| m.content),
-                    tool_use: log_entry.tool_use,
-                    tool_use_result: log_entry.tool_use_result,
-                    is_sidechain: log_entry.is_sidechain,
-                    usage,
-                    role,
-                    message_id,
-                    model,
-                    stop_reason,
-                    project_path: None,
-                    subtype,
-                    system_metadata,
-                };
+                let mut claude_message = log_entry_to_claude_message(log_entry, line_idx);
+                if options.omit_tool_results {
+                    omit_tool_results(&mut claude_message);
+                }
 
                 // sequence_number reflects global position, not local index
                 let seq = (start_idx + i) as i32;
-                let universal = claude_message_to_universal(
+                let mut universal = claude_message_to_universal(
                     &claude_message,
                     project_id.clone(),
-                    source_id.clone(),
+                    session_path.to_string(),
                     seq,
                 );
+                universal.provider_root = Some(provider_root.clone());
+                if let Some(max_bytes) = options.max_content_bytes {
+                    cap_message_content(&mut universal, max_bytes);
+                }
                 messages.push(universal);
             }
+            Err(e) if strict => return Err(malformed_line_error(session_path, line_idx, e)),
             Err(_e) => {
                 #[cfg(debug_assertions)]
                 eprintln!(
@@ -928,7 +1663,7 @@ pub async fn load_session_messages_paginated(
     let _elapsed = start_time.elapsed();
     #[cfg(debug_assertions)]
     eprintln!(
-        "load_session_messages_paginated performance: {} messages loaded (of {} total) in {}ms",
+        "load_message_page performance: {} messages loaded (of {} total) in {}ms",
         messages.len(),
         total_count,
         _elapsed.as_millis()
@@ -942,18 +1677,333 @@ pub async fn load_session_messages_paginated(
     })
 }
 
+/// Fetch one content item of a Claude session message without any size cap.
+///
+/// Companion to the `max_content_bytes` option of the loaders: when a content
+/// item comes back with `truncated: true`, the viewer calls this with the
+/// message id and the item's index in `content` to get the full data.
+#[tauri::command]
+pub async fn get_full_content(
+    session_path: String,
+    message_id: String,
+    content_index: usize,
+) -> Result<UniversalContent, String> {
+    let content = read_session_to_string(Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+
+    for (line_idx, line) in strip_bom(&content).lines().enumerate() {
+        // Cheap substring probe before a full parse
+        if !line.contains(message_id.as_str()) {
+            continue;
+        }
+        let Ok(log_entry) = serde_json::from_str::<RawLogEntry>(line) else {
+            continue;
+        };
+        if log_entry.uuid.as_deref() != Some(message_id.as_str()) {
+            continue;
+        }
+
+        let (project_id, _) = session_project_id_and_root(&session_path);
+        let claude_message = log_entry_to_claude_message(log_entry, line_idx);
+        let universal =
+            claude_message_to_universal(&claude_message, project_id, session_path.clone(), 0);
+        return universal
+            .content
+            .into_iter()
+            .nth(content_index)
+            .ok_or_else(|| {
+                format!(
+                    "CONTENT_NOT_FOUND: Message {} has no content item at index {}",
+                    message_id, content_index
+                )
+            });
+    }
+
+    Err(format!(
+        "MESSAGE_NOT_FOUND: Message {} not found in {}",
+        message_id, session_path
+    ))
+}
+
+/// Parent/child structure of a session's messages, shared by
+/// [`replay_order`] and [`build_thread`] so both treat branches, orphans and
+/// parent cycles alike. Iterative throughout, since a linear conversation is
+/// a chain as deep as the session is long.
+struct MessageTree {
+    /// Replies of each message, oldest first.
+    children: Vec<Vec<usize>>,
+    /// Messages without a parent, oldest first.
+    roots: Vec<usize>,
+    /// Messages whose parent is not in the session, or that only hang off a
+    /// parent cycle, oldest first; each starts its own traversal.
+    detached: Vec<usize>,
+    /// Every message once as `(index, depth)`, depth-first pre-order: from
+    /// each root (depth 0), then from each detached message (depth 1).
+    order: Vec<(usize, i32)>,
+}
+
+impl MessageTree {
+    /// `links` holds each message's `(id, parent id, timestamp)`.
+    fn new(links: &[(&str, Option<&str>, &str)]) -> Self {
+        let sort_keys: Vec<(Option<DateTime<Utc>>, usize)> = links
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, _, timestamp))| {
+                let ts = DateTime::parse_from_rfc3339(timestamp)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc));
+                (ts, idx)
+            })
+            .collect();
+        let index_by_id: std::collections::HashMap<&str, usize> = links
+            .iter()
+            .enumerate()
+            .map(|(idx, (id, _, _))| (*id, idx))
+            .collect();
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); links.len()];
+        let mut roots: Vec<usize> = Vec::new();
+        for (idx, (_, parent, _)) in links.iter().enumerate() {
+            match parent.map(|p| index_by_id.get(p)) {
+                None => roots.push(idx),
+                Some(Some(&parent_idx)) if parent_idx != idx => children[parent_idx].push(idx),
+                // Unknown or self parent: detached, found by the sweep below
+                Some(_) => {}
+            }
+        }
+        roots.sort_by_key(|&idx| sort_keys[idx]);
+        for siblings in &mut children {
+            siblings.sort_by_key(|&idx| sort_keys[idx]);
+        }
+
+        // Depth-first pre-order from the roots, then from every message not
+        // reached yet, oldest first, one level deeper
+        let mut by_time: Vec<usize> = (0..links.len()).collect();
+        by_time.sort_by_key(|&idx| sort_keys[idx]);
+        let mut visited = vec![false; links.len()];
+        let mut order: Vec<(usize, i32)> = Vec::with_capacity(links.len());
+        let mut detached: Vec<usize> = Vec::new();
+        let starts = roots
+            .iter()
+            .map(|&idx| (idx, 0))
+            .chain(by_time.iter().map(|&idx| (idx, 1)));
+        for (start, start_depth) in starts {
+            if visited[start] {
+                continue;
+            }
+            if start_depth == 1 {
+                detached.push(start);
+            }
+            let mut stack = vec![(start, start_depth)];
+            while let Some((idx, depth)) = stack.pop() {
+                if visited[idx] {
+                    continue;
+                }
+                visited[idx] = true;
+                order.push((idx, depth));
+                stack.extend(children[idx].iter().rev().map(|&child| (child, depth + 1)));
+            }
+        }
+
+        Self {
+            children,
+            roots,
+            detached,
+            order,
+        }
+    }
+}
+
+/// Order messages along their `parentUuid` links: depth-first from each root,
+/// visiting siblings (branches) oldest first. Messages whose parent is not in
+/// the session, or that only hang off a parent cycle, follow in timestamp
+/// order (see [`MessageTree`]).
+fn replay_order(messages: Vec<ClaudeMessage>) -> Vec<ClaudeMessage> {
+    let links: Vec<(&str, Option<&str>, &str)> = messages
+        .iter()
+        .map(|msg| {
+            (
+                msg.uuid.as_str(),
+                msg.parent_uuid.as_deref(),
+                msg.timestamp.as_str(),
+            )
+        })
+        .collect();
+    let tree = MessageTree::new(&links);
+
+    let mut slots: Vec<Option<ClaudeMessage>> = messages.into_iter().map(Some).collect();
+    tree.order
+        .into_iter()
+        .filter_map(|(idx, _)| slots[idx].take())
+        .collect()
+}
+
+/// Load a session in conversation order for "play through" mode: messages
+/// follow the `parentUuid` chain from the root rather than file order, which
+/// can be out of order after edits and branches. Summaries and noise entries
+/// are left out of the result but still link the chain.
+#[tauri::command]
+pub async fn load_session_for_replay(session_path: String) -> Result<Vec<ClaudeMessage>, String> {
+    let content = read_session_to_string(Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+
+    let mut messages = Vec::new();
+    for (line_idx, line) in strip_bom(&content).lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(log_entry) = serde_json::from_str::<RawLogEntry>(line) else {
+            continue;
+        };
+        if log_entry.message_type == "summary" {
+            continue;
+        }
+        messages.push(log_entry_to_claude_message(log_entry, line_idx));
+    }
+
+    Ok(replay_order(messages)
+        .into_iter()
+        .filter(|msg| !is_noise_message_type(&msg.message_type))
+        .collect())
+}
+
+/// A session's messages as a tree of `parentUuid` links: one root node per
+/// conversation start, with each message's replies (several when the
+/// conversation branched) as children, oldest first. Messages whose parent is
+/// not in the session (or that only hang off a parent cycle) are collected
+/// under a trailing synthetic root with no message. Noise entries are left
+/// out, but links through them are kept. Each message's `depth` is set to
+/// its depth in the tree (roots 0, orphans 1).
+///
+/// The response is the JSON of a `Vec<ThreadNode>`, serialized by
+/// [`serialize_thread`] because a long conversation nests too deep for the
+/// async runtime's stack.
+#[tauri::command]
+pub async fn get_session_thread(session_path: String) -> Result<tauri::ipc::Response, String> {
+    let messages = load_session_messages(session_path, Some(true), None, None).await?;
+    serialize_thread(build_thread(messages)).map(tauri::ipc::Response::new)
+}
+
+/// Stack reserved per tree level when serializing a thread (debug builds
+/// use a few hundred bytes per level; this leaves ample headroom).
+const THREAD_STACK_PER_LEVEL: usize = 8 * 1024;
+
+/// Serialize and drop `tree` on a thread whose stack grows with the tree's
+/// depth: serde and `Drop` both recurse once per level, and a linear
+/// conversation is a chain as deep as the session is long.
+fn serialize_thread(tree: Vec<ThreadNode>) -> Result<String, String> {
+    let mut depth = 0;
+    let mut pending: Vec<(&ThreadNode, usize)> = tree.iter().map(|node| (node, 1)).collect();
+    while let Some((node, level)) = pending.pop() {
+        depth = depth.max(level);
+        pending.extend(node.children.iter().map(|child| (child, level + 1)));
+    }
+
+    std::thread::Builder::new()
+        .name("session-thread-json".to_string())
+        .stack_size((8 << 20) + depth * THREAD_STACK_PER_LEVEL)
+        .spawn(move || serde_json::to_string(&tree))
+        .map_err(|e| format!("SESSION_THREAD_ERROR: Failed to start serializer: {}", e))?
+        .join()
+        .map_err(|_| "SESSION_THREAD_ERROR: Serializer panicked".to_string())?
+        .map_err(|e| format!("SESSION_THREAD_ERROR: Failed to serialize thread: {}", e))
+}
+
+/// Tree construction for [`get_session_thread`], on top of [`MessageTree`].
+fn build_thread(messages: Vec<UniversalMessage>) -> Vec<ThreadNode> {
+    let is_noise = |msg: &UniversalMessage| {
+        msg.provider_metadata
+            .get("original_type")
+            .and_then(|t| t.as_str())
+            .is_some_and(is_noise_message_type)
+    };
+
+    // Parent links of dropped noise entries, to re-attach their replies
+    let noise_parents: std::collections::HashMap<String, Option<String>> = messages
+        .iter()
+        .filter(|msg| is_noise(msg))
+        .map(|msg| (msg.id.clone(), msg.parent_id.clone()))
+        .collect();
+    let mut messages: Vec<UniversalMessage> =
+        messages.into_iter().filter(|msg| !is_noise(msg)).collect();
+    let parents: Vec<Option<String>> = messages
+        .iter()
+        .map(|msg| {
+            let mut parent = msg.parent_id.clone();
+            // Bounded walk: noise links could form a cycle
+            for _ in 0..=noise_parents.len() {
+                match parent.as_ref().and_then(|p| noise_parents.get(p)) {
+                    Some(grandparent) => parent = grandparent.clone(),
+                    None => break,
+                }
+            }
+            parent
+        })
+        .collect();
+
+    let links: Vec<(&str, Option<&str>, &str)> = messages
+        .iter()
+        .zip(&parents)
+        .map(|(msg, parent)| (msg.id.as_str(), parent.as_deref(), msg.timestamp.as_str()))
+        .collect();
+    let MessageTree {
+        children,
+        roots,
+        detached,
+        order,
+    } = MessageTree::new(&links);
+    for &(idx, depth) in &order {
+        messages[idx].depth = Some(depth);
+    }
+
+    // Assemble bottom-up: in reverse pre-order every child is built before
+    // its parent. A child already claimed by another path stays where it is.
+    let mut slots: Vec<Option<UniversalMessage>> = messages.into_iter().map(Some).collect();
+    let mut built: Vec<Option<ThreadNode>> = (0..slots.len()).map(|_| None).collect();
+    for &(idx, _) in order.iter().rev() {
+        let node_children = children[idx]
+            .iter()
+            .filter_map(|&child| built[child].take())
+            .collect();
+        built[idx] = Some(ThreadNode {
+            message: slots[idx].take(),
+            children: node_children,
+        });
+    }
+
+    let mut tree: Vec<ThreadNode> = roots.iter().filter_map(|&idx| built[idx].take()).collect();
+    let orphans: Vec<ThreadNode> = detached
+        .iter()
+        .filter_map(|&idx| built[idx].take())
+        .collect();
+    if !orphans.is_empty() {
+        tree.push(ThreadNode {
+            message: None,
+            children: orphans,
+        });
+    }
+    tree
+}
+
+/// Number of displayable messages in a session file. The file is read line
+/// by line, so counting many large sessions at once stays cheap on memory.
 #[tauri::command]
 pub async fn get_session_message_count(
     session_path: String,
     exclude_sidechain: Option<bool>,
     include_noise: Option<bool>,
 ) -> Result<usize, String> {
-    let content = fs::read_to_string(&session_path)
+    use std::io::BufRead;
+
+    let reader = open_session_reader(Path::new(&session_path))
         .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
 
     let mut count = 0;
 
-    for line in content.lines() {
+    for (idx, line) in reader.lines().enumerate() {
+        let line =
+            line.map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+        let line = if idx == 0 { strip_bom(&line) } else { &line };
         if line.trim().is_empty() {
             continue;
         }
@@ -973,41 +2023,143 @@ pub async fn get_session_message_count(
     Ok(count)
 }
 
-/// Parse search query to extract quoted phrases and individual words
-/// Example: `askmeevery "pricing update"` -> [(false, "askmeevery"), (true, "pricing update")]
-fn parse_search_query(query: &str) -> Vec<(bool, String)> {
-    let mut terms = Vec::new();
+/// A parsed search query: a boolean expression over a table of distinct terms.
+///
+/// Each term is `(is_quoted, text)`; leaves of [`SearchExpr`] index into
+/// `terms`, so the terms can be compiled into one [`QueryMatcher`] and every
+/// haystack is scanned only once regardless of how the expression is nested.
+#[derive(Debug, Clone, PartialEq)]
+struct SearchQuery {
+    terms: Vec<(bool, String)>,
+    expr: SearchExpr,
+}
+
+/// Boolean search expression produced by [`parse_search_query`].
+#[derive(Debug, Clone, PartialEq)]
+enum SearchExpr {
+    /// Index into [`SearchQuery::terms`].
+    Term(usize),
+    And(Vec<SearchExpr>),
+    Or(Vec<SearchExpr>),
+    Not(Box<SearchExpr>),
+}
+
+impl SearchExpr {
+    /// Evaluate against the per-term presence flags from
+    /// [`QueryMatcher::matched_patterns`].
+    fn eval(&self, present: &[bool]) -> bool {
+        match self {
+            SearchExpr::Term(idx) => present.get(*idx).copied().unwrap_or(false),
+            SearchExpr::And(items) => items.iter().all(|e| e.eval(present)),
+            SearchExpr::Or(items) => items.iter().any(|e| e.eval(present)),
+            SearchExpr::Not(inner) => !inner.eval(present),
+        }
+    }
+
+    /// True when a match needs at least one term to be *present*. A purely
+    /// exclusionary query (`-test`) would otherwise match nearly every message.
+    fn requires_match(&self) -> bool {
+        match self {
+            SearchExpr::Term(_) => true,
+            SearchExpr::And(items) => items.iter().any(|e| e.requires_match()),
+            SearchExpr::Or(items) => items.iter().all(|e| e.requires_match()),
+            SearchExpr::Not(_) => false,
+        }
+    }
+
+    /// Set `out[idx]` for every term that occurs outside a `NOT`. Only those
+    /// may match fuzzily: `-test` should not also exclude "text".
+    fn mark_positive_terms(&self, negated: bool, out: &mut [bool]) {
+        match self {
+            SearchExpr::Term(idx) => {
+                if !negated {
+                    if let Some(slot) = out.get_mut(*idx) {
+                        *slot = true;
+                    }
+                }
+            }
+            SearchExpr::And(items) | SearchExpr::Or(items) => {
+                for item in items {
+                    item.mark_positive_terms(negated, out);
+                }
+            }
+            SearchExpr::Not(inner) => inner.mark_positive_terms(!negated, out),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum QueryToken {
+    Term(bool, String),
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+/// Split a raw query into terms, `OR`/`NOT` keywords, `-` prefixes and
+/// parentheses.
+fn tokenize_search_query(query: &str) -> Vec<QueryToken> {
+    fn flush_word(word: &mut String, tokens: &mut Vec<QueryToken>) {
+        if word.is_empty() {
+            return;
+        }
+        match word.as_str() {
+            "OR" => tokens.push(QueryToken::Or),
+            "NOT" => tokens.push(QueryToken::Not),
+            // `-term` excludes; `--flag` and a lone `-` stay literal
+            w if w.len() > 1 && w.starts_with('-') && !w[1..].starts_with('-') => {
+                tokens.push(QueryToken::Not);
+                tokens.push(QueryToken::Term(false, w[1..].to_string()));
+            }
+            w => tokens.push(QueryToken::Term(false, w.to_string())),
+        }
+        word.clear();
+    }
+
+    /// Flush before a quote or `(`; a bare `-` right before it negates the group.
+    fn flush_before_group(word: &mut String, tokens: &mut Vec<QueryToken>) {
+        if word == "-" {
+            tokens.push(QueryToken::Not);
+            word.clear();
+        } else {
+            flush_word(word, tokens);
+        }
+    }
+
+    let mut tokens = Vec::new();
     let mut current_term = String::new();
     let mut in_quotes = false;
-    let mut chars = query.chars().peekable();
 
-    while let Some(ch) = chars.next() {
+    for ch in query.chars() {
         match ch {
             // Handle both regular quotes and smart quotes (curly quotes)
             '"' | '“' | '”' | '\'' | '‘' | '’' => {
                 if in_quotes {
                     // End of quoted phrase
-                    if !current_term.is_empty() {
-                        terms.push((true, current_term.clone())); // true = quoted
-                        current_term.clear();
+                    if !current_term.trim().is_empty() {
+                        tokens.push(QueryToken::Term(true, current_term.clone()));
                     }
+                    current_term.clear();
                     in_quotes = false;
                 } else {
                     // Start of quoted phrase
                     // Save any accumulated unquoted term first
-                    if !current_term.is_empty() {
-                        terms.push((false, current_term.trim().to_string()));
-                        current_term.clear();
-                    }
+                    flush_before_group(&mut current_term, &mut tokens);
                     in_quotes = true;
                 }
             }
-            ' ' if !in_quotes => {
-                // Space outside quotes - word boundary
-                if !current_term.is_empty() {
-                    terms.push((false, current_term.trim().to_string()));
-                    current_term.clear();
-                }
+            '(' if !in_quotes => {
+                flush_before_group(&mut current_term, &mut tokens);
+                tokens.push(QueryToken::Open);
+            }
+            ')' if !in_quotes => {
+                flush_word(&mut current_term, &mut tokens);
+                tokens.push(QueryToken::Close);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                // Whitespace outside quotes - word boundary
+                flush_word(&mut current_term, &mut tokens);
             }
             _ => {
                 current_term.push(ch);
@@ -1015,13 +2167,160 @@ fn parse_search_query(query: &str) -> Vec<(bool, String)> {
         }
     }
 
-    // Add any remaining term
-    if !current_term.is_empty() {
-        terms.push((in_quotes, current_term.trim().to_string()));
+    // An unterminated quote still counts as a phrase
+    if in_quotes {
+        let phrase = current_term.trim();
+        if !phrase.is_empty() {
+            tokens.push(QueryToken::Term(true, phrase.to_string()));
+        }
+    } else {
+        flush_word(&mut current_term, &mut tokens);
+    }
+
+    tokens
+}
+
+/// Recursive-descent parser over [`QueryToken`]s; see [`parse_search_query`].
+struct QueryParser<'a> {
+    tokens: &'a [QueryToken],
+    pos: usize,
+    terms: Vec<(bool, String)>,
+}
+
+impl<'a> QueryParser<'a> {
+    fn collapse(
+        mut items: Vec<SearchExpr>,
+        wrap: fn(Vec<SearchExpr>) -> SearchExpr,
+    ) -> Option<SearchExpr> {
+        match items.len() {
+            0 => None,
+            1 => items.pop(),
+            _ => Some(wrap(items)),
+        }
+    }
+
+    /// Register a term, reusing the slot of an identical (case-insensitive) one.
+    fn intern(&mut self, quoted: bool, text: &str) -> usize {
+        if let Some(idx) = self
+            .terms
+            .iter()
+            .position(|(q, t)| *q == quoted && t.eq_ignore_ascii_case(text))
+        {
+            return idx;
+        }
+        self.terms.push((quoted, text.to_string()));
+        self.terms.len() - 1
+    }
+
+    /// `or := and ("OR" and)*`
+    fn parse_or(&mut self) -> Option<SearchExpr> {
+        let mut branches = Vec::new();
+        loop {
+            if let Some(expr) = self.parse_and() {
+                branches.push(expr);
+            }
+            match self.tokens.get(self.pos) {
+                Some(QueryToken::Or) => self.pos += 1,
+                _ => break,
+            }
+        }
+        Self::collapse(branches, SearchExpr::Or)
+    }
+
+    /// `and := primary+`
+    fn parse_and(&mut self) -> Option<SearchExpr> {
+        let mut items = Vec::new();
+        while let Some(token) = self.tokens.get(self.pos) {
+            if matches!(token, QueryToken::Or | QueryToken::Close) {
+                break;
+            }
+            if let Some(expr) = self.parse_primary() {
+                items.push(expr);
+            }
+        }
+        Self::collapse(items, SearchExpr::And)
+    }
+
+    /// `primary := ("NOT" | "-") primary | term | "(" or ")"`
+    ///
+    /// Stops without consuming at `OR` / `)` / end of input.
+    fn parse_primary(&mut self) -> Option<SearchExpr> {
+        match self.tokens.get(self.pos)? {
+            QueryToken::Or | QueryToken::Close => None,
+            QueryToken::Not => {
+                self.pos += 1;
+                self.parse_primary()
+                    .map(|expr| SearchExpr::Not(Box::new(expr)))
+            }
+            QueryToken::Open => {
+                self.pos += 1;
+                let expr = self.parse_or();
+                // A missing ")" is closed implicitly at end of input
+                if self.tokens.get(self.pos) == Some(&QueryToken::Close) {
+                    self.pos += 1;
+                }
+                expr
+            }
+            QueryToken::Term(quoted, text) => {
+                self.pos += 1;
+                Some(SearchExpr::Term(self.intern(*quoted, text)))
+            }
+        }
+    }
+}
+
+/// Parse a search query into a boolean expression.
+///
+/// Grammar (lenient — malformed input never errors):
+///
+/// ```text
+/// query   := or
+/// or      := and ("OR" and)*
+/// and     := primary+                 (adjacent terms are implicitly AND-ed)
+/// primary := ("NOT" | "-") primary | word | "quoted phrase" | "(" or ")"
+/// ```
+///
+/// - `OR` and `NOT` must be uppercase; lowercase `or` / `not` are ordinary
+///   search words.
+/// - `-term`, `-"phrase"`, `-(group)` and `NOT term` exclude: a message
+///   containing an excluded term fails the match. `--flag` stays literal.
+/// - Quoted phrases (straight or smart quotes) match as exact substrings.
+/// - Unbalanced parentheses are tolerated: a missing `)` closes at the end of
+///   the query and a stray `)` is ignored. Dangling `OR`s and empty groups are
+///   dropped.
+///
+/// Example: `(error OR panic) "exit code" -test` matches text containing the
+/// phrase `exit code` and at least one of `error` / `panic`, but not `test`.
+///
+/// Returns `None` when the query contains no terms, or only excluded ones.
+fn parse_search_query(query: &str) -> Option<SearchQuery> {
+    let tokens = tokenize_search_query(query);
+    let mut parser = QueryParser {
+        tokens: &tokens,
+        pos: 0,
+        terms: Vec::new(),
+    };
+
+    // Top level: AND together everything, skipping stray ")" tokens
+    let mut parts = Vec::new();
+    loop {
+        if let Some(expr) = parser.parse_or() {
+            parts.push(expr);
+        }
+        if parser.pos >= tokens.len() {
+            break;
+        }
+        parser.pos += 1;
     }
 
-    // Filter out empty strings
-    terms.into_iter().filter(|(_, s)| !s.is_empty()).collect()
+    let expr = QueryParser::collapse(parts, SearchExpr::And)?;
+    if !expr.requires_match() {
+        return None;
+    }
+    Some(SearchQuery {
+        terms: parser.terms,
+        expr,
+    })
 }
 
 /// Normalize quotes in text - converts all smart quotes to regular quotes
@@ -1037,54 +2336,373 @@ fn normalize_quotes(text: &str) -> String {
 
 /// Build an aho-corasick matcher for the parsed search terms.
 ///
-/// Quoted terms become a single contiguous phrase pattern; unquoted terms
-/// contribute one pattern per whitespace-separated word. A haystack matches
-/// only when *every* resulting pattern is present (logical AND), preserving the
-/// previous `matches_search_terms` semantics while avoiding a fresh
-/// `to_lowercase()` allocation of the content on every probe.
-fn build_terms_matcher(terms: &[(bool, String)]) -> QueryMatcher {
-    let mut patterns: Vec<String> = Vec::new();
-    for (is_quoted, term) in terms {
-        if *is_quoted {
-            patterns.push(normalize_quotes(term));
-        } else {
-            for word in term.split_whitespace() {
-                patterns.push(normalize_quotes(word));
+/// Every distinct term of the query becomes one pattern (quoted phrases stay
+/// contiguous), in [`SearchQuery::terms`] order. The matcher reports which
+/// terms are present in a single pass and [`matches_search_terms`] evaluates
+/// the boolean expression over that, avoiding a fresh `to_lowercase()`
+/// allocation of the content on every probe.
+fn build_terms_matcher(query: &SearchQuery) -> QueryMatcher {
+    let patterns: Vec<String> = query
+        .terms
+        .iter()
+        .map(|(_, term)| normalize_quotes(term))
+        .collect();
+    QueryMatcher::from_patterns(&patterns)
+}
+
+/// True when `haystack` satisfies the boolean expression of `query`.
+/// `matcher` must come from [`build_terms_matcher`] for the same query.
+fn matches_search_terms(query: &SearchQuery, matcher: &QueryMatcher, haystack: &str) -> bool {
+    query.expr.eval(&matcher.matched_patterns(haystack))
+}
+
+/// Edit distance used by fuzzy search when the filters do not set one.
+const DEFAULT_MAX_EDIT_DISTANCE: usize = 2;
+
+/// Terms of `query` that may match fuzzily: unquoted words outside a `NOT`.
+/// Quoted phrases always match verbatim.
+fn fuzzy_candidate_terms(query: &SearchQuery) -> Vec<bool> {
+    let mut positive = vec![false; query.terms.len()];
+    query.expr.mark_positive_terms(false, &mut positive);
+    positive
+        .into_iter()
+        .zip(&query.terms)
+        .map(|(positive, (quoted, _))| positive && !quoted)
+        .collect()
+}
+
+/// Typo-tolerant variant of [`matches_search_terms`]. A candidate term (see
+/// [`fuzzy_candidate_terms`]) that is not found verbatim counts as present
+/// when some word of `haystack` is within `max_distance` edits of it.
+///
+/// Returns `None` on no match, otherwise the summed edit distance of the
+/// terms that only matched approximately.
+fn matches_search_terms_fuzzy(
+    query: &SearchQuery,
+    matcher: &QueryMatcher,
+    candidates: &[bool],
+    max_distance: usize,
+    haystack: &str,
+) -> Option<usize> {
+    let mut present = matcher.matched_patterns(haystack);
+    let mut words = None;
+    let mut fuzziness = 0;
+    for (idx, (_, term)) in query.terms.iter().enumerate() {
+        if present[idx] || !candidates[idx] {
+            continue;
+        }
+        let words = words.get_or_insert_with(|| FuzzyWords::new(haystack));
+        if let Some(distance) = words.closest(term, max_distance) {
+            present[idx] = true;
+            fuzziness += distance;
+        }
+    }
+    query.expr.eval(&present).then_some(fuzziness)
+}
+
+/// How `search_messages` interprets its query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SearchMode {
+    /// Case-insensitive boolean term query (see [`parse_search_query`]).
+    #[default]
+    Literal,
+    /// The query is a regular expression, case-sensitive unless it uses `(?i)`.
+    Regex,
+    /// The whole query is one exact-case substring.
+    CaseSensitive,
+}
+
+impl SearchMode {
+    /// Parse the `mode` argument of the search commands; `None` is literal.
+    fn parse(mode: Option<&str>) -> Result<Self, String> {
+        match mode {
+            None | Some("literal") => Ok(SearchMode::Literal),
+            Some("regex") => Ok(SearchMode::Regex),
+            Some("case-sensitive") => Ok(SearchMode::CaseSensitive),
+            Some(other) => Err(format!(
+                "SEARCH_INVALID_ARGUMENT: Unknown search mode '{}' (expected literal, regex or case-sensitive)",
+                other
+            )),
+        }
+    }
+}
+
+/// A query compiled once per search.
+enum SearchPattern {
+    Terms {
+        query: SearchQuery,
+        matcher: QueryMatcher,
+    },
+    /// Regex and case-sensitive modes.
+    Regex(Regex),
+}
+
+/// Compile `query` for `mode`. `Ok(None)` means nothing can match (blank
+/// query, or a literal query without required terms).
+fn build_search_pattern(query: &str, mode: SearchMode) -> Result<Option<SearchPattern>, String> {
+    if query.trim().is_empty() {
+        return Ok(None);
+    }
+    // Haystacks are quote-normalized, so patterns must be too
+    let pattern = match mode {
+        SearchMode::Literal => {
+            let Some(query) = parse_search_query(query) else {
+                return Ok(None);
+            };
+            let matcher = build_terms_matcher(&query);
+            if matcher.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(SearchPattern::Terms { query, matcher }));
+        }
+        SearchMode::Regex => normalize_quotes(query),
+        SearchMode::CaseSensitive => regex::escape(&normalize_quotes(query.trim())),
+    };
+    Regex::new(&pattern)
+        .map(|re| Some(SearchPattern::Regex(re)))
+        .map_err(|e| format!("SEARCH_INVALID_REGEX: {}", e))
+}
+
+/// A session file queued for [`search_session_file`].
+struct SearchFile {
+    path: std::path::PathBuf,
+    project_path: Option<String>,
+    raw_project_name: String,
+    session_path: String,
+}
+
+type SearchDateRange = (DateTime<chrono::FixedOffset>, DateTime<chrono::FixedOffset>);
+
+/// Query state shared by every file of one search.
+struct SearchContext<'a> {
+    pattern: &'a SearchPattern,
+    filters: &'a SearchFilters,
+    date_range: Option<SearchDateRange>,
+    /// `Some((candidate terms, max edit distance))` in fuzzy literal mode.
+    fuzzy: Option<(Vec<bool>, usize)>,
+}
+
+/// Matching messages of one session file, in file order.
+fn search_session_file(
+    file: &SearchFile,
+    ctx: &SearchContext,
+) -> Vec<(ClaudeMessage, SearchMatch)> {
+    let mut hits = Vec::new();
+    let Ok(content) = read_session_to_string(std::path::Path::new(&file.path)) else {
+        return hits;
+    };
+    for (line_num, line) in strip_bom(&content).lines().enumerate() {
+        if let Ok(log_entry) = serde_json::from_str::<RawLogEntry>(line) {
+            // Filter by message type
+            if let Some(ref msg_type_filter) = ctx.filters.message_type {
+                if msg_type_filter != "all" && log_entry.message_type != *msg_type_filter {
+                    continue;
+                }
+            }
+
+            if log_entry.message_type == "user" || log_entry.message_type == "assistant" {
+                if let Some(message_content) = &log_entry.message {
+                    // Filter by date range
+                    if let (Some((start, end)), Some(ref timestamp)) =
+                        (ctx.date_range, &log_entry.timestamp)
+                    {
+                        if let Ok(msg_time) = DateTime::parse_from_rfc3339(timestamp) {
+                            if msg_time < start || msg_time > end {
+                                continue;
+                            }
+                        } else {
+                            continue;
+                        }
+                    }
+
+                    // Filter by tool calls
+                    if let Some(has_tool_calls_filter) = ctx.filters.has_tool_calls {
+                        let has_tool_calls = log_entry.tool_use.is_some()
+                            || log_entry.tool_use_result.is_some()
+                            || (if let serde_json::Value::Array(arr) = &message_content.content {
+                                arr.iter().any(|item| {
+                                    item.get("type").and_then(|v| v.as_str()) == Some("tool_use")
+                                })
+                            } else {
+                                false
+                            });
+
+                        if has_tool_calls != has_tool_calls_filter {
+                            continue;
+                        }
+                    }
+
+                    // Filter by errors
+                    if let Some(has_errors_filter) = ctx.filters.has_errors {
+                        let has_errors = if let Some(ref result) = log_entry.tool_use_result {
+                            result
+                                .get("stderr")
+                                .and_then(|s| s.as_str())
+                                .map(|s| !s.is_empty())
+                                .unwrap_or(false)
+                                || result
+                                    .get("is_error")
+                                    .and_then(|e| e.as_bool())
+                                    .unwrap_or(false)
+                        } else {
+                            false
+                        };
+
+                        if has_errors != has_errors_filter {
+                            continue;
+                        }
+                    }
+
+                    let content_str = match &message_content.content {
+                        serde_json::Value::String(s) => s.clone(),
+                        serde_json::Value::Array(arr) => {
+                            // Extract text from array content items
+                            arr.iter()
+                                .filter_map(|item| {
+                                    if let Some(text) = item.get("text") {
+                                        text.as_str().map(|s| s.to_string())
+                                    } else {
+                                        None
+                                    }
+                                })
+                                .collect::<Vec<String>>()
+                                .join(" ")
+                        }
+                        _ => "".to_string(),
+                    };
+
+                    let haystack = normalize_quotes(&content_str);
+                    let fuzziness = match (ctx.pattern, &ctx.fuzzy) {
+                        (
+                            SearchPattern::Terms { query, matcher },
+                            Some((candidates, max_distance)),
+                        ) => {
+                            match matches_search_terms_fuzzy(
+                                query,
+                                matcher,
+                                candidates,
+                                *max_distance,
+                                &haystack,
+                            ) {
+                                Some(fuzziness) => Some(fuzziness),
+                                None => continue,
+                            }
+                        }
+                        (SearchPattern::Terms { query, matcher }, None)
+                            if matches_search_terms(query, matcher, &haystack) =>
+                        {
+                            None
+                        }
+                        (SearchPattern::Regex(re), _) if re.is_match(&haystack) => None,
+                        _ => continue,
+                    };
+                    let search_match = SearchMatch {
+                        project_name: resolve_project_name(
+                            log_entry.cwd.as_deref(),
+                            &file.raw_project_name,
+                        ),
+                        project_path: file.project_path.clone().unwrap_or_default(),
+                        session_path: file.session_path.clone(),
+                        fuzziness,
+                    };
+                    let subtype = log_entry.subtype.clone();
+                    let system_metadata = build_system_metadata(&log_entry);
+                    let claude_message = ClaudeMessage {
+                        uuid: log_entry.uuid.unwrap_or_else(|| {
+                            format!("{}-line-{}", Uuid::new_v4().to_string(), line_num + 1)
+                        }),
+                        parent_uuid: log_entry.parent_uuid,
+                        session_id: log_entry
+                            .session_id
+                            .unwrap_or_else(|| "unknown-session".to_string()),
+                        timestamp: log_entry
+                            .timestamp
+                            .unwrap_or_else(|| Utc::now().to_rfc3339()),
+                        message_type: log_entry.message_type,
+                        content: Some(message_content.content.clone()),
+                        tool_use: log_entry.tool_use,
+                        tool_use_result: log_entry.tool_use_result,
+                        is_sidechain: log_entry.is_sidechain,
+                        usage: message_content.usage.clone(),
+                        role: Some(message_content.role.clone()),
+                        message_id: message_content.id.clone(),
+                        model: message_content.model.clone(),
+                        stop_reason: message_content.stop_reason.clone(),
+                        project_path: file.project_path.clone(),
+                        subtype,
+                        system_metadata,
+                    };
+                    hits.push((claude_message, search_match));
+                }
             }
         }
     }
-    QueryMatcher::from_patterns(&patterns)
+    hits
 }
 
-#[tauri::command]
-pub async fn search_messages(
-    claude_path: String,
-    query: String,
-    filters: SearchFilters,
+/// Convert search hits to universal messages numbered from `first_sequence`.
+fn search_hits_to_universal(
+    hits: Vec<(ClaudeMessage, SearchMatch)>,
+    claude_path: &str,
+    first_sequence: usize,
+) -> Vec<UniversalMessage> {
+    hits.into_iter()
+        .enumerate()
+        .map(|(i, (msg, search_match))| {
+            // Use the full project path, not just the last component
+            // This ensures the frontend can match it against project.path
+            let project_id = msg
+                .project_path
+                .as_ref()
+                .map(|path| path.to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+
+            let mut universal = claude_message_to_universal(
+                &msg,
+                project_id,
+                search_match.session_path.clone(),
+                (first_sequence + i) as i32,
+            );
+            universal.provider_root = Some(claude_path.to_string());
+            universal
+                .provider_metadata
+                .insert("search_match".to_string(), json!(search_match));
+            universal
+        })
+        .collect()
+}
+
+/// Receives the matches of one session file during a search.
+type SearchHitsCallback<'a> = dyn Fn(&[UniversalMessage]) + Sync + 'a;
+
+/// Search every Claude session file under `claude_path`.
+///
+/// Files are scanned in parallel on the shared rayon pool (the app-wide
+/// concurrency cap). `on_file_hits` is called with each file's matches as soon
+/// as that file is done, in completion order; the returned list holds all
+/// matches in file order, numbered consecutively.
+fn search_claude_sessions(
+    claude_path: &str,
+    query: &str,
+    mode: SearchMode,
+    filters: &SearchFilters,
+    on_file_hits: Option<&SearchHitsCallback<'_>>,
 ) -> Result<Vec<UniversalMessage>, String> {
     // Validate and canonicalize the path for better error handling.
     // Note: Path traversal is not a security concern for desktop apps where the user
     // already has full filesystem access. This validation catches programming errors
     // and provides clearer error messages when the path doesn't exist.
-    let canonical_claude_path = std::fs::canonicalize(&claude_path)
+    let canonical_claude_path = std::fs::canonicalize(claude_path)
         .map_err(|e| format!("SEARCH_INVALID_PATH: Failed to resolve claude path: {}", e))?;
 
-    let projects_path = canonical_claude_path.join("projects");
-    let mut all_messages = Vec::new();
-
-    if !projects_path.exists() {
-        return Ok(vec![]);
-    }
-
-    // Parse the search query into terms
-    let search_terms = parse_search_query(&query);
-    if search_terms.is_empty() {
+    // Compile the query once and reuse it for every message in every file.
+    // An invalid regex is reported even when there is nothing to search.
+    let Some(pattern) = build_search_pattern(query, mode)? else {
         return Ok(vec![]);
-    }
+    };
 
-    // Build the matcher once and reuse it for every message in every file.
-    let matcher = build_terms_matcher(&search_terms);
-    if matcher.is_empty() {
+    let projects_path = canonical_claude_path.join("projects");
+    if !projects_path.exists() {
         return Ok(vec![]);
     }
 
@@ -1101,10 +2719,11 @@ pub async fn search_messages(
         None
     };
 
+    let mut files = Vec::new();
     for entry in WalkDir::new(&projects_path)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|e| is_session_file(e.path()))
     {
         // Extract project path from file path
         // Path format: ~/.claude/projects/[project_name]/[session].jsonl
@@ -1112,6 +2731,13 @@ pub async fn search_messages(
             .path()
             .parent() // Get parent directory (project folder)
             .map(|p| normalize_windows_path(&p.to_string_lossy().to_string()));
+        let raw_project_name = entry
+            .path()
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let session_path = normalize_windows_path(&entry.path().to_string_lossy());
 
         // Filter by project if specified
         if let Some(ref project_filters) = filters.projects {
@@ -1133,151 +2759,142 @@ pub async fn search_messages(
             }
         }
 
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            for (line_num, line) in content.lines().enumerate() {
-                if let Ok(log_entry) = serde_json::from_str::<RawLogEntry>(line) {
-                    // Filter by message type
-                    if let Some(ref msg_type_filter) = filters.message_type {
-                        if msg_type_filter != "all" && log_entry.message_type != *msg_type_filter {
-                            continue;
-                        }
-                    }
+        files.push(SearchFile {
+            path: entry.into_path(),
+            project_path,
+            raw_project_name,
+            session_path,
+        });
+    }
 
-                    if log_entry.message_type == "user" || log_entry.message_type == "assistant" {
-                        if let Some(message_content) = &log_entry.message {
-                            // Filter by date range
-                            if let (Some((start, end)), Some(ref timestamp)) =
-                                (date_range, &log_entry.timestamp)
-                            {
-                                if let Ok(msg_time) = DateTime::parse_from_rfc3339(timestamp) {
-                                    if msg_time < start || msg_time > end {
-                                        continue;
-                                    }
-                                } else {
-                                    continue;
-                                }
-                            }
+    let fuzzy = match &pattern {
+        SearchPattern::Terms { query, .. } if filters.fuzzy.unwrap_or(false) => Some((
+            fuzzy_candidate_terms(query),
+            filters
+                .max_edit_distance
+                .unwrap_or(DEFAULT_MAX_EDIT_DISTANCE),
+        )),
+        _ => None,
+    };
+    let ctx = SearchContext {
+        pattern: &pattern,
+        filters,
+        date_range,
+        fuzzy,
+    };
+    let per_file: Vec<Vec<UniversalMessage>> = files
+        .par_iter()
+        .map(|file| {
+            let hits = search_hits_to_universal(search_session_file(file, &ctx), claude_path, 0);
+            if let Some(on_file_hits) = on_file_hits {
+                if !hits.is_empty() {
+                    on_file_hits(&hits);
+                }
+            }
+            hits
+        })
+        .collect();
 
-                            // Filter by tool calls
-                            if let Some(has_tool_calls_filter) = filters.has_tool_calls {
-                                let has_tool_calls = log_entry.tool_use.is_some()
-                                    || log_entry.tool_use_result.is_some()
-                                    || (if let serde_json::Value::Array(arr) =
-                                        &message_content.content
-                                    {
-                                        arr.iter().any(|item| {
-                                            item.get("type").and_then(|v| v.as_str())
-                                                == Some("tool_use")
-                                        })
-                                    } else {
-                                        false
-                                    });
+    let mut universal_messages: Vec<UniversalMessage> = per_file.into_iter().flatten().collect();
+    for (i, msg) in universal_messages.iter_mut().enumerate() {
+        msg.sequence_number = i as i32;
+    }
 
-                                if has_tool_calls != has_tool_calls_filter {
-                                    continue;
-                                }
-                            }
+    Ok(universal_messages)
+}
 
-                            // Filter by errors
-                            if let Some(has_errors_filter) = filters.has_errors {
-                                let has_errors = if let Some(ref result) = log_entry.tool_use_result
-                                {
-                                    result
-                                        .get("stderr")
-                                        .and_then(|s| s.as_str())
-                                        .map(|s| !s.is_empty())
-                                        .unwrap_or(false)
-                                        || result
-                                            .get("is_error")
-                                            .and_then(|e| e.as_bool())
-                                            .unwrap_or(false)
-                                } else {
-                                    false
-                                };
-
-                                if has_errors != has_errors_filter {
-                                    continue;
-                                }
-                            }
+/// Default number of messages returned per `search_messages` page.
+const SEARCH_PAGE_SIZE: usize = 100;
+
+/// Search all Claude sessions. `mode` is `"literal"` (the default: a
+/// case-insensitive boolean term query), `"regex"` or `"case-sensitive"`;
+/// fuzzy matching and quoted phrases only apply in literal mode.
+///
+/// Returns the hits in `[offset, offset + limit)` (`limit` defaults to
+/// [`SEARCH_PAGE_SIZE`]) with the total number of hits. Sequence numbers are
+/// positions in the full result, so they continue across pages.
+#[tauri::command]
+pub async fn search_messages(
+    claude_path: String,
+    query: String,
+    filters: SearchFilters,
+    mode: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<SearchResult, String> {
+    let limit = limit.unwrap_or(SEARCH_PAGE_SIZE);
+    let offset = offset.unwrap_or(0);
+    let messages = search_all_messages(claude_path, query, filters, mode).await?;
+    Ok(search_page(messages, limit, offset))
+}
 
-                            let content_str = match &message_content.content {
-                                serde_json::Value::String(s) => s.clone(),
-                                serde_json::Value::Array(arr) => {
-                                    // Extract text from array content items
-                                    arr.iter()
-                                        .filter_map(|item| {
-                                            if let Some(text) = item.get("text") {
-                                                text.as_str().map(|s| s.to_string())
-                                            } else {
-                                                None
-                                            }
-                                        })
-                                        .collect::<Vec<String>>()
-                                        .join(" ")
-                                }
-                                _ => "".to_string(),
-                            };
-
-                            if matcher.is_match(&normalize_quotes(&content_str)) {
-                                let subtype = log_entry.subtype.clone();
-                                let system_metadata = build_system_metadata(&log_entry);
-                                let claude_message = ClaudeMessage {
-                                    uuid: log_entry.uuid.unwrap_or_else(|| {
-                                        format!(
-                                            "{}-line-{}",
-                                            Uuid::new_v4().to_string(),
-                                            line_num + 1
-                                        )
-                                    }),
-                                    parent_uuid: log_entry.parent_uuid,
-                                    session_id: log_entry
-                                        .session_id
-                                        .unwrap_or_else(|| "unknown-session".to_string()),
-                                    timestamp: log_entry
-                                        .timestamp
-                                        .unwrap_or_else(|| Utc::now().to_rfc3339()),
-                                    message_type: log_entry.message_type,
-                                    content: Some(message_content.content.clone()),
-                                    tool_use: log_entry.tool_use,
-                                    tool_use_result: log_entry.tool_use_result,
-                                    is_sidechain: log_entry.is_sidechain,
-                                    usage: message_content.usage.clone(),
-                                    role: Some(message_content.role.clone()),
-                                    message_id: message_content.id.clone(),
-                                    model: message_content.model.clone(),
-                                    stop_reason: message_content.stop_reason.clone(),
-                                    project_path: project_path.clone(),
-                                    subtype,
-                                    system_metadata,
-                                };
-                                all_messages.push(claude_message);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+/// Cut one page out of the full, ordered hit list.
+fn search_page(messages: Vec<UniversalMessage>, limit: usize, offset: usize) -> SearchResult {
+    let total = messages.len();
+    let window_end = offset.saturating_add(limit);
+    SearchResult {
+        messages: messages.into_iter().skip(offset).take(limit).collect(),
+        total,
+        has_more: total > window_end,
+        ignored_filters: Vec::new(),
     }
+}
 
-    // Convert ClaudeMessages to UniversalMessages
-    let source_id = claude_path.clone();
-    let universal_messages: Vec<UniversalMessage> = all_messages
-        .iter()
-        .enumerate()
-        .map(|(i, msg)| {
-            // Use the full project path, not just the last component
-            // This ensures the frontend can match it against project.path
-            let project_id = msg
-                .project_path
-                .as_ref()
-                .map(|path| path.to_string())
-                .unwrap_or_else(|| "unknown".to_string());
+/// Every hit of a Claude search, unpaginated (see [`search_messages`]).
+pub(crate) async fn search_all_messages(
+    claude_path: String,
+    query: String,
+    filters: SearchFilters,
+    mode: Option<String>,
+) -> Result<Vec<UniversalMessage>, String> {
+    let mode = SearchMode::parse(mode.as_deref())?;
+    tokio::task::spawn_blocking(move || {
+        search_claude_sessions(&claude_path, &query, mode, &filters, None)
+    })
+    .await
+    .map_err(|e| format!("SEARCH_TASK_ERROR: Search task failed: {}", e))?
+}
 
-            claude_message_to_universal(msg, project_id, source_id.clone(), i as i32)
-        })
-        .collect();
+/// Event carrying the matches of one session file while
+/// `search_messages_streaming` is still running.
+pub const SEARCH_PARTIAL_RESULTS_EVENT: &str = "search-partial-results";
 
-    Ok(universal_messages)
+#[derive(Debug, Clone, serde::Serialize)]
+struct SearchPartialResults<'a> {
+    search_id: &'a str,
+    messages: &'a [UniversalMessage],
+}
+
+/// Like [`search_messages`], but emits each file's matches as a
+/// [`SEARCH_PARTIAL_RESULTS_EVENT`] (tagged with `search_id`) as soon as the
+/// file is scanned, so the UI can show hits while the search runs. All hits
+/// are still returned at the end, unpaginated.
+#[tauri::command]
+pub async fn search_messages_streaming(
+    app_handle: tauri::AppHandle,
+    claude_path: String,
+    query: String,
+    filters: SearchFilters,
+    search_id: String,
+    mode: Option<String>,
+) -> Result<Vec<UniversalMessage>, String> {
+    use tauri::Emitter;
+
+    let mode = SearchMode::parse(mode.as_deref())?;
+    tokio::task::spawn_blocking(move || {
+        let emit = |messages: &[UniversalMessage]| {
+            let payload = SearchPartialResults {
+                search_id: &search_id,
+                messages,
+            };
+            if let Err(e) = app_handle.emit(SEARCH_PARTIAL_RESULTS_EVENT, payload) {
+                eprintln!("Failed to emit partial search results: {}", e);
+            }
+        };
+        search_claude_sessions(&claude_path, &query, mode, &filters, Some(&emit))
+    })
+    .await
+    .map_err(|e| format!("SEARCH_TASK_ERROR: Search task failed: {}", e))?
 }
 
 /// Fixes a problematic session by removing interrupted messages
@@ -1526,4 +3143,1503 @@ mod tests {
         }
         assert_eq!(entrypoint.as_deref(), Some("claude-desktop"));
     }
+
+    // ── Search query grammar: AND / OR / quoted phrases / grouping ─────────
+
+    fn query_matches(query: &str, haystack: &str) -> bool {
+        let parsed = parse_search_query(query).expect("query should have terms");
+        let matcher = build_terms_matcher(&parsed);
+        matches_search_terms(&parsed, &matcher, haystack)
+    }
+
+    #[test]
+    fn test_search_query_implicit_and() {
+        assert!(query_matches("timeout retry", "retry after a Timeout"));
+        assert!(!query_matches("timeout retry", "a timeout only"));
+    }
+
+    #[test]
+    fn test_search_query_or() {
+        assert!(query_matches("error OR panic", "thread panicked"));
+        assert!(query_matches("error OR panic", "an error occurred"));
+        assert!(!query_matches("error OR panic", "all good"));
+    }
+
+    #[test]
+    fn test_search_query_lowercase_or_is_a_word() {
+        assert!(!query_matches("fail or crash", "a fail and a crash"));
+        assert!(query_matches("fail or crash", "fail or crash"));
+    }
+
+    #[test]
+    fn test_search_query_and_binds_tighter_than_or() {
+        // `a b OR c` == `(a AND b) OR c`
+        assert!(query_matches("build failed OR panic", "the build failed"));
+        assert!(query_matches("build failed OR panic", "panic!"));
+        assert!(!query_matches("build failed OR panic", "the build passed"));
+    }
+
+    #[test]
+    fn test_search_query_parentheses_group() {
+        let q = "(error OR panic) tokio";
+        assert!(query_matches(q, "tokio runtime panic"));
+        assert!(query_matches(q, "tokio error"));
+        assert!(!query_matches(q, "tokio runtime started"));
+        assert!(!query_matches(q, "std panic"));
+    }
+
+    #[test]
+    fn test_search_query_quoted_phrase_with_or() {
+        let q = r#""exit code" OR "segmentation fault""#;
+        assert!(query_matches(q, "process exit code 1"));
+        assert!(query_matches(q, "Segmentation fault (core dumped)"));
+        assert!(!query_matches(q, "code exited; fault"));
+    }
+
+    #[test]
+    fn test_search_query_quoted_or_is_literal() {
+        let parsed = parse_search_query(r#""this OR that""#).unwrap();
+        assert_eq!(parsed.terms, vec![(true, "this OR that".to_string())]);
+        assert_eq!(parsed.expr, SearchExpr::Term(0));
+    }
+
+    #[test]
+    fn test_search_query_structure() {
+        let parsed = parse_search_query(r#"rust ("borrow checker" OR lifetime)"#).unwrap();
+        assert_eq!(
+            parsed.terms,
+            vec![
+                (false, "rust".to_string()),
+                (true, "borrow checker".to_string()),
+                (false, "lifetime".to_string()),
+            ]
+        );
+        assert_eq!(
+            parsed.expr,
+            SearchExpr::And(vec![
+                SearchExpr::Term(0),
+                SearchExpr::Or(vec![SearchExpr::Term(1), SearchExpr::Term(2)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_search_query_duplicate_terms_share_a_slot() {
+        let parsed = parse_search_query("error OR (Error warn)").unwrap();
+        assert_eq!(parsed.terms.len(), 2);
+    }
+
+    #[test]
+    fn test_search_query_malformed_is_lenient() {
+        assert!(parse_search_query("").is_none());
+        assert!(parse_search_query("( ) OR").is_none());
+        assert!(query_matches("OR error OR", "error"));
+        assert!(query_matches("(error panic", "panic error"));
+        assert!(query_matches("error) panic", "panic error"));
+        assert!(!query_matches("error) panic", "error"));
+    }
+
+    #[test]
+    fn test_search_query_dash_excludes_term() {
+        assert!(query_matches("timeout -test", "request timeout in prod"));
+        assert!(!query_matches("timeout -test", "timeout in test suite"));
+        assert!(!query_matches("timeout -test", "TEST hit a Timeout"));
+    }
+
+    #[test]
+    fn test_search_query_not_keyword_excludes_term() {
+        assert!(query_matches("timeout NOT test", "request timeout"));
+        assert!(!query_matches("timeout NOT test", "timeout in tests"));
+        // lowercase `not` is a plain word
+        assert!(query_matches("timeout not", "not a timeout"));
+    }
+
+    #[test]
+    fn test_search_query_excluded_phrase_and_group() {
+        let q = r#"error -"connection reset""#;
+        assert!(query_matches(q, "error: reset the connection"));
+        assert!(!query_matches(q, "error: connection reset by peer"));
+
+        let q = "error -(test OR mock)";
+        assert!(query_matches(q, "prod error"));
+        assert!(!query_matches(q, "mock error"));
+        assert!(!query_matches(q, "error in test"));
+    }
+
+    #[test]
+    fn test_search_query_exclusion_with_or_and_phrase() {
+        let q = r#"(panic OR "stack overflow") -tokio"#;
+        assert!(query_matches(q, "stack overflow in parser"));
+        assert!(!query_matches(q, "tokio worker panic"));
+    }
+
+    #[test]
+    fn test_search_query_double_dash_is_literal() {
+        let parsed = parse_search_query("--force").unwrap();
+        assert_eq!(parsed.terms, vec![(false, "--force".to_string())]);
+        assert_eq!(parsed.expr, SearchExpr::Term(0));
+        assert!(query_matches("git --force", "git push --force"));
+    }
+
+    #[test]
+    fn test_search_query_only_exclusions_is_none() {
+        assert!(parse_search_query("-test").is_none());
+        assert!(parse_search_query("NOT test -mock").is_none());
+        assert!(parse_search_query("error OR -test").is_none());
+    }
+
+    #[test]
+    fn test_search_query_smart_quotes() {
+        assert!(query_matches("“pricing update”", "the pricing update shipped"));
+        assert!(!query_matches("“pricing update”", "update the pricing"));
+    }
+
+    // ── search_messages: navigation target for each hit ────────────────────
+
+    #[tokio::test]
+    async fn test_search_messages_attaches_search_match() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir
+            .path()
+            .join("projects")
+            .join("-home-me-my-cool-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let session_file = project_dir.join("s1.jsonl");
+        fs::write(
+            &session_file,
+            concat!(
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","cwd":"/home/me/my-cool-app","message":{"role":"user","content":"why the timeout?"}}"#,
+                "\n",
+                r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","type":"assistant","cwd":"/home/me/my-cool-app","message":{"role":"assistant","content":"no match here"}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let results = search_messages(
+            temp_dir.path().to_string_lossy().to_string(),
+            "timeout".to_string(),
+            SearchFilters::default(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .messages;
+
+        assert_eq!(results.len(), 1);
+        let search_match: SearchMatch =
+            serde_json::from_value(results[0].provider_metadata["search_match"].clone()).unwrap();
+        assert_eq!(search_match.project_name, "my-cool-app");
+        assert!(search_match.session_path.ends_with("s1.jsonl"));
+        assert!(search_match.project_path.ends_with("-home-me-my-cool-app"));
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_pages_and_reports_total() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let hit_count = SEARCH_PAGE_SIZE + 20;
+        let content: String = (0..hit_count)
+            .map(|i| {
+                format!(
+                    "{}\n",
+                    serde_json::json!({
+                        "uuid": format!("u{}", i),
+                        "sessionId": "s1",
+                        "timestamp": "2025-06-01T10:00:00Z",
+                        "type": "user",
+                        "message": {"role": "user", "content": "deploy failed"},
+                    })
+                )
+            })
+            .collect();
+        fs::write(project_dir.join("s1.jsonl"), content).unwrap();
+        let claude_path = temp_dir.path().to_string_lossy().to_string();
+        let search = |limit: Option<usize>, offset: Option<usize>| {
+            search_messages(
+                claude_path.clone(),
+                "deploy".to_string(),
+                SearchFilters::default(),
+                None,
+                limit,
+                offset,
+            )
+        };
+
+        let first = search(None, None).await.unwrap();
+        assert_eq!(first.messages.len(), SEARCH_PAGE_SIZE);
+        assert_eq!(first.total, hit_count);
+        assert!(first.has_more);
+
+        let last = search(Some(50), Some(SEARCH_PAGE_SIZE)).await.unwrap();
+        assert_eq!(last.messages.len(), 20);
+        assert_eq!(last.total, hit_count);
+        assert!(!last.has_more);
+        assert_eq!(last.messages[0].sequence_number, SEARCH_PAGE_SIZE as i32);
+
+        let past_end = search(Some(10), Some(hit_count + 5)).await.unwrap();
+        assert!(past_end.messages.is_empty());
+        assert_eq!(past_end.total, hit_count);
+    }
+
+    #[test]
+    fn test_search_reports_hits_per_file_and_aggregates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        for (session, hits) in [("s1", 2), ("s2", 0), ("s3", 1)] {
+            let mut content = String::new();
+            for i in 0..3 {
+                let text = if i < hits {
+                    "deploy failed"
+                } else {
+                    "all good"
+                };
+                let line = serde_json::json!({
+                    "uuid": format!("{}-{}", session, i),
+                    "sessionId": session,
+                    "timestamp": "2025-06-01T10:00:00Z",
+                    "type": "user",
+                    "message": {"role": "user", "content": text},
+                });
+                content.push_str(&format!("{}\n", line));
+            }
+            fs::write(project_dir.join(format!("{}.jsonl", session)), content).unwrap();
+        }
+
+        let batches = std::sync::Mutex::new(Vec::new());
+        let on_hits = |hits: &[UniversalMessage]| batches.lock().unwrap().push(hits.len());
+        let results = search_claude_sessions(
+            &temp_dir.path().to_string_lossy(),
+            "deploy",
+            SearchMode::Literal,
+            &SearchFilters::default(),
+            Some(&on_hits),
+        )
+        .unwrap();
+
+        let mut batches = batches.into_inner().unwrap();
+        batches.sort_unstable();
+        assert_eq!(batches, vec![1, 2]);
+        assert_eq!(results.len(), 3);
+        let sequence: Vec<i32> = results.iter().map(|m| m.sequence_number).collect();
+        assert_eq!(sequence, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_search_applies_date_type_tool_and_error_filters() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let lines = [
+            serde_json::json!({
+                "uuid": "u1", "sessionId": "s1", "timestamp": "2025-06-01T10:00:00Z",
+                "type": "user",
+                "message": {"role": "user", "content": "deploy please"},
+            }),
+            serde_json::json!({
+                "uuid": "a1", "sessionId": "s1", "timestamp": "2025-06-02T10:00:00Z",
+                "type": "assistant",
+                "message": {"role": "assistant", "content": [
+                    {"type": "text", "text": "running deploy"},
+                    {"type": "tool_use", "id": "t1", "name": "Bash", "input": {}},
+                ]},
+            }),
+            serde_json::json!({
+                "uuid": "u2", "sessionId": "s1", "timestamp": "2025-06-03T10:00:00Z",
+                "type": "user",
+                "toolUseResult": {"stdout": "", "stderr": "deploy: permission denied"},
+                "message": {"role": "user", "content": "deploy output"},
+            }),
+        ];
+        let content: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(project_dir.join("s1.jsonl"), content).unwrap();
+        let claude_path = temp_dir.path().to_string_lossy().to_string();
+        let ids = |filters: SearchFilters| -> Vec<String> {
+            let mut ids: Vec<String> =
+                search_claude_sessions(&claude_path, "deploy", SearchMode::Literal, &filters, None)
+                    .unwrap()
+                    .into_iter()
+                    .map(|m| m.id)
+                    .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids(SearchFilters::default()), vec!["a1", "u1", "u2"]);
+        assert_eq!(
+            ids(SearchFilters {
+                date_range: Some(vec![
+                    "2025-06-01T12:00:00Z".to_string(),
+                    "2025-06-02T12:00:00Z".to_string(),
+                ]),
+                ..Default::default()
+            }),
+            vec!["a1"]
+        );
+        assert_eq!(
+            ids(SearchFilters {
+                message_type: Some("user".to_string()),
+                ..Default::default()
+            }),
+            vec!["u1", "u2"]
+        );
+        // A tool result counts as tool activity, like a tool_use content item
+        assert_eq!(
+            ids(SearchFilters {
+                has_tool_calls: Some(false),
+                ..Default::default()
+            }),
+            vec!["u1"]
+        );
+        assert_eq!(
+            ids(SearchFilters {
+                has_errors: Some(true),
+                ..Default::default()
+            }),
+            vec!["u2"]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_search_tolerates_typos_and_reports_fuzziness() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let mut content = String::new();
+        for (i, text) in ["the deployment timed out", "a test of the text"]
+            .iter()
+            .enumerate()
+        {
+            let line = serde_json::json!({
+                "uuid": format!("u{}", i),
+                "sessionId": "s1",
+                "timestamp": "2025-06-01T10:00:00Z",
+                "type": "user",
+                "message": {"role": "user", "content": text},
+            });
+            content.push_str(&format!("{}\n", line));
+        }
+        fs::write(project_dir.join("s1.jsonl"), content).unwrap();
+        let claude_path = temp_dir.path().to_string_lossy().to_string();
+        let search = |query: &str, fuzzy: Option<bool>, max_edit_distance: Option<usize>| {
+            let filters = SearchFilters {
+                fuzzy,
+                max_edit_distance,
+                ..Default::default()
+            };
+            search_claude_sessions(&claude_path, query, SearchMode::Literal, &filters, None)
+                .unwrap()
+        };
+        let fuzziness = |msg: &UniversalMessage| {
+            serde_json::from_value::<SearchMatch>(msg.provider_metadata["search_match"].clone())
+                .unwrap()
+                .fuzziness
+        };
+
+        // Exact mode stays the default
+        assert!(search("deploymnet", None, None).is_empty());
+        assert_eq!(fuzziness(&search("deployment", None, None)[0]), None);
+
+        let results = search("deploymnet", Some(true), None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(fuzziness(&results[0]), Some(2));
+        assert!(search("deploymnet", Some(true), Some(1)).is_empty());
+        assert_eq!(
+            fuzziness(&search("deployment", Some(true), None)[0]),
+            Some(0)
+        );
+
+        // Quoted phrases and exclusions stay exact
+        assert!(search("\"deploymnet\"", Some(true), None).is_empty());
+        assert_eq!(search("test -tezt", Some(true), None).len(), 1);
+    }
+
+    #[test]
+    fn test_search_modes_regex_and_case_sensitive() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let mut content = String::new();
+        for (i, text) in [
+            "fn on_click_handler() {}",
+            "the HttpClient failed",
+            "\"quoted words\" here",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let line = serde_json::json!({
+                "uuid": format!("u{}", i),
+                "sessionId": "s1",
+                "timestamp": "2025-06-01T10:00:00Z",
+                "type": "user",
+                "message": {"role": "user", "content": text},
+            });
+            content.push_str(&format!("{}\n", line));
+        }
+        fs::write(project_dir.join("s1.jsonl"), content).unwrap();
+        let claude_path = temp_dir.path().to_string_lossy().to_string();
+        let search = |query: &str, mode: Option<&str>| {
+            let mode = SearchMode::parse(mode)?;
+            search_claude_sessions(&claude_path, query, mode, &SearchFilters::default(), None)
+                .map(|results| results.len())
+        };
+
+        assert_eq!(search(r"fn\s+\w+_handler", Some("regex")), Ok(1));
+        assert_eq!(search(r"(?i)httpclient", Some("regex")), Ok(1));
+        assert_eq!(search("httpclient", Some("regex")), Ok(0));
+        let err = search("fn(", Some("regex")).unwrap_err();
+        assert!(err.starts_with("SEARCH_INVALID_REGEX:"), "{}", err);
+
+        assert_eq!(search("HttpClient", Some("case-sensitive")), Ok(1));
+        assert_eq!(search("httpclient", Some("case-sensitive")), Ok(0));
+        // No phrase parsing outside literal mode: quotes are matched verbatim
+        assert_eq!(search("\"quoted words\"", Some("case-sensitive")), Ok(1));
+        assert_eq!(search("\"words quoted\"", Some("case-sensitive")), Ok(0));
+
+        assert_eq!(search("httpclient", None), Ok(1));
+        assert_eq!(search("httpclient", Some("literal")), Ok(1));
+        let err = search("x", Some("glob")).unwrap_err();
+        assert!(err.starts_with("SEARCH_INVALID_ARGUMENT:"), "{}", err);
+    }
+
+    // ── activity_score ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_activity_score_orders_by_recency_size_and_tools() {
+        let now = DateTime::parse_from_rfc3339("2025-06-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let score = |ts: &str, messages, tools| activity_score(ts, messages, tools, now);
+
+        let fresh = score("2025-06-15T12:00:00Z", 10, 0);
+        let week_old = score("2025-06-08T12:00:00Z", 10, 0);
+        assert!(fresh > week_old);
+        // One half-life costs half the recency weight
+        assert!((fresh - week_old - ACTIVITY_RECENCY_WEIGHT / 2.0).abs() < 0.01);
+
+        assert!(score("2025-06-08T12:00:00Z", 200, 0) > week_old);
+        assert!(score("2025-06-08T12:00:00Z", 10, 20) > week_old);
+        assert_eq!(score("not a date", 0, 0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_loaded_session_has_activity_score() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(
+            project_dir.join("s1.jsonl"),
+            concat!(
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hi"}}"#,
+                "\n",
+                r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Bash","input":{}},{"type":"tool_use","id":"t2","name":"Read","input":{}}]}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let sessions = collect_project_sessions(
+            project_dir.to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let expected = activity_score("2025-06-01T10:00:05Z", 2, 2, Utc::now());
+        assert!((sessions[0].activity_score - expected).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_cached_sessions_are_rescored_on_each_load() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let session_file = project_dir.join("s1.jsonl");
+        let now = Utc::now();
+        let last = (now - chrono::Duration::hours(1)).to_rfc3339();
+        fs::write(
+            &session_file,
+            serde_json::json!({
+                "uuid": "a1", "sessionId": "s1", "timestamp": last, "type": "assistant",
+                "message": {"role": "assistant", "content": [
+                    {"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}
+                ]},
+            })
+            .to_string()
+                + "\n",
+        )
+        .unwrap();
+        let project_path = project_dir.to_string_lossy().to_string();
+        let cache = SessionCacheState::default();
+        let load =
+            || collect_project_sessions(project_path.clone(), None, None, None, Some(&cache));
+
+        let mut sessions = load().await.unwrap();
+        assert_eq!(sessions[0].tool_use_count, 1);
+
+        // A cache entry scored long ago must not keep its stale recency
+        let metadata = fs::metadata(&session_file).unwrap();
+        let options = SessionParseOptions {
+            exclude_sidechain: false,
+            include_noise: false,
+            max_line_bytes: DEFAULT_MAX_LINE_BYTES,
+        };
+        let mut stale = sessions[0].clone();
+        stale.activity_score = 999.0;
+        cache.insert(session_file.clone(), &metadata, options, Some(stale));
+        let reloaded = load().await.unwrap();
+        let expected = activity_score(&last, 1, 1, Utc::now());
+        assert!((reloaded[0].activity_score - expected).abs() < 0.01);
+
+        // Recency decays as `now` advances
+        let fresh = sessions[0].activity_score;
+        finalize_project_sessions(&mut sessions, now + chrono::Duration::days(30));
+        assert!(sessions[0].activity_score < fresh - 5.0);
+        let expected_later = activity_score(&last, 1, 1, now + chrono::Duration::days(30));
+        assert!((sessions[0].activity_score - expected_later).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_group_sessions_by_day_puts_unknown_last() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let line = |ts: &str| {
+            serde_json::json!({
+                "uuid": ts, "sessionId": ts, "timestamp": ts, "type": "user",
+                "message": {"role": "user", "content": "hi"},
+            })
+            .to_string()
+        };
+        for (name, ts) in [
+            ("a.jsonl", "2025-06-01T10:00:00Z"),
+            ("b.jsonl", "2025-06-02T10:00:00Z"),
+            ("c.jsonl", "2025-06-02T12:00:00Z"),
+        ] {
+            fs::write(temp_dir.path().join(name), line(ts)).unwrap();
+        }
+
+        let mut sessions = collect_project_sessions(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let undated = sessions
+            .iter_mut()
+            .find(|s| s.file_path.ends_with("a.jsonl"))
+            .unwrap();
+        undated.last_message_time = "not a time".to_string();
+        undated.last_modified = "not a time".to_string();
+
+        let days = group_sessions_by_day(sessions);
+        let dates: Vec<(&str, usize)> = days
+            .iter()
+            .map(|d| (d.date.as_str(), d.session_count))
+            .collect();
+        assert_eq!(dates, vec![("2025-06-02", 2), ("unknown", 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_group_resumed_sessions_merges_files_by_session_id() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let line = |uuid: &str, session: &str, ts: &str, text: &str| {
+            serde_json::json!({
+                "uuid": uuid, "sessionId": session, "timestamp": ts, "type": "user",
+                "message": {"role": "user", "content": text},
+            })
+            .to_string()
+                + "\n"
+        };
+        let tools = |uuid: &str, ts: &str, count: usize| {
+            let calls: Vec<serde_json::Value> = (0..count)
+                .map(|i| {
+                    serde_json::json!({
+                        "type": "tool_use", "id": format!("{uuid}-{i}"), "name": "Read", "input": {},
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "uuid": uuid, "sessionId": "chain", "timestamp": ts, "type": "assistant",
+                "message": {"role": "assistant", "content": calls},
+            })
+            .to_string()
+                + "\n"
+        };
+        fs::write(
+            project_dir.join("first.jsonl"),
+            line("u1", "chain", "2025-06-01T10:00:00Z", "start")
+                + &tools("a1", "2025-06-01T10:01:00Z", 1)
+                + &line("u2", "chain", "2025-06-01T10:05:00Z", "more"),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("resumed.jsonl"),
+            line("u3", "chain", "2025-06-02T09:00:00Z", "resume")
+                + &tools("a2", "2025-06-02T09:10:00Z", 2)
+                + &line("u4", "chain", "2025-06-02T09:30:00Z", "again")
+                + &line("u5", "chain", "2025-06-02T09:45:00Z", "done"),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("other.jsonl"),
+            line("u6", "solo", "2025-06-01T08:00:00Z", "unrelated"),
+        )
+        .unwrap();
+
+        let sessions = collect_project_sessions(
+            project_dir.to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(sessions.len(), 3);
+        assert!(sessions
+            .iter()
+            .all(|s| s.file_paths == vec![s.file_path.clone()]));
+
+        let now = DateTime::parse_from_rfc3339("2025-06-03T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let grouped = group_resumed_sessions(sessions, now);
+        assert_eq!(grouped.len(), 2);
+        let chain = grouped
+            .iter()
+            .find(|s| s.actual_session_id == "chain")
+            .unwrap();
+        assert_eq!(chain.message_count, 7);
+        assert_eq!(chain.tool_use_count, 3);
+        assert_eq!(
+            chain.activity_score,
+            activity_score("2025-06-02T09:45:00Z", 7, 3, now)
+        );
+        assert_eq!(chain.first_message_time, "2025-06-01T10:00:00Z");
+        assert_eq!(chain.last_message_time, "2025-06-02T09:45:00Z");
+        assert_eq!(chain.first_user_prompt.as_deref(), Some("start"));
+        assert!(chain.file_path.ends_with("resumed.jsonl"));
+        let files: Vec<&str> = chain
+            .file_paths
+            .iter()
+            .map(|p| p.rsplit(['/', '\\']).next().unwrap())
+            .collect();
+        assert_eq!(files, vec!["first.jsonl", "resumed.jsonl"]);
+
+        let solo = grouped
+            .iter()
+            .find(|s| s.actual_session_id == "solo")
+            .unwrap();
+        assert_eq!(solo.file_paths.len(), 1);
+    }
+
+    // ── max_content_bytes / get_full_content ───────────────────────────────
+
+    fn write_large_session(temp_dir: &tempfile::TempDir) -> String {
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let session_file = project_dir.join("s1.jsonl");
+        let line = serde_json::json!({
+            "uuid": "u1",
+            "sessionId": "s1",
+            "timestamp": "2025-06-01T10:00:00Z",
+            "type": "user",
+            "message": {"role": "user", "content": "x".repeat(5000)},
+        });
+        fs::write(&session_file, format!("{}\n", line)).unwrap();
+        session_file.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_load_session_messages_caps_content_and_full_content_restores_it() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_path = write_large_session(&temp_dir);
+
+        let uncapped = load_session_messages(session_path.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert!(uncapped[0].content[0].data.get("truncated").is_none());
+
+        let capped = load_session_messages(session_path.clone(), None, Some(100), None)
+            .await
+            .unwrap();
+        let item = &capped[0].content[0];
+        assert_eq!(item.data["truncated"], true);
+        assert!(item.data["originalSize"].as_u64().unwrap() > 5000);
+        assert_eq!(
+            item.size,
+            item.data["originalSize"].as_u64().map(|n| n as usize)
+        );
+
+        let full = get_full_content(session_path, capped[0].id.clone(), 0)
+            .await
+            .unwrap();
+        assert_eq!(full.data, uncapped[0].content[0].data);
+    }
+
+    #[tokio::test]
+    async fn test_get_full_content_reports_missing_targets() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_path = write_large_session(&temp_dir);
+
+        let err = get_full_content(session_path.clone(), "nope".to_string(), 0)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("MESSAGE_NOT_FOUND"));
+
+        let err = get_full_content(session_path, "u1".to_string(), 9)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("CONTENT_NOT_FOUND"));
+    }
+
+    // ── load_session_messages_light / get_tool_result ──────────────────────
+
+    #[tokio::test]
+    async fn test_light_loader_omits_tool_results_and_get_tool_result_fetches_them() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let session_file = project_dir.join("s1.jsonl");
+        let tool_result = serde_json::json!({"stdout": "y".repeat(2000), "stderr": ""});
+        let block_content = "y".repeat(2000);
+        let lines = [
+            serde_json::json!({
+                "uuid": "u1", "sessionId": "s1", "timestamp": "2025-06-01T10:00:00Z",
+                "type": "user", "message": {"role": "user", "content": "run it"},
+            }),
+            serde_json::json!({
+                "uuid": "u2", "sessionId": "s1", "timestamp": "2025-06-01T10:00:05Z",
+                "type": "user",
+                "message": {"role": "user", "content": [{
+                    "type": "tool_result", "tool_use_id": "t1", "content": block_content,
+                }]},
+                "toolUseResult": tool_result,
+            }),
+        ];
+        let body: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(&session_file, &body).unwrap();
+        let session_path = session_file.to_string_lossy().to_string();
+
+        let full =
+            load_session_messages_paginated(session_path.clone(), 0, 10, None, None, None, None)
+                .await
+                .unwrap();
+        let light = load_session_messages_light(session_path.clone(), 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(light.total_count, full.total_count);
+        assert_eq!(light.has_more, full.has_more);
+        assert_eq!(light.next_offset, full.next_offset);
+
+        let placeholder = &light.messages[1].provider_metadata["raw_tool_use_result"];
+        assert_eq!(placeholder["omitted"], true);
+        assert_eq!(
+            placeholder["size"],
+            serde_json::to_string(&tool_result).unwrap().len()
+        );
+
+        let block = &light.messages[1].content[0].data;
+        assert_eq!(block["tool_use_id"], "t1");
+        assert_eq!(block["content"]["omitted"], true);
+
+        let fetched = get_tool_result(session_path.clone(), "u2".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(fetched, tool_result);
+        let fetched = get_tool_result(session_path.clone(), "u2".to_string(), Some("t1".into()))
+            .await
+            .unwrap();
+        assert_eq!(fetched, block_content.as_str());
+
+        let err = get_tool_result(session_path, "u1".to_string(), None)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("TOOL_RESULT_NOT_FOUND"));
+
+        // Compressed sessions are read the same way
+        let gz_file = project_dir.join("s2.jsonl.gz");
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, body.as_bytes()).unwrap();
+        fs::write(&gz_file, encoder.finish().unwrap()).unwrap();
+        let fetched = get_tool_result(
+            gz_file.to_string_lossy().to_string(),
+            "u2".to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fetched, tool_result);
+    }
+
+    // ── ParseMode ──────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_load_session_messages_filtered_by_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_file = temp_dir.path().join("s1.jsonl");
+        fs::write(
+            &session_file,
+            [
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"show me the code"}}"#,
+                r#"{"uuid":"a1","sessionId":"s1","timestamp":"2025-06-01T10:00:01Z","type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Here:\n```rust\nfn main() {}\n```"}]}}"#,
+                r#"{"uuid":"a2","sessionId":"s1","timestamp":"2025-06-01T10:00:02Z","type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"/a"}}]}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let path = session_file.to_string_lossy().to_string();
+        let ids = |messages: Vec<UniversalMessage>| -> Vec<String> {
+            messages.into_iter().map(|m| m.id).collect()
+        };
+
+        let with_code = MessageContentFilter {
+            has_code_block: Some(true),
+            ..Default::default()
+        };
+        let messages = load_session_messages_filtered(path.clone(), with_code)
+            .await
+            .unwrap();
+        assert_eq!(ids(messages), vec!["a1"]);
+
+        let tools = MessageContentFilter {
+            has_tool_use: Some(true),
+            ..Default::default()
+        };
+        let messages = load_session_messages_filtered(path.clone(), tools)
+            .await
+            .unwrap();
+        assert_eq!(ids(messages), vec!["a2"]);
+
+        let long_user = MessageContentFilter {
+            min_length: Some(10),
+            role: Some(crate::models::universal::MessageRole::User),
+            ..Default::default()
+        };
+        let messages = load_session_messages_filtered(path, long_user)
+            .await
+            .unwrap();
+        assert_eq!(ids(messages), vec!["u1"]);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_grouped_by_last_activity_day() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session = |uuid: &str, time: &str, tokens: u32| {
+            format!(
+                concat!(
+                    r#"{{"uuid":"{}u","sessionId":"{}","timestamp":"{}","type":"user","message":{{"role":"user","content":"hi"}}}}"#,
+                    "\n",
+                    r#"{{"uuid":"{}a","sessionId":"{}","timestamp":"{}","type":"assistant","message":{{"role":"assistant","id":"m{}","content":[{{"type":"text","text":"ok"}}],"usage":{{"input_tokens":{},"output_tokens":0}}}}}}"#,
+                    "\n",
+                ),
+                uuid, uuid, time, uuid, uuid, time, uuid, tokens
+            )
+        };
+        for (id, time, tokens) in [
+            ("a", "2025-06-01T09:00:00Z", 10),
+            ("b", "2025-06-01T18:00:00Z", 20),
+            ("c", "2025-06-03T12:00:00Z", 5),
+        ] {
+            let path = temp_dir.path().join(format!("{}.jsonl", id));
+            fs::write(path, session(id, time, tokens)).unwrap();
+        }
+
+        let days = get_sessions_by_day(temp_dir.path().to_string_lossy().to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2025-06-03");
+        assert_eq!(days[0].session_count, 1);
+        assert_eq!(days[0].total_tokens, 5);
+        assert_eq!(days[1].date, "2025-06-01");
+        assert_eq!(days[1].session_count, 2);
+        assert_eq!(days[1].total_tokens, 30);
+        assert_eq!(days[1].sessions[0].actual_session_id, "b");
+    }
+
+    #[tokio::test]
+    async fn test_gzipped_sessions_load_like_plain_ones() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let content = concat!(
+            r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hi"}}"#,
+            "\n",
+            r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","type":"user","message":{"role":"user","content":"again"}}"#,
+            "\n",
+        );
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        let session_file = temp_dir.path().join("s1.jsonl.gz");
+        fs::write(&session_file, encoder.finish().unwrap()).unwrap();
+        let session_path = session_file.to_string_lossy().to_string();
+
+        let sessions = collect_project_sessions(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].message_count, 2);
+
+        let messages = load_session_messages(session_path.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+
+        let count = get_session_message_count(session_path.clone(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let page = load_session_messages_paginated(session_path, 0, 1, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.messages.len(), 1);
+        assert!(page.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_message_count_skips_summaries_and_optionally_sidechains() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_file = temp_dir.path().join("s1.jsonl");
+        fs::write(
+            &session_file,
+            concat!(
+                r#"{"type":"summary","summary":"Fix the build","leafUuid":"u2"}"#,
+                "\n",
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hi"}}"#,
+                "\n\n",
+                r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","type":"assistant","isSidechain":true,"message":{"role":"assistant","content":"side"}}"#,
+                "\n",
+                "not json\n",
+            ),
+        )
+        .unwrap();
+        let session_path = session_file.to_string_lossy().to_string();
+
+        let all = get_session_message_count(session_path.clone(), None, None)
+            .await
+            .unwrap();
+        let main_only = get_session_message_count(session_path, Some(true), None)
+            .await
+            .unwrap();
+        assert_eq!((all, main_only), (2, 1));
+
+        let missing = get_session_message_count(
+            temp_dir
+                .path()
+                .join("gone.jsonl")
+                .to_string_lossy()
+                .to_string(),
+            None,
+            None,
+        )
+        .await;
+        assert!(missing.unwrap_err().starts_with("SESSION_READ_ERROR"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_parse_mode_reports_first_malformed_line() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_file = temp_dir.path().join("s1.jsonl");
+        fs::write(
+            &session_file,
+            concat!(
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hi"}}"#,
+                "\n",
+                "{not json\n",
+                r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","type":"user","message":{"role":"user","content":"again"}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        let session_path = session_file.to_string_lossy().to_string();
+
+        let lenient = load_session_messages(session_path.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(lenient.len(), 2);
+
+        let err = load_session_messages(session_path.clone(), None, None, Some(ParseMode::Strict))
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("SESSION_PARSE_ERROR"));
+        assert!(err.contains("line 2 of"));
+
+        let page = load_session_messages_paginated(
+            session_path.clone(),
+            0,
+            10,
+            None,
+            None,
+            None,
+            Some(ParseMode::Lenient),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.total_count, 2);
+
+        let err = load_session_messages_paginated(
+            session_path,
+            0,
+            10,
+            None,
+            None,
+            None,
+            Some(ParseMode::Strict),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("line 2 of"));
+    }
+
+    // ── load_project_sessions_verbose ──────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_load_project_sessions_verbose_reports_warnings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(
+            project_dir.join("s1.jsonl"),
+            concat!(
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hello"}}"#,
+                "\n",
+                "{not json\n",
+                r#"{"sessionId":"s1","type":"assistant","message":{"role":"assistant","content":"hi"}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let project_path = project_dir.to_string_lossy().to_string();
+        let report = load_project_sessions_verbose(project_path.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(report.sessions.len(), 1);
+
+        let kinds: Vec<(usize, &str)> = report
+            .warnings
+            .iter()
+            .map(|w| (w.line, w.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (2, "unparseable_line"),
+                (3, "missing_uuid"),
+                (3, "missing_timestamp")
+            ]
+        );
+        assert!(report.warnings[0].file_path.ends_with("s1.jsonl"));
+
+        // The plain loader still returns the same sessions
+        let sessions = collect_project_sessions(project_path, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), report.sessions.len());
+    }
+
+    // ── BOM / CRLF tolerance ───────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_bom_and_crlf_session_parses_like_clean_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lines = [
+            r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hello"}}"#,
+            r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","type":"assistant","message":{"role":"assistant","content":"hi there"}}"#,
+        ];
+        let clean_dir = temp_dir.path().join("clean");
+        let windows_dir = temp_dir.path().join("windows");
+        fs::create_dir_all(&clean_dir).unwrap();
+        fs::create_dir_all(&windows_dir).unwrap();
+        let clean_file = clean_dir.join("s1.jsonl");
+        let windows_file = windows_dir.join("s1.jsonl");
+        fs::write(&clean_file, format!("{}\n", lines.join("\n"))).unwrap();
+        fs::write(&windows_file, format!("\u{FEFF}{}\r\n", lines.join("\r\n"))).unwrap();
+
+        let load = |path: &std::path::Path| {
+            load_session_messages(path.to_string_lossy().to_string(), None, None, None)
+        };
+        let clean = load(&clean_file).await.unwrap();
+        let windows = load(&windows_file).await.unwrap();
+        assert_eq!(clean.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&clean[0].content).unwrap(),
+            serde_json::to_value(&windows[0].content).unwrap()
+        );
+        assert_eq!(
+            windows.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            vec!["u1", "u2"]
+        );
+
+        let count =
+            get_session_message_count(windows_file.to_string_lossy().to_string(), None, None)
+                .await
+                .unwrap();
+        assert_eq!(count, 2);
+
+        let clean_sessions = collect_project_sessions(
+            clean_dir.to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let windows_sessions = collect_project_sessions(
+            windows_dir.to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(windows_sessions.len(), 1);
+        assert_eq!(
+            windows_sessions[0].message_count,
+            clean_sessions[0].message_count
+        );
+        assert_eq!(
+            windows_sessions[0].first_message_time,
+            clean_sessions[0].first_message_time
+        );
+    }
+
+    // ── max line length guard ──────────────────────────────────────────────
+
+    #[test]
+    fn test_read_capped_line_skips_oversized_lines() {
+        let data = b"short\r\nthis line is far too long\nok";
+        // Tiny buffer so lines span several fill_buf chunks
+        let mut reader = std::io::BufReader::with_capacity(4, &data[..]);
+        let mut buf = Vec::new();
+        let mut next = |buf: &mut Vec<u8>| read_capped_line(&mut reader, buf, 10).unwrap();
+
+        assert_eq!(next(&mut buf), Some(5));
+        assert_eq!(buf, b"short");
+        assert_eq!(next(&mut buf), Some(25));
+        assert!(buf.is_empty());
+        assert_eq!(next(&mut buf), Some(2));
+        assert_eq!(buf, b"ok");
+        assert_eq!(next(&mut buf), None);
+    }
+
+    #[tokio::test]
+    async fn test_load_project_sessions_skips_lines_over_cap() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let huge = serde_json::json!({
+            "uuid": "u2", "sessionId": "s1", "timestamp": "2025-06-01T10:00:05Z",
+            "type": "user", "message": {"role": "user", "content": "x".repeat(4096)},
+        });
+        fs::write(
+            project_dir.join("s1.jsonl"),
+            format!(
+                "{}\n{}\n",
+                r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{"role":"user","content":"hello"}}"#,
+                huge
+            ),
+        )
+        .unwrap();
+
+        let project_path = project_dir.to_string_lossy().to_string();
+        let report = load_project_sessions_verbose(project_path.clone(), None, None, Some(1024))
+            .await
+            .unwrap();
+        assert_eq!(report.sessions[0].message_count, 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].kind, "line_too_long");
+        assert_eq!(report.warnings[0].line, 2);
+
+        let sessions = collect_project_sessions(project_path, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(sessions[0].message_count, 2);
+    }
+
+    // ── list_error_sessions ────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_list_error_sessions_uses_is_error_and_stderr() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let user = |session: &str, extra: serde_json::Value| {
+            let mut line = serde_json::json!({
+                "uuid": format!("{}-u1", session), "sessionId": session,
+                "timestamp": "2025-06-01T10:00:00Z", "type": "user",
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "t1", "content": "ok"},
+                ]},
+            });
+            for (k, v) in extra.as_object().unwrap() {
+                line[k] = v.clone();
+            }
+            format!("{}\n", line)
+        };
+        fs::write(
+            project_dir.join("clean.jsonl"),
+            user("clean", serde_json::json!({})),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("flagged.jsonl"),
+            user(
+                "flagged",
+                serde_json::json!({"message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "t1", "content": "boom", "is_error": true},
+                ]}}),
+            ),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("stderr.jsonl"),
+            user(
+                "stderr",
+                serde_json::json!({"toolUseResult": {"stdout": "", "stderr": "command not found"}}),
+            ),
+        )
+        .unwrap();
+
+        let sessions = list_error_sessions(project_dir.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        let mut ids: Vec<&str> = sessions
+            .iter()
+            .map(|s| s.actual_session_id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["flagged", "stderr"]);
+    }
+
+    // ── load_session_for_replay ────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_load_session_for_replay_follows_parent_chain() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_file = temp_dir.path().join("s1.jsonl");
+        let entry = |uuid: &str, parent: Option<&str>, ts: &str, kind: &str| {
+            serde_json::json!({
+                "uuid": uuid, "parentUuid": parent, "sessionId": "s1", "timestamp": ts,
+                "type": kind, "message": {"role": kind, "content": uuid},
+            })
+        };
+        // File order differs from conversation order; "p1" is a noise entry
+        // inside the chain and "o1" references a parent that is not in the file.
+        let lines = [
+            entry("a2", Some("p1"), "2025-06-01T10:00:03Z", "assistant"),
+            entry("o1", Some("gone"), "2025-06-01T10:00:02Z", "user"),
+            serde_json::json!({
+                "uuid": "p1", "parentUuid": "u1", "sessionId": "s1",
+                "timestamp": "2025-06-01T10:00:01Z", "type": "progress",
+            }),
+            entry("u1", None, "2025-06-01T10:00:00Z", "user"),
+            entry("u2", Some("a2"), "2025-06-01T10:00:04Z", "user"),
+        ];
+        let body: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(&session_file, body).unwrap();
+
+        let messages = load_session_for_replay(session_file.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        let order: Vec<&str> = messages.iter().map(|m| m.uuid.as_str()).collect();
+        assert_eq!(order, vec!["u1", "a2", "u2", "o1"]);
+    }
+
+    // ── get_session_thread ─────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_session_thread_nests_branches_and_collects_orphans() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_file = temp_dir.path().join("s1.jsonl");
+        let entry = |uuid: &str, parent: Option<&str>, ts: &str, kind: &str| {
+            serde_json::json!({
+                "uuid": uuid, "parentUuid": parent, "sessionId": "s1", "timestamp": ts,
+                "type": kind, "message": {"role": kind, "content": uuid},
+            })
+        };
+        // "a1" has two replies (an edited prompt), "p1" is a noise entry
+        // inside the chain and "o1" references a parent that is not in the file.
+        let lines = [
+            entry("u1", None, "2025-06-01T10:00:00Z", "user"),
+            entry("a1", Some("u1"), "2025-06-01T10:00:01Z", "assistant"),
+            entry("u3", Some("a1"), "2025-06-01T10:00:05Z", "user"),
+            entry("u2", Some("a1"), "2025-06-01T10:00:02Z", "user"),
+            serde_json::json!({
+                "uuid": "p1", "parentUuid": "u2", "sessionId": "s1",
+                "timestamp": "2025-06-01T10:00:03Z", "type": "progress",
+            }),
+            entry("a2", Some("p1"), "2025-06-01T10:00:04Z", "assistant"),
+            entry("o1", Some("gone"), "2025-06-01T09:00:00Z", "user"),
+        ];
+        let body: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(&session_file, body).unwrap();
+
+        let messages = load_session_messages(
+            session_file.to_string_lossy().to_string(),
+            Some(true),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let tree = build_thread(messages);
+
+        // (id, depth) in pre-order, "-" for the synthetic orphan root
+        fn flatten(nodes: &[ThreadNode], out: &mut Vec<(String, Option<i32>)>) {
+            for node in nodes {
+                out.push(match &node.message {
+                    Some(msg) => (msg.id.clone(), msg.depth),
+                    None => ("-".to_string(), None),
+                });
+                flatten(&node.children, out);
+            }
+        }
+        let mut flat = Vec::new();
+        flatten(&tree, &mut flat);
+        let expected: Vec<(String, Option<i32>)> = [
+            ("u1", Some(0)),
+            ("a1", Some(1)),
+            ("u2", Some(2)),
+            ("a2", Some(3)),
+            ("u3", Some(2)),
+            ("-", None),
+            ("o1", Some(1)),
+        ]
+        .into_iter()
+        .map(|(id, depth)| (id.to_string(), depth))
+        .collect();
+        assert_eq!(flat, expected);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].children[0].children.len(), 2);
+    }
+
+    #[test]
+    fn test_session_thread_handles_long_chains_and_cycles() {
+        let message = |id: String, parent: Option<String>| UniversalMessage {
+            parent_id: parent,
+            timestamp: "2025-06-01T10:00:00Z".to_string(),
+            ..claude_message_to_universal(
+                &log_entry_to_claude_message(
+                    serde_json::from_value(serde_json::json!({
+                        "uuid": id, "sessionId": "s1", "type": "user",
+                        "timestamp": "2025-06-01T10:00:00Z",
+                        "message": {"role": "user", "content": "hi"},
+                    }))
+                    .unwrap(),
+                    0,
+                ),
+                "p".to_string(),
+                "s".to_string(),
+                0,
+            )
+        };
+        let chain_len = 5_000;
+        let mut messages: Vec<UniversalMessage> = (0..chain_len)
+            .map(|i| message(format!("m{i}"), (i > 0).then(|| format!("m{}", i - 1))))
+            .collect();
+        // Two messages that only point at each other
+        messages.push(message("x".to_string(), Some("y".to_string())));
+        messages.push(message("y".to_string(), Some("x".to_string())));
+
+        // Building, serializing and dropping the tree must fit in the small
+        // stack of an async runtime worker
+        let json = std::thread::Builder::new()
+            .stack_size(2 * 1024 * 1024)
+            .spawn(move || serialize_thread(build_thread(messages)).unwrap())
+            .unwrap()
+            .join()
+            .unwrap();
+        assert!(json.starts_with("[{\"message\":{"));
+        assert!(json.contains("\"m4999\""));
+        // The cycle lands under the synthetic root
+        assert!(json.contains("{\"message\":null,\"children\":["));
+        assert!(json.contains("\"x\"") && json.contains("\"y\""));
+    }
+
+    // ── source_id / provider_root ──────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_loaded_messages_point_at_session_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_path = write_large_session(&temp_dir);
+        let root = temp_dir.path().to_string_lossy().to_string();
+
+        let messages = load_session_messages(session_path.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(messages[0].source_id, session_path);
+        assert_eq!(messages[0].provider_root.as_deref(), Some(root.as_str()));
+
+        let page =
+            load_session_messages_paginated(session_path.clone(), 0, 10, None, None, None, None)
+                .await
+                .unwrap();
+        let first = &page.messages[0];
+        assert_eq!(first.source_id, session_path);
+        assert_eq!(first.provider_root.as_deref(), Some(root.as_str()));
+    }
+    // ── first_user_prompt ──────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_first_user_prompt_is_separate_from_summary() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let prompt = format!(
+            "Refactor the parser. {}",
+            "Keep the tests green. ".repeat(10)
+        );
+        let lines = [
+            serde_json::json!({"type": "summary", "summary": "Parser refactor", "leafUuid": "u2"}),
+            serde_json::json!({
+                "uuid": "u1", "sessionId": "s1", "timestamp": "2025-06-01T10:00:00Z",
+                "type": "user",
+                "message": {"role": "user", "content": [{"type": "text", "text": prompt}]},
+            }),
+            serde_json::json!({
+                "uuid": "u2", "sessionId": "s1", "timestamp": "2025-06-01T10:00:05Z",
+                "type": "assistant", "message": {"role": "assistant", "content": "On it."},
+            }),
+        ];
+        let body: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(temp_dir.path().join("s1.jsonl"), body).unwrap();
+
+        let project_path = temp_dir.path().to_string_lossy().to_string();
+        let sessions = collect_project_sessions(project_path, None, None, None, None)
+            .await
+            .unwrap();
+        let session = &sessions[0];
+        assert_eq!(session.summary.as_deref(), Some("Parser refactor"));
+        assert_eq!(session.first_user_prompt.as_deref(), Some(prompt.trim()));
+
+        let long = serde_json::json!("x".repeat(FIRST_USER_PROMPT_MAX_CHARS + 1)).to_string();
+        let capped = extract_first_user_prompt(Some(&long)).unwrap();
+        assert_eq!(capped.chars().count(), FIRST_USER_PROMPT_MAX_CHARS + 3);
+        let image_only = extract_first_user_prompt(Some(r#"[{"type":"image"}]"#));
+        assert_eq!(image_only, None);
+    }
 }
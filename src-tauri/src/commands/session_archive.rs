@@ -0,0 +1,497 @@
+//! Archive Claude sessions into a `.tar.gz` file and restore them.
+//!
+//! Each session is stored as `<project-folder>/<uuid>.jsonl`, together with
+//! its associated `<uuid>/` folder (subagents, tool-results) when present, so
+//! an archive unpacked into `~/.claude/projects` restores the sessions in
+//! place. [`restore_sessions`] extracts the JSONL files into one project.
+
+use crate::commands::session_delete::{trash_session, validate_session_file};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use tauri::command;
+
+/// Result of [`archive_sessions`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveReport {
+    pub archive_path: String,
+    pub archived_sessions: Vec<String>,
+    /// Size of the archived session files and folders.
+    pub original_bytes: u64,
+    pub archive_bytes: u64,
+    /// Disk space freed: `original_bytes - archive_bytes` when the originals
+    /// were removed, otherwise 0.
+    pub bytes_saved: u64,
+    pub originals_removed: bool,
+}
+
+/// A validated session and its path inside the archive.
+struct ArchiveEntry {
+    path: PathBuf,
+    name: PathBuf,
+}
+
+/// Writes the given sessions into a new `.tar.gz` at `archive_path` and, with
+/// `remove_originals`, moves the archived sessions to the trash afterwards.
+///
+/// Every session must pass the same checks as [`delete_session`] and live
+/// directly in a project folder of a Claude `projects` tree. The archive must
+/// not exist yet. Nothing is removed unless the whole archive was written.
+///
+/// [`delete_session`]: crate::commands::session_delete::delete_session
+#[command]
+pub async fn archive_sessions(
+    session_paths: Vec<String>,
+    archive_path: String,
+    remove_originals: bool,
+) -> Result<ArchiveReport, String> {
+    if session_paths.is_empty() {
+        return Err("No sessions selected for archiving".to_string());
+    }
+    let archive = Path::new(&archive_path);
+    validate_archive_path(archive)?;
+
+    let entries = session_paths
+        .iter()
+        .map(|path| archive_entry(Path::new(path)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let original_bytes = entries.iter().map(|e| session_size(&e.path)).sum();
+
+    if let Err(e) = write_archive(archive, &entries) {
+        // Never leave a truncated archive behind
+        let _ = fs::remove_file(archive);
+        return Err(e);
+    }
+    let archive_bytes = fs::metadata(archive)
+        .map_err(|e| format!("Failed to read archive size: {e}"))?
+        .len();
+
+    if remove_originals {
+        for entry in &entries {
+            trash_session(&entry.path)?;
+        }
+    }
+
+    Ok(ArchiveReport {
+        archive_path,
+        archived_sessions: entries
+            .iter()
+            .map(|e| e.path.to_string_lossy().to_string())
+            .collect(),
+        original_bytes,
+        archive_bytes,
+        bytes_saved: if remove_originals {
+            original_bytes.saturating_sub(archive_bytes)
+        } else {
+            0
+        },
+        originals_removed: remove_originals,
+    })
+}
+
+fn validate_archive_path(archive: &Path) -> Result<(), String> {
+    if !archive.is_absolute() {
+        return Err("Archive path must be absolute".to_string());
+    }
+    let name = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if !(name.ends_with(".tar.gz") || name.ends_with(".tgz")) {
+        return Err("Archive path must end in .tar.gz or .tgz".to_string());
+    }
+    if fs::symlink_metadata(archive).is_ok() {
+        return Err(format!("Archive already exists: {}", archive.display()));
+    }
+    Ok(())
+}
+
+/// Validate a session path and name it `<project-folder>/<file>` in the
+/// archive. The project folder must sit directly in a `projects` directory.
+fn archive_entry(path: &Path) -> Result<ArchiveEntry, String> {
+    validate_session_file(path)?;
+
+    let canonical =
+        fs::canonicalize(path).map_err(|e| format!("Failed to resolve session path: {e}"))?;
+    let project_dir = canonical
+        .parent()
+        .ok_or_else(|| "Session is not inside a project folder".to_string())?;
+    let in_projects_tree = project_dir
+        .parent()
+        .and_then(|p| p.file_name())
+        .is_some_and(|n| n == "projects");
+    if !in_projects_tree {
+        return Err(format!(
+            "Session is not inside a Claude projects folder: {}",
+            path.display()
+        ));
+    }
+
+    let (Some(project_name), Some(file_name)) = (project_dir.file_name(), canonical.file_name())
+    else {
+        return Err("Invalid session path".to_string());
+    };
+    Ok(ArchiveEntry {
+        name: Path::new(project_name).join(file_name),
+        path: canonical,
+    })
+}
+
+/// Bytes of a session file plus its associated folder, if any.
+fn session_size(path: &Path) -> u64 {
+    let file_bytes = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let dir_bytes: u64 = walkdir::WalkDir::new(path.with_extension(""))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+    file_bytes + dir_bytes
+}
+
+fn write_archive(archive: &Path, entries: &[ArchiveEntry]) -> Result<(), String> {
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(archive)
+        .map_err(|e| format!("Failed to create archive: {e}"))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    // Store symlinks as links rather than archiving what they point to
+    builder.follow_symlinks(false);
+
+    for entry in entries {
+        builder
+            .append_path_with_name(&entry.path, &entry.name)
+            .map_err(|e| format!("Failed to add {} to archive: {e}", entry.path.display()))?;
+
+        let associated_dir = entry.path.with_extension("");
+        if let Ok(dir_meta) = fs::symlink_metadata(&associated_dir) {
+            if !dir_meta.file_type().is_symlink() && dir_meta.is_dir() {
+                builder
+                    .append_dir_all(entry.name.with_extension(""), &associated_dir)
+                    .map_err(|e| {
+                        format!("Failed to add {} to archive: {e}", associated_dir.display())
+                    })?;
+            }
+        }
+    }
+
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to finish archive: {e}"))?;
+    Ok(())
+}
+
+/// Extracts the JSONL files of a `.tar.gz` archive into `dest_project_path`.
+///
+/// Entries are placed relative to their project folder in the archive, so
+/// `<project>/<uuid>.jsonl` and `<project>/<uuid>/subagents/*.jsonl` land at
+/// `<dest>/<uuid>.jsonl` and `<dest>/<uuid>/subagents/`. Other files are
+/// ignored, as are files with no parseable JSON line. Existing files are
+/// skipped unless `overwrite` is set. Returns the restored paths.
+#[command]
+pub async fn restore_sessions(
+    archive_path: String,
+    dest_project_path: String,
+    overwrite: bool,
+) -> Result<Vec<String>, String> {
+    let dest = Path::new(&dest_project_path);
+    if !dest.is_absolute() {
+        return Err("Destination project path must be absolute".to_string());
+    }
+    let dest_meta = fs::symlink_metadata(dest)
+        .map_err(|_| format!("Destination project folder not found: {dest_project_path}"))?;
+    if !dest_meta.file_type().is_dir() {
+        return Err("Destination project path must be a directory".to_string());
+    }
+
+    let file = fs::File::open(&archive_path).map_err(|e| format!("Failed to open archive: {e}"))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read archive: {e}"))?;
+
+    let mut restored = Vec::new();
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Failed to read archive: {e}"))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(relative) = entry.path().ok().and_then(|p| restore_target(&p)) else {
+            continue;
+        };
+
+        let target = dest.join(&relative);
+        match fs::symlink_metadata(&target) {
+            Ok(meta) if meta.file_type().is_symlink() || !meta.is_file() => continue,
+            Ok(_) if !overwrite => continue,
+            _ => {}
+        }
+
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to read {} from archive: {e}", relative.display()))?;
+        if !has_jsonl_line(&content) {
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            create_dirs_in(dest, parent)?;
+        }
+        fs::write(&target, content)
+            .map_err(|e| format!("Failed to write {}: {e}", target.display()))?;
+        restored.push(target.to_string_lossy().to_string());
+    }
+
+    Ok(restored)
+}
+
+/// Path below the destination project for an archive entry, or `None` for
+/// entries that are not `.jsonl` files or would escape the project folder.
+/// The leading project folder of `<project>/<file>` entries is dropped.
+fn restore_target(entry_path: &Path) -> Option<PathBuf> {
+    let mut parts = Vec::new();
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    if parts.len() > 1 {
+        parts.remove(0);
+    }
+    let relative: PathBuf = parts.iter().collect();
+    (relative.extension().and_then(|e| e.to_str()) == Some("jsonl")).then_some(relative)
+}
+
+/// Create `dir` and its missing parents, refusing to pass through symlinks
+/// so that writes stay inside `root`.
+fn create_dirs_in(root: &Path, dir: &Path) -> Result<(), String> {
+    let relative = dir
+        .strip_prefix(root)
+        .map_err(|_| format!("Refusing to write outside {}", root.display()))?;
+    let mut current = root.to_path_buf();
+    for component in relative.components() {
+        current.push(component);
+        match fs::symlink_metadata(&current) {
+            Ok(meta) if meta.file_type().is_dir() => {}
+            Ok(_) => {
+                return Err(format!(
+                    "Refusing to restore through non-directory {}",
+                    current.display()
+                ))
+            }
+            Err(_) => fs::create_dir(&current)
+                .map_err(|e| format!("Failed to create {}: {e}", current.display()))?,
+        }
+    }
+    Ok(())
+}
+
+/// True when at least one line of `content` is valid JSON.
+fn has_jsonl_line(content: &[u8]) -> bool {
+    content
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .any(|line| serde_json::from_slice::<serde_json::Value>(line).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use tempfile::TempDir;
+
+    fn write_session(root: &Path, project: &str, id: &str, content: &str) -> String {
+        let dir = root.join("projects").join(project);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{id}.jsonl"));
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn archives_sessions_with_associated_folders() {
+        let tmp = TempDir::new().unwrap();
+        let content = r#"{"type":"user","message":{"role":"user","content":"hello"}}"#.repeat(50);
+        let s1 = write_session(tmp.path(), "-home-me-app", "s1", &content);
+        let s2 = write_session(tmp.path(), "-home-me-lib", "s2", &content);
+        let subagents = tmp.path().join("projects/-home-me-app/s1/subagents");
+        fs::create_dir_all(&subagents).unwrap();
+        fs::write(subagents.join("agent-1.jsonl"), "{}").unwrap();
+        let archive = tmp.path().join("old.tar.gz");
+
+        let report = archive_sessions(
+            vec![s1.clone(), s2],
+            archive.to_string_lossy().into(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.archived_sessions.len(), 2);
+        assert_eq!(report.original_bytes, 2 * content.len() as u64 + 2);
+        assert!(report.archive_bytes > 0);
+        assert_eq!(report.bytes_saved, 0);
+        assert!(Path::new(&s1).exists());
+
+        let mut tar = tar::Archive::new(GzDecoder::new(fs::File::open(&archive).unwrap()));
+        let mut names: Vec<String> = tar
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert!(names.contains(&"-home-me-app/s1.jsonl".to_string()));
+        assert!(names.contains(&"-home-me-lib/s2.jsonl".to_string()));
+        assert!(names.contains(&"-home-me-app/s1/subagents/agent-1.jsonl".to_string()));
+    }
+
+    #[tokio::test]
+    async fn rejects_sessions_outside_projects_tree() {
+        let tmp = TempDir::new().unwrap();
+        let stray = tmp.path().join("s1.jsonl");
+        fs::write(&stray, "{}").unwrap();
+        let archive = tmp.path().join("old.tar.gz");
+
+        let err = archive_sessions(
+            vec![stray.to_string_lossy().into()],
+            archive.to_string_lossy().into(),
+            false,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.starts_with("Session is not inside a Claude projects folder"));
+        assert!(!archive.exists());
+    }
+
+    #[tokio::test]
+    async fn rejects_existing_or_misnamed_archive() {
+        let tmp = TempDir::new().unwrap();
+        let s1 = write_session(tmp.path(), "-home-me-app", "s1", "{}");
+
+        let existing = tmp.path().join("old.tar.gz");
+        fs::write(&existing, "keep me").unwrap();
+        let err = archive_sessions(vec![s1.clone()], existing.to_string_lossy().into(), false)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("Archive already exists"));
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "keep me");
+
+        let zip = tmp.path().join("old.zip");
+        let err = archive_sessions(vec![s1], zip.to_string_lossy().into(), false)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "Archive path must end in .tar.gz or .tgz");
+    }
+
+    fn write_test_archive(path: &Path, files: &[(&str, &str)]) {
+        let file = fs::File::create(path).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            // Raw name bytes: `set_path` refuses the `..` entries under test
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn restores_archived_sessions_into_project() {
+        let tmp = TempDir::new().unwrap();
+        let line = r#"{"type":"user","message":{"role":"user","content":"hello"}}"#;
+        let s1 = write_session(tmp.path(), "-home-me-app", "s1", line);
+        let subagents = tmp.path().join("projects/-home-me-app/s1/subagents");
+        fs::create_dir_all(&subagents).unwrap();
+        fs::write(subagents.join("agent-1.jsonl"), line).unwrap();
+        let archive = tmp.path().join("old.tar.gz");
+        archive_sessions(vec![s1], archive.to_string_lossy().into(), false)
+            .await
+            .unwrap();
+
+        let dest = tmp.path().join("restored");
+        fs::create_dir(&dest).unwrap();
+        let mut restored = restore_sessions(
+            archive.to_string_lossy().into(),
+            dest.to_string_lossy().into(),
+            false,
+        )
+        .await
+        .unwrap();
+        restored.sort();
+
+        assert_eq!(
+            restored,
+            vec![
+                dest.join("s1.jsonl").to_string_lossy().to_string(),
+                dest.join("s1/subagents/agent-1.jsonl")
+                    .to_string_lossy()
+                    .to_string(),
+            ]
+        );
+        assert_eq!(fs::read_to_string(dest.join("s1.jsonl")).unwrap(), line);
+    }
+
+    #[tokio::test]
+    async fn restore_skips_existing_invalid_and_escaping_entries() {
+        let tmp = TempDir::new().unwrap();
+        let archive = tmp.path().join("old.tar.gz");
+        write_test_archive(
+            &archive,
+            &[
+                ("proj/existing.jsonl", "{\"type\":\"user\"}"),
+                ("proj/garbage.jsonl", "not json\n\n"),
+                ("proj/notes.txt", "{}"),
+                ("proj/../../evil.jsonl", "{}"),
+                ("proj/new.jsonl", "\n{\"type\":\"summary\"}\n"),
+            ],
+        );
+        let dest = tmp.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+        fs::write(dest.join("existing.jsonl"), "keep").unwrap();
+
+        let restored = restore_sessions(
+            archive.to_string_lossy().into(),
+            dest.to_string_lossy().into(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            restored,
+            vec![dest.join("new.jsonl").to_string_lossy().to_string()]
+        );
+        assert_eq!(
+            fs::read_to_string(dest.join("existing.jsonl")).unwrap(),
+            "keep"
+        );
+        assert!(!dest.join("garbage.jsonl").exists());
+        assert!(!tmp.path().join("evil.jsonl").exists());
+
+        let restored = restore_sessions(
+            archive.to_string_lossy().into(),
+            dest.to_string_lossy().into(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(
+            fs::read_to_string(dest.join("existing.jsonl")).unwrap(),
+            "{\"type\":\"user\"}"
+        );
+    }
+}
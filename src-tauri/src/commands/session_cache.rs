@@ -0,0 +1,170 @@
+//! In-memory cache of parsed Claude sessions for `load_project_sessions`.
+//!
+//! Entries are keyed by session file path and reused while the file's
+//! modification time and size are unchanged, so reopening a project only
+//! re-parses the files that changed since the last load. The cache lives in
+//! Tauri-managed [`SessionCacheState`]; `clear_session_cache` empties it.
+
+use crate::models::ClaudeSession;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
+use tauri::State;
+
+/// Options that change the `ClaudeSession` parsed from a file; a cached entry
+/// is only reused for the options it was parsed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SessionParseOptions {
+    pub exclude_sidechain: bool,
+    pub include_noise: bool,
+    pub max_line_bytes: usize,
+}
+
+struct CachedSession {
+    modified: SystemTime,
+    size: u64,
+    options: SessionParseOptions,
+    /// None when the file was parsed but yielded no session
+    session: Option<ClaudeSession>,
+}
+
+/// Tauri-managed cache of parsed sessions, keyed by file path.
+#[derive(Default)]
+pub struct SessionCacheState {
+    entries: Mutex<HashMap<PathBuf, CachedSession>>,
+}
+
+impl SessionCacheState {
+    /// A poisoned lock only means a parse panicked mid-update; the map itself
+    /// is still consistent, so keep using it.
+    fn entries(&self) -> MutexGuard<'_, HashMap<PathBuf, CachedSession>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The cached parse of `path` if the file's mtime and size are unchanged.
+    /// `Some(None)` is a cached "no session in this file".
+    pub(crate) fn get(
+        &self,
+        path: &Path,
+        metadata: &fs::Metadata,
+        options: SessionParseOptions,
+    ) -> Option<Option<ClaudeSession>> {
+        let modified = metadata.modified().ok()?;
+        let entries = self.entries();
+        let cached = entries.get(path)?;
+        (cached.modified == modified && cached.size == metadata.len() && cached.options == options)
+            .then(|| cached.session.clone())
+    }
+
+    /// Store the parse of `path`, replacing any stale entry.
+    pub(crate) fn insert(
+        &self,
+        path: PathBuf,
+        metadata: &fs::Metadata,
+        options: SessionParseOptions,
+        session: Option<ClaudeSession>,
+    ) {
+        let Ok(modified) = metadata.modified() else {
+            return;
+        };
+        self.entries().insert(
+            path,
+            CachedSession {
+                modified,
+                size: metadata.len(),
+                options,
+                session,
+            },
+        );
+    }
+
+    /// Drop entries under `dir` whose file is no longer in `present`.
+    pub(crate) fn retain_present(&self, dir: &Path, present: &HashSet<PathBuf>) {
+        self.entries()
+            .retain(|path, _| !path.starts_with(dir) || present.contains(path));
+    }
+
+    fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    fn clear(&self) {
+        self.entries().clear();
+    }
+}
+
+/// Forget all cached sessions so the next load re-parses every file.
+#[tauri::command]
+pub async fn clear_session_cache(cache: State<'_, SessionCacheState>) -> Result<(), String> {
+    log::debug!("Clearing {} cached session(s)", cache.len());
+    cache.clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::session::collect_project_sessions;
+
+    fn session_line(prompt: &str) -> String {
+        format!(
+            r#"{{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-01T10:00:00Z","type":"user","message":{{"role":"user","content":"{prompt}"}}}}"#
+        ) + "\n"
+    }
+
+    async fn load(project_path: &str, cache: &SessionCacheState) -> Vec<ClaudeSession> {
+        collect_project_sessions(project_path.to_string(), None, None, None, Some(cache))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reuses_sessions_until_mtime_or_size_changes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        let session_file = project_dir.join("s1.jsonl");
+        fs::write(&session_file, session_line("alpha")).unwrap();
+        let project_path = project_dir.to_string_lossy().to_string();
+        let cache = SessionCacheState::default();
+
+        let prompt = |sessions: Vec<ClaudeSession>| sessions[0].first_user_prompt.clone();
+
+        assert_eq!(
+            prompt(load(&project_path, &cache).await).as_deref(),
+            Some("alpha")
+        );
+
+        // Same size and mtime: the cached parse is served
+        let modified = fs::metadata(&session_file).unwrap().modified().unwrap();
+        fs::write(&session_file, session_line("omega")).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&session_file)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(
+            prompt(load(&project_path, &cache).await).as_deref(),
+            Some("alpha")
+        );
+
+        // A size change invalidates the entry
+        fs::write(&session_file, session_line("omega!")).unwrap();
+        assert_eq!(
+            prompt(load(&project_path, &cache).await).as_deref(),
+            Some("omega!")
+        );
+
+        assert_eq!(cache.len(), 1);
+
+        // Deleted files are dropped from the cache
+        fs::remove_file(&session_file).unwrap();
+        assert!(load(&project_path, &cache).await.is_empty());
+        assert_eq!(cache.len(), 0);
+    }
+}
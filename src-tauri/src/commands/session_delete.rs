@@ -1,5 +1,6 @@
 use std::fs;
-use std::path::Path;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 use tauri::command;
 
 /// Moves a Claude session's JSONL file and its associated folder
@@ -11,7 +12,54 @@ use tauri::command;
 #[command]
 pub async fn delete_session(file_path: String) -> Result<(), String> {
     let path = Path::new(&file_path);
+    validate_session_file(path)?;
+    trash_session(path)
+}
 
+/// Finds the sessions of a project folder that hold no real messages — only
+/// summary / file-history-snapshot lines, as left behind by aborted Claude
+/// runs — and moves them to the trash like [`delete_session`].
+///
+/// With `dry_run` nothing is deleted. Returns the affected session paths.
+/// Only plain `.jsonl` files directly inside `project_path` are considered,
+/// and each passes the same checks as a single-session delete.
+#[command]
+pub async fn delete_empty_sessions(
+    project_path: String,
+    dry_run: bool,
+) -> Result<Vec<String>, String> {
+    let project_dir = Path::new(&project_path);
+    if !project_dir.is_absolute() {
+        return Err("Project path must be absolute".to_string());
+    }
+    let metadata = fs::symlink_metadata(project_dir)
+        .map_err(|_| format!("Project folder not found: {project_path}"))?;
+    if !metadata.file_type().is_dir() {
+        return Err("Project path must be a directory".to_string());
+    }
+
+    let entries =
+        fs::read_dir(project_dir).map_err(|e| format!("Failed to read project folder: {e}"))?;
+    let mut empty_sessions: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| validate_session_file(path).is_ok())
+        .filter(|path| is_empty_session(path))
+        .collect();
+    empty_sessions.sort();
+
+    let mut affected = Vec::with_capacity(empty_sessions.len());
+    for path in empty_sessions {
+        if !dry_run {
+            trash_session(&path)?;
+        }
+        affected.push(path.to_string_lossy().to_string());
+    }
+    Ok(affected)
+}
+
+/// Checks shared by single and bulk deletes: an absolute, plain (non-symlink)
+/// `.jsonl` file with a well-formed session ID.
+pub(crate) fn validate_session_file(path: &Path) -> Result<(), String> {
     if !path.is_absolute() {
         return Err("Session path must be absolute".to_string());
     }
@@ -32,8 +80,8 @@ pub async fn delete_session(file_path: String) -> Result<(), String> {
         return Err("Invalid session ID format".to_string());
     }
 
-    let metadata =
-        fs::symlink_metadata(path).map_err(|_| format!("Session file not found: {file_path}"))?;
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|_| format!("Session file not found: {}", path.display()))?;
 
     if metadata.file_type().is_symlink() {
         return Err("Session file cannot be a symlink".to_string());
@@ -43,6 +91,39 @@ pub async fn delete_session(file_path: String) -> Result<(), String> {
         return Err("Session target must be a regular .jsonl file".to_string());
     }
 
+    Ok(())
+}
+
+/// True when no line of the session is a user or assistant message.
+/// Unparseable lines count as content, so a damaged session is never
+/// mistaken for an empty one.
+fn is_empty_session(path: &Path) -> bool {
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    for line in BufReader::new(file).lines() {
+        let Ok(line) = line else {
+            return false;
+        };
+        let line = line.trim_start_matches('\u{feff}').trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+            return false;
+        };
+        if matches!(
+            entry.get("type").and_then(|t| t.as_str()),
+            Some("user" | "assistant")
+        ) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Trash a validated session file, then its associated folder.
+pub(crate) fn trash_session(path: &Path) -> Result<(), String> {
     // Trash the .jsonl first (authoritative artifact), then the associated folder.
     trash::delete(path).map_err(|e| format!("Failed to move session file to trash: {e}"))?;
 
@@ -143,4 +224,58 @@ mod tests {
             .unwrap_err();
         assert!(err.starts_with("Session file not found"));
     }
+
+    #[tokio::test]
+    async fn empty_sessions_dry_run_lists_only_sessions_without_messages() {
+        let dir = TempDir::new().unwrap();
+        let write = |name: &str, content: &str| fs::write(dir.path().join(name), content).unwrap();
+        write(
+            "aborted.jsonl",
+            concat!(
+                r#"{"type":"summary","summary":"New chat"}"#,
+                "\n",
+                r#"{"type":"file-history-snapshot","messageId":"m1"}"#,
+                "\n",
+            ),
+        );
+        write("blank.jsonl", "");
+        write(
+            "real.jsonl",
+            concat!(
+                r#"{"type":"summary","summary":"Fix"}"#,
+                "\n",
+                r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+                "\n",
+            ),
+        );
+        write("damaged.jsonl", "{not json\n");
+        write("notes.txt", "");
+        write("bad name.jsonl", "");
+
+        let affected = delete_empty_sessions(dir.path().to_string_lossy().into(), true)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = affected
+            .iter()
+            .map(|p| {
+                Path::new(p)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(names, vec!["aborted.jsonl", "blank.jsonl"]);
+        // Dry run leaves everything in place
+        assert!(dir.path().join("aborted.jsonl").exists());
+    }
+
+    #[tokio::test]
+    async fn empty_sessions_reject_relative_project_path() {
+        let err = delete_empty_sessions("relative/project".into(), true)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "Project path must be absolute");
+    }
 }
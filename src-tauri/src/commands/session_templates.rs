@@ -0,0 +1,209 @@
+//! Session templates
+//!
+//! A template pre-seeds a new session with a system prompt and an initial user
+//! message ("start a new session with my standard instructions").
+//! `create_session_from_template` writes the session through
+//! `create_claude_session`; templates themselves are persisted by name in the
+//! store plugin's `session-templates.json` (app data directory).
+
+use crate::commands::session_writer::{
+    create_claude_session, CreateSessionRequest, CreateSessionResponse, MessageInput,
+};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const SESSION_TEMPLATES_STORE: &str = "session-templates.json";
+const SESSION_TEMPLATES_KEY: &str = "templates";
+
+/// Reusable starting point for a new session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTemplate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    pub initial_message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>, // Working directory for the session (defaults to project path)
+}
+
+fn text_message(role: &str, text: &str) -> MessageInput {
+    MessageInput {
+        role: role.to_string(),
+        content: serde_json::Value::String(text.to_string()),
+        parent_id: None,
+        model: None,
+        tool_use: None,
+        tool_use_result: None,
+        usage: None,
+    }
+}
+
+/// Messages written for `template`: the system prompt (when non-blank)
+/// followed by the initial user message.
+fn template_messages(template: &SessionTemplate) -> Result<Vec<MessageInput>, String> {
+    if template.initial_message.trim().is_empty() {
+        return Err(format!(
+            "SESSION_TEMPLATE_INVALID: Template '{}' has an empty initial message",
+            template.name
+        ));
+    }
+
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = template
+        .system_prompt
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        messages.push(text_message("system", system_prompt));
+    }
+    messages.push(text_message("user", &template.initial_message));
+    Ok(messages)
+}
+
+/// Insert `template`, replacing a saved template with the same name.
+fn upsert_template(templates: &mut Vec<SessionTemplate>, template: SessionTemplate) {
+    match templates.iter_mut().find(|t| t.name == template.name) {
+        Some(existing) => *existing = template,
+        None => templates.push(template),
+    }
+}
+
+fn read_session_templates(app_handle: &AppHandle) -> Result<Vec<SessionTemplate>, String> {
+    let store = app_handle
+        .store(SESSION_TEMPLATES_STORE)
+        .map_err(|e| format!("SESSION_TEMPLATE_READ_ERROR: Failed to open store: {e}"))?;
+
+    // A missing or malformed value is treated as no templates
+    Ok(store
+        .get(SESSION_TEMPLATES_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn write_session_templates(
+    app_handle: &AppHandle,
+    templates: &[SessionTemplate],
+) -> Result<(), String> {
+    let store = app_handle
+        .store(SESSION_TEMPLATES_STORE)
+        .map_err(|e| format!("SESSION_TEMPLATE_WRITE_ERROR: Failed to open store: {e}"))?;
+    let value = serde_json::to_value(templates)
+        .map_err(|e| format!("SESSION_TEMPLATE_WRITE_ERROR: Failed to serialize templates: {e}"))?;
+    store.set(SESSION_TEMPLATES_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("SESSION_TEMPLATE_WRITE_ERROR: Failed to save store: {e}"))
+}
+
+/// Create a new session in `project_path` pre-seeded from `template`.
+#[tauri::command]
+pub async fn create_session_from_template(
+    project_path: String,
+    template: SessionTemplate,
+) -> Result<CreateSessionResponse, String> {
+    let messages = template_messages(&template)?;
+    create_claude_session(CreateSessionRequest {
+        project_path,
+        messages,
+        summary: template.summary,
+        cwd: template.cwd,
+    })
+    .await
+}
+
+/// List saved session templates.
+#[tauri::command]
+pub async fn get_session_templates(app_handle: AppHandle) -> Result<Vec<SessionTemplate>, String> {
+    read_session_templates(&app_handle)
+}
+
+/// Save a session template, replacing any template with the same name.
+#[tauri::command]
+pub async fn save_session_template(
+    app_handle: AppHandle,
+    template: SessionTemplate,
+) -> Result<(), String> {
+    if template.name.trim().is_empty() {
+        return Err("SESSION_TEMPLATE_INVALID: Template name is empty".to_string());
+    }
+    template_messages(&template)?;
+
+    let mut templates = read_session_templates(&app_handle)?;
+    upsert_template(&mut templates, template);
+    write_session_templates(&app_handle, &templates)
+}
+
+/// Delete the saved template called `name`. Returns whether one was removed.
+#[tauri::command]
+pub async fn delete_session_template(app_handle: AppHandle, name: String) -> Result<bool, String> {
+    let mut templates = read_session_templates(&app_handle)?;
+    let before = templates.len();
+    templates.retain(|t| t.name != name);
+    if templates.len() == before {
+        return Ok(false);
+    }
+    write_session_templates(&app_handle, &templates)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(system_prompt: Option<&str>) -> SessionTemplate {
+        SessionTemplate {
+            name: "review".to_string(),
+            system_prompt: system_prompt.map(str::to_string),
+            initial_message: "Review the latest diff.".to_string(),
+            summary: None,
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn upsert_replaces_by_name() {
+        let mut templates = vec![template(None)];
+        upsert_template(&mut templates, template(Some("Be strict.")));
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].system_prompt.as_deref(), Some("Be strict."));
+    }
+
+    #[test]
+    fn rejects_empty_initial_message() {
+        let mut invalid = template(None);
+        invalid.initial_message = "  ".to_string();
+        let err = template_messages(&invalid).unwrap_err();
+        assert!(err.starts_with("SESSION_TEMPLATE_INVALID"));
+    }
+
+    #[tokio::test]
+    async fn creates_seeded_session() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let response = create_session_from_template(
+            temp_dir.path().to_string_lossy().to_string(),
+            template(Some("Follow the team style guide.")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.message_count, 2);
+
+        let content = std::fs::read_to_string(&response.session_path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        // file-history-snapshot, system prompt, user message
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1]["type"], "system");
+        assert_eq!(
+            lines[1]["message"]["content"],
+            "Follow the team style guide."
+        );
+        assert_eq!(lines[2]["type"], "user");
+        assert_eq!(lines[2]["parentUuid"], lines[1]["uuid"]);
+        assert_eq!(lines[2]["sessionId"], response.session_id);
+    }
+}
@@ -1,7 +1,8 @@
+use crate::utils::find_line_ranges;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File, OpenOptions};
-use std::io::{BufWriter, Write};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -80,6 +81,8 @@ pub struct ExtractMessageRangeResponse {
     pub summary: Option<String>,
     pub message_count: usize,
     pub cwd: Option<String>, // Extracted working directory from source session
+    pub first_timestamp: Option<String>, // Original timestamp of the first extracted message
+    pub last_timestamp: Option<String>, // Original timestamp of the last extracted message
 }
 
 // ============================================================================
@@ -97,8 +100,8 @@ pub async fn create_claude_project(
         PathBuf::from(path)
     } else {
         // Use ~/.claude/projects/ as default
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+        let home_dir = crate::utils::resolve_home()
+            .ok_or_else(|| "Could not determine home directory".to_string())?;
         home_dir.join(".claude").join("projects")
     };
 
@@ -152,6 +155,11 @@ pub async fn create_claude_session(
         ));
     }
 
+    // Reject malformed content before creating any file
+    for msg in &request.messages {
+        validate_message_content(&msg.content)?;
+    }
+
     // Generate session ID (UUID)
     let session_id = Uuid::new_v4().to_string();
 
@@ -251,6 +259,11 @@ pub async fn append_to_claude_session(
         .and_then(|p| p.to_str())
         .ok_or_else(|| "Invalid session file path".to_string())?;
 
+    // Reject malformed content before appending anything
+    for msg in &messages {
+        validate_message_content(&msg.content)?;
+    }
+
     // Read the last message to get its UUID (for parent linking)
     let last_uuid = get_last_message_uuid(&session_file_path)?;
 
@@ -284,6 +297,107 @@ pub async fn append_to_claude_session(
     Ok(message_count)
 }
 
+/// Append an empty message that is filled in incrementally with
+/// `update_streaming_message`. Returns the new message's UUID.
+#[tauri::command]
+pub async fn append_streaming_message(
+    session_path: String,
+    role: String,
+) -> Result<String, String> {
+    if role != "user" && role != "assistant" {
+        return Err(format!("Invalid role for streaming message: {}", role));
+    }
+
+    let session_file_path = PathBuf::from(&session_path);
+    if !session_file_path.exists() {
+        return Err(format!(
+            "Session file does not exist: {}",
+            session_file_path.display()
+        ));
+    }
+
+    let session_id = session_file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| "Invalid session file name".to_string())?
+        .to_string();
+    let cwd_path = session_file_path
+        .parent()
+        .and_then(|p| p.to_str())
+        .ok_or_else(|| "Invalid session file path".to_string())?;
+
+    let last_uuid = get_last_message_uuid(&session_file_path)?;
+    let placeholder = MessageInput {
+        role,
+        content: serde_json::json!([{ "type": "text", "text": "" }]),
+        parent_id: None,
+        model: None,
+        tool_use: None,
+        tool_use_result: None,
+        usage: None,
+    };
+    let jsonl_msg =
+        convert_to_jsonl_format(&placeholder, &session_id, 0, cwd_path, last_uuid.as_deref())?;
+    let uuid = jsonl_msg
+        .get("uuid")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Failed to generate message UUID".to_string())?;
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .open(&session_file_path)
+        .map_err(|e| format!("Failed to open session file: {}", e))?;
+    ensure_trailing_newline(&mut file)?;
+    write_jsonl_line(&mut file, &jsonl_msg)?;
+
+    Ok(uuid)
+}
+
+/// Append `content_delta` to the text of the streaming message `uuid`.
+/// The message must still be the last line of the session; that line is
+/// rewritten in place so the file stays valid JSONL after every update.
+#[tauri::command]
+pub async fn update_streaming_message(
+    session_path: String,
+    uuid: String,
+    content_delta: String,
+) -> Result<(), String> {
+    let session_file_path = PathBuf::from(&session_path);
+    let data =
+        fs::read(&session_file_path).map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    let (start, end) = find_line_ranges(&data)
+        .into_iter()
+        .rev()
+        .find(|&(start, end)| !data[start..end].iter().all(u8::is_ascii_whitespace))
+        .ok_or_else(|| format!("Session file is empty: {}", session_path))?;
+
+    let mut msg: serde_json::Value = serde_json::from_slice(&data[start..end])
+        .map_err(|e| format!("Failed to parse JSONL line: {}", e))?;
+    if msg.get("uuid").and_then(|v| v.as_str()) != Some(uuid.as_str()) {
+        return Err(format!(
+            "Streaming message {} is not the last message of the session",
+            uuid
+        ));
+    }
+
+    append_text_delta(&mut msg, &content_delta)?;
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(&session_file_path)
+        .map_err(|e| format!("Failed to open session file: {}", e))?;
+    file.set_len(start as u64)
+        .map_err(|e| format!("Failed to truncate session file: {}", e))?;
+    file.seek(SeekFrom::Start(start as u64))
+        .map_err(|e| format!("Failed to seek session file: {}", e))?;
+    write_jsonl_line(&mut file, &msg)?;
+
+    Ok(())
+}
+
 /// Extract a range of messages from an existing session
 #[tauri::command]
 pub async fn extract_message_range(
@@ -378,6 +492,8 @@ pub async fn extract_message_range(
 
     // Convert to MessageInput format
     let mut converted_messages: Vec<MessageInput> = Vec::new();
+    let mut first_timestamp: Option<String> = None;
+    let mut last_timestamp: Option<String> = None;
 
     for msg in extracted_messages {
         // Extract the nested "message" object
@@ -426,22 +542,28 @@ pub async fn extract_message_range(
             None
         };
 
-        // Extract tool_use and tool_use_result from top level
-        let tool_use = msg.get("toolUse").cloned();
+        // Real sessions keep tool calls as tool_use blocks in the content
+        // array; older writers used a top-level toolUse field
+        let tool_use = msg
+            .get("toolUse")
+            .cloned()
+            .or_else(|| first_tool_use_block(&content));
         let tool_use_result = msg.get("toolUseResult").cloned();
 
-        // Build parent chain (we'll set parent_id to previous message for linear chain)
-        let parent_id = if converted_messages.is_empty() {
-            None
-        } else {
-            // Link to previous message (we'll generate UUIDs later)
-            Some("previous".to_string()) // Placeholder
-        };
+        // Keep the source time span (the converted messages get new timestamps)
+        if let Some(timestamp) = msg.get("timestamp").and_then(|v| v.as_str()) {
+            if first_timestamp.is_none() {
+                first_timestamp = Some(timestamp.to_string());
+            }
+            last_timestamp = Some(timestamp.to_string());
+        }
 
         converted_messages.push(MessageInput {
             role,
             content,
-            parent_id,
+            // The writer re-links extracted messages into a linear chain
+            // with freshly generated UUIDs
+            parent_id: None,
             model,
             tool_use,
             tool_use_result,
@@ -461,9 +583,39 @@ pub async fn extract_message_range(
         summary,
         message_count,
         cwd: extracted_cwd,
+        first_timestamp,
+        last_timestamp,
     })
 }
 
+/// Copy a session up to and including `message_id` into a new session in the
+/// same project folder. `cwd` defaults to the source session's directory.
+pub(crate) async fn fork_session_until(
+    session_path: &str,
+    message_id: &str,
+    cwd: Option<String>,
+) -> Result<CreateSessionResponse, String> {
+    let project_path = PathBuf::from(session_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid session file path".to_string())?;
+
+    let extracted = extract_message_range(ExtractMessageRangeRequest {
+        session_path: session_path.to_string(),
+        start_message_id: None,
+        end_message_id: Some(message_id.to_string()),
+    })
+    .await?;
+
+    create_claude_session(CreateSessionRequest {
+        project_path,
+        messages: extracted.messages,
+        summary: extracted.summary,
+        cwd: cwd.or(extracted.cwd),
+    })
+    .await
+}
+
 // ============================================================================
 // HELPER FUNCTIONS
 // ============================================================================
@@ -490,6 +642,77 @@ fn create_summary_message(
     })
 }
 
+/// Helper: Check that message content has a shape the viewer can load:
+/// a string, or an array of content items that each carry a string `type`.
+fn validate_message_content(content: &serde_json::Value) -> Result<(), String> {
+    match content {
+        serde_json::Value::String(_) => Ok(()),
+        serde_json::Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                if item.get("type").and_then(|t| t.as_str()).is_none() {
+                    return Err(format!(
+                        "SESSION_INVALID_CONTENT: Content item {} must be an object with a string 'type'",
+                        idx
+                    ));
+                }
+            }
+            Ok(())
+        }
+        other => Err(format!(
+            "SESSION_INVALID_CONTENT: Message content must be a string or an array of content items, got {}",
+            json_type_name(other)
+        )),
+    }
+}
+
+/// Helper: Whether `value` is a `{ "type": "tool_use", ... }` content block
+fn is_tool_use_block(value: &serde_json::Value) -> bool {
+    value.get("type").and_then(|t| t.as_str()) == Some("tool_use")
+}
+
+/// Helper: First tool_use block of a content array
+fn first_tool_use_block(content: &serde_json::Value) -> Option<serde_json::Value> {
+    content
+        .as_array()?
+        .iter()
+        .find(|item| is_tool_use_block(item))
+        .cloned()
+}
+
+/// Helper: Add a tool_use block to message content unless a block with the
+/// same id is already present. String content becomes a text block first.
+fn merge_tool_use_block(content: &mut serde_json::Value, tool_use: &serde_json::Value) {
+    if let serde_json::Value::String(text) = content {
+        *content = if text.is_empty() {
+            serde_json::json!([])
+        } else {
+            serde_json::json!([{ "type": "text", "text": text }])
+        };
+    }
+    let Some(items) = content.as_array_mut() else {
+        return;
+    };
+    let id = tool_use.get("id");
+    let already_present = items
+        .iter()
+        .any(|item| is_tool_use_block(item) && (id.is_none() || item.get("id") == id));
+    if !already_present {
+        items.push(tool_use.clone());
+    }
+}
+
+/// Helper: JSON type name for error messages
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
 /// Helper: Convert MessageInput to Claude Code JSONL format
 fn convert_to_jsonl_format(
     msg: &MessageInput,
@@ -498,14 +721,26 @@ fn convert_to_jsonl_format(
     project_path: &str,
     previous_uuid: Option<&str>,
 ) -> Result<serde_json::Value, String> {
+    validate_message_content(&msg.content)?;
+
     // Generate UUID for this message
     let msg_uuid = Uuid::new_v4().to_string();
     let timestamp = Utc::now().to_rfc3339();
 
+    // Re-emit a tool_use block into the content array unless it is already there
+    let mut content = msg.content.clone();
+    let mut top_level_tool_use = msg.tool_use.clone();
+    if let Some(tool_use) = &msg.tool_use {
+        if is_tool_use_block(tool_use) {
+            merge_tool_use_block(&mut content, tool_use);
+            top_level_tool_use = None;
+        }
+    }
+
     // Build message object
     let mut message_obj = serde_json::json!({
         "role": msg.role,
-        "content": msg.content,
+        "content": content,
     });
 
     // Add optional fields to message object
@@ -591,10 +826,10 @@ fn convert_to_jsonl_format(
         }
     }
 
-    // Add optional tool_use
-    if let Some(tool_use) = &msg.tool_use {
+    // Add optional legacy (non-block) tool_use
+    if let Some(tool_use) = top_level_tool_use {
         if let Some(obj) = jsonl_msg.as_object_mut() {
-            obj.insert("toolUse".to_string(), tool_use.clone());
+            obj.insert("toolUse".to_string(), tool_use);
         }
     }
 
@@ -619,6 +854,54 @@ fn write_jsonl_line<W: Write>(writer: &mut W, value: &serde_json::Value) -> Resu
     Ok(())
 }
 
+/// Helper: Append `delta` to the last text block of a message's content
+/// (plain string content is extended directly).
+fn append_text_delta(msg: &mut serde_json::Value, delta: &str) -> Result<(), String> {
+    let content = msg
+        .get_mut("message")
+        .and_then(|m| m.get_mut("content"))
+        .ok_or_else(|| "Streaming message has no content".to_string())?;
+
+    match content {
+        serde_json::Value::String(text) => text.push_str(delta),
+        serde_json::Value::Array(blocks) => {
+            let last_text = blocks
+                .last_mut()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+                .and_then(|b| b.get_mut("text"));
+            match last_text {
+                Some(serde_json::Value::String(text)) => text.push_str(delta),
+                _ => blocks.push(serde_json::json!({ "type": "text", "text": delta })),
+            }
+        }
+        _ => return Err("Streaming message content is not text".to_string()),
+    }
+    Ok(())
+}
+
+/// Helper: Make sure a file opened for appending ends with a newline, so the
+/// next JSONL line does not get glued onto an unterminated last line.
+fn ensure_trailing_newline(file: &mut File) -> Result<(), String> {
+    use std::io::Read;
+
+    let len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+    if len == 0 {
+        return Ok(());
+    }
+
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::Start(len - 1))
+        .and_then(|_| file.read_exact(&mut last))
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+    if last[0] != b'\n' {
+        writeln!(file).map_err(|e| format!("Failed to write JSONL line: {}", e))?;
+    }
+    Ok(())
+}
+
 /// Helper: Get the UUID of the last message in a session file
 fn get_last_message_uuid(session_file_path: &PathBuf) -> Result<Option<String>, String> {
     use std::io::{BufRead, BufReader};
@@ -646,3 +929,232 @@ fn get_last_message_uuid(session_file_path: &PathBuf) -> Result<Option<String>,
 
     Ok(last_uuid)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn create_session_rejects_malformed_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for content in [
+            serde_json::json!(42),
+            serde_json::json!({"text": "no type"}),
+            serde_json::json!([{"type": "text", "text": "ok"}, "bare string"]),
+        ] {
+            let err = create_claude_session(CreateSessionRequest {
+                project_path: temp_dir.path().to_string_lossy().to_string(),
+                messages: vec![MessageInput {
+                    role: "user".to_string(),
+                    content,
+                    parent_id: None,
+                    model: None,
+                    tool_use: None,
+                    tool_use_result: None,
+                    usage: None,
+                }],
+                summary: None,
+                cwd: None,
+            })
+            .await
+            .unwrap_err();
+            assert!(err.starts_with("SESSION_INVALID_CONTENT"), "{}", err);
+        }
+        // Nothing was written
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn extracted_range_preserves_content_tool_use() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.jsonl");
+        let lines = [
+            serde_json::json!({
+                "uuid": "u1", "type": "user",
+                "message": {"role": "user", "content": "list files"},
+            }),
+            serde_json::json!({
+                "uuid": "a1", "type": "assistant", "timestamp": "2025-06-01T10:00:05Z",
+                "message": {"role": "assistant", "model": "claude-sonnet-4", "content": [
+                    {"type": "text", "text": "Listing."},
+                    {"type": "tool_use", "id": "toolu_1", "name": "Bash", "input": {"command": "ls"}},
+                ]},
+            }),
+            serde_json::json!({
+                "uuid": "u2", "type": "user", "timestamp": "2025-06-01T10:00:09Z",
+                "toolUseResult": {"stdout": "a.txt"},
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "a.txt"},
+                ]},
+            }),
+        ];
+        let body: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(&source, body).unwrap();
+
+        let extracted = extract_message_range(ExtractMessageRangeRequest {
+            session_path: source.to_string_lossy().to_string(),
+            start_message_id: None,
+            end_message_id: None,
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            extracted.messages[1].tool_use.as_ref().unwrap()["id"],
+            "toolu_1"
+        );
+        assert!(extracted.messages.iter().all(|m| m.parent_id.is_none()));
+        assert_eq!(
+            extracted.first_timestamp.as_deref(),
+            Some("2025-06-01T10:00:05Z")
+        );
+        assert_eq!(
+            extracted.last_timestamp.as_deref(),
+            Some("2025-06-01T10:00:09Z")
+        );
+
+        let created = create_claude_session(CreateSessionRequest {
+            project_path: temp_dir.path().to_string_lossy().to_string(),
+            messages: extracted.messages,
+            summary: None,
+            cwd: None,
+        })
+        .await
+        .unwrap();
+        let content = fs::read_to_string(&created.session_path).unwrap();
+        let written: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        let assistant = &written[2];
+        assert_eq!(assistant["parentUuid"], written[1]["uuid"]);
+        assert_eq!(written[3]["parentUuid"], assistant["uuid"]);
+        assert!(assistant.get("toolUse").is_none());
+        let blocks = assistant["message"]["content"].as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1]["id"], "toolu_1");
+        assert_eq!(written[3]["toolUseResult"]["stdout"], "a.txt");
+    }
+
+    #[test]
+    fn tool_use_block_is_moved_into_content() {
+        let msg = MessageInput {
+            role: "assistant".to_string(),
+            content: serde_json::json!("Running it."),
+            parent_id: None,
+            model: None,
+            tool_use: Some(serde_json::json!({
+                "type": "tool_use", "id": "toolu_9", "name": "Read", "input": {},
+            })),
+            tool_use_result: None,
+            usage: None,
+        };
+        let jsonl = convert_to_jsonl_format(&msg, "s1", 0, "/tmp", None).unwrap();
+        assert!(jsonl.get("toolUse").is_none());
+        assert_eq!(
+            jsonl["message"]["content"],
+            serde_json::json!([
+                {"type": "text", "text": "Running it."},
+                {"type": "tool_use", "id": "toolu_9", "name": "Read", "input": {}},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn streaming_message_is_rewritten_in_place() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let created = create_claude_session(CreateSessionRequest {
+            project_path: temp_dir.path().to_string_lossy().to_string(),
+            messages: vec![MessageInput {
+                role: "user".to_string(),
+                content: serde_json::json!("Write a haiku"),
+                parent_id: None,
+                model: None,
+                tool_use: None,
+                tool_use_result: None,
+                usage: None,
+            }],
+            summary: None,
+            cwd: None,
+        })
+        .await
+        .unwrap();
+        let path = created.session_path.clone();
+
+        let uuid = append_streaming_message(path.clone(), "assistant".to_string())
+            .await
+            .unwrap();
+        for delta in ["Autumn ", "moonlight"] {
+            update_streaming_message(path.clone(), uuid.clone(), delta.to_string())
+                .await
+                .unwrap();
+            // Every intermediate state is valid JSONL
+            let content = fs::read_to_string(&path).unwrap();
+            assert!(content.ends_with('\n'));
+            for line in content.lines() {
+                serde_json::from_str::<serde_json::Value>(line).unwrap();
+            }
+        }
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        let last = &lines[2];
+        assert_eq!(last["uuid"], uuid.as_str());
+        assert_eq!(last["parentUuid"], lines[1]["uuid"]);
+        assert_eq!(
+            last["message"]["content"],
+            serde_json::json!([{ "type": "text", "text": "Autumn moonlight" }])
+        );
+
+        // Once another message follows, the streaming message is closed
+        let next = append_streaming_message(path.clone(), "user".to_string())
+            .await
+            .unwrap();
+        assert_ne!(next, uuid);
+        assert!(update_streaming_message(path, uuid, "!".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn fork_session_until_copies_prefix_into_new_session() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.jsonl");
+        let lines = [
+            serde_json::json!({
+                "uuid": "u1", "type": "user", "cwd": "/work/app",
+                "message": {"role": "user", "content": "first"},
+            }),
+            serde_json::json!({
+                "uuid": "a1", "type": "assistant",
+                "message": {"role": "assistant", "content": [{"type": "text", "text": "reply"}]},
+            }),
+            serde_json::json!({
+                "uuid": "u2", "type": "user",
+                "message": {"role": "user", "content": "dropped"},
+            }),
+        ];
+        let body: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(&source, body).unwrap();
+
+        let forked = fork_session_until(source.to_str().unwrap(), "a1", None)
+            .await
+            .unwrap();
+        assert_eq!(forked.message_count, 2);
+
+        let written = fs::read_to_string(&forked.session_path).unwrap();
+        assert!(PathBuf::from(&forked.session_path).starts_with(temp_dir.path()));
+        assert!(written.contains("reply"));
+        assert!(!written.contains("dropped"));
+        assert!(written.contains("\"cwd\":\"/work/app\""));
+
+        let err = fork_session_until(source.to_str().unwrap(), "missing", None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("missing"), "{}", err);
+    }
+}
@@ -1,13 +1,12 @@
 use crate::models::universal::UniversalMessage;
 use crate::models::*;
-use crate::utils::find_line_ranges;
+use crate::utils::{find_line_ranges, is_session_file, read_session_bytes};
 use chrono::{DateTime, Datelike, Timelike, Utc};
-use memmap2::Mmap;
 use rayon::prelude::*;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 // ============================================================================
@@ -44,6 +43,20 @@ pub struct PaginatedTokenStats {
     pub has_more: bool,
 }
 
+/// Response for token stats over an explicit list of sessions
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SessionsTokenStats {
+    pub stats: Vec<SessionTokenStats>,
+    pub failures: Vec<SessionStatsFailure>,
+}
+
+/// A session that was skipped by `get_sessions_token_stats`, and why
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SessionStatsFailure {
+    pub session_path: String,
+    pub error: String,
+}
+
 // ---------------------------------------------------------------------------
 // Lightweight struct for global stats: only the fields we actually need.
 // Skips expensive fields like snapshot, data, hook_infos, etc.
@@ -108,6 +121,7 @@ struct ProjectSessionFileStats {
     total_messages: u32,
     token_distribution: TokenDistribution,
     tool_usage: HashMap<String, (u32, u32)>,
+    tool_durations: HashMap<String, (u64, u32)>, // (total_ms, completed_calls)
     daily_stats: HashMap<String, DailyStats>,
     activity_data: HashMap<(u8, u8), (u32, u64)>,
     session_duration_minutes: u32,
@@ -430,6 +444,83 @@ fn extract_token_usage_from_global_entry(entry: &GlobalStatsLogEntry) -> TokenUs
     usage
 }
 
+/// Sum the billed tokens of one session file, streaming it line by line with
+/// the lightweight global-stats entry (no full message parse). Unreadable
+/// files count as zero.
+pub(crate) fn count_session_file_tokens(session_path: &Path) -> u64 {
+    let Ok(file) = fs::File::open(session_path) else {
+        return 0;
+    };
+
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let Ok(mmap) = read_session_bytes(&file, session_path) else {
+        return 0;
+    };
+
+    let mut total_tokens = 0u64;
+    for (start, end) in find_line_ranges(&mmap) {
+        let mut line_bytes = mmap[start..end].to_vec();
+        let Some(entry) = parse_global_stats_entry_simd(&mut line_bytes) else {
+            continue;
+        };
+
+        let usage = extract_token_usage_from_global_entry(&entry);
+        let has_usage = token_usage_has_token_fields(&usage);
+        if should_include_stats_entry(
+            &entry.message_type,
+            entry.is_sidechain,
+            has_usage,
+            StatsMode::BillingTotal,
+        ) {
+            total_tokens += token_usage_totals(&usage).4;
+        }
+    }
+    total_tokens
+}
+
+/// Minimal entry for model discovery: only `message.model` is deserialized.
+#[derive(Debug, Deserialize)]
+struct ModelScanEntry {
+    message: Option<ModelScanMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelScanMessage {
+    model: Option<String>,
+}
+
+/// Distinct models referenced in one session file. Lines without a `"model"`
+/// key are skipped before parsing, and placeholder models such as
+/// `<synthetic>` are ignored.
+fn collect_session_file_models(session_path: &Path) -> HashSet<String> {
+    let mut models = HashSet::new();
+    let Ok(file) = fs::File::open(session_path) else {
+        return models;
+    };
+
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let Ok(mmap) = read_session_bytes(&file, session_path) else {
+        return models;
+    };
+
+    let finder = memchr::memmem::Finder::new(b"\"model\"");
+    for (start, end) in find_line_ranges(&mmap) {
+        let line = &mmap[start..end];
+        if finder.find(line).is_none() {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_slice::<ModelScanEntry>(line) else {
+            continue;
+        };
+        if let Some(model) = entry.message.and_then(|m| m.model) {
+            if !model.is_empty() && !model.starts_with('<') {
+                models.insert(model);
+            }
+        }
+    }
+    models
+}
+
 /// Track tool usage from the lightweight global stats entry
 fn track_tool_usage_from_global_entry(
     entry: &GlobalStatsLogEntry,
@@ -621,6 +712,76 @@ fn calculate_session_duration(
     }
 }
 
+/// Remember when each `tool_use` block of an assistant message was issued,
+/// keyed by its block id, until the matching `tool_result` arrives.
+fn track_tool_starts(
+    message: &ClaudeMessage,
+    issued_at: DateTime<Utc>,
+    pending: &mut HashMap<String, (String, DateTime<Utc>)>,
+) {
+    if message.message_type != "assistant" {
+        return;
+    }
+    let Some(items) = message.content.as_ref().and_then(|c| c.as_array()) else {
+        return;
+    };
+    for item in items {
+        if item.get("type").and_then(|v| v.as_str()) != Some("tool_use") {
+            continue;
+        }
+        if let (Some(id), Some(name)) = (
+            item.get("id").and_then(|v| v.as_str()),
+            item.get("name").and_then(|v| v.as_str()),
+        ) {
+            pending.insert(id.to_string(), (name.to_string(), issued_at));
+        }
+    }
+}
+
+/// Pair the `tool_result` blocks of a message with their pending `tool_use`
+/// (by `tool_use_id`) and add the elapsed time to `durations`. Tool uses that
+/// never get a result stay pending and are left out of the average.
+fn track_tool_results(
+    message: &ClaudeMessage,
+    completed_at: DateTime<Utc>,
+    pending: &mut HashMap<String, (String, DateTime<Utc>)>,
+    durations: &mut HashMap<String, (u64, u32)>,
+) {
+    let Some(items) = message.content.as_ref().and_then(|c| c.as_array()) else {
+        return;
+    };
+    for item in items {
+        if item.get("type").and_then(|v| v.as_str()) != Some("tool_result") {
+            continue;
+        }
+        let Some(id) = item.get("tool_use_id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let Some((name, issued_at)) = pending.remove(id) else {
+            continue;
+        };
+        // A result stamped before its call is clock skew, not a duration
+        let Ok(elapsed_ms) = u64::try_from((completed_at - issued_at).num_milliseconds()) else {
+            continue;
+        };
+        let entry = durations.entry(name).or_insert((0, 0));
+        entry.0 += elapsed_ms;
+        entry.1 += 1;
+    }
+}
+
+/// Fill `avg_execution_time` (milliseconds) from per-tool `(total_ms,
+/// completed_calls)`; tools without a completed call keep `None`.
+fn apply_tool_durations(tools: &mut [ToolUsageStats], durations: &HashMap<String, (u64, u32)>) {
+    for tool in tools {
+        if let Some(&(total_ms, completed)) = durations.get(&tool.tool_name) {
+            if completed > 0 {
+                tool.avg_execution_time = Some(total_ms as f32 / completed as f32);
+            }
+        }
+    }
+}
+
 fn build_tool_usage_stats(tool_usage: HashMap<String, (u32, u32)>) -> Vec<ToolUsageStats> {
     let mut tools = tool_usage
         .into_iter()
@@ -655,7 +816,6 @@ fn resolve_provider_project_name(_provider: StatsProvider, project_path: &str) -
 
 /// Process a single session file using lightweight deserialization for global stats.
 /// Only parses fields needed for stats (timestamp, usage, model, tool names).
-#[allow(unsafe_code)] // Required for mmap performance optimization
 fn process_session_file_for_global_stats(
     session_path: &PathBuf,
     mode: StatsMode,
@@ -664,9 +824,8 @@ fn process_session_file_for_global_stats(
 ) -> Option<SessionFileStats> {
     let file = fs::File::open(session_path).ok()?;
 
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let mmap = read_session_bytes(&file, session_path).ok()?;
 
     let project_name = session_path
         .parent()
@@ -791,7 +950,6 @@ fn process_session_file_for_global_stats(
 }
 
 /// Process a single session file for project stats
-#[allow(unsafe_code)] // Required for mmap performance optimization
 fn process_session_file_for_project_stats(
     session_path: &PathBuf,
     mode: StatsMode,
@@ -800,12 +958,13 @@ fn process_session_file_for_project_stats(
 ) -> Option<ProjectSessionFileStats> {
     let file = fs::File::open(session_path).ok()?;
 
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let mmap = read_session_bytes(&file, session_path).ok()?;
 
     let mut stats = ProjectSessionFileStats::default();
     let mut session_timestamps: Vec<DateTime<Utc>> = Vec::new();
+    // tool_use id -> (tool name, issued at), for counted tool calls awaiting a result
+    let mut pending_tools: HashMap<String, (String, DateTime<Utc>)> = HashMap::new();
 
     // Use SIMD-accelerated line detection
     let line_ranges = find_line_ranges(&mmap);
@@ -816,6 +975,17 @@ fn process_session_file_for_project_stats(
 
         if let Some(log_entry) = parse_raw_log_entry_simd(&mut line_bytes) {
             if let Ok(message) = ClaudeMessage::try_from(log_entry) {
+                // Results complete a counted call even when their own
+                // message is filtered out of the stats below
+                if let Some(completed_at) = parse_timestamp_utc(&message.timestamp) {
+                    track_tool_results(
+                        &message,
+                        completed_at,
+                        &mut pending_tools,
+                        &mut stats.tool_durations,
+                    );
+                }
+
                 let usage = extract_token_usage(&message);
                 let has_usage = token_usage_has_token_fields(&usage);
                 if !should_include_stats_entry(
@@ -871,6 +1041,9 @@ fn process_session_file_for_project_stats(
 
                 // Track tool usage
                 track_tool_usage(&message, &mut stats.tool_usage);
+                if let Some(issued_at) = parsed_ts {
+                    track_tool_starts(&message, issued_at, &mut pending_tools);
+                }
             }
         }
     }
@@ -887,7 +1060,6 @@ fn process_session_file_for_project_stats(
 }
 
 /// Process a single session file for comparison stats (lightweight)
-#[allow(unsafe_code)] // Required for mmap performance optimization
 fn process_session_file_for_comparison(
     session_path: &PathBuf,
     mode: StatsMode,
@@ -896,9 +1068,8 @@ fn process_session_file_for_comparison(
 ) -> Option<SessionComparisonStats> {
     let file = fs::File::open(session_path).ok()?;
 
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let mmap = read_session_bytes(&file, session_path).ok()?;
 
     let mut session_id: Option<String> = None;
     let mut total_tokens: u64 = 0;
@@ -975,7 +1146,6 @@ fn process_session_file_for_comparison(
 }
 
 /// Synchronous version of session token stats extraction for parallel processing
-#[allow(unsafe_code)] // Required for mmap performance optimization
 fn extract_session_token_stats_sync(
     session_path: &PathBuf,
     mode: StatsMode,
@@ -984,9 +1154,8 @@ fn extract_session_token_stats_sync(
 ) -> Option<SessionTokenStats> {
     let file = fs::File::open(session_path).ok()?;
 
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    // Memory-mapped for `.jsonl`, decompressed for `.jsonl.gz`
+    let mmap = read_session_bytes(&file, session_path).ok()?;
 
     let project_name = session_path
         .parent()
@@ -1210,17 +1379,25 @@ fn extract_universal_token_usage(message: &UniversalMessage) -> (u64, u64, u64,
 }
 
 /// Load UniversalMessages for a session based on provider
-async fn load_universal_session_messages(
+pub(crate) async fn load_universal_session_messages(
     provider_id: &str,
     source_path: &str,
     session_id: &str,
 ) -> Result<Vec<UniversalMessage>, String> {
     match provider_id {
         "claude-code" => {
-            // For Claude Code, source_path is the JSONL file path
+            // For Claude Code, source_path is the JSONL file path, unless it is
+            // the project directory and session_id the file path (as returned
+            // by get_project_session_ids)
             // load_session_messages now returns UniversalMessage
             use crate::commands::session::load_session_messages;
-            load_session_messages(source_path.to_string(), None).await
+            let id_path = Path::new(session_id);
+            let session_path = if is_session_file(id_path) && id_path.is_absolute() {
+                session_id
+            } else {
+                source_path
+            };
+            load_session_messages(session_path.to_string(), None, None, None).await
         }
         "cursor" => {
             // For Cursor, we need to construct the encoded path format that load_cursor_messages expects
@@ -1261,9 +1438,16 @@ async fn get_project_session_ids(
 ) -> Result<Vec<String>, String> {
     match provider_id {
         "claude-code" => {
-            // For Claude Code, scan JSONL files in project directory
-            // Not implemented yet
-            Err("Claude Code universal analytics not yet implemented".to_string())
+            // For Claude Code, source_path is the project directory and each
+            // JSONL file path is a session id
+            let mut session_files: Vec<String> = WalkDir::new(source_path)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|e| is_session_file(e.path()))
+                .map(|e| e.path().to_string_lossy().to_string())
+                .collect();
+            session_files.sort();
+            Ok(session_files)
         }
         "cursor" => {
             // For Cursor, load sessions from workspace
@@ -1279,6 +1463,59 @@ async fn get_project_session_ids(
     }
 }
 
+/// List every distinct model name found in the Claude sessions under
+/// `claude_path`, sorted. Used to populate model filters and pricing config.
+#[tauri::command]
+pub async fn list_known_models(claude_path: String) -> Result<Vec<String>, String> {
+    let projects_path = PathBuf::from(&claude_path).join("projects");
+    if !projects_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let session_files: Vec<PathBuf> = WalkDir::new(&projects_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_session_file(e.path()))
+        .map(|e| e.into_path())
+        .collect();
+
+    let models: HashSet<String> = session_files
+        .par_iter()
+        .map(|path| collect_session_file_models(path))
+        .reduce(HashSet::new, |mut acc, models| {
+            acc.extend(models);
+            acc
+        });
+
+    let mut models: Vec<String> = models.into_iter().collect();
+    models.sort();
+    Ok(models)
+}
+
+// ============================================================================
+// TOKEN ESTIMATE
+// ============================================================================
+
+/// Characters per token used by the rough token estimate.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Approximate token count of `text` (1 token ≈ 4 characters, rounded up).
+/// Characters are counted as UTF-16 code units so the result matches the
+/// estimate the viewer computes from JavaScript string lengths.
+pub(crate) fn estimate_tokens(text: &str) -> usize {
+    text.encode_utf16().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Estimate the number of tokens in `text`, e.g. for a live count while
+/// drafting a message. This is an approximation, not the model's tokenizer;
+/// `model` is accepted for forward compatibility and currently does not
+/// change the estimate.
+#[tauri::command]
+pub async fn count_tokens(text: String, model: Option<String>) -> Result<usize, String> {
+    let _ = model;
+    Ok(estimate_tokens(&text))
+}
+
 // ============================================================================
 // TAURI COMMANDS - CLAUDE-SPECIFIC (rewritten with date/mode/pagination)
 // ============================================================================
@@ -1355,7 +1592,7 @@ pub async fn get_project_token_stats(
     let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
         .into_iter()
         .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|e| is_session_file(e.path()))
         .map(|e| e.path().to_path_buf())
         .collect();
 
@@ -1398,6 +1635,49 @@ pub async fn get_project_token_stats(
     })
 }
 
+/// Token stats for an explicit list of sessions (e.g. a custom comparison
+/// set), in input order. Sessions are processed in parallel; one that is not
+/// an absolute path or has no countable messages is reported in `failures`
+/// rather than failing the whole batch.
+#[tauri::command]
+pub async fn get_sessions_token_stats(
+    session_paths: Vec<String>,
+) -> Result<SessionsTokenStats, String> {
+    let mode = parse_stats_mode(None);
+
+    let results: Vec<Result<SessionTokenStats, String>> = session_paths
+        .par_iter()
+        .map(|session_path| {
+            let path = PathBuf::from(session_path);
+            if !path.is_absolute() {
+                return Err("session_path must be absolute".to_string());
+            }
+            extract_session_token_stats_sync(&path, mode, None, None)
+                .ok_or_else(|| "no valid messages found".to_string())
+        })
+        .collect();
+
+    let mut stats = Vec::with_capacity(results.len());
+    let mut failures = Vec::new();
+    for (session_path, result) in session_paths.into_iter().zip(results) {
+        match result {
+            Ok(session_stats) => stats.push(session_stats),
+            Err(error) => {
+                log::warn!(
+                    "get_sessions_token_stats: skipping {}: {}",
+                    session_path,
+                    error
+                );
+                failures.push(SessionStatsFailure {
+                    session_path,
+                    error,
+                });
+            }
+        }
+    }
+    Ok(SessionsTokenStats { stats, failures })
+}
+
 #[tauri::command]
 pub async fn get_project_stats_summary(
     project_path: String,
@@ -1429,7 +1709,7 @@ pub async fn get_project_stats_summary(
     let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
         .into_iter()
         .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|e| is_session_file(e.path()))
         .map(|e| e.path().to_path_buf())
         .collect();
     let scan_time = start.elapsed();
@@ -1452,6 +1732,7 @@ pub async fn get_project_stats_summary(
 
     let mut session_durations: Vec<u32> = Vec::new();
     let mut tool_usage_map: HashMap<String, (u32, u32)> = HashMap::new();
+    let mut tool_duration_map: HashMap<String, (u64, u32)> = HashMap::new();
     let mut daily_stats_map: HashMap<String, DailyStats> = HashMap::new();
     let mut activity_map: HashMap<(u8, u8), (u32, u64)> = HashMap::new();
     let mut session_count_by_date: HashMap<String, usize> = HashMap::new();
@@ -1471,6 +1752,11 @@ pub async fn get_project_stats_summary(
             entry.0 += usage;
             entry.1 += success;
         }
+        for (name, (total_ms, completed)) in stats.tool_durations {
+            let entry = tool_duration_map.entry(name).or_insert((0, 0));
+            entry.0 += total_ms;
+            entry.1 += completed;
+        }
 
         // Aggregate daily stats
         for (date, daily) in stats.daily_stats {
@@ -1515,6 +1801,7 @@ pub async fn get_project_stats_summary(
     }
 
     summary.most_used_tools = build_tool_usage_stats(tool_usage_map);
+    apply_tool_durations(&mut summary.most_used_tools, &tool_duration_map);
 
     summary.daily_stats = daily_stats_map.into_values().collect();
     summary.daily_stats.sort_by(|a, b| a.date.cmp(&b.date));
@@ -1563,6 +1850,157 @@ pub async fn get_project_stats_summary(
     Ok(summary)
 }
 
+/// Longest `get_burn_rate` window, in days.
+const MAX_BURN_RATE_WINDOW_DAYS: u32 = 365;
+
+/// Model whose [`MODEL_PRICING`] rates price daily stats. Daily stats carry
+/// no model, so every day uses the default (Sonnet) rates the dashboard falls
+/// back to.
+const DAILY_COST_MODEL: &str = "claude-sonnet-4";
+
+/// Parse a burn-rate window: a day count with an optional `d` (days), `w`
+/// (weeks) or `m` (30-day months) suffix, e.g. "7d", "2w", "30". Clamped to
+/// 1..=365 days.
+fn parse_window_days(window: &str) -> Result<u32, String> {
+    let window = window.trim().to_lowercase();
+    let (number, unit_days) = match window.char_indices().last() {
+        Some((i, 'd')) => (&window[..i], 1),
+        Some((i, 'w')) => (&window[..i], 7),
+        Some((i, 'm')) => (&window[..i], 30),
+        _ => (window.as_str(), 1),
+    };
+    let count: u32 = number.trim().parse().map_err(|_| {
+        format!(
+            "STATS_INVALID_ARGUMENT: Invalid window '{}' (expected e.g. \"7d\", \"2w\", \"1m\")",
+            window
+        )
+    })?;
+    Ok(count
+        .saturating_mul(unit_days)
+        .clamp(1, MAX_BURN_RATE_WINDOW_DAYS))
+}
+
+/// Estimated USD cost of one day's tokens (see [`DAILY_COST_MODEL`]). Cache
+/// tokens are priced as cache reads, by far the larger share.
+fn estimate_daily_cost(day: &DailyStats) -> f64 {
+    let cache_tokens = day
+        .total_tokens
+        .saturating_sub(day.input_tokens + day.output_tokens);
+    model_pricing(DAILY_COST_MODEL).map_or(0.0, |pricing| {
+        pricing.cost(day.input_tokens, day.output_tokens, 0, cache_tokens)
+    })
+}
+
+/// Average the daily stats of the `window_days` days ending on `today`
+/// (days without activity count as zero) and project them over `today`'s month.
+fn compute_burn_rate(
+    daily_stats: &[DailyStats],
+    window_days: u32,
+    today: chrono::NaiveDate,
+) -> BurnRate {
+    let first_day = today - chrono::Duration::days(i64::from(window_days) - 1);
+    let (first, last) = (
+        first_day.format("%Y-%m-%d").to_string(),
+        today.format("%Y-%m-%d").to_string(),
+    );
+    let in_window: Vec<&DailyStats> = daily_stats
+        .iter()
+        .filter(|d| d.date >= first && d.date <= last)
+        .collect();
+
+    let total_tokens: u64 = in_window.iter().map(|d| d.total_tokens).sum();
+    let total_cost: f64 = in_window.iter().map(|d| estimate_daily_cost(d)).sum();
+    let tokens_per_day = total_tokens as f64 / f64::from(window_days);
+    let cost_per_day = total_cost / f64::from(window_days);
+
+    let month_start = today.with_day(1).unwrap_or(today);
+    let next_month_start = month_start
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(month_start);
+    let days_in_month = (next_month_start - month_start).num_days() as f64;
+
+    BurnRate {
+        window_days,
+        total_tokens,
+        tokens_per_day,
+        cost_per_day,
+        projected_month_tokens: (tokens_per_day * days_in_month).round() as u64,
+        projected_month_cost: cost_per_day * days_in_month,
+    }
+}
+
+/// Tokens and estimated cost per day over a trailing `window` ("7d", "30d",
+/// ... see [`parse_window_days`]) of a project's `daily_stats`, plus a
+/// projection for the current month. Days are UTC.
+#[tauri::command]
+pub async fn get_burn_rate(project_path: String, window: String) -> Result<BurnRate, String> {
+    let window_days = parse_window_days(&window)?;
+    let today = Utc::now().date_naive();
+    let window_start = (today - chrono::Duration::days(i64::from(window_days) - 1))
+        .and_hms_opt(0, 0, 0)
+        .map(|dt| dt.and_utc().to_rfc3339());
+
+    let summary = get_project_stats_summary(project_path, window_start, None, None).await?;
+    Ok(compute_burn_rate(&summary.daily_stats, window_days, today))
+}
+
+/// Pick the model with the most tokens (ties broken by name) from a
+/// model -> tokens map; `None` when no model used any tokens.
+fn dominant_model(model_tokens: HashMap<String, u64>) -> Option<ModelShare> {
+    let total_tokens: u64 = model_tokens.values().sum();
+    if total_tokens == 0 {
+        return None;
+    }
+    let (model_name, token_count) = model_tokens
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))?;
+    Some(ModelShare {
+        model_name,
+        token_count,
+        total_tokens,
+        share_percent: token_count as f64 / total_tokens as f64 * 100.0,
+    })
+}
+
+/// The model that consumed the most tokens in a Claude project, with its
+/// share of all model-attributed tokens (the per-model breakdown of
+/// `get_global_stats_summary`, restricted to one project). `None` when no
+/// message names a model.
+#[tauri::command]
+pub async fn get_dominant_model(project_path: String) -> Result<Option<ModelShare>, String> {
+    if !Path::new(&project_path).is_absolute() {
+        return Err("STATS_INVALID_ARGUMENT: project_path must be absolute".to_string());
+    }
+
+    let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|e| is_session_file(e.path()))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+
+    let model_tokens = session_files
+        .par_iter()
+        .filter_map(|path| {
+            process_session_file_for_global_stats(path, StatsMode::BillingTotal, None, None)
+        })
+        .map(|stats| {
+            stats
+                .model_usage
+                .into_iter()
+                .map(|(model, usage)| (model, usage.1))
+                .collect::<HashMap<String, u64>>()
+        })
+        .reduce(HashMap::new, |mut acc, models| {
+            for (model, tokens) in models {
+                *acc.entry(model).or_insert(0) += tokens;
+            }
+            acc
+        });
+
+    Ok(dominant_model(model_tokens))
+}
+
 #[tauri::command]
 pub async fn get_session_comparison(
     session_id: String,
@@ -1580,7 +2018,7 @@ pub async fn get_session_comparison(
     let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
         .into_iter()
         .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|e| is_session_file(e.path()))
         .map(|e| e.path().to_path_buf())
         .collect();
     let scan_time = start.elapsed();
@@ -1709,7 +2147,7 @@ pub async fn get_global_stats_summary(
                     for entry in WalkDir::new(&project_path)
                         .into_iter()
                         .filter_map(std::result::Result::ok)
-                        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+                        .filter(|e| is_session_file(e.path()))
                     {
                         session_files.push(entry.path().to_path_buf());
                     }
@@ -1944,6 +2382,155 @@ pub async fn get_global_stats_summary(
     Ok(summary)
 }
 
+// ============================================================================
+// SESSION COST
+// ============================================================================
+
+/// USD per million tokens for one model family.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ModelPricing {
+    input: f64,
+    output: f64,
+    cache_creation: f64,
+    cache_read: f64,
+}
+
+impl ModelPricing {
+    /// Anthropic rates: cache writes cost 1.25x input, cache reads 0.1x.
+    fn claude(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_creation: input * 1.25,
+            cache_read: input * 0.1,
+        }
+    }
+
+    /// OpenAI rates: no cache-write surcharge, cached input at `cache_read`.
+    fn openai(input: f64, output: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_creation: input,
+            cache_read,
+        }
+    }
+
+    fn cost(&self, input: u64, output: u64, cache_creation: u64, cache_read: u64) -> f64 {
+        (input as f64 * self.input
+            + output as f64 * self.output
+            + cache_creation as f64 * self.cache_creation
+            + cache_read as f64 * self.cache_read)
+            / 1_000_000.0
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Public API list prices, keyed by model-name prefix. A model uses the
+    /// longest key it starts with, so dated ids like
+    /// `claude-sonnet-4-5-20250929` need no entry of their own.
+    static ref MODEL_PRICING: HashMap<&'static str, ModelPricing> = HashMap::from([
+        ("claude-opus-4-5", ModelPricing::claude(5.0, 25.0)),
+        ("claude-opus-4", ModelPricing::claude(15.0, 75.0)),
+        ("claude-sonnet-4", ModelPricing::claude(3.0, 15.0)),
+        ("claude-haiku-4-5", ModelPricing::claude(1.0, 5.0)),
+        ("claude-3-opus", ModelPricing::claude(15.0, 75.0)),
+        ("claude-3-7-sonnet", ModelPricing::claude(3.0, 15.0)),
+        ("claude-3-5-sonnet", ModelPricing::claude(3.0, 15.0)),
+        ("claude-3-5-haiku", ModelPricing::claude(0.8, 4.0)),
+        ("claude-3-haiku", ModelPricing::claude(0.25, 1.25)),
+        ("gpt-5", ModelPricing::openai(1.25, 10.0, 0.125)),
+        ("gpt-5-mini", ModelPricing::openai(0.25, 2.0, 0.025)),
+        ("gpt-4.1", ModelPricing::openai(2.0, 8.0, 0.5)),
+        ("gpt-4.1-mini", ModelPricing::openai(0.4, 1.6, 0.1)),
+        ("gpt-4o", ModelPricing::openai(2.5, 10.0, 1.25)),
+        ("gpt-4o-mini", ModelPricing::openai(0.15, 0.6, 0.075)),
+        ("o3", ModelPricing::openai(2.0, 8.0, 0.5)),
+        ("o4-mini", ModelPricing::openai(1.1, 4.4, 0.275)),
+    ]);
+}
+
+/// Pricing for `model` from [`MODEL_PRICING`], matched by longest prefix.
+fn model_pricing(model: &str) -> Option<ModelPricing> {
+    MODEL_PRICING
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(*prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, pricing)| *pricing)
+}
+
+/// Model name used for messages with tokens but no model.
+const UNKNOWN_MODEL: &str = "unknown";
+
+/// Group the tokens of `messages` by model and price each group.
+fn compute_session_cost(session_id: String, messages: &[UniversalMessage]) -> SessionCost {
+    let mut by_model: HashMap<String, (u64, u64, u64, u64)> = HashMap::new();
+    for message in messages {
+        let (input, output, cache_creation, cache_read) = extract_universal_token_usage(message);
+        if input + output + cache_creation + cache_read == 0 {
+            continue;
+        }
+        let model = message.model.as_deref().unwrap_or(UNKNOWN_MODEL);
+        let entry = by_model.entry(model.to_string()).or_default();
+        entry.0 += input;
+        entry.1 += output;
+        entry.2 += cache_creation;
+        entry.3 += cache_read;
+    }
+
+    let mut models: Vec<ModelCost> = by_model
+        .into_iter()
+        .map(|(model, (input, output, cache_creation, cache_read))| {
+            let pricing = model_pricing(&model);
+            ModelCost {
+                cost: pricing.map_or(0.0, |p| p.cost(input, output, cache_creation, cache_read)),
+                priced: pricing.is_some(),
+                model,
+                input_tokens: input,
+                output_tokens: output,
+                cache_creation_tokens: cache_creation,
+                cache_read_tokens: cache_read,
+            }
+        })
+        .collect();
+    models.sort_by(|a, b| {
+        b.cost
+            .total_cmp(&a.cost)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+
+    let unpriced_models: Vec<String> = models
+        .iter()
+        .filter(|m| !m.priced)
+        .map(|m| m.model.clone())
+        .collect();
+    SessionCost {
+        session_id,
+        total_cost: models.iter().map(|m| m.cost).sum(),
+        has_unpriced_models: !unpriced_models.is_empty(),
+        unpriced_models,
+        models,
+    }
+}
+
+/// Estimated USD cost of a session at public API list prices, per model.
+/// Models missing from the pricing table cost nothing and are listed in
+/// `unpriced_models`.
+#[tauri::command]
+pub async fn get_session_cost(
+    provider_id: String,
+    source_path: String,
+    session_id: String,
+) -> Result<SessionCost, String> {
+    // Validate absolute path
+    if !std::path::Path::new(&source_path).is_absolute() {
+        return Err("STATS_INVALID_ARGUMENT: source_path must be absolute".to_string());
+    }
+
+    let messages = load_universal_session_messages(&provider_id, &source_path, &session_id).await?;
+    Ok(compute_session_cost(session_id, &messages))
+}
+
 // ============================================================================
 // UNIVERSAL STATS COMMANDS (fork-only, kept as-is)
 // ============================================================================
@@ -2049,6 +2636,19 @@ pub async fn get_universal_project_token_stats(
 
     let session_ids = get_project_session_ids(&provider_id, &source_path, &project_id).await?;
 
+    if provider_id == "claude-code" {
+        // Same per-session extraction as get_project_token_stats, so the two agree
+        let mode = parse_stats_mode(None);
+        let mut session_stats: Vec<SessionTokenStats> = session_ids
+            .par_iter()
+            .filter_map(|session_id| {
+                extract_session_token_stats_sync(&PathBuf::from(session_id), mode, None, None)
+            })
+            .collect();
+        session_stats.sort_by_key(|s| std::cmp::Reverse(s.total_tokens));
+        return Ok(session_stats);
+    }
+
     let mut session_stats = Vec::new();
 
     for session_id in session_ids {
@@ -2085,6 +2685,12 @@ pub async fn get_universal_project_stats_summary(
         return Err("STATS_INVALID_ARGUMENT: source_path must be absolute".to_string());
     }
 
+    if provider_id == "claude-code" {
+        // The Claude-specific summary scans the same session files; delegating
+        // keeps both commands' numbers identical
+        return get_project_stats_summary(source_path, None, None, None).await;
+    }
+
     let project_name = project_id.clone();
     let session_ids = get_project_session_ids(&provider_id, &source_path, &project_id).await?;
 
@@ -2368,3 +2974,442 @@ impl TryFrom<RawLogEntry> for ClaudeMessage {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn list_known_models_collects_distinct_models() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(
+            project_dir.join("s1.jsonl"),
+            concat!(
+                r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4","content":[]}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"role":"assistant","model":"<synthetic>","content":[]}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("s2.jsonl"),
+            concat!(
+                r#"{"type":"assistant","message":{"role":"assistant","model":"claude-opus-4","content":[]}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4","content":[]}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let models = list_known_models(temp_dir.path().to_string_lossy().to_string())
+            .await
+            .unwrap();
+        assert_eq!(models, vec!["claude-opus-4", "claude-sonnet-4"]);
+    }
+
+    #[test]
+    fn estimate_tokens_rounds_up_per_four_chars() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("abcdefghi"), 3);
+        // Non-BMP characters count as two UTF-16 units, like JS `length`
+        assert_eq!(estimate_tokens("😀😀"), 1);
+    }
+
+    #[test]
+    fn test_parse_window_days() {
+        assert_eq!(parse_window_days("7d"), Ok(7));
+        assert_eq!(parse_window_days(" 30D "), Ok(30));
+        assert_eq!(parse_window_days("2w"), Ok(14));
+        assert_eq!(parse_window_days("1m"), Ok(30));
+        assert_eq!(parse_window_days("10"), Ok(10));
+        assert_eq!(parse_window_days("0d"), Ok(1));
+        assert_eq!(parse_window_days("5000d"), Ok(MAX_BURN_RATE_WINDOW_DAYS));
+        assert!(parse_window_days("week")
+            .unwrap_err()
+            .starts_with("STATS_INVALID_ARGUMENT"));
+    }
+
+    #[test]
+    fn test_compute_burn_rate_averages_trailing_window() {
+        let day = |date: &str, input: u64, output: u64, cache: u64| DailyStats {
+            date: date.to_string(),
+            total_tokens: input + output + cache,
+            input_tokens: input,
+            output_tokens: output,
+            ..Default::default()
+        };
+        let stats = vec![
+            day("2025-02-20", 9_000_000, 0, 0), // before the window
+            day("2025-02-27", 1_000_000, 0, 0),
+            day("2025-02-28", 0, 1_000_000, 1_000_000),
+        ];
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 2, 28).unwrap();
+
+        let rate = compute_burn_rate(&stats, 2, today);
+        assert_eq!(rate.window_days, 2);
+        assert_eq!(rate.total_tokens, 3_000_000);
+        assert_eq!(rate.tokens_per_day, 1_500_000.0);
+        // (3 + 15 + 0.30) / 2 days
+        assert!((rate.cost_per_day - 9.15).abs() < 1e-9);
+        // February 2025 has 28 days
+        assert_eq!(rate.projected_month_tokens, 42_000_000);
+        assert!((rate.projected_month_cost - 9.15 * 28.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_get_dominant_model() {
+        let temp = tempfile::tempdir().unwrap();
+        let line = |model: &str, tokens: u64| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": format!("u{}", tokens),
+                "timestamp": "2025-01-01T00:00:00Z",
+                "message": {
+                    "role": "assistant",
+                    "model": model,
+                    "content": "hi",
+                    "usage": {"input_tokens": tokens, "output_tokens": 0}
+                }
+            })
+            .to_string()
+        };
+        fs::write(
+            temp.path().join("a.jsonl"),
+            [line("claude-opus-4", 300), line("claude-sonnet-4", 100)].join("\n"),
+        )
+        .unwrap();
+        fs::write(temp.path().join("b.jsonl"), line("claude-opus-4", 100)).unwrap();
+
+        let project_path = temp.path().to_string_lossy().to_string();
+        let share = get_dominant_model(project_path).await.unwrap().unwrap();
+        assert_eq!(share.model_name, "claude-opus-4");
+        assert_eq!(share.token_count, 400);
+        assert_eq!(share.total_tokens, 500);
+        assert!((share.share_percent - 80.0).abs() < 1e-9);
+
+        let empty = tempfile::tempdir().unwrap();
+        let empty_path = empty.path().to_string_lossy().to_string();
+        assert_eq!(get_dominant_model(empty_path).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_sessions_token_stats_skips_failures() {
+        let temp = tempfile::tempdir().unwrap();
+        let line = |uuid: &str, tokens: u64| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "timestamp": "2025-01-01T00:00:00Z",
+                "message": {
+                    "role": "assistant",
+                    "model": "claude-sonnet-4",
+                    "content": "hi",
+                    "usage": {"input_tokens": tokens, "output_tokens": 5}
+                }
+            })
+            .to_string()
+        };
+        fs::write(temp.path().join("a.jsonl"), line("a1", 100)).unwrap();
+        fs::write(temp.path().join("b.jsonl"), line("b1", 40)).unwrap();
+        fs::write(temp.path().join("empty.jsonl"), "").unwrap();
+        let path = |name: &str| temp.path().join(name).to_string_lossy().to_string();
+
+        let result = get_sessions_token_stats(vec![
+            path("b.jsonl"),
+            path("missing.jsonl"),
+            "relative.jsonl".to_string(),
+            path("empty.jsonl"),
+            path("a.jsonl"),
+        ])
+        .await
+        .unwrap();
+
+        let totals: Vec<u64> = result.stats.iter().map(|s| s.total_tokens).collect();
+        assert_eq!(totals, vec![45, 105]);
+
+        let failures: Vec<(&str, &str)> = result
+            .failures
+            .iter()
+            .map(|f| (f.session_path.as_str(), f.error.as_str()))
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                (path("missing.jsonl").as_str(), "no valid messages found"),
+                ("relative.jsonl", "session_path must be absolute"),
+                (path("empty.jsonl").as_str(), "no valid messages found"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_universal_claude_project_stats_match_claude_specific() {
+        let temp = tempfile::tempdir().unwrap();
+        let line = |uuid: &str, ts: &str, tokens: u64| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "timestamp": ts,
+                "message": {
+                    "role": "assistant",
+                    "model": "claude-sonnet-4",
+                    "content": "hi",
+                    "usage": {"input_tokens": tokens, "output_tokens": 5}
+                }
+            })
+            .to_string()
+        };
+        fs::write(
+            temp.path().join("a.jsonl"),
+            [
+                line("a1", "2025-01-01T10:00:00Z", 100),
+                line("a2", "2025-01-01T10:30:00Z", 50),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("b.jsonl"),
+            line("b1", "2025-01-02T09:00:00Z", 20),
+        )
+        .unwrap();
+        let project_path = temp.path().to_string_lossy().to_string();
+
+        let ids = get_project_session_ids("claude-code", &project_path, "app")
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids[0].ends_with("a.jsonl") && ids[1].ends_with("b.jsonl"));
+
+        let universal = get_universal_project_token_stats(
+            "claude-code".to_string(),
+            project_path.clone(),
+            "app".to_string(),
+        )
+        .await
+        .unwrap();
+        let specific = get_project_token_stats(project_path.clone(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let totals = |stats: &[SessionTokenStats]| -> Vec<(u64, usize)> {
+            stats
+                .iter()
+                .map(|s| (s.total_tokens, s.message_count))
+                .collect()
+        };
+        assert_eq!(totals(&universal), totals(&specific.items));
+        assert_eq!(totals(&universal), vec![(160, 2), (25, 1)]);
+
+        let universal = get_universal_project_stats_summary(
+            "claude-code".to_string(),
+            project_path.clone(),
+            "app".to_string(),
+        )
+        .await
+        .unwrap();
+        let specific = get_project_stats_summary(project_path, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(universal.total_sessions, specific.total_sessions);
+        assert_eq!(universal.total_messages, specific.total_messages);
+        assert_eq!(universal.total_tokens, specific.total_tokens);
+        assert_eq!(universal.total_tokens, 185);
+
+        let empty = tempfile::tempdir().unwrap();
+        let empty_path = empty.path().to_string_lossy().to_string();
+        assert_eq!(
+            get_project_session_ids("claude-code", &empty_path, "empty").await,
+            Ok(Vec::new())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_project_token_stats_include_gzip_sessions() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let line = |uuid: &str, tokens: u64| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "sessionId": uuid,
+                "timestamp": "2025-01-01T10:00:00Z",
+                "message": {
+                    "role": "assistant",
+                    "model": "claude-sonnet-4",
+                    "content": "hi",
+                    "usage": {"input_tokens": tokens, "output_tokens": 5}
+                }
+            })
+            .to_string()
+        };
+        fs::write(temp.path().join("plain.jsonl"), line("plain", 20)).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(line("archived", 100).as_bytes()).unwrap();
+        fs::write(
+            temp.path().join("archived.jsonl.gz"),
+            encoder.finish().unwrap(),
+        )
+        .unwrap();
+        let project_path = temp.path().to_string_lossy().to_string();
+
+        let stats = get_project_token_stats(project_path.clone(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let totals: Vec<u64> = stats.items.iter().map(|s| s.total_tokens).collect();
+        assert_eq!(totals, vec![105, 25]);
+
+        let summary = get_project_stats_summary(project_path.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(summary.total_sessions, 2);
+        assert_eq!(summary.total_tokens, 130);
+
+        let archived = get_universal_session_token_stats(
+            "claude-code".to_string(),
+            project_path,
+            temp.path()
+                .join("archived.jsonl.gz")
+                .to_string_lossy()
+                .to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(archived.total_tokens, 105);
+    }
+
+    #[tokio::test]
+    async fn test_project_stats_average_tool_execution_time() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool_call = |uuid: &str, ts: &str, calls: &[(&str, &str)]| {
+            let content: Vec<serde_json::Value> = calls
+                .iter()
+                .map(|(id, name)| {
+                    serde_json::json!({"type": "tool_use", "id": id, "name": name, "input": {}})
+                })
+                .collect();
+            serde_json::json!({
+                "type": "assistant", "uuid": uuid, "timestamp": ts,
+                "message": {
+                    "role": "assistant", "content": content,
+                    "usage": {"input_tokens": 10, "output_tokens": 5}
+                }
+            })
+            .to_string()
+        };
+        let tool_result = |uuid: &str, ts: &str, id: &str| {
+            serde_json::json!({
+                "type": "user", "uuid": uuid, "timestamp": ts,
+                "message": {
+                    "role": "user",
+                    "content": [{"type": "tool_result", "tool_use_id": id, "content": "ok"}]
+                }
+            })
+            .to_string()
+        };
+        fs::write(
+            temp.path().join("s.jsonl"),
+            [
+                tool_call(
+                    "a1",
+                    "2025-01-01T10:00:00Z",
+                    &[("t1", "Bash"), ("t2", "Read")],
+                ),
+                tool_result("u1", "2025-01-01T10:00:02Z", "t1"),
+                tool_call("a2", "2025-01-01T10:01:00Z", &[("t3", "Bash")]),
+                tool_result("u2", "2025-01-01T10:01:04.500Z", "t3"),
+                // t2 never completes
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let summary =
+            get_project_stats_summary(temp.path().to_string_lossy().to_string(), None, None, None)
+                .await
+                .unwrap();
+        let avg = |name: &str| {
+            summary
+                .most_used_tools
+                .iter()
+                .find(|t| t.tool_name == name)
+                .unwrap()
+                .avg_execution_time
+        };
+        assert_eq!(avg("Bash"), Some(3250.0));
+        assert_eq!(avg("Read"), None);
+    }
+
+    #[test]
+    fn test_model_pricing_matches_longest_prefix() {
+        let opus = model_pricing("claude-opus-4-1-20250805").unwrap();
+        assert_eq!(opus.input, 15.0);
+        assert_eq!(
+            model_pricing("claude-opus-4-5-20251101").unwrap().input,
+            5.0
+        );
+        assert_eq!(model_pricing("gpt-4o-mini-2024-07-18").unwrap().output, 0.6);
+        assert_eq!(model_pricing("gpt-4o-2024-08-06").unwrap().output, 10.0);
+        assert_eq!(model_pricing("llama-3"), None);
+        // 1M input + 1M cache reads on Sonnet: $3 + $0.30
+        let sonnet = model_pricing("claude-sonnet-4-20250514").unwrap();
+        assert!((sonnet.cost(1_000_000, 0, 0, 1_000_000) - 3.3).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_get_session_cost_groups_by_model() {
+        let temp = tempfile::tempdir().unwrap();
+        let line = |uuid: &str, model: &str, input: u64, output: u64| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "sessionId": "s1",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "message": {
+                    "role": "assistant",
+                    "model": model,
+                    "content": "hi",
+                    "usage": {"input_tokens": input, "output_tokens": output}
+                }
+            })
+            .to_string()
+        };
+        let session = temp.path().join("s1.jsonl");
+        fs::write(
+            &session,
+            [
+                line("a1", "claude-sonnet-4-20250514", 1_000_000, 0),
+                line("a2", "claude-sonnet-4-20250514", 0, 100_000),
+                line("a3", "local-model", 500, 500),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let cost = get_session_cost(
+            "claude-code".to_string(),
+            session.to_string_lossy().to_string(),
+            "s1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        // $3 input + $1.50 output
+        assert!((cost.total_cost - 4.5).abs() < 1e-9);
+        assert_eq!(cost.models.len(), 2);
+        assert_eq!(cost.models[0].model, "claude-sonnet-4-20250514");
+        assert_eq!(cost.models[0].output_tokens, 100_000);
+        assert!(!cost.models[1].priced);
+        assert_eq!(cost.models[1].cost, 0.0);
+        assert!(cost.has_unpriced_models);
+        assert_eq!(cost.unpriced_models, vec!["local-model"]);
+    }
+}
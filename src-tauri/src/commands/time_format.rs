@@ -0,0 +1,233 @@
+//! Relative time strings ("3 hours ago", "yesterday") for session lists and
+//! search results, so every view formats timestamps the same way.
+//!
+//! Locales follow the app's UI languages (`en`, `ko`, `ja`, `zh-CN`, `zh-TW`,
+//! `ru`); anything else falls back to English.
+
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+/// Coarse distance from a timestamp to now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RelativeTime {
+    JustNow,
+    Yesterday,
+    Ago(i64, Unit),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Locale {
+    En,
+    Ko,
+    Ja,
+    ZhCn,
+    ZhTw,
+    Ru,
+}
+
+impl Locale {
+    /// Match a BCP 47 tag (`en-US`, `zh-Hant`, `ko_KR`, ...) to a UI language.
+    fn parse(tag: &str) -> Self {
+        let tag = tag.to_ascii_lowercase().replace('_', "-");
+        let mut parts = tag.split('-');
+        match parts.next().unwrap_or_default() {
+            "ko" => Locale::Ko,
+            "ja" => Locale::Ja,
+            "ru" => Locale::Ru,
+            "zh" => {
+                if parts.any(|p| matches!(p, "tw" | "hk" | "mo" | "hant")) {
+                    Locale::ZhTw
+                } else {
+                    Locale::ZhCn
+                }
+            }
+            _ => Locale::En,
+        }
+    }
+}
+
+/// Format an RFC 3339 timestamp relative to now, e.g. "3 hours ago".
+///
+/// `locale` is a language tag such as `ko` or `zh-TW` (default English).
+/// Timestamps in the future (clock skew) read as "just now".
+#[tauri::command]
+pub fn format_relative_time(timestamp: String, locale: Option<String>) -> Result<String, String> {
+    let time = DateTime::parse_from_rfc3339(&timestamp)
+        .map_err(|e| format!("INVALID_TIMESTAMP: {}: {}", timestamp, e))?;
+    let locale = Locale::parse(locale.as_deref().unwrap_or("en"));
+    Ok(render(
+        relative_time(time.with_timezone(&Utc), Utc::now()),
+        locale,
+    ))
+}
+
+fn relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> RelativeTime {
+    let minutes = (now - time).num_minutes();
+    let hours = minutes / 60;
+    let days = hours / 24;
+    match () {
+        _ if minutes < 1 => RelativeTime::JustNow,
+        _ if minutes < 60 => RelativeTime::Ago(minutes, Unit::Minute),
+        _ if hours < 24 => RelativeTime::Ago(hours, Unit::Hour),
+        _ if days < 2 => RelativeTime::Yesterday,
+        _ if days < 30 => RelativeTime::Ago(days, Unit::Day),
+        _ if days < 365 => RelativeTime::Ago(days / 30, Unit::Month),
+        _ => RelativeTime::Ago(days / 365, Unit::Year),
+    }
+}
+
+fn render(relative: RelativeTime, locale: Locale) -> String {
+    let (n, unit) = match relative {
+        RelativeTime::JustNow => {
+            return match locale {
+                Locale::En => "just now",
+                Locale::Ko => "방금 전",
+                Locale::Ja => "たった今",
+                Locale::ZhCn => "刚刚",
+                Locale::ZhTw => "剛剛",
+                Locale::Ru => "только что",
+            }
+            .to_string()
+        }
+        RelativeTime::Yesterday => {
+            return match locale {
+                Locale::En => "yesterday",
+                Locale::Ko => "어제",
+                Locale::Ja => "昨日",
+                Locale::ZhCn | Locale::ZhTw => "昨天",
+                Locale::Ru => "вчера",
+            }
+            .to_string()
+        }
+        RelativeTime::Ago(n, unit) => (n, unit),
+    };
+
+    match locale {
+        Locale::En => {
+            let word = match unit {
+                Unit::Minute => "minute",
+                Unit::Hour => "hour",
+                Unit::Day => "day",
+                Unit::Month => "month",
+                Unit::Year => "year",
+            };
+            let plural = if n == 1 { "" } else { "s" };
+            format!("{} {}{} ago", n, word, plural)
+        }
+        Locale::Ko => {
+            let word = match unit {
+                Unit::Minute => "분",
+                Unit::Hour => "시간",
+                Unit::Day => "일",
+                Unit::Month => "개월",
+                Unit::Year => "년",
+            };
+            format!("{}{} 전", n, word)
+        }
+        Locale::Ja => {
+            let word = match unit {
+                Unit::Minute => "分",
+                Unit::Hour => "時間",
+                Unit::Day => "日",
+                Unit::Month => "か月",
+                Unit::Year => "年",
+            };
+            format!("{}{}前", n, word)
+        }
+        Locale::ZhCn => {
+            let word = match unit {
+                Unit::Minute => "分钟",
+                Unit::Hour => "小时",
+                Unit::Day => "天",
+                Unit::Month => "个月",
+                Unit::Year => "年",
+            };
+            format!("{}{}前", n, word)
+        }
+        Locale::ZhTw => {
+            let word = match unit {
+                Unit::Minute => "分鐘",
+                Unit::Hour => "小時",
+                Unit::Day => "天",
+                Unit::Month => "個月",
+                Unit::Year => "年",
+            };
+            format!("{}{}前", n, word)
+        }
+        Locale::Ru => {
+            // one / few / many, as in "1 минуту", "2 минуты", "5 минут"
+            let forms = match unit {
+                Unit::Minute => ["минуту", "минуты", "минут"],
+                Unit::Hour => ["час", "часа", "часов"],
+                Unit::Day => ["день", "дня", "дней"],
+                Unit::Month => ["месяц", "месяца", "месяцев"],
+                Unit::Year => ["год", "года", "лет"],
+            };
+            let form = match (n % 10, n % 100) {
+                (1, m) if m != 11 => forms[0],
+                (2..=4, m) if !(12..=14).contains(&m) => forms[1],
+                _ => forms[2],
+            };
+            format!("{} {} назад", n, form)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ago(minutes: i64, locale: &str) -> String {
+        let now = Utc::now();
+        render(
+            relative_time(now - Duration::minutes(minutes), now),
+            Locale::parse(locale),
+        )
+    }
+
+    #[test]
+    fn english_buckets() {
+        assert_eq!(ago(0, "en"), "just now");
+        assert_eq!(ago(-5, "en"), "just now");
+        assert_eq!(ago(1, "en"), "1 minute ago");
+        assert_eq!(ago(45, "en"), "45 minutes ago");
+        assert_eq!(ago(3 * 60, "en"), "3 hours ago");
+        assert_eq!(ago(30 * 60, "en-US"), "yesterday");
+        assert_eq!(ago(5 * 24 * 60, "en"), "5 days ago");
+        assert_eq!(ago(65 * 24 * 60, "en"), "2 months ago");
+        assert_eq!(ago(400 * 24 * 60, "en"), "1 year ago");
+    }
+
+    #[test]
+    fn other_locales() {
+        assert_eq!(ago(3 * 60, "ko"), "3시간 전");
+        assert_eq!(ago(30 * 60, "ja"), "昨日");
+        assert_eq!(ago(10, "zh-CN"), "10分钟前");
+        assert_eq!(ago(10, "zh-Hant"), "10分鐘前");
+        assert_eq!(ago(10, "xx"), "10 minutes ago");
+    }
+
+    #[test]
+    fn russian_plural_forms() {
+        assert_eq!(ago(1, "ru"), "1 минуту назад");
+        assert_eq!(ago(3, "ru"), "3 минуты назад");
+        assert_eq!(ago(11, "ru"), "11 минут назад");
+        assert_eq!(ago(21, "ru"), "21 минуту назад");
+        assert_eq!(ago(5 * 60, "ru-RU"), "5 часов назад");
+    }
+
+    #[test]
+    fn rejects_invalid_timestamp() {
+        let err = format_relative_time("yesterday".to_string(), None).unwrap_err();
+        assert!(err.starts_with("INVALID_TIMESTAMP"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Session keyword/topic extraction
+//!
+//! `get_session_topics` gives a quick "what was this session about" list
+//! without an LLM: term frequency over the user and assistant text of a
+//! session, minus the built-in and custom stopwords (see
+//! [`crate::utils::stopwords`]). Tokenization splits on Unicode (not ASCII) word
+//! boundaries; scripts written without spaces (Han, Hiragana, Katakana) are
+//! indexed as overlapping character bigrams.
+
+use crate::commands::metadata::{custom_stopwords, MetadataState};
+use crate::models::{RawLogEntry, TopicTerm};
+use crate::utils::read_session_to_string;
+use crate::utils::stopwords::{Stopwords, DEFAULT_LANGUAGE};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Minimum length (in chars) of a space-delimited word to count as a topic.
+const MIN_WORD_CHARS: usize = 3;
+
+/// Scripts written without spaces between words.
+fn is_unspaced_script(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    )
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '\''
+}
+
+/// Split `text` into lowercase candidate terms.
+fn tokenize(text: &str, out: &mut Vec<String>) {
+    for word in text.split(|c: char| !is_word_char(c)) {
+        let word = word.trim_matches('\'');
+        if word.is_empty() {
+            continue;
+        }
+
+        // Separate spaced and unspaced runs inside one word (e.g. "Rust编程")
+        let chars: Vec<char> = word.chars().collect();
+        let mut start = 0;
+        while start < chars.len() {
+            let unspaced = is_unspaced_script(chars[start]);
+            let end = chars[start..]
+                .iter()
+                .position(|&c| is_unspaced_script(c) != unspaced)
+                .map_or(chars.len(), |n| start + n);
+            let run = &chars[start..end];
+
+            if unspaced {
+                if run.len() == 1 {
+                    out.push(run[0].to_string());
+                } else {
+                    out.extend(run.windows(2).map(|pair| pair.iter().collect::<String>()));
+                }
+            } else if run.len() >= MIN_WORD_CHARS && !run.iter().all(|c| c.is_numeric()) {
+                out.push(run.iter().collect::<String>().to_lowercase());
+            }
+            start = end;
+        }
+    }
+}
+
+/// Collect the displayable text of a message (strings and `text` blocks only;
+/// tool calls, tool results and thinking are left out).
+fn message_text(content: &Value, out: &mut Vec<String>) {
+    match content {
+        Value::String(text) => tokenize(text, out),
+        Value::Array(items) => {
+            for item in items {
+                if item.get("type").and_then(|t| t.as_str()) == Some("text") {
+                    if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
+                        tokenize(text, out);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rank terms of the session JSONL `content` by frequency.
+fn extract_topics(content: &str, top_n: usize, stopwords: &Stopwords) -> Vec<TopicTerm> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut tokens = Vec::new();
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<RawLogEntry>(line) else {
+            continue;
+        };
+        let Some(message) = entry.message else {
+            continue;
+        };
+        if message.role != "user" && message.role != "assistant" {
+            continue;
+        }
+
+        tokens.clear();
+        message_text(&message.content, &mut tokens);
+        for token in tokens.drain(..) {
+            if !stopwords.contains(&token) {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut topics: Vec<TopicTerm> = counts
+        .into_iter()
+        .map(|(term, frequency)| TopicTerm { term, frequency })
+        .collect();
+    topics.sort_by(|a, b| {
+        b.frequency
+            .cmp(&a.frequency)
+            .then_with(|| a.term.cmp(&b.term))
+    });
+    topics.truncate(top_n);
+    topics
+}
+
+/// Get the `top_n` most frequent non-stopword terms of a session's user and
+/// assistant text.
+#[tauri::command]
+pub async fn get_session_topics(
+    session_path: String,
+    top_n: usize,
+    state: tauri::State<'_, MetadataState>,
+) -> Result<Vec<TopicTerm>, String> {
+    let content = read_session_to_string(Path::new(&session_path))
+        .map_err(|e| format!("SESSION_READ_ERROR: Failed to read session file: {}", e))?;
+    let custom = custom_stopwords(&state).unwrap_or_else(|e| {
+        eprintln!("Failed to load custom stopwords: {}", e);
+        Vec::new()
+    });
+    let stopwords = Stopwords::new(DEFAULT_LANGUAGE, &custom);
+    Ok(extract_topics(&content, top_n, &stopwords))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(text: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        tokenize(text, &mut out);
+        out
+    }
+
+    #[test]
+    fn tokenize_handles_unicode_words() {
+        assert_eq!(terms("Über café, naïve"), vec!["über", "café", "naïve"]);
+        assert_eq!(terms("ошибка сборки"), vec!["ошибка", "сборки"]);
+        // Short words and bare numbers are dropped
+        assert_eq!(terms("a to 2024 fix"), vec!["fix"]);
+    }
+
+    #[test]
+    fn tokenize_bigrams_unspaced_scripts() {
+        assert_eq!(terms("Rust编程语言"), vec!["rust", "编程", "程语", "语言"]);
+        assert_eq!(terms("字"), vec!["字"]);
+    }
+
+    #[test]
+    fn extract_ranks_user_and_assistant_text() {
+        let lines = [
+            r#"{"type":"user","message":{"role":"user","content":"The parser panics on parser input"}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Fixing the parser panic"},{"type":"tool_use","id":"t","name":"Edit","input":{"x":"parser parser parser"}}]}}"#,
+            r#"{"type":"system","message":{"role":"system","content":"parser parser parser parser"}}"#,
+        ]
+        .join("\n");
+
+        let topics = extract_topics(&lines, 2, &Stopwords::new(DEFAULT_LANGUAGE, &[]));
+        assert_eq!(
+            topics,
+            vec![
+                TopicTerm {
+                    term: "parser".to_string(),
+                    frequency: 3
+                },
+                TopicTerm {
+                    term: "fixing".to_string(),
+                    frequency: 1
+                },
+            ]
+        );
+
+        let custom = Stopwords::new(DEFAULT_LANGUAGE, &["Parser".to_string()]);
+        assert_eq!(extract_topics(&lines, 1, &custom)[0].term, "fixing");
+    }
+}
@@ -6,10 +6,10 @@ mod utils;
 use crate::cli::{get_startup_session_hint, parse_session_hint, StartupSessionHint};
 use crate::commands::adapters::gemini::GeminiHashResolver;
 use crate::commands::{
-    aider::*, antigravity::*, claude_settings::*, codex::*, cursor::*, edits::*, feedback::*, files::*,
-    gemini::*, cline::*, forgecode::*, mcp_presets::*, metadata::*, multi_provider::*, opencode::*,
-    project::*, rename::*,
-    resume::*, secure_update::*, session::*, session_delete::*, session_writer::*, settings::*, stats::*,
+    aider::*, antigravity::*, claude_settings::*, codex::*, context_usage::*, cursor::*, edits::*, export::*, feedback::*, files::*,
+    gemini::*, logging::*, cline::*, forgecode::*, mcp_presets::*, metadata::*, multi_provider::*, opencode::*,
+    productivity::*, project::*, rename::*,
+    resume::*, search_history::*, secure_update::*, session::*, session_archive::*, session_cache::*, session_delete::*, session_templates::*, session_writer::*, settings::*, stats::*, time_format::*, topics::*,
     unified_presets::*, update::*, watcher::*, wsl::*,
 };
 use std::sync::Mutex;
@@ -44,9 +44,11 @@ pub fn run() {
                 log::error!("single_instance callback panicked; argv dropped");
             }
         }))
+        .plugin(log_plugin())
         .manage(GeminiResolverState(Mutex::new(GeminiHashResolver::new())))
         .manage(WatcherMap::default())
         .manage(MetadataState::default())
+        .manage(SessionCacheState::default())
         .manage(startup_session_hint)
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
@@ -61,30 +63,64 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // CLI session launch (--session <uuid>)
             get_startup_session_hint,
+            set_log_level,
+            get_log_path,
+            export_logs,
             resolve_session_by_id,
             get_claude_folder_path,
             validate_claude_folder,
             scan_projects,
+            scan_projects_since,
             load_project_sessions,
+            load_project_sessions_verbose,
+            clear_session_cache,
+            list_error_sessions,
+            get_sessions_by_day,
+            get_productivity_metrics,
             load_session_messages,
             load_session_messages_paginated,
+            load_session_messages_filtered,
+            load_session_for_replay,
+            get_session_thread,
             get_session_message_count,
+            get_full_content,
+            load_session_messages_light,
+            get_tool_result,
+            export_messages_api,
+            export_session_markdown,
+            get_session_topics,
+            find_duplicate_tool_results,
+            get_context_pressure,
             search_messages,
+            search_messages_streaming,
+            add_search_history,
+            get_search_history,
             delete_session,
+            delete_empty_sessions,
+            archive_sessions,
+            restore_sessions,
             fix_session,
+            count_tokens,
+            list_known_models,
             get_session_token_stats,
             get_project_token_stats,
+            get_sessions_token_stats,
             get_project_stats_summary,
+            get_burn_rate,
+            get_dominant_model,
+            get_session_cost,
             get_session_comparison,
             check_for_updates,
             check_for_updates_secure,
             verify_download_integrity,
             send_feedback,
             get_system_info,
+            get_app_metadata,
             open_github_issues,
             // Cursor IDE support (v2.0.0)
             get_cursor_path,
             validate_cursor_folder,
+            cursor_global_db_status,
             scan_cursor_workspaces,
             load_cursor_sessions,
             load_cursor_messages,
@@ -96,11 +132,18 @@ pub fn run() {
             get_universal_session_comparison,
             // File Activities (v1.5.0+)
             get_file_activities,
+            diff_file_across_sessions,
             // Session Writing (v1.6.0+)
             create_claude_project,
             create_claude_session,
             append_to_claude_session,
+            append_streaming_message,
+            update_streaming_message,
             extract_message_range,
+            create_session_from_template,
+            get_session_templates,
+            save_session_template,
+            delete_session_template,
             // Gemini CLI support (v1.7.0)
             get_gemini_path,
             validate_gemini_folder,
@@ -112,6 +155,9 @@ pub fn run() {
             get_codex_path,
             validate_codex_folder,
             scan_codex_projects,
+            scan_codex_sessions,
+            search_codex_messages,
+            group_codex_sessions,
             load_codex_sessions,
             load_codex_messages,
             // OpenCode support (v1.9.0)
@@ -155,8 +201,13 @@ pub fn run() {
             // Resume functionality
             resume_session,
             get_resume_command,
+            preview_resume,
+            resume_from_message,
+            open_project_folder,
+            set_project_cwd_override,
             get_session_cwd,
             provider_supports_resume,
+            session_supports_resume,
             // File watcher (real-time session detection)
             start_file_watcher,
             stop_file_watcher,
@@ -190,9 +241,14 @@ pub fn run() {
             delete_unified_preset,
             // Multi-provider unified commands (v1.9.0)
             detect_providers,
+            get_provider_last_activity,
+            format_relative_time,
             scan_all_projects,
             load_provider_sessions,
             load_provider_messages,
+            get_message_metadata,
+            load_session_universal_paginated,
+            get_universal_message_count,
             search_all_providers,
             // WSL support (Windows) — distro detection + AI-tool dirs
             detect_wsl_distros,
@@ -208,6 +264,7 @@ pub fn run() {
             get_project_metadata,
             set_project_hidden,
             set_project_custom_name,
+            set_custom_stopwords,
             get_all_metadata,
             clear_all_metadata,
             load_user_metadata
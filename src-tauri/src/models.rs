@@ -25,6 +25,17 @@ pub struct MessageContent {
     pub usage: Option<TokenUsage>,
 }
 
+/// How session loaders treat malformed JSONL lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseMode {
+    /// Log and skip malformed lines (default)
+    #[default]
+    Lenient,
+    /// Fail on the first malformed line
+    Strict,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawLogEntry {
     pub uuid: Option<String>,
@@ -205,6 +216,15 @@ pub struct ClaudeProject {
     pub message_count: usize,
     #[serde(rename = "lastModified")]
     pub last_modified: String,
+    /// Summed token usage across the project's sessions; only filled when
+    /// `scan_projects` is called with `include_tokens`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<u64>,
+    /// Working directory decoded from the project folder name (see
+    /// `utils::decode_project_path`); best-effort, as real hyphens are
+    /// indistinguishable from path separators.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -223,10 +243,58 @@ pub struct ClaudeSession {
     pub summary: Option<String>,
     pub git_branch: Option<String>, // Git branch name
     pub git_commit: Option<String>, // Git commit hash (short, 8 chars)
+    /// Text of the first user message, capped separately from `summary`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_user_prompt: Option<String>,
     /// Originating client for Claude Code sessions: "cli" / "claude-vscode" / "claude-desktop".
     /// `None` for non-Claude providers or sessions predating the entrypoint field.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entrypoint: Option<String>,
+    /// Recency, message count and tool usage combined into one number for a
+    /// "most significant" sort (higher is more significant). Recency is taken
+    /// at load time, so compare scores only within one load.
+    #[serde(default)]
+    pub activity_score: f64,
+    /// `tool_use` blocks in the file; kept so `activity_score` can be
+    /// recomputed when a cached session is served to a later load.
+    #[serde(skip)]
+    pub tool_use_count: usize,
+    /// Every JSONL file of the session: just `file_path`, or, when
+    /// `load_project_sessions` groups resumed sessions, all files sharing
+    /// `actual_session_id`, oldest first.
+    #[serde(default)]
+    pub file_paths: Vec<String>,
+}
+
+/// Sessions of a project whose last activity falls on one day (`get_sessions_by_day`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayGroup {
+    /// UTC date, "YYYY-MM-DD"
+    pub date: String,
+    pub session_count: usize,
+    /// Billed tokens (input, output and cache) of the day's sessions
+    pub total_tokens: u64,
+    /// The day's sessions, newest first
+    pub sessions: Vec<ClaudeSession>,
+}
+
+/// A data-quality problem found while scanning a session file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionParseWarning {
+    pub file_path: String,
+    pub line: usize, // 1-based
+    /// "unparseable_line", "line_too_long", "missing_uuid", "missing_session_id"
+    /// or "missing_timestamp"
+    pub kind: String,
+    pub message: String,
+}
+
+/// Sessions of a project plus the parse warnings collected while scanning them
+/// (`load_project_sessions_verbose`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSessionsReport {
+    pub sessions: Vec<ClaudeSession>,
+    pub warnings: Vec<SessionParseWarning>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -237,6 +305,58 @@ pub struct MessagePage {
     pub next_offset: usize,
 }
 
+/// A message and its replies in a session's `parentUuid` tree
+/// (see `get_session_thread`). `message.depth` is the node's depth.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadNode {
+    /// None only for the synthetic root that collects orphaned messages
+    pub message: Option<universal::UniversalMessage>,
+    /// Replies, oldest first; more than one means the conversation branched
+    pub children: Vec<ThreadNode>,
+}
+
+/// Which kinds of message the universal loader keeps. Each kind is kept
+/// unless its flag is `Some(false)`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageIncludeFilters {
+    /// Sidechain-like messages: Claude sidechains, standalone reasoning records
+    pub sidechain: Option<bool>,
+    /// Messages carrying only tool calls/results and no text
+    pub tool_only: Option<bool>,
+    /// System-role messages (Gemini info/warning, Codex system events, ...)
+    pub system: Option<bool>,
+}
+
+impl MessageIncludeFilters {
+    /// True when at least one kind of message is filtered out.
+    pub fn is_active(&self) -> bool {
+        [self.sidechain, self.tool_only, self.system].contains(&Some(false))
+    }
+}
+
+/// Content conditions for `load_session_messages_filtered`. Every set field
+/// must hold; `Some(false)` on a flag requires its absence.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageContentFilter {
+    /// A fenced code block in the text, or a code content item
+    pub has_code_block: Option<bool>,
+    /// Tool calls or tool-use content items
+    pub has_tool_use: Option<bool>,
+    /// Minimum length of the message text, in characters
+    pub min_length: Option<usize>,
+    pub role: Option<universal::MessageRole>,
+}
+
+/// A page of messages from any provider. `total_count` is `None` when the
+/// provider's loader cannot report it without reading the whole session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniversalMessagePage {
+    pub messages: Vec<universal::UniversalMessage>,
+    pub total_count: Option<usize>,
+    pub has_more: bool,
+    pub next_offset: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionTokenStats {
     pub session_id: String,
@@ -281,6 +401,22 @@ pub struct ActivityHeatmap {
     pub tokens_used: u64,
 }
 
+/// Average token spend over a trailing window (`get_burn_rate`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct BurnRate {
+    /// Window length in days, today included
+    pub window_days: u32,
+    /// Tokens used in the window
+    pub total_tokens: u64,
+    pub tokens_per_day: f64,
+    /// Estimated USD per day at default (Sonnet) API rates
+    pub cost_per_day: f64,
+    /// `tokens_per_day` times the days of the current month
+    pub projected_month_tokens: u64,
+    /// `cost_per_day` times the days of the current month
+    pub projected_month_cost: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProjectStatsSummary {
     pub project_name: String,
@@ -344,6 +480,42 @@ pub struct ModelStats {
     pub cache_read_tokens: u64,
 }
 
+/// Tokens and estimated cost of one model within a session (`get_session_cost`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelCost {
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    /// Estimated USD; 0 when the model has no known pricing
+    pub cost: f64,
+    pub priced: bool,
+}
+
+/// Estimated USD cost of a session at API list prices (`get_session_cost`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionCost {
+    pub session_id: String,
+    pub total_cost: f64,
+    /// Per-model breakdown, most expensive first
+    pub models: Vec<ModelCost>,
+    /// True when some tokens could not be priced, so `total_cost` is a lower bound
+    pub has_unpriced_models: bool,
+    pub unpriced_models: Vec<String>,
+}
+
+/// The model with the largest token share of a project (`get_dominant_model`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelShare {
+    pub model_name: String,
+    pub token_count: u64,
+    /// Tokens of all messages that name a model
+    pub total_tokens: u64,
+    /// `token_count` as a percentage (0-100) of `total_tokens`
+    pub share_percent: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectRanking {
     pub project_name: String,
@@ -386,6 +558,85 @@ pub struct SearchFilters {
     pub has_errors: Option<bool>,
     #[serde(rename = "hasFileChanges")]
     pub has_file_changes: Option<bool>,
+    /// Tolerate small typos in unquoted search words. Off by default: plain
+    /// search stays an exact, case-insensitive substring match.
+    pub fuzzy: Option<bool>,
+    /// Largest edit distance a fuzzy word match may have (default 2).
+    #[serde(rename = "maxEditDistance")]
+    pub max_edit_distance: Option<usize>,
+}
+
+/// One page of search hits, returned by `search_messages` and
+/// `search_cursor_messages`.
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub messages: Vec<universal::UniversalMessage>,
+    /// Number of matching messages; can exceed `messages.len()` when only a
+    /// page was requested.
+    pub total: usize,
+    /// Whether matches remain after this page.
+    pub has_more: bool,
+    /// Filters that were provided but could not be applied, e.g. `dateRange`
+    /// for Cursor (global-DB bubbles carry no timestamps).
+    #[serde(rename = "ignoredFilters", skip_serializing_if = "Vec::is_empty")]
+    pub ignored_filters: Vec<String>,
+}
+
+/// Navigation target for a `search_messages` hit, attached to each matched
+/// message under `providerMetadata.search_match` so a result can open the
+/// exact session without the frontend re-deriving it from paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchMatch {
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    #[serde(rename = "sessionPath")]
+    pub session_path: String,
+    /// Fuzzy searches only: total edit distance of the terms that matched
+    /// approximately (`0` means every term was found verbatim).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzziness: Option<usize>,
+}
+
+/// A salient term of a session and how often it occurs (`get_session_topics`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopicTerm {
+    pub term: String,
+    pub frequency: usize,
+}
+
+/// Tool results of a session with identical content (`find_duplicate_tool_results`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Content hash shared by every result of the group
+    pub hash: String,
+    /// Name of the tool that produced the first result, when known
+    pub tool_name: Option<String>,
+    /// `tool_use_id`s of the results, in session order
+    pub tool_use_ids: Vec<String>,
+    /// Number of results in the group (`tool_use_ids.len()`)
+    pub count: usize,
+    /// Estimated tokens of one result
+    pub tokens_per_result: usize,
+    /// Estimated tokens spent on the repeats (every result after the first)
+    pub wasted_tokens: usize,
+}
+
+/// Context size at one assistant turn of a session (`get_context_pressure`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextPoint {
+    /// `uuid` of the assistant entry
+    pub message_id: String,
+    pub timestamp: Option<String>,
+    /// Prompt size of the turn: input plus cache creation and cache read tokens
+    pub context_tokens: u64,
+    /// True when the turn reported no usage and the size was approximated
+    pub estimated: bool,
+    /// `context_tokens` as a fraction of the context limit
+    pub limit_ratio: f64,
+    /// True once `limit_ratio` reaches the warning threshold
+    pub near_limit: bool,
 }
 
 // ============================================================================
@@ -410,6 +661,9 @@ pub struct FileChange {
     pub new_string: String,
     pub line_start: Option<usize>,
     pub line_end: Option<usize>,
+    /// Edit `replace_all`: every occurrence of `old_string` is replaced
+    #[serde(default)]
+    pub replace_all: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -431,6 +685,10 @@ pub struct FileActivity {
     pub size_before: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size_after: Option<usize>,
+    /// Read of only part of the file (`offset`/`limit`, or fewer lines
+    /// returned than the file has): `content_after` is a slice, not a snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_read: Option<bool>,
 
     // Diff information
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -453,6 +711,30 @@ pub struct FileActivityFilters {
     pub file_extensions: Option<Vec<String>>,
     #[serde(rename = "searchQuery")]
     pub search_query: Option<String>,
+    /// RFC3339 cutoff: sessions whose file was last modified before it are
+    /// skipped without being parsed
+    pub since: Option<String>,
+}
+
+/// Dashboard aggregate of a project's sessions and file activity
+/// (`get_productivity_metrics`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductivityMetrics {
+    /// Number of sessions in the project
+    pub session_count: usize,
+    /// Sum over sessions of the gaps between consecutive messages, each gap
+    /// capped at 15 minutes so idle stretches don't count as work
+    pub active_hours: f64,
+    /// Distinct files written or edited (Write, Edit, MultiEdit activities)
+    pub files_touched: usize,
+    /// Lines added across all edit activities
+    pub lines_added: usize,
+    /// Lines removed across all edit activities
+    pub lines_removed: usize,
+    /// Distinct UTC dates with at least one session (by last message)
+    pub active_days: usize,
+    /// `session_count / active_days` (0 without sessions)
+    pub sessions_per_day: f64,
 }
 
 // ============================================================================
@@ -527,6 +809,9 @@ pub struct AppMetadata {
     pub projects: std::collections::HashMap<String, ProjectMeta>,
     #[serde(default = "default_metadata_version")]
     pub version: u32,
+    /// User stopwords merged with the built-in lists (see `set_custom_stopwords`)
+    #[serde(default)]
+    pub custom_stopwords: Vec<String>,
 }
 
 fn default_metadata_version() -> u32 {
@@ -587,6 +872,7 @@ impl AppMetadata {
             sessions: std::collections::HashMap::new(),
             projects: std::collections::HashMap::new(),
             version: 1,
+            custom_stopwords: Vec::new(),
         }
     }
 
@@ -34,6 +34,11 @@ pub struct UniversalMessage {
     #[serde(rename = "providerId")]
     pub provider_id: String,
 
+    // Base directory of the provider install; `source_id` is the concrete
+    // file or database the message was read from
+    #[serde(skip_serializing_if = "Option::is_none", rename = "providerRoot")]
+    pub provider_root: Option<String>,
+
     // TEMPORAL (REQUIRED)
     pub timestamp: String,
 
@@ -368,6 +373,7 @@ pub enum ContentType {
     Thinking,
     WebSearch,
     Command,
+    Binary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,8 +1,24 @@
+use crate::models::universal::UniversalMessage;
+
+// ============================================================================
+// STOPWORDS MODULE
+// ============================================================================
+pub mod stopwords;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Strip a leading UTF-8 byte order mark (written by some Windows editors)
+/// so the first JSONL line still parses.
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{FEFF}').unwrap_or(content)
+}
+
 /// Splits a byte slice into `(start, end)` byte offset pairs for each line.
 ///
 /// Uses SIMD-accelerated `memchr` for fast newline detection (5-10x faster
 /// than byte-by-byte iteration on large files). Designed for use with
 /// memory-mapped files where random byte-offset access is O(1).
+/// A leading UTF-8 BOM and trailing `\r` (CRLF files) are excluded from the ranges.
 pub fn find_line_ranges(data: &[u8]) -> Vec<(usize, usize)> {
     use memchr::memchr_iter;
 
@@ -13,7 +29,11 @@ pub fn find_line_ranges(data: &[u8]) -> Vec<(usize, usize)> {
     // Pre-allocate based on estimated average line length (~500 bytes for JSONL)
     let estimated_lines = data.len() / 500;
     let mut ranges = Vec::with_capacity(estimated_lines.max(16));
-    let mut start = 0;
+    let mut start = if data.starts_with(UTF8_BOM) {
+        UTF8_BOM.len()
+    } else {
+        0
+    };
 
     for pos in memchr_iter(b'\n', data) {
         // Trim trailing \r for Windows-style line endings
@@ -43,17 +63,140 @@ pub fn find_line_ranges(data: &[u8]) -> Vec<(usize, usize)> {
     ranges
 }
 
+/// Display name for an encoded Claude project folder name, from the name
+/// alone: the root and first two components (e.g. `-Users-jack-`) are
+/// dropped and the rest is kept as is, so `-Users-jack-my-app` is `my-app`.
+/// Use [`project_folder_display_name`] to settle hyphens against the disk.
 pub fn extract_project_name(raw_project_name: &str) -> String {
     if raw_project_name.starts_with('-') {
         let parts: Vec<&str> = raw_project_name.splitn(4, '-').collect();
         if parts.len() == 4 {
-            parts[3].to_string()
-        } else {
-            raw_project_name.to_string()
+            return parts[3].to_string();
         }
-    } else {
-        raw_project_name.to_string()
     }
+    raw_project_name.to_string()
+}
+
+/// Display name for an encoded Claude project folder name, reversed against
+/// the filesystem (see [`decode_project_path_with`]): Claude replaces every
+/// path separator (and `.`) with `-`, so `-Users-jack-client-ai-code-tracker`
+/// may be `/Users/jack/client/ai-code-tracker` or
+/// `/Users/jack/client-ai-code-tracker`. Falls back to
+/// [`extract_project_name`] when no candidate directory exists.
+///
+/// The disk is probed once per folder name; later calls are served from a
+/// process-wide cache.
+pub fn project_folder_display_name(raw_project_name: &str) -> String {
+    lazy_static::lazy_static! {
+        static ref DISPLAY_NAMES: std::sync::Mutex<std::collections::HashMap<String, String>> =
+            std::sync::Mutex::new(std::collections::HashMap::new());
+    }
+    if let Some(name) = DISPLAY_NAMES
+        .lock()
+        .ok()
+        .and_then(|names| names.get(raw_project_name).cloned())
+    {
+        return name;
+    }
+    let name =
+        project_folder_display_name_with(raw_project_name, &|p: &std::path::Path| p.is_dir());
+    if let Ok(mut names) = DISPLAY_NAMES.lock() {
+        names.insert(raw_project_name.to_string(), name.clone());
+    }
+    name
+}
+
+fn project_folder_display_name_with(
+    raw_project_name: &str,
+    is_dir: &dyn Fn(&std::path::Path) -> bool,
+) -> String {
+    decode_project_path_with(raw_project_name, is_dir)
+        .as_deref()
+        .and_then(std::path::Path::file_name)
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| extract_project_name(raw_project_name))
+}
+
+/// Best-effort original working directory for an encoded Claude project
+/// folder name, e.g. `-home-me-app` -> `/home/me/app`.
+///
+/// The encoding is lossy: a real hyphen and a path separator both become `-`.
+/// Directories that still exist on disk settle the ambiguity; otherwise every
+/// `-` is read as a separator, so `-home-me-my-app` decodes to
+/// `/home/me/my/app` even if the project was `/home/me/my-app`. `None` for
+/// names that are not encoded absolute paths.
+pub fn decode_project_path(folder_name: &str) -> Option<String> {
+    decode_project_path_with(folder_name, &|p: &std::path::Path| p.is_dir())
+        .or_else(|| decode_project_path_with(folder_name, &|_: &std::path::Path| true))
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Reverse Claude's project folder encoding to the directory it names.
+///
+/// Unix names start with `-` (the root); Windows names start with a drive
+/// letter followed by `--` (`C:\`). Each `-` is either a path separator or a
+/// literal hyphen, and an empty token marks a dot (`--config` is `/.config`).
+/// Candidates are checked component by component with `is_dir`, preferring
+/// separators over hyphens; `None` when no candidate exists.
+fn decode_project_path_with(
+    raw_project_name: &str,
+    is_dir: &dyn Fn(&std::path::Path) -> bool,
+) -> Option<std::path::PathBuf> {
+    let tokens: Vec<&str> = raw_project_name.split('-').collect();
+    let (root, rest) = match tokens.as_slice() {
+        ["", rest @ ..] => (std::path::PathBuf::from("/"), rest),
+        [drive, "", rest @ ..]
+            if drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic()) =>
+        {
+            (std::path::PathBuf::from(format!("{}:\\", drive)), rest)
+        }
+        _ => return None,
+    };
+    if rest.is_empty() || rest.iter().all(|t| t.is_empty()) {
+        return None;
+    }
+    decode_components(&root, rest, is_dir)
+}
+
+fn decode_components(
+    base: &std::path::Path,
+    tokens: &[&str],
+    is_dir: &dyn Fn(&std::path::Path) -> bool,
+) -> Option<std::path::PathBuf> {
+    if tokens.is_empty() {
+        return Some(base.to_path_buf());
+    }
+    for end in 1..=tokens.len() {
+        let joined = tokens[..end].join("-");
+        // A leading empty token is a `.` that was encoded as `-`
+        let component = match joined.strip_prefix('-') {
+            Some(rest) if tokens[0].is_empty() => format!(".{}", rest),
+            _ => joined,
+        };
+        if component.is_empty() || component == "." {
+            continue;
+        }
+        let candidate = base.join(&component);
+        if is_dir(&candidate) {
+            if let Some(found) = decode_components(&candidate, &tokens[end..], is_dir) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Resolve a display name for a Claude Code project.
+///
+/// Prefers the last component of the session's recorded `cwd` (the real
+/// working directory, so dashes in directory names survive), falling back to
+/// [`extract_project_name`] on the encoded folder name when no `cwd` is known.
+pub fn resolve_project_name(cwd: Option<&str>, raw_project_name: &str) -> String {
+    cwd.map(|c| c.trim_end_matches(['/', '\\']))
+        .and_then(|c| c.rsplit(['/', '\\']).next())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| extract_project_name(raw_project_name))
 }
 
 /// Estimate message count from file size (more accurate calculation)
@@ -173,6 +316,455 @@ pub fn extract_git_info(
     (branch, commit)
 }
 
+// ============================================================================
+// SESSION FILES (plain and gzip-compressed JSONL)
+// ============================================================================
+
+/// Suffix of gzip-compressed session files (`<session>.jsonl.gz`).
+pub const GZIP_SESSION_SUFFIX: &str = ".jsonl.gz";
+
+/// True for gzip-compressed session files (`.jsonl.gz`).
+pub fn is_gzip_session(path: &std::path::Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| name.ends_with(GZIP_SESSION_SUFFIX))
+}
+
+/// True for session files: `.jsonl`, or gzip-compressed `.jsonl.gz`.
+pub fn is_session_file(path: &std::path::Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("jsonl") || is_gzip_session(path)
+}
+
+/// Open a session file for line-by-line reading, decompressing `.jsonl.gz`
+/// files on the fly.
+pub fn open_session_reader(
+    path: &std::path::Path,
+) -> std::io::Result<Box<dyn std::io::BufRead + Send>> {
+    let file = std::fs::File::open(path)?;
+    if is_gzip_session(path) {
+        Ok(Box::new(std::io::BufReader::new(
+            flate2::read::MultiGzDecoder::new(file),
+        )))
+    } else {
+        Ok(Box::new(std::io::BufReader::new(file)))
+    }
+}
+
+/// Read a whole session file as text, decompressing `.jsonl.gz`.
+pub fn read_session_to_string(path: &std::path::Path) -> std::io::Result<String> {
+    use std::io::Read;
+    let mut content = String::new();
+    open_session_reader(path)?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Full content of a session file: memory-mapped for plain `.jsonl`,
+/// decompressed into memory for `.jsonl.gz`. Derefs to the (decompressed)
+/// bytes, so both go through the same line-splitting path.
+pub enum SessionBytes {
+    Mapped(memmap2::Mmap),
+    Decompressed(Vec<u8>),
+}
+
+impl std::ops::Deref for SessionBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            SessionBytes::Mapped(mmap) => mmap,
+            SessionBytes::Decompressed(bytes) => bytes,
+        }
+    }
+}
+
+/// Read a session file opened as `file` from `path` (see [`SessionBytes`]).
+pub fn read_session_bytes(
+    file: &std::fs::File,
+    path: &std::path::Path,
+) -> std::io::Result<SessionBytes> {
+    if is_gzip_session(path) {
+        use std::io::Read;
+        let mut bytes = Vec::new();
+        flate2::read::MultiGzDecoder::new(file).read_to_end(&mut bytes)?;
+        Ok(SessionBytes::Decompressed(bytes))
+    } else {
+        // SAFETY: The file is opened read-only and kept open by the caller for
+        // the lifetime of the map. Session files are append-only.
+        unsafe { memmap2::Mmap::map(file) }.map(SessionBytes::Mapped)
+    }
+}
+
+/// Uncompressed size of a session file, for size-based estimates. For
+/// `.jsonl.gz` this is the gzip ISIZE trailer (size mod 2^32 of the last
+/// member), falling back to the compressed size if it cannot be read.
+pub fn session_content_size(path: &std::path::Path, metadata: &std::fs::Metadata) -> u64 {
+    if !is_gzip_session(path) || metadata.len() < 4 {
+        return metadata.len();
+    }
+    use std::io::{Read, Seek, SeekFrom};
+    let mut trailer = [0u8; 4];
+    std::fs::File::open(path)
+        .and_then(|mut file| {
+            file.seek(SeekFrom::End(-4))?;
+            file.read_exact(&mut trailer)
+        })
+        .map(|_| u64::from(u32::from_le_bytes(trailer)))
+        .unwrap_or(metadata.len())
+}
+
+// ============================================================================
+// IGNORE FILE (.history-viewer-ignore)
+// ============================================================================
+
+/// File in the Claude root listing project folders and session files to skip
+/// in `scan_projects` / `load_project_sessions`.
+///
+/// Syntax, one glob pattern per line (gitignore-like):
+/// - blank lines and lines starting with `#` are skipped
+/// - patterns match paths relative to `projects/` with `/` separators, e.g.
+///   `-tmp-*` (project folders) or `my-project/agent-*.jsonl` (sessions)
+/// - `*`, `?` and `[...]` don't cross `/`; `**` does
+/// - a pattern without `/` matches the last path component at any depth; a
+///   leading `/` anchors it to `projects/`, a trailing `/` is ignored
+///
+/// Precedence: a leading `!` re-includes what an earlier line ignored and the
+/// last matching line wins. An ignored project folder hides all its sessions,
+/// which `!` lines for those sessions can't bring back.
+pub const IGNORE_FILE_NAME: &str = ".history-viewer-ignore";
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: glob::Pattern,
+    negated: bool,
+    /// Pattern has no `/`: match the last path component only
+    basename_only: bool,
+}
+
+/// Parsed [`IGNORE_FILE_NAME`] rules, matched against paths under `base`
+/// (the Claude root's `projects/` directory).
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    base: std::path::PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Parse ignore file `text` for paths under `base`. Invalid patterns are
+    /// skipped.
+    pub fn parse(base: &std::path::Path, text: &str) -> Self {
+        let rules = strip_bom(text)
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let basename_only = !line.trim_end_matches('/').contains('/');
+                let line = line.trim_start_matches('/').trim_end_matches('/');
+                match glob::Pattern::new(line) {
+                    Ok(pattern) => Some(IgnoreRule {
+                        pattern,
+                        negated,
+                        basename_only,
+                    }),
+                    Err(e) => {
+                        eprintln!(
+                            "Skipping invalid {} pattern '{}': {}",
+                            IGNORE_FILE_NAME, line, e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self {
+            base: base.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// Rules of the ignore file in `claude_root`; empty when it is missing or
+    /// unreadable.
+    pub fn load(claude_root: &std::path::Path) -> Self {
+        let base = claude_root.join("projects");
+        match std::fs::read_to_string(claude_root.join(IGNORE_FILE_NAME)) {
+            Ok(text) => Self::parse(&base, &text),
+            Err(_) => Self {
+                base,
+                rules: Vec::new(),
+            },
+        }
+    }
+
+    /// Rules for the files of `project_path`, a folder under a Claude root's
+    /// `projects/`; empty for any other path.
+    pub fn for_project(project_path: &std::path::Path) -> Self {
+        match project_path.parent() {
+            Some(projects) if projects.file_name().is_some_and(|n| n == "projects") => {
+                projects.parent().map(Self::load).unwrap_or_default()
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Whether `path` (a project folder or session file under `projects/`) or
+    /// one of its parent folders is ignored. Paths outside `projects/` never
+    /// are.
+    pub fn is_ignored(&self, path: &std::path::Path) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let Ok(relative) = path.strip_prefix(&self.base) else {
+            return false;
+        };
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        (1..=components.len()).any(|depth| self.matches(&components[..depth]))
+    }
+
+    /// Last-match-wins verdict for one relative path
+    fn matches(&self, components: &[String]) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        let joined = components.join("/");
+        let name = components.last().map(String::as_str).unwrap_or_default();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                let candidate = if rule.basename_only { name } else { &joined };
+                rule.pattern.matches_with(candidate, options)
+            })
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+// ============================================================================
+// FENCED CODE BLOCKS
+// ============================================================================
+
+/// A fenced code block of markdown text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FencedCodeBlock {
+    /// First word of the info string (e.g. "rust"), if any
+    pub language: Option<String>,
+    pub code: String,
+}
+
+/// Scan markdown `text` for fenced code blocks (CommonMark style): an opening
+/// line of three or more backticks or tildes, indented at most three spaces,
+/// closed by a fence of the same character that is at least as long. An
+/// unclosed block runs to the end of the text.
+pub fn find_fenced_code_blocks(text: &str) -> Vec<FencedCodeBlock> {
+    /// `(fence char, fence length, rest of line)` for a fence line.
+    fn fence(line: &str) -> Option<(char, usize, &str)> {
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            return None;
+        }
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = trimmed.chars().take_while(|c| *c == marker).count();
+        (len >= 3).then(|| (marker, len, &trimmed[len..]))
+    }
+
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, FencedCodeBlock)> = None;
+    let mut code_lines: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        match (&open, fence(line)) {
+            (None, Some((marker, len, info))) => {
+                // Backtick info strings may not contain backticks
+                if marker == '`' && info.contains('`') {
+                    continue;
+                }
+                let language = info.split_whitespace().next().map(String::from);
+                let block = FencedCodeBlock {
+                    language,
+                    code: String::new(),
+                };
+                open = Some((marker, len, block));
+                code_lines.clear();
+            }
+            (Some((marker, len, _)), Some((close, close_len, rest)))
+                if close == *marker && close_len >= *len && rest.trim().is_empty() =>
+            {
+                if let Some((_, _, mut block)) = open.take() {
+                    block.code = code_lines.join("\n");
+                    blocks.push(block);
+                }
+            }
+            (Some(_), _) => code_lines.push(line),
+            (None, None) => {}
+        }
+    }
+
+    if let Some((_, _, mut block)) = open {
+        block.code = code_lines.join("\n");
+        blocks.push(block);
+    }
+    blocks
+}
+
+// ============================================================================
+// CONTENT SIZE CAP
+// ============================================================================
+
+/// Truncate every string inside `value` to at most `max_bytes` bytes (on a
+/// char boundary). Returns true when anything was cut.
+fn truncate_strings(value: &mut serde_json::Value, max_bytes: usize) -> bool {
+    match value {
+        serde_json::Value::String(s) if s.len() > max_bytes => {
+            let mut end = max_bytes;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            s.truncate(end);
+            true
+        }
+        serde_json::Value::Array(items) => {
+            // No short-circuiting: every oversized string must be cut
+            let mut cut = false;
+            for item in items.iter_mut() {
+                cut |= truncate_strings(item, max_bytes);
+            }
+            cut
+        }
+        serde_json::Value::Object(map) => {
+            let mut cut = false;
+            for item in map.values_mut() {
+                cut |= truncate_strings(item, max_bytes);
+            }
+            cut
+        }
+        _ => false,
+    }
+}
+
+/// Shrink a JSON value whose serialized size exceeds `max_bytes`.
+///
+/// Strings inside it are cut to `max_bytes` and the value is marked with
+/// `truncated: true` and `originalSize` (serialized bytes); non-object values
+/// are wrapped as `{ "value": ... }` to carry the marker. Returns the original
+/// size when the value was truncated.
+pub fn cap_json_value(value: &mut serde_json::Value, max_bytes: usize) -> Option<usize> {
+    let original_size = serde_json::to_string(value).map(|s| s.len()).unwrap_or(0);
+    if original_size <= max_bytes || !truncate_strings(value, max_bytes) {
+        return None;
+    }
+
+    if !value.is_object() {
+        *value = serde_json::json!({ "value": value.take() });
+    }
+    if let Some(map) = value.as_object_mut() {
+        map.insert("truncated".to_string(), serde_json::json!(true));
+        map.insert("originalSize".to_string(), serde_json::json!(original_size));
+    }
+    Some(original_size)
+}
+
+/// Point `source_id` at the concrete file or database the messages were read
+/// from and record the provider's base directory in `provider_root`.
+pub fn set_message_source(
+    messages: &mut [UniversalMessage],
+    source_path: &str,
+    provider_root: &str,
+) {
+    for msg in messages {
+        msg.source_id = source_path.to_string();
+        msg.provider_root = Some(provider_root.to_string());
+    }
+}
+
+/// SHA-256 (hex) of a content item's payload: its `text`, else its
+/// `content` (tool results), else the whole serialized value. Ids and other
+/// envelope fields are left out so identical outputs hash the same.
+pub fn content_hash(data: &serde_json::Value) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    match data.get("text").or_else(|| data.get("content")) {
+        Some(serde_json::Value::String(text)) => hasher.update(text.as_bytes()),
+        Some(other) => hasher.update(other.to_string().as_bytes()),
+        None => hasher.update(data.to_string().as_bytes()),
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Fill `hash` on every content item of a message that has none (see
+/// [`content_hash`]). Run before capping so the hash covers the full content.
+pub fn hash_message_content(msg: &mut UniversalMessage) {
+    for item in msg.content.iter_mut() {
+        if item.hash.is_none() {
+            item.hash = Some(content_hash(&item.data));
+        }
+    }
+}
+
+/// Cap oversized content items of a message (see [`cap_json_value`]).
+///
+/// Truncated items also get `size` set to their original serialized size.
+/// Raw copies kept in `provider_metadata` (e.g. `raw_tool_use_result`) are
+/// capped the same way so they don't reintroduce the bloat. The full content
+/// stays retrievable via `get_full_content`.
+pub fn cap_message_content(msg: &mut UniversalMessage, max_bytes: usize) {
+    for item in msg.content.iter_mut() {
+        if let Some(original_size) = cap_json_value(&mut item.data, max_bytes) {
+            item.size = Some(original_size);
+        }
+    }
+    for value in msg.provider_metadata.values_mut() {
+        cap_json_value(value, max_bytes);
+    }
+}
+
+/// Prefix of the `provider_metadata` keys holding raw copies of the source
+/// entry (`raw_content`, `raw_tool_use`, `raw_tool_use_result`).
+pub const RAW_METADATA_PREFIX: &str = "raw_";
+
+/// Drop the raw `provider_metadata` copies of a message. They are usually the
+/// bulk of its IPC payload and stay retrievable via `get_message_metadata`.
+pub fn strip_raw_metadata(msg: &mut UniversalMessage) {
+    msg.provider_metadata
+        .retain(|key, _| !key.starts_with(RAW_METADATA_PREFIX));
+}
+
+// ============================================================================
+// HOME DIRECTORY RESOLUTION
+// ============================================================================
+
+/// Environment variable that replaces the user's home directory in every path
+/// lookup, for portable installs and hermetic integration tests.
+pub const HOME_OVERRIDE_ENV: &str = "HISTORY_VIEWER_HOME";
+
+/// Parse a `HISTORY_VIEWER_HOME` value; unset or empty means no override.
+fn parse_home_override(value: Option<std::ffi::OsString>) -> Option<std::path::PathBuf> {
+    value
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
+/// The `HISTORY_VIEWER_HOME` override, when set.
+pub fn home_override() -> Option<std::path::PathBuf> {
+    parse_home_override(std::env::var_os(HOME_OVERRIDE_ENV))
+}
+
+/// Home directory used for all path detection: `$HISTORY_VIEWER_HOME` when
+/// set, otherwise the user's real home directory. Use this instead of
+/// `dirs::home_dir()`.
+pub fn resolve_home() -> Option<std::path::PathBuf> {
+    home_override().or_else(dirs::home_dir)
+}
+
 // ============================================================================
 // CUSTOM CLAUDE CONFIGURATION DIRECTORIES
 // ============================================================================
@@ -183,13 +775,13 @@ pub fn extract_git_info(
 /// left untouched. Returns the input unchanged when no home directory is found.
 pub fn expand_home_prefix(raw: &str) -> String {
     if raw == "~" {
-        if let Some(home) = dirs::home_dir() {
+        if let Some(home) = resolve_home() {
             return home.to_string_lossy().to_string();
         }
         return raw.to_string();
     }
     if let Some(rest) = raw.strip_prefix("~/").or_else(|| raw.strip_prefix("~\\")) {
-        if let Some(home) = dirs::home_dir() {
+        if let Some(home) = resolve_home() {
             return home.join(rest).to_string_lossy().to_string();
         }
     }
@@ -294,6 +886,301 @@ pub fn resolve_claude_config_dir_from(raw: Option<String>) -> Option<String> {
     }
 }
 
+#[cfg(test)]
+mod line_range_tests {
+    use super::*;
+
+    #[test]
+    fn line_ranges_skip_bom_and_carriage_returns() {
+        let data = b"\xEF\xBB\xBF{\"a\":1}\r\n\r\n{\"b\":2}\r";
+        let lines: Vec<&[u8]> = find_line_ranges(data)
+            .into_iter()
+            .map(|(start, end)| &data[start..end])
+            .collect();
+        assert_eq!(lines, vec![&b"{\"a\":1}"[..], &b"{\"b\":2}"[..]]);
+        assert_eq!(strip_bom("\u{FEFF}{}"), "{}");
+        assert_eq!(strip_bom("{}"), "{}");
+    }
+}
+
+#[cfg(test)]
+mod project_name_tests {
+    use super::*;
+
+    #[test]
+    fn resolve_prefers_cwd_basename() {
+        assert_eq!(
+            resolve_project_name(Some("/home/me/my-cool-app"), "-home-me-my-cool-app"),
+            "my-cool-app"
+        );
+        assert_eq!(
+            resolve_project_name(Some(r"C:\Users\me\repo\"), "C--Users-me-repo"),
+            "repo"
+        );
+    }
+
+    fn fake_fs(dirs: &'static [&'static str]) -> impl Fn(&std::path::Path) -> bool {
+        move |p: &std::path::Path| dirs.iter().any(|d| std::path::Path::new(d) == p)
+    }
+
+    #[test]
+    fn extract_decodes_against_existing_directories() {
+        let nested = fake_fs(&[
+            "/Users",
+            "/Users/jack",
+            "/Users/jack/client",
+            "/Users/jack/client/ai-code-tracker",
+        ]);
+        assert_eq!(
+            project_folder_display_name_with("-Users-jack-client-ai-code-tracker", &nested),
+            "ai-code-tracker"
+        );
+
+        let hyphenated = fake_fs(&[
+            "/Users",
+            "/Users/jack",
+            "/Users/jack/client-ai-code-tracker",
+        ]);
+        assert_eq!(
+            project_folder_display_name_with("-Users-jack-client-ai-code-tracker", &hyphenated),
+            "client-ai-code-tracker"
+        );
+
+        let dotted = fake_fs(&[
+            "/home",
+            "/home/me",
+            "/home/me/.config",
+            "/home/me/.config/nvim",
+        ]);
+        assert_eq!(
+            decode_project_path_with("-home-me--config-nvim", &dotted),
+            Some(std::path::PathBuf::from("/home/me/.config/nvim"))
+        );
+    }
+
+    #[test]
+    fn extract_uses_the_name_alone() {
+        assert_eq!(
+            extract_project_name("-Users-jack-ai-code-tracker"),
+            "ai-code-tracker"
+        );
+        assert_eq!(
+            extract_project_name("-Users-jack-client-ai-code-tracker"),
+            "client-ai-code-tracker"
+        );
+        assert_eq!(extract_project_name("-Users-jack"), "-Users-jack");
+        assert_eq!(extract_project_name("plain-name"), "plain-name");
+    }
+
+    #[test]
+    fn display_name_falls_back_when_nothing_exists() {
+        let empty = fake_fs(&[]);
+        assert_eq!(
+            project_folder_display_name_with("-Users-jack-client-ai-code-tracker", &empty),
+            "client-ai-code-tracker"
+        );
+        assert_eq!(
+            project_folder_display_name_with("plain-name", &empty),
+            "plain-name"
+        );
+        assert_eq!(decode_project_path_with("plain-name", &empty), None);
+        assert_eq!(decode_project_path_with("-", &empty), None);
+    }
+
+    #[test]
+    fn decode_reads_every_hyphen_as_separator_when_nothing_exists() {
+        assert_eq!(
+            decode_project_path("-nonexistent-root-my-app").as_deref(),
+            Some("/nonexistent/root/my/app")
+        );
+        assert_eq!(
+            decode_project_path("-nonexistent-me--config").as_deref(),
+            Some("/nonexistent/me/.config")
+        );
+        assert_eq!(decode_project_path("relative-name"), None);
+    }
+
+    #[test]
+    fn resolve_falls_back_to_folder_name() {
+        assert_eq!(resolve_project_name(None, "-home-me-project"), "project");
+        assert_eq!(
+            resolve_project_name(Some(""), "-home-me-project"),
+            "project"
+        );
+        assert_eq!(resolve_project_name(Some("/"), "plain"), "plain");
+    }
+}
+
+#[cfg(test)]
+mod session_file_tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use std::io::{BufRead, Write};
+    use std::path::Path;
+
+    #[test]
+    fn recognizes_plain_and_gzip_sessions() {
+        assert!(is_session_file(Path::new("/p/s.jsonl")));
+        assert!(is_session_file(Path::new("/p/s.jsonl.gz")));
+        assert!(!is_session_file(Path::new("/p/s.json.gz")));
+        assert!(!is_session_file(Path::new("/p/s.gz")));
+        assert!(is_gzip_session(Path::new("/p/s.jsonl.gz")));
+        assert!(!is_gzip_session(Path::new("/p/s.jsonl")));
+    }
+
+    #[test]
+    fn gzip_sessions_read_decompressed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("s.jsonl.gz");
+        let content = "{\"a\":1}\n{\"b\":2}\n";
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let lines: Vec<String> = open_session_reader(&path)
+            .unwrap()
+            .lines()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(lines, vec!["{\"a\":1}", "{\"b\":2}"]);
+
+        let file = std::fs::File::open(&path).unwrap();
+        assert_eq!(
+            &*read_session_bytes(&file, &path).unwrap(),
+            content.as_bytes()
+        );
+
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(session_content_size(&path, &metadata), content.len() as u64);
+    }
+}
+
+#[cfg(test)]
+mod ignore_rules_tests {
+    use super::*;
+    use std::path::Path;
+
+    fn rules(text: &str) -> IgnoreRules {
+        IgnoreRules::parse(Path::new("/c/projects"), text)
+    }
+
+    #[test]
+    fn ignores_projects_and_sessions_by_glob() {
+        let rules = rules("# throwaway\n-tmp-*\n\n/keep/agent-*.jsonl\n**/*.jsonl.gz\n");
+        assert!(rules.is_ignored(Path::new("/c/projects/-tmp-scratch")));
+        assert!(rules.is_ignored(Path::new("/c/projects/-tmp-scratch/s.jsonl")));
+        assert!(rules.is_ignored(Path::new("/c/projects/keep/agent-1.jsonl")));
+        assert!(rules.is_ignored(Path::new("/c/projects/keep/sub/old.jsonl.gz")));
+        assert!(!rules.is_ignored(Path::new("/c/projects/keep/s.jsonl")));
+        // `*` doesn't cross `/`
+        assert!(!rules.is_ignored(Path::new("/c/projects/keep/sub/agent-1.jsonl")));
+        // Outside projects/
+        assert!(!rules.is_ignored(Path::new("/elsewhere/-tmp-x")));
+    }
+
+    #[test]
+    fn last_matching_line_wins() {
+        let rules = rules("*.jsonl\n!important.jsonl\n-tmp-*\n!-tmp-x/important.jsonl\n");
+        assert!(rules.is_ignored(Path::new("/c/projects/p/a.jsonl")));
+        assert!(!rules.is_ignored(Path::new("/c/projects/p/important.jsonl")));
+        // An ignored project folder can't be re-entered
+        assert!(rules.is_ignored(Path::new("/c/projects/-tmp-x/important.jsonl")));
+    }
+
+    #[test]
+    fn for_project_reads_the_claude_root_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let project = temp.path().join("projects").join("-tmp-x");
+        std::fs::create_dir_all(&project).unwrap();
+        assert!(!IgnoreRules::for_project(&project).is_ignored(&project));
+
+        std::fs::write(temp.path().join(IGNORE_FILE_NAME), "-tmp-*\n").unwrap();
+        assert!(IgnoreRules::for_project(&project).is_ignored(&project.join("s.jsonl")));
+        assert!(!IgnoreRules::for_project(temp.path()).is_ignored(&project));
+    }
+}
+
+#[cfg(test)]
+mod fenced_code_block_tests {
+    use super::*;
+
+    #[test]
+    fn finds_backtick_and_tilde_blocks() {
+        let text = "Intro\n```rust ignore\nfn main() {}\n```\ntext\n  ~~~~\na\n~~~\nb\n~~~~\n";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(
+            blocks,
+            vec![
+                FencedCodeBlock {
+                    language: Some("rust".to_string()),
+                    code: "fn main() {}".to_string()
+                },
+                // A shorter fence does not close a longer one
+                FencedCodeBlock {
+                    language: None,
+                    code: "a\n~~~\nb".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_inline_code_and_keeps_unclosed_blocks() {
+        assert!(find_fenced_code_blocks("use `x` and ``y``").is_empty());
+        // Four spaces of indentation make an indented code line, not a fence
+        assert!(find_fenced_code_blocks("    ```\nindented\n    ```").is_empty());
+        let blocks = find_fenced_code_blocks("```py\nprint(1)");
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].code, "print(1)");
+    }
+}
+
+#[cfg(test)]
+mod content_cap_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn cap_leaves_small_values_alone() {
+        let mut value = json!({"text": "short"});
+        assert_eq!(cap_json_value(&mut value, 100), None);
+        assert_eq!(value, json!({"text": "short"}));
+    }
+
+    #[test]
+    fn cap_truncates_and_marks_objects() {
+        let mut value = json!({"type": "text", "text": "a".repeat(500)});
+        let original = serde_json::to_string(&value).unwrap().len();
+        assert_eq!(cap_json_value(&mut value, 50), Some(original));
+        assert_eq!(value["text"].as_str().unwrap().len(), 50);
+        assert_eq!(value["type"], "text");
+        assert_eq!(value["truncated"], true);
+        assert_eq!(value["originalSize"], original);
+    }
+
+    #[test]
+    fn content_hash_ignores_envelope_fields() {
+        let a = json!({"type": "tool_result", "tool_use_id": "a", "content": "same"});
+        let b = json!({"type": "tool_result", "tool_use_id": "b", "content": "same"});
+        let c = json!({"type": "tool_result", "tool_use_id": "a", "content": "other"});
+        assert_eq!(content_hash(&a), content_hash(&b));
+        assert_ne!(content_hash(&a), content_hash(&c));
+        assert_eq!(
+            content_hash(&json!({"text": "hi"})),
+            content_hash(&json!({"text": "hi", "x": 1}))
+        );
+    }
+
+    #[test]
+    fn cap_wraps_non_object_values() {
+        let mut value = json!("é".repeat(100));
+        assert!(cap_json_value(&mut value, 11).is_some());
+        // Cut lands on a char boundary
+        assert_eq!(value["value"].as_str().unwrap(), "é".repeat(5));
+        assert_eq!(value["truncated"], true);
+    }
+}
+
 #[cfg(test)]
 mod custom_claude_dir_tests {
     use super::*;
@@ -374,3 +1261,20 @@ mod custom_claude_dir_tests {
         let _ = fs::remove_dir_all(&tmp);
     }
 }
+
+#[cfg(test)]
+mod home_override_tests {
+    use super::*;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    #[test]
+    fn empty_or_unset_override_is_ignored() {
+        assert_eq!(parse_home_override(None), None);
+        assert_eq!(parse_home_override(Some(OsString::new())), None);
+        assert_eq!(
+            parse_home_override(Some(OsString::from("/portable/home"))),
+            Some(PathBuf::from("/portable/home"))
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Built-in stopword lists for the keyword/topic features
+//!
+//! Lists are embedded per language and merged with the user's custom words
+//! (persisted in `metadata.json`, see `set_custom_stopwords`) via
+//! [`Stopwords`]. All entries are lowercase, matching the tokenizers.
+
+use std::collections::HashSet;
+
+/// Language used when none is requested.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// English words too common to say anything about a conversation.
+pub const ENGLISH: &[&str] = &[
+    "about", "above", "after", "again", "all", "also", "and", "any", "are", "because", "been",
+    "before", "being", "below", "between", "both", "but", "can", "could", "did", "does", "doing",
+    "done", "down", "each", "few", "for", "from", "further", "get", "got", "had", "has", "have",
+    "having", "her", "here", "hers", "him", "his", "how", "into", "its", "itself", "just", "let",
+    "like", "make", "may", "more", "most", "much", "must", "need", "not", "now", "off", "once",
+    "one", "only", "other", "our", "ours", "out", "over", "own", "same", "see", "she", "should",
+    "some", "such", "than", "that", "the", "their", "theirs", "them", "then", "there", "these",
+    "they", "this", "those", "through", "too", "under", "until", "use", "used", "using", "very",
+    "want", "was", "way", "were", "what", "when", "where", "which", "while", "who", "whom", "why",
+    "will", "with", "would", "yes", "you", "your", "yours", "i'm", "it's", "don't", "i'll",
+    "let's", "that's", "there's", "can't", "won't", "doesn't", "didn't", "isn't",
+];
+
+/// Built-in list for an ISO 639-1 code or English language name
+/// (case-insensitive), or `None` for unsupported languages.
+pub fn builtin(language: &str) -> Option<&'static [&'static str]> {
+    match language.to_lowercase().as_str() {
+        "en" | "english" => Some(ENGLISH),
+        _ => None,
+    }
+}
+
+/// Normalize user-supplied words: trimmed, lowercase, deduplicated (first
+/// occurrence wins), empty entries dropped.
+pub fn normalize_custom(words: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    words
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty() && seen.insert(w.clone()))
+        .collect()
+}
+
+/// A built-in list merged with custom words.
+#[derive(Debug, Clone, Default)]
+pub struct Stopwords {
+    words: HashSet<String>,
+}
+
+impl Stopwords {
+    /// Built-in words of `language` (none when unsupported) plus `custom`.
+    pub fn new(language: &str, custom: &[String]) -> Self {
+        let mut words: HashSet<String> = builtin(language)
+            .unwrap_or_default()
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        words.extend(normalize_custom(custom));
+        Self { words }
+    }
+
+    /// Whether the lowercase `word` should be ignored.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_builtin_and_custom_words() {
+        let stopwords = Stopwords::new("English", &[" TODO ".to_string()]);
+        assert!(stopwords.contains("the"));
+        assert!(stopwords.contains("todo"));
+        assert!(!stopwords.contains("parser"));
+
+        let custom_only = Stopwords::new("xx", &["the".to_string()]);
+        assert!(custom_only.contains("the"));
+        assert!(!custom_only.contains("and"));
+    }
+
+    #[test]
+    fn normalize_custom_dedupes_and_drops_empty() {
+        let words = ["Foo", "", "  ", "foo", "bar"].map(String::from);
+        assert_eq!(normalize_custom(&words), vec!["foo", "bar"]);
+    }
+}
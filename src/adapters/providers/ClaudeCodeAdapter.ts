@@ -35,7 +35,7 @@ import { ProviderID, ErrorCode } from '../../types/providers';
 import { invoke } from '@tauri-apps/api/core';
 
 // UI display types (projects/sessions use UI format for component compatibility)
-import type { UIProject, UISession, MessagePage } from '../../types/index';
+import type { UIProject, UISession, MessagePage, MessageSearchResult } from '../../types/index';
 
 // ============================================================================
 // ADAPTER IMPLEMENTATION
@@ -350,8 +350,8 @@ export class ClaudeCodeAdapter implements IConversationAdapter {
     }
 
     try {
-      // Backend now returns UniversalMessage directly
-      const universalMessages = await invoke<UniversalMessage[]>('search_messages', {
+      // Backend returns a page of UniversalMessage hits plus the total count
+      const result = await invoke<MessageSearchResult>('search_messages', {
         claudePath: sourcePaths[0],
         query,
         filters: {
@@ -368,8 +368,8 @@ export class ClaudeCodeAdapter implements IConversationAdapter {
 
       return {
         success: true,
-        data: universalMessages,
-        totalMatches: universalMessages.length,
+        data: result.messages,
+        totalMatches: result.total,
         searchDuration: 0,
       };
     } catch (error) {
@@ -41,6 +41,7 @@ interface CursorWorkspace {
   state_db_path: string;
   session_count: number;
   last_activity?: string; // ISO 8601 timestamp of most recent composer
+  diagnostics?: string; // Why session_count is zero, when it is
 }
 
 interface CursorSession {
@@ -4,6 +4,8 @@
  * Helper functions for analytics calculations and formatting.
  */
 
+import type { SessionTokenStats } from "../../../types";
+
 /**
  * Calculate growth rate between two values
  */
@@ -79,25 +81,89 @@ export const hasExplicitModelPricing = (modelName: string): boolean =>
   findModelPricing(modelName) != null;
 
 /**
- * Calculate Claude API pricing for a model
+ * Estimated cost split by token kind. Cache writes and cache reads are billed
+ * at different rates, so they are reported separately.
  */
-export const calculateModelPrice = (
+export interface CostBreakdown {
+  inputCost: number;
+  outputCost: number;
+  cacheCreationCost: number;
+  cacheReadCost: number;
+  /** What the cache reads saved compared to paying the plain input rate */
+  cacheReadSavings: number;
+  /** Extra paid for cache writes over the plain input rate */
+  cacheCreationOverhead: number;
+  totalCost: number;
+}
+
+/**
+ * Calculate the cost breakdown of token usage for a model
+ */
+export const calculateCostBreakdown = (
   modelName: string,
   inputTokens: number,
   outputTokens: number,
   cacheCreationTokens: number,
   cacheReadTokens: number
-): number => {
+): CostBreakdown => {
   const modelPricing = findModelPricing(modelName) ?? DEFAULT_PRICING;
+  const perToken = (rate: number) => rate / 1000000;
 
-  const inputCost = (inputTokens / 1000000) * modelPricing.input;
-  const outputCost = (outputTokens / 1000000) * modelPricing.output;
-  const cacheWriteCost = (cacheCreationTokens / 1000000) * modelPricing.cacheWrite;
-  const cacheReadCost = (cacheReadTokens / 1000000) * modelPricing.cacheRead;
+  const inputCost = inputTokens * perToken(modelPricing.input);
+  const outputCost = outputTokens * perToken(modelPricing.output);
+  const cacheCreationCost = cacheCreationTokens * perToken(modelPricing.cacheWrite);
+  const cacheReadCost = cacheReadTokens * perToken(modelPricing.cacheRead);
 
-  return inputCost + outputCost + cacheWriteCost + cacheReadCost;
+  return {
+    inputCost,
+    outputCost,
+    cacheCreationCost,
+    cacheReadCost,
+    cacheReadSavings: cacheReadTokens * perToken(modelPricing.input) - cacheReadCost,
+    cacheCreationOverhead: cacheCreationCost - cacheCreationTokens * perToken(modelPricing.input),
+    totalCost: inputCost + outputCost + cacheCreationCost + cacheReadCost,
+  };
 };
 
+/**
+ * Calculate the cost breakdown of a session's token stats
+ */
+export const calculateSessionCostBreakdown = (
+  modelName: string,
+  stats: Pick<
+    SessionTokenStats,
+    | "total_input_tokens"
+    | "total_output_tokens"
+    | "total_cache_creation_tokens"
+    | "total_cache_read_tokens"
+  >
+): CostBreakdown =>
+  calculateCostBreakdown(
+    modelName,
+    stats.total_input_tokens,
+    stats.total_output_tokens,
+    stats.total_cache_creation_tokens,
+    stats.total_cache_read_tokens
+  );
+
+/**
+ * Calculate Claude API pricing for a model
+ */
+export const calculateModelPrice = (
+  modelName: string,
+  inputTokens: number,
+  outputTokens: number,
+  cacheCreationTokens: number,
+  cacheReadTokens: number
+): number =>
+  calculateCostBreakdown(
+    modelName,
+    inputTokens,
+    outputTokens,
+    cacheCreationTokens,
+    cacheReadTokens
+  ).totalCost;
+
 /**
  * Format a number as a currency string (USD)
  */
@@ -5,6 +5,7 @@
  */
 
 import {
+  calculateCostBreakdown,
   calculateModelPrice,
   formatNumber,
   hasExplicitModelPricing,
@@ -32,6 +33,8 @@ interface ModelUsageLike {
 
 export interface GlobalCostSummary {
   totalEstimatedCost: number;
+  cacheCreationCost: number;
+  cacheReadCost: number;
   coveragePercent: number;
   coveredTokens: number;
 }
@@ -74,16 +77,21 @@ export const calculateGlobalCostSummary = (
   totalTokens: number
 ): GlobalCostSummary => {
   let totalEstimatedCost = 0;
+  let cacheCreationCost = 0;
+  let cacheReadCost = 0;
   let coveredTokens = 0;
 
   for (const model of models) {
-    totalEstimatedCost += calculateModelPrice(
+    const breakdown = calculateCostBreakdown(
       model.model_name,
       model.input_tokens,
       model.output_tokens,
       model.cache_creation_tokens,
       model.cache_read_tokens
     );
+    totalEstimatedCost += breakdown.totalCost;
+    cacheCreationCost += breakdown.cacheCreationCost;
+    cacheReadCost += breakdown.cacheReadCost;
 
     if (hasExplicitModelPricing(model.model_name)) {
       coveredTokens += model.token_count;
@@ -95,6 +103,8 @@ export const calculateGlobalCostSummary = (
 
   return {
     totalEstimatedCost,
+    cacheCreationCost,
+    cacheReadCost,
     coveragePercent,
     coveredTokens,
   };
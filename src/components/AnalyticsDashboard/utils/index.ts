@@ -10,8 +10,11 @@ export {
   formatNumber,
   formatCurrency,
   calculateModelPrice,
+  calculateCostBreakdown,
+  calculateSessionCostBreakdown,
   hasExplicitModelPricing,
   getHeatColor,
+  type CostBreakdown,
 } from "./calculations";
 
 // Tool name utilities
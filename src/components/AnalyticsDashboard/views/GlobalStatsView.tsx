@@ -170,6 +170,12 @@ export const GlobalStatsView: React.FC<GlobalStatsViewProps> = ({
         <span className="px-2 py-1 rounded-md bg-muted/40 text-muted-foreground text-[11px]">
           {t("analytics.pricingCoverage", "Pricing coverage")}: {costSummary.coveragePercent.toFixed(1)}%
         </span>
+        <span className="px-2 py-1 rounded-md bg-muted/40 text-muted-foreground text-[11px]">
+          {t("analytics.cacheWriteCost", "Cache write cost")}: {formatCurrency(costSummary.cacheCreationCost)}
+        </span>
+        <span className="px-2 py-1 rounded-md bg-muted/40 text-muted-foreground text-[11px]">
+          {t("analytics.cacheReadCost", "Cache read cost")}: {formatCurrency(costSummary.cacheReadCost)}
+        </span>
         <span className="px-2 py-1 rounded-md bg-muted/40 text-muted-foreground text-[11px]">
           {t("analytics.lastUpdated", "Last updated")}: {lastUpdated}
         </span>
@@ -12,7 +12,7 @@ import {
 import { Dialog, DialogContent } from "@/components/ui/dialog";
 import { Input } from "@/components/ui/input";
 import { useAppStore } from "@/store/useAppStore";
-import type { ClaudeMessage, ClaudeSession, ContentItem } from "@/types";
+import type { ClaudeMessage, ClaudeSession, ContentItem, MessageSearchResult } from "@/types";
 import { getProviderLabel } from "@/utils/providers";
 
 type GlobalSearchResult = ClaudeMessage;
@@ -95,11 +95,11 @@ export const GlobalSearchModal = ({
 
             setIsSearching(true);
             try {
-                const searchResults = await invoke<GlobalSearchResult[]>(
+                const searchResults = await invoke<MessageSearchResult<GlobalSearchResult>>(
                     "search_messages",
                     { claudePath, query: trimmedQuery, filters: {}, limit: MAX_RESULTS },
                 );
-                setResults(searchResults);
+                setResults(searchResults.messages);
                 setSelectedIndex(0);
             } catch (error) {
                 console.error("Global search failed:", error);
@@ -1,5 +1,8 @@
 import { describe, it, expect } from "vitest";
-import { calculateModelPrice } from "../components/AnalyticsDashboard/utils/calculations";
+import {
+  calculateCostBreakdown,
+  calculateModelPrice,
+} from "../components/AnalyticsDashboard/utils/calculations";
 
 // Per-1M-token input cost for a given model (output/cache zeroed).
 const inputRate = (model: string) =>
@@ -31,4 +34,22 @@ describe("model pricing", () => {
     expect(inputRate("claude-sonnet-4-6")).toBe(3);
     expect(inputRate("claude-haiku-4-5")).toBe(1);
   });
+
+  it("reports cache write and cache read cost separately", () => {
+    const breakdown = calculateCostBreakdown(
+      "claude-sonnet-4",
+      0,
+      0,
+      1_000_000,
+      1_000_000
+    );
+    expect(breakdown.cacheCreationCost).toBeCloseTo(3.75);
+    expect(breakdown.cacheReadCost).toBeCloseTo(0.3);
+    // Reads at $0.30 instead of the $3 input rate; writes cost $0.75 extra
+    expect(breakdown.cacheReadSavings).toBeCloseTo(2.7);
+    expect(breakdown.cacheCreationOverhead).toBeCloseTo(0.75);
+    expect(breakdown.totalCost).toBeCloseTo(
+      calculateModelPrice("claude-sonnet-4", 0, 0, 1_000_000, 1_000_000)
+    );
+  });
 });
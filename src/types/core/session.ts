@@ -54,6 +54,10 @@ export interface ClaudeProject {
   session_count: number;
   message_count: number;
   last_modified: string;
+  /** Summed session token usage (only when scanned with includeTokens) */
+  total_tokens?: number;
+  /** Working directory decoded from the folder name (best-effort: hyphens are ambiguous) */
+  cwd?: string;
   /** Git worktree info */
   git_info?: GitInfo;
   /** Provider identifier (claude, codex, opencode) */
@@ -72,6 +76,10 @@ export interface ClaudeSession {
   has_tool_use: boolean;
   has_errors: boolean;
   summary?: string;
+  /** Text of the first user message (capped separately from summary) */
+  first_user_prompt?: string;
+  /** Recency + message count + tool usage; higher sorts as more significant */
+  activity_score?: number;
   relevance?: number;
   /** Provider identifier (claude, codex, opencode) */
   provider?: ProviderId;
@@ -158,6 +158,7 @@ export interface UISession {
   session_id: string; // Unique ID based on file path
   actual_session_id: string; // Actual session ID from the messages
   file_path: string; // Full path to the JSONL file
+  file_paths?: string[]; // All files of the session (resumed sessions, when grouped)
   project_name: string;
   message_count: number;
   first_message_time: string;
@@ -205,6 +206,20 @@ export interface MessagePage {
   next_offset: number;
 }
 
+// One page of hits from search_messages / search_cursor_messages
+export interface MessageSearchResult<T = UniversalMessage> {
+  messages: T[];
+  total: number; // All matches, not just this page
+  has_more: boolean;
+  ignoredFilters?: string[]; // Filters the provider could not apply
+}
+
+// get_session_thread: nested reply tree; the synthetic orphan root has message null
+export interface ThreadNode {
+  message: UniversalMessage | null;
+  children: ThreadNode[];
+}
+
 export interface PaginationState {
   currentOffset: number;
   pageSize: number;
@@ -360,11 +375,30 @@ export interface DailyStats {
   active_hours: number;
 }
 
+// Trailing-window token spend (get_burn_rate); costs are USD estimates
+export interface BurnRate {
+  window_days: number;
+  total_tokens: number;
+  tokens_per_day: number;
+  cost_per_day: number;
+  projected_month_tokens: number;
+  projected_month_cost: number;
+}
+
+export interface ResumePreview {
+  cwd: string | null;
+  command: string;
+  interactive_command: string | null;
+  cli_name: string | null;
+  cli_installed: boolean;
+  directory_exists: boolean;
+}
+
 export interface ToolUsageStats {
   tool_name: string;
   usage_count: number;
   success_rate: number;
-  avg_execution_time?: number;
+  avg_execution_time?: number; // Milliseconds, over calls that returned a result
 }
 
 export interface ActivityHeatmap {
@@ -412,6 +446,17 @@ export interface PaginatedTokenStats {
   has_more: boolean;
 }
 
+// Token stats for an explicit list of sessions
+export interface SessionStatsFailure {
+  session_path: string;
+  error: string;
+}
+
+export interface SessionsTokenStats {
+  stats: SessionTokenStats[];
+  failures: SessionStatsFailure[];
+}
+
 // Global statistics types (upstream-enhanced)
 export interface GlobalStatsSummary {
   total_projects: number;
@@ -450,6 +495,32 @@ export interface ModelStats {
   cache_read_tokens: number;
 }
 
+export interface ModelCost {
+  model: string;
+  input_tokens: number;
+  output_tokens: number;
+  cache_creation_tokens: number;
+  cache_read_tokens: number;
+  cost: number; // USD, 0 when unpriced
+  priced: boolean;
+}
+
+export interface SessionCost {
+  session_id: string;
+  total_cost: number;
+  models: ModelCost[];
+  has_unpriced_models: boolean;
+  unpriced_models: string[];
+}
+
+// Largest per-model token share of a project (get_dominant_model)
+export interface ModelShare {
+  model_name: string;
+  token_count: number;
+  total_tokens: number;
+  share_percent: number; // 0-100
+}
+
 export interface ProjectRanking {
   project_name: string;
   sessions: number;
@@ -513,6 +584,7 @@ export interface FileChange {
   new_string: string;
   line_start?: number;
   line_end?: number;
+  replace_all?: boolean; // Edit replaced every occurrence
 }
 
 export interface FileActivity {
@@ -529,6 +601,7 @@ export interface FileActivity {
   content_after?: string;
   size_before?: number;
   size_after?: number;
+  partial_read?: boolean; // content_after is only a slice of the file
 
   // Diff information
   changes?: FileChange[];
@@ -543,6 +616,14 @@ export interface FileActivityFilters {
   operations?: FileOperation[]; // Use typed FileOperation instead of string
   fileExtensions?: string[];
   searchQuery?: string;
+  since?: string; // RFC3339; skips sessions whose file is older
+}
+
+// Payload of the `file-activity-progress` event (all-projects scans)
+export interface FileActivityProgress {
+  projects_done: number;
+  projects_total: number;
+  activities_found: number;
 }
 
 // ============================================================================
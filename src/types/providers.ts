@@ -22,6 +22,29 @@ export interface DetectedProvider {
   error?: string;
 }
 
+/**
+ * One provider install to search with the `search_all_providers` Tauri
+ * command. `path` is used by claude-code, cursor and codex; the other
+ * providers resolve their own data directories.
+ */
+export interface ProviderSource {
+  provider_id: string;
+  path: string;
+}
+
+/**
+ * Session totals of an available provider.
+ * Returned by the `get_provider_last_activity` Tauri command.
+ */
+export interface ProviderActivity {
+  /** Provider id as in `DetectedProvider.id` */
+  id: string;
+  display_name: string;
+  session_count: number;
+  /** Timestamp of the most recent session (null when there are none) */
+  last_activity_at: string | null;
+}
+
 // ============================================================================
 // PROVIDER DEFINITION
 // ============================================================================
@@ -143,6 +166,7 @@ export enum ContentType {
   THINKING = 'thinking',
   WEB_SEARCH = 'web_search',
   COMMAND = 'command',
+  BINARY = 'binary',
 }
 
 // ============================================================================
@@ -85,6 +85,8 @@ export interface ExtractMessageRangeResponse {
   summary?: string;
   message_count: number;
   cwd?: string; // Extracted working directory from source session
+  first_timestamp?: string; // Original timestamp of the first extracted message
+  last_timestamp?: string; // Original timestamp of the last extracted message
 }
 
 // ============================================================================
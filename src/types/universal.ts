@@ -12,8 +12,9 @@ export interface UniversalMessage {
   id: string;
   sessionId: string;
   projectId: string;
-  sourceId: string;
+  sourceId: string; // Concrete file or database the message was read from
   providerId: string;
+  providerRoot?: string; // Base directory of the provider install
 
   // TEMPORAL (REQUIRED)
   timestamp: string; // ISO 8601 ONLY
@@ -44,6 +45,29 @@ export interface UniversalMessage {
   providerMetadata: Record<string, unknown>;
 }
 
+/** Kinds of message load_session_universal_paginated drops when set to false. */
+export interface MessageIncludeFilters {
+  sidechain?: boolean;
+  tool_only?: boolean;
+  system?: boolean;
+}
+
+/** Content conditions for load_session_messages_filtered; every set field must hold. */
+export interface MessageContentFilter {
+  has_code_block?: boolean;
+  has_tool_use?: boolean;
+  min_length?: number;
+  role?: MessageRole;
+}
+
+/** Page returned by load_session_universal_paginated. */
+export interface UniversalMessagePage {
+  messages: UniversalMessage[];
+  total_count: number | null; // null when the provider cannot report it cheaply
+  has_more: boolean;
+  next_offset: number;
+}
+
 // ============================================================================
 // UNIVERSAL CONTENT
 // ============================================================================
@@ -218,6 +242,8 @@ export interface SearchFilters {
   hasErrors?: boolean;
   hasFileChanges?: boolean;
   model?: string;
+  fuzzy?: boolean; // Tolerate small typos in unquoted words (default off)
+  maxEditDistance?: number; // Fuzzy mode only (default 2)
 }
 
 // ============================================================================